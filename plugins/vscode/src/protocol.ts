@@ -13,7 +13,22 @@ export type ServerMessage =
 	| StatusMessage
 	| ConnectionAckMessage
 	| DiagnosticsRequestMessage
-	| CloseDiffMessage;
+	| CloseDiffMessage
+	| ModelsMessage
+	| ToolDescriptionMessage
+	| ToolListMessage
+	| SessionMessage
+	| SessionListMessage
+	| ContextRemainingMessage
+	| TokenCountMessage
+	| CostEstimateMessage
+	| ProviderReadyMessage
+	| ChatResponseMessage
+	| MCPStatusMessage
+	| ToolResultsMessage
+	| ToolResultMessage
+	| BashCancelledMessage
+	| MCPRawResultMessage;
 
 // Message types from Extension to CLI
 export type ClientMessage =
@@ -22,7 +37,23 @@ export type ClientMessage =
 	| RejectChangeMessage
 	| GetStatusMessage
 	| ContextMessage
-	| DiagnosticsResponseMessage;
+	| DiagnosticsResponseMessage
+	| GetModelsMessage
+	| DescribeToolMessage
+	| ListToolsMessage
+	| SaveSessionMessage
+	| LoadSessionMessage
+	| ListSessionsMessage
+	| CountRemainingMessage
+	| CountTokensMessage
+	| EstimateCostMessage
+	| InitProviderMessage
+	| ChatRequestMessage
+	| GetMCPStatusMessage
+	| ExecuteToolsMessage
+	| ExecuteToolMessage
+	| CancelBashMessage
+	| MCPRawRequestMessage;
 
 // File change notification (when nanocoder wants to modify a file)
 export interface FileChangeMessage {
@@ -80,6 +111,192 @@ export interface CloseDiffMessage {
 	id: string;
 }
 
+// Models offered by the current provider, in reply to get_models
+export interface ModelsMessage {
+	type: 'models';
+	models: ModelInfo[];
+	error?: string;
+}
+
+export interface ModelInfo {
+	id: string;
+	name?: string;
+	contextLength?: number;
+	family?: string;
+	parameterSize?: string;
+}
+
+// A single tool's definition, in reply to describe_tool
+export interface ToolDescriptionMessage {
+	type: 'tool_description';
+	name: string;
+	tool?: ToolDescription;
+	error?: string;
+}
+
+// Tool definitions matching a list_tools request
+export interface ToolListMessage {
+	type: 'tools';
+	tools: ToolDescription[];
+	error?: string;
+}
+
+// Health of each configured MCP server, in reply to get_mcp_status
+export interface MCPStatusMessage {
+	type: 'mcp_status';
+	servers: MCPServerInfo[];
+	error?: string;
+}
+
+// A server's raw result, in reply to mcp_raw_request
+export interface MCPRawResultMessage {
+	type: 'mcp_raw_result';
+	server: string;
+	method: string;
+	result?: Record<string, unknown>;
+	error?: string;
+}
+
+export interface MCPServerInfo {
+	name: string;
+	connected: boolean;
+	toolCount: number;
+	// Most recent connection error, if the server failed to connect
+	lastError?: string;
+	// Responses that matched no pending request, when there were any
+	droppedResponses?: number;
+}
+
+// Reply to session_save and session_load. A load carries the restored
+// conversation.
+export interface SessionMessage {
+	type: 'session';
+	action: 'save' | 'load';
+	id: string;
+	messages?: ConversationMessage[];
+	error?: string;
+}
+
+// Saved sessions, most recently updated first, in reply to session_list
+export interface SessionListMessage {
+	type: 'sessions';
+	sessions: SessionInfo[];
+	error?: string;
+}
+
+export interface SessionInfo {
+	id: string;
+	// ISO 8601 timestamp
+	updatedAt: string;
+	messageCount: number;
+}
+
+// Context window headroom, in reply to count_remaining. percent is the
+// share of the window used, to one decimal place.
+export interface ContextRemainingMessage {
+	type: 'context_remaining';
+	used: number;
+	max: number;
+	remaining: number;
+	percent: number;
+	error?: string;
+}
+
+// Token count, in reply to count_tokens. maxContext and fits are left out
+// when the model's context size is unknown.
+export interface TokenCountMessage {
+	type: 'token_count';
+	model: string;
+	tokens: number;
+	maxContext?: number;
+	fits?: boolean;
+	error?: string;
+}
+
+// Estimated spend in USD, in reply to estimate_cost. cost is null when the
+// model's prices are unknown.
+export interface CostEstimateMessage {
+	type: 'cost_estimate';
+	model: string;
+	cost: {input: number; output: number; total: number} | null;
+	error?: string;
+}
+
+// In reply to init_provider, with the model the new client starts on
+export interface ProviderReadyMessage {
+	type: 'provider_ready';
+	provider: string;
+	model?: string;
+	error?: string;
+}
+
+// The answer to a chat request, from the provider that handled it
+export interface ChatResponseMessage {
+	type: 'chat_response';
+	provider: string;
+	content: string;
+	error?: string;
+}
+
+// Results of an execute_tools request, in the order the calls were sent
+export interface ToolResultsMessage {
+	type: 'tool_results';
+	results: ToolResultInfo[];
+	error?: string;
+}
+
+// Result of an execute_tool request. result.id is the generated id when the
+// call was sent without one.
+export interface ToolResultMessage {
+	type: 'tool_result';
+	result?: ToolResultInfo;
+	error?: string;
+}
+
+// Reply to cancel_bash. cancelled is false when no call with the id was
+// running.
+export interface BashCancelledMessage {
+	type: 'bash_cancelled';
+	id: string;
+	cancelled: boolean;
+	error?: string;
+}
+
+export interface ConversationMessage {
+	role: 'user' | 'assistant' | 'system' | 'developer' | 'tool';
+	content: string;
+	tool_calls?: Array<{
+		id: string;
+		function: {name: string; arguments: Record<string, unknown>};
+	}>;
+	tool_call_id?: string;
+	name?: string;
+}
+
+export interface ToolDescription {
+	name: string;
+	description?: string;
+	// JSON Schema for the tool's arguments
+	inputSchema: Record<string, unknown>;
+	category?: string;
+	// Sample calls that can be shown to the model as few-shot guidance
+	examples?: Array<{description: string; arguments: Record<string, unknown>}>;
+}
+
+export interface ToolCallInfo {
+	id: string;
+	function: {name: string; arguments: Record<string, unknown>};
+}
+
+// A tool call's output, or its error when the tool failed or was refused
+export interface ToolResultInfo {
+	id: string;
+	output?: string;
+	error?: string;
+	// Set when cancel_bash stopped the call before it finished
+	cancelled?: boolean;
+}
+
 // User prompt from extension
 export interface SendPromptMessage {
 	type: 'send_prompt';
@@ -111,6 +328,123 @@ export interface GetStatusMessage {
 	type: 'get_status';
 }
 
+// Request the models the current provider offers
+export interface GetModelsMessage {
+	type: 'get_models';
+}
+
+// Request the connection state and tool count of each MCP server
+export interface GetMCPStatusMessage {
+	type: 'get_mcp_status';
+}
+
+// Send any JSON-RPC request to an MCP server, e.g. logging/setLevel. A
+// debugging aid: refused unless nanocoder runs with NANOCODER_LOG_LEVEL=debug.
+export interface MCPRawRequestMessage {
+	type: 'mcp_raw_request';
+	server: string;
+	method: string;
+	params?: Record<string, unknown>;
+}
+
+// Request the full definition of one tool
+export interface DescribeToolMessage {
+	type: 'describe_tool';
+	name: string;
+}
+
+// Request tool definitions, optionally filtered. Filters combine; with none,
+// every tool is returned.
+export interface ListToolsMessage {
+	type: 'list_tools';
+	categories?: string[];
+	// e.g. an MCP server's tool name prefix
+	namePrefix?: string;
+	names?: string[];
+}
+
+// Save the current conversation as a checkpoint named id, replacing any
+// earlier save. No file snapshots are taken.
+export interface SaveSessionMessage {
+	type: 'session_save';
+	id: string;
+}
+
+// Replace the current conversation with a saved checkpoint's
+export interface LoadSessionMessage {
+	type: 'session_load';
+	id: string;
+}
+
+// Request the saved sessions
+export interface ListSessionsMessage {
+	type: 'session_list';
+}
+
+// Ask how much of the active model's context window is left. Counts the
+// given messages, or the live session when there are none, plus the tool
+// definitions in use and any unsent draft prompt.
+export interface CountRemainingMessage {
+	type: 'count_remaining';
+	messages?: ConversationMessage[];
+	draft?: string;
+}
+
+// Count the tokens a request would use with any model's tokenizer. provider
+// defaults to the current one; tools are names of registered tools whose
+// definitions are counted too.
+export interface CountTokensMessage {
+	type: 'count_tokens';
+	model: string;
+	provider?: string;
+	messages: ConversationMessage[];
+	tools?: string[];
+}
+
+// Estimate what token usage with a model costs, from models.dev prices
+export interface EstimateCostMessage {
+	type: 'estimate_cost';
+	model: string;
+	usage: {prompt_tokens: number; completion_tokens: number};
+}
+
+// Create a client for a configured provider and register it under its
+// name, alongside the CLI's current client rather than replacing it
+export interface InitProviderMessage {
+	type: 'init_provider';
+	provider: string;
+}
+
+// One-shot chat without tools, answered by the named provider's client or
+// by the CLI's current client when provider is left out
+export interface ChatRequestMessage {
+	type: 'chat';
+	messages: ConversationMessage[];
+	provider?: string;
+}
+
+// Run several tool calls in one round trip. The calls run in parallel.
+// Tools that need approval only run when a confirmation handler approves
+// them.
+export interface ExecuteToolsMessage {
+	type: 'execute_tools';
+	toolCalls: ToolCallInfo[];
+}
+
+// Run one tool call, approved as for execute_tools. It can be a full tool
+// call or just a tool name and its arguments, which run under a generated id.
+export interface ExecuteToolMessage {
+	type: 'execute_tool';
+	toolCall: ToolCallInfo | {name: string; arguments: Record<string, unknown>};
+}
+
+// Stop a running execute_bash call, sent by execute_tools or execute_tool,
+// by its tool call id. Its result comes back marked cancelled.
+export interface CancelBashMessage {
+	type: 'cancel_bash';
+	id: string;
+}
+
 // Send workspace context to CLI
 export interface ContextMessage {
 	type: 'context';
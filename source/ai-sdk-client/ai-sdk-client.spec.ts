@@ -1,5 +1,8 @@
+import {createServer} from 'node:http';
+import type {AddressInfo} from 'node:net';
 import test from 'ava';
-import type {AIProviderConfig} from '@/types/index';
+import {jsonSchema, tool} from '@/types/core';
+import type {AIProviderConfig, ToolChoice} from '@/types/index';
 import {AISDKClient} from './ai-sdk-client.js';
 
 test('AISDKClient constructor initializes with config', t => {
@@ -115,3 +118,72 @@ test('AISDKClient handles config without models', t => {
 	const client = new AISDKClient(config);
 	t.is(client.getCurrentModel(), '');
 });
+
+test('AISDKClient sends the tool choice as tool_choice', async t => {
+	const bodies: Record<string, unknown>[] = [];
+	const server = createServer((req, res) => {
+		let raw = '';
+		req.on('data', chunk => {
+			raw += chunk;
+		});
+		req.on('end', () => {
+			bodies.push(JSON.parse(raw));
+			res.writeHead(200, {'content-type': 'application/json'});
+			res.end(
+				JSON.stringify({
+					id: 'chatcmpl-1',
+					object: 'chat.completion',
+					created: 0,
+					model: 'test-model',
+					choices: [
+						{
+							index: 0,
+							message: {role: 'assistant', content: 'ok'},
+							finish_reason: 'stop',
+						},
+					],
+					usage: {prompt_tokens: 1, completion_tokens: 1, total_tokens: 2},
+				}),
+			);
+		});
+	});
+	await new Promise<void>(resolve => server.listen(0, '127.0.0.1', resolve));
+	const {port} = server.address() as AddressInfo;
+
+	try {
+		const client = new AISDKClient({
+			name: 'TestProvider',
+			type: 'openai',
+			models: ['test-model'],
+			config: {baseURL: `http://127.0.0.1:${port}/v1`, apiKey: 'test-key'},
+		});
+		const tools = {
+			read_file: tool({
+				description: 'Read a file',
+				inputSchema: jsonSchema<{path: string}>({
+					type: 'object',
+					properties: {path: {type: 'string'}},
+					required: ['path'],
+				}),
+			}),
+		};
+		const choices: ToolChoice[] = [
+			'auto',
+			'none',
+			{type: 'tool', toolName: 'read_file'},
+		];
+
+		for (const toolChoice of choices) {
+			await client.chat([{role: 'user', content: 'hi'}], tools, {}, undefined, {
+				toolChoice,
+			});
+		}
+
+		t.deepEqual(
+			bodies.map(body => body.tool_choice),
+			['auto', 'none', {type: 'function', function: {name: 'read_file'}}],
+		);
+	} finally {
+		server.close();
+	}
+});
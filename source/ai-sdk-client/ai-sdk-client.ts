@@ -1,18 +1,24 @@
 import {createOpenAICompatible} from '@ai-sdk/openai-compatible';
 import type {LanguageModel} from 'ai';
-import {Agent} from 'undici';
+import type {Dispatcher} from 'undici';
 import {TIMEOUT_SOCKET_DEFAULT_MS} from '@/constants';
-import {getModelContextLimit} from '@/models/index.js';
+import {getModelContextLimit, getModelOutputLimit} from '@/models/index.js';
 import type {
 	AIProviderConfig,
 	AISDKCoreTool,
+	ChatOptions,
 	LLMChatResponse,
 	LLMClient,
 	Message,
+	ProviderModelInfo,
 	StreamCallbacks,
 } from '@/types/index';
+import {createHttpDispatcher} from '@/utils/http-dispatcher';
 import {getLogger} from '@/utils/logging';
 import {handleChat} from './chat/chat-handler.js';
+import {resolveAutoMaxTokens} from './chat/max-tokens.js';
+import {resolveToolBudget} from './chat/tool-budget.js';
+import {listProviderModels} from './providers/model-listing.js';
 import {createProvider} from './providers/provider-factory.js';
 
 export class AISDKClient implements LLMClient {
@@ -20,8 +26,9 @@ export class AISDKClient implements LLMClient {
 	private currentModel: string;
 	private availableModels: string[];
 	private providerConfig: AIProviderConfig;
-	private undiciAgent: Agent;
+	private undiciAgent: Dispatcher;
 	private cachedContextSize: number;
+	private cachedOutputLimit: number;
 	private maxRetries: number;
 
 	constructor(providerConfig: AIProviderConfig) {
@@ -31,6 +38,7 @@ export class AISDKClient implements LLMClient {
 		this.availableModels = providerConfig.models;
 		this.currentModel = providerConfig.models[0] || '';
 		this.cachedContextSize = 0;
+		this.cachedOutputLimit = 0;
 		// Default to 2 retries (same as AI SDK default), or use configured value
 		this.maxRetries = providerConfig.maxRetries ?? 2;
 
@@ -50,7 +58,7 @@ export class AISDKClient implements LLMClient {
 				? 0
 				: (effectiveSocketTimeout ?? TIMEOUT_SOCKET_DEFAULT_MS);
 
-		this.undiciAgent = new Agent({
+		this.undiciAgent = createHttpDispatcher({
 			connect: {
 				timeout: resolvedSocketTimeout,
 			},
@@ -67,19 +75,22 @@ export class AISDKClient implements LLMClient {
 	}
 
 	/**
-	 * Fetch and cache context size from models.dev
+	 * Fetch and cache context size and output limit from models.dev
 	 */
 	private async updateContextSize(): Promise<void> {
 		const logger = getLogger();
 		try {
 			const contextSize = await getModelContextLimit(this.currentModel);
 			this.cachedContextSize = contextSize || 0;
+			const outputLimit = await getModelOutputLimit(this.currentModel);
+			this.cachedOutputLimit = outputLimit || 0;
 		} catch (error) {
 			logger.debug('Failed to get model context size', {
 				model: this.currentModel,
 				error,
 			});
 			this.cachedContextSize = 0;
+			this.cachedOutputLimit = 0;
 		}
 	}
 
@@ -120,6 +131,13 @@ export class AISDKClient implements LLMClient {
 		return Promise.resolve(this.availableModels);
 	}
 
+	/**
+	 * List the models the provider offers via its models endpoint
+	 */
+	listModels(): Promise<ProviderModelInfo[]> {
+		return listProviderModels(this.providerConfig, this.undiciAgent);
+	}
+
 	/**
 	 * Stream chat with real-time token updates
 	 */
@@ -128,20 +146,40 @@ export class AISDKClient implements LLMClient {
 		tools: Record<string, AISDKCoreTool>,
 		callbacks: StreamCallbacks,
 		signal?: AbortSignal,
+		options: ChatOptions = {},
 	): Promise<LLMChatResponse> {
 		// Get the language model instance from the provider
 		const model = this.provider(this.currentModel) as unknown as LanguageModel;
 
+		const requestTools = await resolveToolBudget(
+			this.providerConfig,
+			this.currentModel,
+			{messages, tools, contextSize: this.cachedContextSize},
+		);
+
+		const maxOutputTokens = await resolveAutoMaxTokens(
+			this.providerConfig,
+			this.currentModel,
+			{
+				messages,
+				tools: requestTools,
+				contextSize: this.cachedContextSize,
+				outputLimit: this.cachedOutputLimit,
+			},
+		);
+
 		// Delegate to chat handler
 		return await handleChat({
 			model,
 			currentModel: this.currentModel,
 			providerConfig: this.providerConfig,
 			messages,
-			tools,
+			tools: requestTools,
 			callbacks,
 			signal,
 			maxRetries: this.maxRetries,
+			maxOutputTokens,
+			toolChoice: options.toolChoice,
 		});
 	}
 
@@ -14,7 +14,12 @@ import type {
 	Message,
 	StreamCallbacks,
 	ToolCall,
+	ToolChoice,
 } from '@/types/index';
+import {
+	repairDanglingToolCalls,
+	validateConversation,
+} from '@/utils/conversation-validation';
 import {
 	endMetrics,
 	formatMemoryUsage,
@@ -32,10 +37,15 @@ import {
 } from '../converters/tool-converter.js';
 import {extractRootError} from '../error-handling/error-extractor.js';
 import {parseAPIError} from '../error-handling/error-parser.js';
+import {getThinkingProviderOptions} from '../providers/thinking.js';
+import {isTruncatedFinishReason} from './finish-reason.js';
+import {addToolPrompt} from './prompt-tools.js';
+import {getBodyLogFields, getErrorStatusCode} from './request-logging.js';
 import {
 	createOnStepFinishHandler,
 	createPrepareStepHandler,
 } from './streaming-handler.js';
+import {getToolsForChoice} from './tool-choice.js';
 import {processXMLToolCalls} from './tool-processor.js';
 
 export interface ChatHandlerParams {
@@ -47,6 +57,10 @@ export interface ChatHandlerParams {
 	callbacks: StreamCallbacks;
 	signal?: AbortSignal;
 	maxRetries: number;
+	// Max output tokens; the provider's default applies when unset
+	maxOutputTokens?: number;
+	// Sent only with native tools; the provider's default applies when unset
+	toolChoice?: ToolChoice;
 }
 
 /**
@@ -59,13 +73,16 @@ export async function handleChat(
 		model,
 		currentModel,
 		providerConfig,
-		messages,
 		tools,
 		callbacks,
 		signal,
 		maxRetries,
+		maxOutputTokens,
+		toolChoice,
 	} = params;
 	const logger = getLogger();
+	// A session saved mid-batch can hold tool calls that never got a result
+	const messages = repairDanglingToolCalls(params.messages);
 
 	// Check if already aborted before starting
 	if (signal?.aborted) {
@@ -85,18 +102,33 @@ export async function handleChat(
 		provider: providerConfig.name,
 	});
 
+	// Fail with a clear message rather than an opaque provider 400
+	validateConversation(messages);
+
 	return await withNewCorrelationContext(async _context => {
 		try {
+			// In prompt mode the tools are described in the system message and
+			// calls come back as <tool_call> text for the XML parser. A tool
+			// choice can't be enforced there, so it narrows what is described;
+			// the history is rewritten even when no tools are described.
+			const hasTools = Object.keys(tools).length > 0;
+			const promptTools = hasTools && providerConfig.toolCalling === 'prompt';
+
 			// Tools are already in AI SDK format - use directly
-			const aiTools = Object.keys(tools).length > 0 ? tools : undefined;
+			const aiTools = hasTools && !promptTools ? tools : undefined;
 
 			// Convert messages to AI SDK v5 ModelMessage format
-			const modelMessages = convertToModelMessages(messages);
+			const modelMessages = convertToModelMessages(
+				promptTools
+					? await addToolPrompt(messages, getToolsForChoice(tools, toolChoice))
+					: messages,
+			);
 
 			logger.debug('AI SDK request prepared', {
 				messageCount: modelMessages.length,
 				hasTools: !!aiTools,
 				toolCount: aiTools ? Object.keys(aiTools).length : 0,
+				...getBodyLogFields(providerConfig, {messages: modelMessages}),
 			});
 
 			// Tools with needsApproval: false auto-execute in the loop
@@ -106,8 +138,11 @@ export async function handleChat(
 				model,
 				messages: modelMessages,
 				tools: aiTools,
+				toolChoice: aiTools ? toolChoice : undefined,
 				abortSignal: signal,
 				maxRetries,
+				maxOutputTokens,
+				providerOptions: getThinkingProviderOptions(providerConfig),
 				stopWhen: stepCountIs(MAX_TOOL_STEPS), // Allow up to MAX_TOOL_STEPS tool execution steps
 				// Can be used to add custom logging, metrics, or step tracking
 				onStepFinish: createOnStepFinishHandler(callbacks),
@@ -116,13 +151,20 @@ export async function handleChat(
 
 			// Get the full text from the result
 			const fullText = result.text;
+			const reasoning = result.reasoningText || undefined;
 
 			logger.debug('AI SDK response received', {
 				responseLength: fullText.length,
+				reasoningLength: reasoning?.length ?? 0,
 				hasToolCalls: !!(result.toolCalls && result.toolCalls.length > 0),
 				toolCallCount: result.toolCalls?.length || 0,
 			});
 
+			// Reasoning arrives before the answer it led to
+			if (reasoning) {
+				callbacks.onReasoning?.(reasoning);
+			}
+
 			// Send the complete text to the callback
 			if (fullText) {
 				callbacks.onToken?.(fullText);
@@ -196,6 +238,17 @@ export async function handleChat(
 				content = xmlResult.cleanedContent;
 			}
 
+			// Warn when the model ran out of output tokens mid-response
+			if (isTruncatedFinishReason(result.finishReason)) {
+				logger.warn('Chat response truncated at max output tokens', {
+					model: currentModel,
+					finishReason: result.finishReason,
+					responseLength: content.length,
+					correlationId,
+					provider: providerConfig.name,
+				});
+			}
+
 			// Calculate performance metrics
 			const finalMetrics = endMetrics(metrics);
 
@@ -204,6 +257,11 @@ export async function handleChat(
 				duration: `${finalMetrics.duration.toFixed(2)}ms`,
 				responseLength: content.length,
 				toolCallsFound: toolCalls.length,
+				usage: {
+					inputTokens: result.totalUsage.inputTokens,
+					outputTokens: result.totalUsage.outputTokens,
+				},
+				...getBodyLogFields(providerConfig, {response: content}),
 				memoryDelta: formatMemoryUsage(
 					finalMetrics.memoryUsage || process.memoryUsage(),
 				),
@@ -221,8 +279,14 @@ export async function handleChat(
 							content,
 							tool_calls: toolCalls.length > 0 ? toolCalls : undefined,
 						},
+						finish_reason: result.finishReason,
 					},
 				],
+				reasoning,
+				usage: {
+					prompt_tokens: result.totalUsage.inputTokens ?? 0,
+					completion_tokens: result.totalUsage.outputTokens ?? 0,
+				},
 				// Include auto-executed messages so they can be added to message history
 				autoExecutedMessages:
 					autoExecutedMessages.length > 0 ? autoExecutedMessages : undefined,
@@ -294,6 +358,7 @@ export async function handleChat(
 			logger.error('Chat request failed', {
 				model: currentModel,
 				duration: `${finalMetrics.duration.toFixed(2)}ms`,
+				statusCode: getErrorStatusCode(error),
 				error: error instanceof Error ? error.message : error,
 				errorName: error instanceof Error ? error.name : 'Unknown',
 				errorType: error?.constructor?.name || 'Unknown',
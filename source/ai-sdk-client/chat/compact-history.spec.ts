@@ -0,0 +1,128 @@
+import test from 'ava';
+import type {LLMChatResponse, LLMClient, Message} from '@/types/core';
+import type {Tokenizer} from '@/types/tokenization';
+import {compactHistory} from './compact-history.js';
+
+console.log('\ncompact-history.spec.ts');
+
+// Roughly four characters per token, with fixed framing overheads
+const tokenizer: Tokenizer = {
+	encode: text => Math.ceil(text.length / 4),
+	countTokens: message => Math.ceil((message.content || '').length / 4) + 4,
+	getMessageOverhead: () => 4,
+	getRequestOverhead: () => 3,
+	getName: () => 'test',
+};
+
+// Records each summarization request and answers with a short summary
+function createMockClient(summary = 'User is fixing the build.') {
+	const requests: Message[][] = [];
+	const client: Pick<LLMClient, 'chat'> = {
+		chat: async (messages): Promise<LLMChatResponse> => {
+			requests.push(messages);
+			return {choices: [{message: {role: 'assistant', content: summary}}]};
+		},
+	};
+	return {client, requests};
+}
+
+const filler = 'x'.repeat(400);
+
+const conversation: Message[] = [
+	{role: 'system', content: 'You are a coding assistant.'},
+	{role: 'user', content: `Why does the build fail? ${filler}`},
+	{
+		role: 'assistant',
+		content: '',
+		tool_calls: [
+			{
+				id: 'call_1',
+				function: {name: 'execute_bash', arguments: {command: 'npm run build'}},
+			},
+		],
+	},
+	{
+		role: 'tool',
+		content: `error TS2304: Cannot find name 'foo'. ${filler}`,
+		tool_call_id: 'call_1',
+		name: 'execute_bash',
+	},
+	{
+		role: 'assistant',
+		content: `The build fails on an undefined name. ${filler}`,
+	},
+	{role: 'user', content: `Fix it please. ${filler}`},
+	{role: 'assistant', content: 'Done, foo is now imported.'},
+	{role: 'user', content: 'Thanks, now run the tests.'},
+];
+
+test('compactHistory leaves a history that already fits alone', async t => {
+	const {client, requests} = createMockClient();
+
+	const result = await compactHistory(client, conversation, {
+		keepRecent: 2,
+		targetTokens: 100000,
+		tokenizer,
+	});
+
+	t.is(result.messages, conversation);
+	t.is(result.summarizedCount, 0);
+	t.is(requests.length, 0);
+});
+
+test('compactHistory summarizes old messages until the history fits', async t => {
+	const {client, requests} = createMockClient();
+
+	const result = await compactHistory(client, conversation, {
+		keepRecent: 2,
+		targetTokens: 100,
+		tokenizer,
+	});
+
+	t.true(requests.length > 0);
+	// The summarization request carries the transcript, tool calls included
+	const transcript = requests[0][1].content;
+	t.regex(transcript, /user: Why does the build fail\?/);
+	t.true(
+		transcript.includes(
+			'assistant called execute_bash({"command":"npm run build"})',
+		),
+	);
+
+	t.true(result.tokens <= 100);
+	t.true(result.messages.length < conversation.length);
+	t.is(result.summarizedCount, 5);
+	// System prompt first, then the summary, then the recent messages verbatim
+	t.deepEqual(result.messages[0], conversation[0]);
+	t.is(result.messages[1].role, 'system');
+	t.regex(result.messages[1].content, /User is fixing the build\./);
+	t.deepEqual(result.messages.slice(2), conversation.slice(-2));
+});
+
+test('compactHistory keeps a tool call with its results', async t => {
+	const {client} = createMockClient();
+
+	// The last three messages are the tool result and what follows it
+	const result = await compactHistory(client, conversation.slice(0, 6), {
+		keepRecent: 3,
+		targetTokens: 1,
+		tokenizer,
+	});
+
+	// The tool call moved into the recent messages along with its result
+	t.deepEqual(result.messages.slice(-4), conversation.slice(2, 6));
+	t.is(result.summarizedCount, 1);
+});
+
+test('compactHistory fails when the model returns no summary', async t => {
+	const {client} = createMockClient('  ');
+
+	await t.throwsAsync(
+		compactHistory(client, conversation, {
+			keepRecent: 2,
+			targetTokens: 100,
+			tokenizer,
+		}),
+		{message: 'The model returned an empty summary'},
+	);
+});
@@ -0,0 +1,137 @@
+import {countRequestTokens} from '@/tokenization/index';
+import type {LLMClient, Message} from '@/types/core';
+import type {Tokenizer} from '@/types/tokenization';
+import {getLogger} from '@/utils/logging';
+
+const SUMMARY_PREFIX = 'Summary of the earlier conversation:';
+
+const SUMMARY_INSTRUCTIONS = `You compress conversations between a user and a coding assistant.
+Summarize the transcript you are given so the assistant can continue the work without it.
+Keep the user's goals and constraints, decisions made, files read or changed, commands run and their important results, errors hit, and anything left to do.
+Leave out greetings and repetition. Reply with the summary only.`;
+
+export interface CompactHistoryOptions {
+	// Messages at the end that are always kept word for word
+	keepRecent: number;
+	// Token count the compacted history should fit in
+	targetTokens: number;
+	tokenizer: Tokenizer;
+	signal?: AbortSignal;
+}
+
+export interface CompactedHistory {
+	messages: Message[];
+	// How many of the original messages were folded into the summary
+	summarizedCount: number;
+	tokens: number;
+}
+
+function isSummary(message: Message): boolean {
+	return (
+		message.role === 'system' && message.content.startsWith(SUMMARY_PREFIX)
+	);
+}
+
+// One message as plain text, so any model can read the transcript
+function renderMessage(message: Message): string {
+	if (isSummary(message)) {
+		return message.content;
+	}
+	if (message.role === 'tool') {
+		return `Result of ${message.name ?? 'tool'}: ${message.content}`;
+	}
+	const lines = message.content ? [`${message.role}: ${message.content}`] : [];
+	for (const toolCall of message.tool_calls ?? []) {
+		const args = JSON.stringify(toolCall.function.arguments);
+		lines.push(`${message.role} called ${toolCall.function.name}(${args})`);
+	}
+	return lines.join('\n');
+}
+
+async function summarize(
+	client: Pick<LLMClient, 'chat'>,
+	messages: Message[],
+	signal?: AbortSignal,
+): Promise<string> {
+	const response = await client.chat(
+		[
+			{role: 'system', content: SUMMARY_INSTRUCTIONS},
+			{role: 'user', content: messages.map(renderMessage).join('\n\n')},
+		],
+		{},
+		{},
+		signal,
+	);
+	const summary = response.choices[0]?.message.content.trim();
+	if (!summary) {
+		throw new Error('The model returned an empty summary');
+	}
+	return summary;
+}
+
+/**
+ * Shrinks a conversation to fit a token target by asking the model to
+ * summarize its oldest messages into a single system note. Older messages are
+ * folded in a batch at a time, each time together with the previous summary,
+ * until the history fits or only the summary is left to fold.
+ *
+ * The leading system prompt and the last `keepRecent` messages are never
+ * summarized, and a tool call is never separated from its results. The result
+ * can still be over the target if those alone don't fit.
+ */
+export async function compactHistory(
+	client: Pick<LLMClient, 'chat'>,
+	messages: Message[],
+	{keepRecent, targetTokens, tokenizer, signal}: CompactHistoryOptions,
+): Promise<CompactedHistory> {
+	let start = 0;
+	while (
+		start < messages.length &&
+		messages[start].role === 'system' &&
+		!isSummary(messages[start])
+	) {
+		start++;
+	}
+	const systemPrompt = messages.slice(0, start);
+	// Keep a tool call together with its results
+	let recentStart = Math.max(start, messages.length - keepRecent);
+	while (recentStart > start && messages[recentStart]?.role === 'tool') {
+		recentStart--;
+	}
+	const recent = messages.slice(recentStart);
+	let older = messages.slice(start, recentStart);
+
+	let summarizedCount = 0;
+	let compacted = messages;
+	let tokens = countRequestTokens(compacted, tokenizer);
+	while (tokens > targetTokens && older.some(message => !isSummary(message))) {
+		// Fold the older half, or everything when there is little left, taking
+		// at least one message besides a previous summary
+		let batchEnd =
+			older.length <= 2 ? older.length : Math.ceil(older.length / 2);
+		batchEnd = Math.max(batchEnd, isSummary(older[0]) ? 2 : 1);
+		while (batchEnd < older.length && older[batchEnd].role === 'tool') {
+			batchEnd++;
+		}
+		const batch = older.slice(0, batchEnd);
+
+		const summary = await summarize(client, batch, signal);
+		summarizedCount += batch.filter(message => !isSummary(message)).length;
+		older = [
+			{role: 'system', content: `${SUMMARY_PREFIX}\n${summary}`},
+			...older.slice(batchEnd),
+		];
+		compacted = [...systemPrompt, ...older, ...recent];
+		tokens = countRequestTokens(compacted, tokenizer);
+	}
+
+	if (summarizedCount > 0) {
+		getLogger().info('Compacted conversation history', {
+			summarizedCount,
+			originalTokens: countRequestTokens(messages, tokenizer),
+			tokens,
+			targetTokens,
+		});
+	}
+	return {messages: compacted, summarizedCount, tokens};
+}
@@ -0,0 +1,37 @@
+import test from 'ava';
+import type {LLMChatResponse} from '@/types/index';
+import {isTruncatedFinishReason, wasTruncated} from './finish-reason.js';
+
+function createResponse(finishReason?: string): LLMChatResponse {
+	return {
+		choices: [
+			{
+				message: {role: 'assistant', content: 'The answer is'},
+				finish_reason: finishReason,
+			},
+		],
+	};
+}
+
+test('wasTruncated returns true for finish_reason "length"', t => {
+	t.true(wasTruncated(createResponse('length')));
+});
+
+test('wasTruncated returns true for finish_reason "max_tokens"', t => {
+	t.true(wasTruncated(createResponse('max_tokens')));
+});
+
+test('wasTruncated returns false for a normal stop', t => {
+	t.false(wasTruncated(createResponse('stop')));
+	t.false(wasTruncated(createResponse('tool-calls')));
+});
+
+test('wasTruncated returns false when finish_reason is missing', t => {
+	t.false(wasTruncated(createResponse()));
+	t.false(wasTruncated({choices: []}));
+});
+
+test('isTruncatedFinishReason handles undefined', t => {
+	t.false(isTruncatedFinishReason(undefined));
+	t.true(isTruncatedFinishReason('length'));
+});
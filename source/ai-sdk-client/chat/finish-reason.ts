@@ -0,0 +1,26 @@
+import type {LLMChatResponse} from '@/types/index';
+
+/**
+ * Finish reasons that indicate the model stopped because it hit the
+ * output token limit. AI SDK reports 'length'; some OpenAI-compatible
+ * providers pass through Anthropic's 'max_tokens' instead.
+ */
+const TRUNCATED_FINISH_REASONS = new Set(['length', 'max_tokens']);
+
+/**
+ * Check whether a raw finish reason means the response was cut off
+ */
+export function isTruncatedFinishReason(finishReason?: string): boolean {
+	return !!finishReason && TRUNCATED_FINISH_REASONS.has(finishReason);
+}
+
+/**
+ * Check whether a chat response was truncated at max output tokens.
+ *
+ * A truncated response usually ends mid-sentence (or mid tool call). Callers
+ * can continue the generation by appending the partial assistant message and
+ * a short "continue" user message, then calling chat() again.
+ */
+export function wasTruncated(response: LLMChatResponse): boolean {
+	return isTruncatedFinishReason(response.choices[0]?.finish_reason);
+}
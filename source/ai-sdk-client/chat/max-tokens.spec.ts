@@ -0,0 +1,132 @@
+import test from 'ava';
+import {jsonSchema, tool} from '@/types/core';
+import type {AIProviderConfig, Message} from '@/types/index';
+import type {Tokenizer} from '@/types/tokenization';
+import {getAutoMaxTokens, resolveAutoMaxTokens} from './max-tokens.js';
+
+console.log('\nmax-tokens.spec.ts');
+
+// Roughly four characters per token, with fixed framing overheads
+const tokenizer: Tokenizer = {
+	encode: text => Math.ceil(text.length / 4),
+	countTokens: message => Math.ceil((message.content || '').length / 4) + 4,
+	getMessageOverhead: () => 4,
+	getRequestOverhead: () => 3,
+	getName: () => 'test',
+};
+
+function promptOfTokens(tokens: number): Message[] {
+	return [{role: 'user', content: 'abcd'.repeat(tokens)}];
+}
+
+test('getAutoMaxTokens uses the remaining context for a small-context model', async t => {
+	const maxTokens = await getAutoMaxTokens({
+		messages: promptOfTokens(3000),
+		tools: {},
+		tokenizer,
+		contextSize: 4096,
+		outputLimit: 4096,
+	});
+
+	// 4096 context - (3000 content + 4 message + 3 request overhead)
+	t.is(maxTokens, 1089);
+});
+
+test('getAutoMaxTokens uses the output cap for a large-context model', async t => {
+	const maxTokens = await getAutoMaxTokens({
+		messages: promptOfTokens(3000),
+		tools: {},
+		tokenizer,
+		contextSize: 200_000,
+		outputLimit: 8192,
+	});
+
+	t.is(maxTokens, 8192);
+});
+
+test('getAutoMaxTokens uses the remaining context when the output cap is unknown', async t => {
+	const maxTokens = await getAutoMaxTokens({
+		messages: promptOfTokens(1000),
+		tools: {},
+		tokenizer,
+		contextSize: 8192,
+	});
+
+	t.is(maxTokens, 8192 - 1007);
+});
+
+test('getAutoMaxTokens counts tool definitions against the context', async t => {
+	const withoutTools = await getAutoMaxTokens({
+		messages: promptOfTokens(1000),
+		tools: {},
+		tokenizer,
+		contextSize: 8192,
+	});
+	const withTools = await getAutoMaxTokens({
+		messages: promptOfTokens(1000),
+		tools: {
+			read_file: tool({
+				description: 'Read the contents of a file from disk',
+				inputSchema: jsonSchema<{path: string}>({
+					type: 'object',
+					properties: {path: {type: 'string'}},
+					required: ['path'],
+				}),
+			}),
+		},
+		tokenizer,
+		contextSize: 8192,
+	});
+
+	t.true(withTools! < withoutTools!);
+});
+
+test('getAutoMaxTokens leaves the choice to the provider when unknown or full', async t => {
+	t.is(
+		await getAutoMaxTokens({
+			messages: promptOfTokens(100),
+			tools: {},
+			tokenizer,
+			contextSize: 0,
+			outputLimit: 4096,
+		}),
+		undefined,
+	);
+	t.is(
+		await getAutoMaxTokens({
+			messages: promptOfTokens(5000),
+			tools: {},
+			tokenizer,
+			contextSize: 4096,
+		}),
+		undefined,
+	);
+});
+
+test('resolveAutoMaxTokens sends nothing unless the provider opts in', async t => {
+	const providerConfig: AIProviderConfig = {
+		name: 'TestProvider',
+		type: 'openai',
+		models: ['test-model'],
+		config: {},
+	};
+	const params = {
+		messages: promptOfTokens(100),
+		tools: {},
+		contextSize: 200_000,
+		outputLimit: 8192,
+	};
+
+	t.is(
+		await resolveAutoMaxTokens(providerConfig, 'test-model', params),
+		undefined,
+	);
+	t.is(
+		await resolveAutoMaxTokens(
+			{...providerConfig, autoMaxTokens: true},
+			'test-model',
+			params,
+		),
+		8192,
+	);
+});
@@ -0,0 +1,93 @@
+import {asSchema} from 'ai';
+import {createTokenizer, getAvailableOutputBudget} from '@/tokenization/index';
+import type {AIProviderConfig, AISDKCoreTool, Message} from '@/types/index';
+import type {Tokenizer} from '@/types/tokenization';
+
+export interface AutoMaxTokensParams {
+	messages: Message[];
+	tools: Record<string, AISDKCoreTool>;
+	tokenizer: Tokenizer;
+	// Model context window; 0 when unknown
+	contextSize: number;
+	// Model's maximum output tokens, when known
+	outputLimit?: number;
+}
+
+/**
+ * Tokens taken up by one tool's name, description and input schema
+ */
+export async function countToolTokens(
+	name: string,
+	coreTool: AISDKCoreTool,
+	tokenizer: Tokenizer,
+): Promise<number> {
+	const parameters = await asSchema(coreTool.inputSchema).jsonSchema;
+	return tokenizer.encode(
+		JSON.stringify({name, description: coreTool.description, parameters}),
+	);
+}
+
+/**
+ * Tokens taken up by tool names, descriptions and input schemas
+ */
+async function countToolDefinitionTokens(
+	tools: Record<string, AISDKCoreTool>,
+	tokenizer: Tokenizer,
+): Promise<number> {
+	let total = 0;
+	for (const [name, coreTool] of Object.entries(tools)) {
+		total += await countToolTokens(name, coreTool, tokenizer);
+	}
+	return total;
+}
+
+/**
+ * Picks max output tokens for a request: the model's output cap, or whatever
+ * is left of the context window after the prompt and tool definitions if
+ * that is smaller. Returns undefined when the context window is unknown or
+ * already full, leaving the choice to the provider.
+ */
+export async function getAutoMaxTokens({
+	messages,
+	tools,
+	tokenizer,
+	contextSize,
+	outputLimit,
+}: AutoMaxTokensParams): Promise<number | undefined> {
+	if (contextSize <= 0) {
+		return undefined;
+	}
+
+	const toolTokens = await countToolDefinitionTokens(tools, tokenizer);
+	const budget = getAvailableOutputBudget(
+		messages,
+		tokenizer,
+		contextSize,
+		toolTokens,
+	);
+	const maxTokens =
+		outputLimit && outputLimit > 0 ? Math.min(outputLimit, budget) : budget;
+
+	return maxTokens > 0 ? maxTokens : undefined;
+}
+
+/**
+ * Max output tokens to send for a provider that opted in with
+ * `autoMaxTokens`, or undefined to send none
+ */
+export async function resolveAutoMaxTokens(
+	providerConfig: AIProviderConfig,
+	model: string,
+	params: Omit<AutoMaxTokensParams, 'tokenizer'>,
+): Promise<number | undefined> {
+	if (!providerConfig.autoMaxTokens) {
+		return undefined;
+	}
+
+	const tokenizer = createTokenizer(providerConfig.name, model);
+	try {
+		return await getAutoMaxTokens({...params, tokenizer});
+	} finally {
+		tokenizer.free?.();
+	}
+}
@@ -0,0 +1,124 @@
+import test from 'ava';
+import {jsonSchema, tool} from '@/types/core';
+import type {Message} from '@/types/index';
+import {addToolPrompt, renderToolPrompt} from './prompt-tools.js';
+
+console.log('\nprompt-tools.spec.ts');
+
+const tools = {
+	read_file: tool({
+		description: 'Read the contents of a file from disk',
+		inputSchema: jsonSchema<{path: string; start_line?: number}>({
+			type: 'object',
+			properties: {
+				path: {type: 'string', description: 'Path to the file'},
+				start_line: {type: 'number'},
+			},
+			required: ['path'],
+		}),
+	}),
+	list_directory: tool({
+		description: 'List the files in a directory',
+		inputSchema: jsonSchema<Record<string, never>>({
+			type: 'object',
+			properties: {},
+		}),
+	}),
+};
+
+test('renderToolPrompt describes each tool and the call format', async t => {
+	const prompt = await renderToolPrompt(tools);
+
+	t.true(prompt.includes('<tool_call name="tool_name">'));
+	t.true(prompt.includes('### read_file'));
+	t.true(prompt.includes('Read the contents of a file from disk'));
+	t.true(prompt.includes('- path (string, required): Path to the file'));
+	t.true(prompt.includes('- start_line (number, optional)'));
+	t.true(prompt.includes('### list_directory\nList the files in a directory'));
+	t.true(prompt.includes('Parameters: none'));
+});
+
+test('addToolPrompt appends the tools to the existing system message', async t => {
+	const messages: Message[] = [
+		{role: 'system', content: 'You are a coding assistant.'},
+		{role: 'user', content: 'Hi'},
+	];
+
+	const result = await addToolPrompt(messages, tools);
+
+	t.is(result.length, 2);
+	t.is(result[0].role, 'system');
+	t.true(result[0].content.startsWith('You are a coding assistant.\n\n'));
+	t.true(result[0].content.includes('### read_file'));
+	t.deepEqual(result[1], messages[1]);
+});
+
+test('addToolPrompt adds a system message when there is none', async t => {
+	const result = await addToolPrompt([{role: 'user', content: 'Hi'}], tools);
+
+	t.is(result.length, 2);
+	t.is(result[0].role, 'system');
+	t.true(result[0].content.includes('### list_directory'));
+	t.is(result[1].role, 'user');
+});
+
+test('addToolPrompt turns earlier tool calls and results into text', async t => {
+	const messages: Message[] = [
+		{role: 'user', content: 'Show a.ts'},
+		{
+			role: 'assistant',
+			content: 'Reading it.',
+			tool_calls: [
+				{id: 'call_1', function: {name: 'read_file', arguments: {path: 'a.ts'}}},
+			],
+		},
+		{
+			role: 'tool',
+			content: 'const a = 1;',
+			tool_call_id: 'call_1',
+			name: 'read_file',
+		},
+	];
+
+	const result = await addToolPrompt(messages, tools);
+
+	t.deepEqual(result.slice(1), [
+		{role: 'user', content: 'Show a.ts'},
+		{
+			role: 'assistant',
+			content:
+				'Reading it.\n<tool_call name="read_file">{"path":"a.ts"}</tool_call>',
+		},
+		{role: 'user', content: 'Result of read_file:\nconst a = 1;'},
+	]);
+});
+
+test('addToolPrompt rewrites the history without a tool prompt when there are no tools', async t => {
+	const messages: Message[] = [
+		{role: 'system', content: 'Be helpful.'},
+		{
+			role: 'assistant',
+			content: '',
+			tool_calls: [
+				{id: 'call_1', function: {name: 'read_file', arguments: {path: 'a.ts'}}},
+			],
+		},
+		{
+			role: 'tool',
+			content: 'const a = 1;',
+			tool_call_id: 'call_1',
+			name: 'read_file',
+		},
+	];
+
+	const result = await addToolPrompt(messages, {});
+
+	t.deepEqual(result, [
+		{role: 'system', content: 'Be helpful.'},
+		{
+			role: 'assistant',
+			content: '<tool_call name="read_file">{"path":"a.ts"}</tool_call>',
+		},
+		{role: 'user', content: 'Result of read_file:\nconst a = 1;'},
+	]);
+});
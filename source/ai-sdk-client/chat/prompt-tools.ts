@@ -0,0 +1,106 @@
+import {asSchema} from 'ai';
+import type {AISDKCoreTool, Message, ToolCall} from '@/types/index';
+
+interface ParameterSchema {
+	type?: string | string[];
+	description?: string;
+}
+
+/**
+ * One line per parameter: name, type, whether it is required, description
+ */
+function renderParameters(schema: Record<string, unknown>): string[] {
+	const properties = (schema.properties ?? {}) as Record<
+		string,
+		ParameterSchema
+	>;
+	const required = new Set((schema.required ?? []) as string[]);
+
+	return Object.entries(properties).map(([name, property]) => {
+		const type = Array.isArray(property.type)
+			? property.type.join(' | ')
+			: (property.type ?? 'any');
+		const details = [type, required.has(name) ? 'required' : 'optional'];
+		const description = property.description
+			? `: ${property.description}`
+			: '';
+		return `- ${name} (${details.join(', ')})${description}`;
+	});
+}
+
+function renderToolCall(toolCall: ToolCall): string {
+	return `<tool_call name="${toolCall.function.name}">${JSON.stringify(toolCall.function.arguments)}</tool_call>`;
+}
+
+/**
+ * Describes the tools and the <tool_call> format for a model without native
+ * tool calling, which is told to write calls as text for the XML parser
+ */
+export async function renderToolPrompt(
+	tools: Record<string, AISDKCoreTool>,
+): Promise<string> {
+	const sections = [
+		'## Tools',
+		'You can call the tools below. To call one, write a tool_call tag with the tool name and its arguments as a JSON object, for example:',
+		'<tool_call name="tool_name">{"parameter": "value"}</tool_call>',
+		'Write one tag per call and nothing else inside it. Each result comes back in the next message.',
+	];
+
+	for (const [name, coreTool] of Object.entries(tools)) {
+		const schema = await asSchema(coreTool.inputSchema).jsonSchema;
+		const parameters = renderParameters(schema as Record<string, unknown>);
+		sections.push(
+			[
+				`### ${name}`,
+				...(coreTool.description ? [coreTool.description] : []),
+				parameters.length > 0
+					? `Parameters:\n${parameters.join('\n')}`
+					: 'Parameters: none',
+			].join('\n'),
+		);
+	}
+
+	return sections.join('\n\n');
+}
+
+/**
+ * Rewrites a conversation for prompt-based tool calling: the tool prompt is
+ * added to the system message, earlier tool calls become <tool_call> text and
+ * tool results become user messages, since the provider has no tool roles.
+ * With no tools the history is still rewritten but no tool prompt is added.
+ */
+export async function addToolPrompt(
+	messages: Message[],
+	tools: Record<string, AISDKCoreTool>,
+): Promise<Message[]> {
+	const converted = messages.map((message): Message => {
+		if (message.role === 'assistant' && message.tool_calls?.length) {
+			const calls = message.tool_calls.map(renderToolCall).join('\n');
+			return {
+				role: 'assistant',
+				content: message.content ? `${message.content}\n${calls}` : calls,
+			};
+		}
+		if (message.role === 'tool') {
+			return {
+				role: 'user',
+				content: `Result of ${message.name ?? 'tool'}:\n${message.content}`,
+			};
+		}
+		return message;
+	});
+
+	if (Object.keys(tools).length === 0) {
+		return converted;
+	}
+
+	const toolPrompt = await renderToolPrompt(tools);
+	const [first, ...rest] = converted;
+	if (first?.role === 'system') {
+		return [
+			{...first, content: `${first.content}\n\n${toolPrompt}`},
+			...rest,
+		];
+	}
+	return [{role: 'system', content: toolPrompt}, ...converted];
+}
@@ -0,0 +1,84 @@
+import test from 'ava';
+import {APICallError, RetryError} from 'ai';
+import type {AIProviderConfig} from '@/types/index';
+import {
+	getBodyLogFields,
+	getErrorStatusCode,
+	redactProviderSecrets,
+} from './request-logging.js';
+
+const API_KEY = 'sk-test-1234567890abcdef';
+
+function createProviderConfig(logBodies?: boolean): AIProviderConfig {
+	return {
+		name: 'test-provider',
+		type: 'openai',
+		models: ['test-model'],
+		logBodies,
+		config: {baseURL: 'https://api.example.com/v1', apiKey: API_KEY},
+	};
+}
+
+test('redactProviderSecrets never emits the api key', t => {
+	const payload = {
+		messages: [
+			{role: 'user', content: `my key is ${API_KEY}, please keep it safe`},
+			{role: 'assistant', content: [{text: `Authorization: Bearer ${API_KEY}`}]},
+		],
+		headers: {authorization: API_KEY},
+	};
+
+	const redacted = redactProviderSecrets(payload, createProviderConfig());
+	const serialized = JSON.stringify(redacted);
+
+	t.false(serialized.includes(API_KEY));
+	t.true(serialized.includes('my key is [REDACTED], please keep it safe'));
+});
+
+test('redactProviderSecrets leaves values alone without an api key', t => {
+	const config = createProviderConfig();
+	config.config.apiKey = undefined;
+	const payload = {content: 'hello'};
+
+	t.deepEqual(redactProviderSecrets(payload, config), payload);
+});
+
+test('getBodyLogFields omits bodies unless logBodies is enabled', t => {
+	t.deepEqual(
+		getBodyLogFields(createProviderConfig(), {messages: ['secret plans']}),
+		{},
+	);
+	t.deepEqual(
+		getBodyLogFields(createProviderConfig(false), {messages: ['secret plans']}),
+		{},
+	);
+});
+
+test('getBodyLogFields includes bodies with the api key redacted when enabled', t => {
+	const fields = getBodyLogFields(createProviderConfig(true), {
+		response: `Use ${API_KEY} to authenticate`,
+	});
+
+	t.deepEqual(fields, {response: 'Use [REDACTED] to authenticate'});
+});
+
+test('getErrorStatusCode reads the status from API call errors', t => {
+	const error = new APICallError({
+		message: 'Unauthorized',
+		url: 'https://api.example.com/v1/chat/completions',
+		requestBodyValues: {},
+		statusCode: 401,
+	});
+
+	t.is(getErrorStatusCode(error), 401);
+	t.is(
+		getErrorStatusCode(
+			new RetryError({message: 'failed', reason: 'maxRetriesExceeded', errors: [error]}),
+		),
+		401,
+	);
+});
+
+test('getErrorStatusCode returns undefined for other errors', t => {
+	t.is(getErrorStatusCode(new Error('socket hang up')), undefined);
+});
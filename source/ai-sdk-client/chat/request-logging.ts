@@ -0,0 +1,70 @@
+import {APICallError} from 'ai';
+import type {AIProviderConfig} from '@/types/index';
+import {extractRootError} from '../error-handling/error-extractor.js';
+
+const REDACTED = '[REDACTED]';
+
+function redactString(value: string, secrets: string[]): string {
+	return secrets.reduce(
+		(redacted, secret) => redacted.split(secret).join(REDACTED),
+		value,
+	);
+}
+
+function redactDeep(value: unknown, secrets: string[]): unknown {
+	if (typeof value === 'string') {
+		return redactString(value, secrets);
+	}
+	if (Array.isArray(value)) {
+		return value.map(item => redactDeep(item, secrets));
+	}
+	if (typeof value === 'object' && value !== null) {
+		return Object.fromEntries(
+			Object.entries(value).map(([key, item]) => [
+				key,
+				redactDeep(item, secrets),
+			]),
+		);
+	}
+	return value;
+}
+
+/**
+ * Replaces every occurrence of the provider's API key in a value that is about
+ * to be logged, however deeply it is nested
+ */
+export function redactProviderSecrets(
+	value: unknown,
+	providerConfig: AIProviderConfig,
+): unknown {
+	const {apiKey} = providerConfig.config;
+	// Trivially short keys would redact unrelated text
+	const secrets = apiKey && apiKey.length >= 4 ? [apiKey] : [];
+	return secrets.length > 0 ? redactDeep(value, secrets) : value;
+}
+
+/**
+ * Request/response payloads for a log entry. Message contents stay out of the
+ * logs unless the provider opts in with `logBodies`, and the API key is always
+ * redacted.
+ */
+export function getBodyLogFields(
+	providerConfig: AIProviderConfig,
+	bodies: Record<string, unknown>,
+): Record<string, unknown> {
+	if (!providerConfig.logBodies) {
+		return {};
+	}
+	return redactProviderSecrets(bodies, providerConfig) as Record<
+		string,
+		unknown
+	>;
+}
+
+/**
+ * HTTP status code of a failed provider call, if the error carries one
+ */
+export function getErrorStatusCode(error: unknown): number | undefined {
+	const rootError = extractRootError(error);
+	return APICallError.isInstance(rootError) ? rootError.statusCode : undefined;
+}
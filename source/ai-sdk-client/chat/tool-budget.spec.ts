@@ -0,0 +1,127 @@
+import test from 'ava';
+import {jsonSchema, tool} from '@/types/core';
+import type {AIProviderConfig, AISDKCoreTool, Message} from '@/types/index';
+import type {Tokenizer} from '@/types/tokenization';
+import {countToolTokens} from './max-tokens.js';
+import {
+	rankToolsByUsage,
+	resolveToolBudget,
+	trimToolsToBudget,
+} from './tool-budget.js';
+
+console.log('\ntool-budget.spec.ts');
+
+// Roughly four characters per token, with fixed framing overheads
+const tokenizer: Tokenizer = {
+	encode: text => Math.ceil(text.length / 4),
+	countTokens: message => Math.ceil((message.content || '').length / 4) + 4,
+	getMessageOverhead: () => 4,
+	getRequestOverhead: () => 3,
+	getName: () => 'test',
+};
+
+function makeTool(description: string): AISDKCoreTool {
+	return tool({
+		description,
+		inputSchema: jsonSchema<{path: string}>({
+			type: 'object',
+			properties: {path: {type: 'string'}},
+			required: ['path'],
+		}),
+	});
+}
+
+const tools: Record<string, AISDKCoreTool> = {
+	read_file: makeTool('Read the contents of a file from disk'),
+	write_file: makeTool('Write contents to a file on disk'),
+	github_create_issue: makeTool(
+		'Create an issue in a GitHub repository with a title, body and labels',
+	),
+	github_list_pulls: makeTool(
+		'List the open pull requests of a GitHub repository, newest first',
+	),
+};
+
+async function tokensFor(names: string[]): Promise<number> {
+	let total = 0;
+	for (const name of names) {
+		total += await countToolTokens(name, tools[name], tokenizer);
+	}
+	return total;
+}
+
+test('trimToolsToBudget keeps every tool when they fit', async t => {
+	const budget = await tokensFor(Object.keys(tools));
+
+	const result = await trimToolsToBudget({tools, tokenizer, budget});
+
+	t.deepEqual(Object.keys(result.tools), Object.keys(tools));
+	t.deepEqual(result.dropped, []);
+	t.is(result.tokens, budget);
+});
+
+test('trimToolsToBudget trims an over-budget tool set to fit', async t => {
+	const budget = await tokensFor(['read_file', 'write_file']);
+
+	const result = await trimToolsToBudget({tools, tokenizer, budget});
+
+	t.deepEqual(Object.keys(result.tools), ['read_file', 'write_file']);
+	t.deepEqual(result.dropped, ['github_create_issue', 'github_list_pulls']);
+	t.true(result.tokens <= budget);
+});
+
+test('trimToolsToBudget keeps priority tools first', async t => {
+	const budget = await tokensFor(['read_file', 'github_list_pulls']);
+
+	const result = await trimToolsToBudget({
+		tools,
+		tokenizer,
+		budget,
+		priority: ['github_list_pulls', 'not_a_tool'],
+	});
+
+	t.deepEqual(Object.keys(result.tools), ['read_file', 'github_list_pulls']);
+	t.deepEqual(result.dropped, ['write_file', 'github_create_issue']);
+});
+
+test('rankToolsByUsage orders tools by how often they were called', t => {
+	const call = (id: string, name: string) => ({
+		id,
+		function: {name, arguments: {}},
+	});
+	const messages: Message[] = [
+		{role: 'user', content: 'hi'},
+		{role: 'assistant', content: '', tool_calls: [call('1', 'read_file')]},
+		{role: 'tool', content: 'x', tool_call_id: '1', name: 'read_file'},
+		{
+			role: 'assistant',
+			content: '',
+			tool_calls: [call('2', 'github_list_pulls'), call('3', 'github_list_pulls')],
+		},
+	];
+
+	t.deepEqual(rankToolsByUsage(messages), ['github_list_pulls', 'read_file']);
+});
+
+test('resolveToolBudget sends every tool unless the provider sets a budget', async t => {
+	const providerConfig: AIProviderConfig = {
+		name: 'TestProvider',
+		type: 'openai',
+		models: ['test-model'],
+		config: {},
+	};
+	const params = {
+		messages: [{role: 'user', content: 'hello'}] as Message[],
+		tools,
+		contextSize: 200_000,
+	};
+
+	t.is(await resolveToolBudget(providerConfig, 'test-model', params), tools);
+
+	const trimmed = await resolveToolBudget(
+		{...providerConfig, maxToolTokens: 0},
+		'test-model',
+		params,
+	);
+	t.deepEqual(trimmed, {});
+});
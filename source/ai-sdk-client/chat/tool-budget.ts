@@ -0,0 +1,129 @@
+import {countRequestTokens, createTokenizer} from '@/tokenization/index';
+import type {AIProviderConfig, AISDKCoreTool, Message} from '@/types/index';
+import type {Tokenizer} from '@/types/tokenization';
+import {getLogger} from '@/utils/logging';
+import {countToolTokens} from './max-tokens.js';
+
+export interface ToolBudgetParams {
+	tools: Record<string, AISDKCoreTool>;
+	tokenizer: Tokenizer;
+	// Tokens the tool definitions may use in total
+	budget: number;
+	// Tool names to keep first, most relevant first. Other tools follow in
+	// their original order.
+	priority?: string[];
+}
+
+export interface TrimmedTools {
+	tools: Record<string, AISDKCoreTool>;
+	dropped: string[];
+	// Tokens used by the tools that were kept
+	tokens: number;
+}
+
+/**
+ * Keeps as many tool definitions as fit in the budget, taking them in
+ * priority order and skipping any that would go over
+ */
+export async function trimToolsToBudget({
+	tools,
+	tokenizer,
+	budget,
+	priority = [],
+}: ToolBudgetParams): Promise<TrimmedTools> {
+	const names = Object.keys(tools);
+	const ranked = [
+		...priority.filter(name => name in tools),
+		...names.filter(name => !priority.includes(name)),
+	];
+
+	const kept = new Set<string>();
+	const dropped: string[] = [];
+	let tokens = 0;
+	for (const name of ranked) {
+		const toolTokens = await countToolTokens(name, tools[name], tokenizer);
+		if (tokens + toolTokens <= budget) {
+			kept.add(name);
+			tokens += toolTokens;
+		} else {
+			dropped.push(name);
+		}
+	}
+
+	// Keep the original order for the tools that stay
+	const trimmed: Record<string, AISDKCoreTool> = {};
+	for (const name of names) {
+		if (kept.has(name)) {
+			trimmed[name] = tools[name];
+		}
+	}
+	return {tools: trimmed, dropped, tokens};
+}
+
+/**
+ * Tool names ordered by how often the conversation has called them, most
+ * used first
+ */
+export function rankToolsByUsage(messages: Message[]): string[] {
+	const counts = new Map<string, number>();
+	for (const message of messages) {
+		for (const toolCall of message.tool_calls ?? []) {
+			const name = toolCall.function.name;
+			counts.set(name, (counts.get(name) ?? 0) + 1);
+		}
+	}
+	return Array.from(counts.keys()).sort(
+		(a, b) => (counts.get(b) ?? 0) - (counts.get(a) ?? 0),
+	);
+}
+
+/**
+ * Tools to send for a provider with `maxToolTokens` set: trimmed to that
+ * budget, or to what is left of the context window after the messages if
+ * that is smaller. Tools the conversation already uses are kept first.
+ * Without `maxToolTokens`, every tool is sent.
+ */
+export async function resolveToolBudget(
+	providerConfig: AIProviderConfig,
+	model: string,
+	params: {
+		messages: Message[];
+		tools: Record<string, AISDKCoreTool>;
+		// Model context window; 0 when unknown
+		contextSize: number;
+	},
+): Promise<Record<string, AISDKCoreTool>> {
+	const {maxToolTokens} = providerConfig;
+	if (maxToolTokens === undefined || Object.keys(params.tools).length === 0) {
+		return params.tools;
+	}
+
+	const tokenizer = createTokenizer(providerConfig.name, model);
+	try {
+		let budget = maxToolTokens;
+		if (params.contextSize > 0) {
+			const remaining =
+				params.contextSize - countRequestTokens(params.messages, tokenizer);
+			budget = Math.min(budget, Math.max(0, remaining));
+		}
+
+		const {tools, dropped, tokens} = await trimToolsToBudget({
+			tools: params.tools,
+			tokenizer,
+			budget,
+			priority: rankToolsByUsage(params.messages),
+		});
+		if (dropped.length > 0) {
+			getLogger().warn('Dropped tool definitions to fit the token budget', {
+				provider: providerConfig.name,
+				model,
+				budget,
+				keptTokens: tokens,
+				dropped,
+			});
+		}
+		return tools;
+	} finally {
+		tokenizer.free?.();
+	}
+}
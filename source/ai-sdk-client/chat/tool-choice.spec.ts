@@ -0,0 +1,43 @@
+import test from 'ava';
+import {jsonSchema, tool} from '@/types/core';
+import {getToolsForChoice} from './tool-choice.js';
+
+console.log('\ntool-choice.spec.ts');
+
+const readFile = tool({
+	description: 'Read a file',
+	inputSchema: jsonSchema<{path: string}>({
+		type: 'object',
+		properties: {path: {type: 'string'}},
+		required: ['path'],
+	}),
+});
+const listDirectory = tool({
+	description: 'List a directory',
+	inputSchema: jsonSchema<Record<string, never>>({
+		type: 'object',
+		properties: {},
+	}),
+});
+const tools = {read_file: readFile, list_directory: listDirectory};
+
+test('getToolsForChoice keeps every tool for auto, required or no choice', t => {
+	t.is(getToolsForChoice(tools), tools);
+	t.is(getToolsForChoice(tools, 'auto'), tools);
+	t.is(getToolsForChoice(tools, 'required'), tools);
+});
+
+test('getToolsForChoice drops every tool for none', t => {
+	t.deepEqual(getToolsForChoice(tools, 'none'), {});
+});
+
+test('getToolsForChoice keeps only a named tool', t => {
+	t.deepEqual(
+		getToolsForChoice(tools, {type: 'tool', toolName: 'read_file'}),
+		{read_file: readFile},
+	);
+	t.deepEqual(
+		getToolsForChoice(tools, {type: 'tool', toolName: 'write_file'}),
+		{},
+	);
+});
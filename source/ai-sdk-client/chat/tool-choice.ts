@@ -0,0 +1,20 @@
+import type {AISDKCoreTool, ToolChoice} from '@/types/index';
+
+/**
+ * The tools a model may call under a tool choice, for providers that can't
+ * enforce one natively: none for 'none', just the named tool for a specific
+ * choice, otherwise all of them. A named tool that isn't available leaves none.
+ */
+export function getToolsForChoice(
+	tools: Record<string, AISDKCoreTool>,
+	toolChoice?: ToolChoice,
+): Record<string, AISDKCoreTool> {
+	if (toolChoice === 'none') {
+		return {};
+	}
+	if (typeof toolChoice === 'object') {
+		const chosen = tools[toolChoice.toolName];
+		return chosen ? {[toolChoice.toolName]: chosen} : {};
+	}
+	return tools;
+}
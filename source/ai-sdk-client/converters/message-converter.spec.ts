@@ -66,6 +66,20 @@ test('convertToModelMessages converts system message', t => {
 	t.is(result[0].content, 'You are a helpful assistant');
 });
 
+test('convertToModelMessages sends developer messages as system instructions', t => {
+	const messages: Message[] = [
+		{
+			role: 'developer',
+			content: 'You are a helpful assistant',
+		},
+	];
+
+	const result = convertToModelMessages(messages);
+	t.is(result.length, 1);
+	t.is(result[0].role, 'system');
+	t.is(result[0].content, 'You are a helpful assistant');
+});
+
 test('convertToModelMessages converts user message', t => {
 	const messages: Message[] = [
 		{
@@ -52,7 +52,9 @@ export function convertToModelMessages(messages: Message[]): ModelMessage[] {
 			};
 		}
 
-		if (msg.role === 'system') {
+		// The AI SDK has no developer role; the provider maps system back to
+		// developer for models that need it
+		if (msg.role === 'system' || msg.role === 'developer') {
 			return {
 				role: 'system',
 				content: msg.content,
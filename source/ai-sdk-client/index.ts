@@ -1,5 +1,6 @@
 // Main client export
 export {AISDKClient} from './ai-sdk-client.js';
+export {wasTruncated} from './chat/finish-reason.js';
 export {isEmptyAssistantMessage} from './converters/message-converter.js';
 // Exported for testing purposes
 export {parseAPIError} from './error-handling/error-parser.js';
@@ -0,0 +1,70 @@
+import test from 'ava';
+import {applyDeveloperRole, requiresDeveloperRole} from './developer-role.js';
+
+console.log('\ndeveloper-role.spec.ts');
+
+test('requiresDeveloperRole matches OpenAI reasoning models', t => {
+	for (const model of [
+		'o1',
+		'o1-mini',
+		'o3',
+		'o3-2025-04-16',
+		'o4-mini',
+		'gpt-5',
+		'gpt-5-mini',
+		'gpt-5.1',
+		'openai/o3',
+		'openai/gpt-5',
+	]) {
+		t.true(requiresDeveloperRole(model), model);
+	}
+});
+
+test('requiresDeveloperRole leaves other model families on the system role', t => {
+	for (const model of [
+		'gpt-4o',
+		'gpt-4.1-mini',
+		'claude-3-5-sonnet',
+		'anthropic/claude-sonnet-4',
+		'llama3.1:8b',
+		'qwen3-coder',
+		'o10x',
+	]) {
+		t.false(requiresDeveloperRole(model), model);
+	}
+});
+
+test('applyDeveloperRole rewrites system messages for reasoning models', t => {
+	const body = JSON.stringify({
+		model: 'o3-mini',
+		messages: [
+			{role: 'system', content: 'You are a coding agent'},
+			{role: 'user', content: 'Hi'},
+		],
+	});
+
+	const rewritten = JSON.parse(applyDeveloperRole(body));
+
+	t.deepEqual(
+		rewritten.messages.map((message: {role: string}) => message.role),
+		['developer', 'user'],
+	);
+	t.is(rewritten.messages[0].content, 'You are a coding agent');
+	t.is(rewritten.model, 'o3-mini');
+});
+
+test('applyDeveloperRole keeps the system role for other models', t => {
+	const body = JSON.stringify({
+		model: 'gpt-4o',
+		messages: [{role: 'system', content: 'You are a coding agent'}],
+	});
+
+	t.is(applyDeveloperRole(body), body);
+});
+
+test('applyDeveloperRole passes through bodies that are not chat requests', t => {
+	t.is(applyDeveloperRole('not json'), 'not json');
+
+	const embeddings = JSON.stringify({model: 'o3', input: 'text'});
+	t.is(applyDeveloperRole(embeddings), embeddings);
+});
@@ -0,0 +1,38 @@
+/**
+ * OpenAI's reasoning models (o1, o3, o4 and gpt-5 families) take instructions
+ * under the `developer` role rather than `system`. Provider prefixes such as
+ * `openai/` (OpenRouter) are ignored when matching.
+ */
+export function requiresDeveloperRole(modelId: string): boolean {
+	const model = modelId.toLowerCase().split('/').pop() ?? '';
+	return /^(?:o[134]|gpt-5)(?:$|[-.:])/.test(model);
+}
+
+/**
+ * Rewrites `system` messages in an OpenAI-compatible chat request body to the
+ * `developer` role when the target model requires it. Other models, and bodies
+ * that are not chat requests, are returned unchanged.
+ */
+export function applyDeveloperRole(body: string): string {
+	let request: {model?: unknown; messages?: unknown};
+	try {
+		request = JSON.parse(body) as typeof request;
+	} catch {
+		return body;
+	}
+
+	if (
+		typeof request.model !== 'string' ||
+		!Array.isArray(request.messages) ||
+		!requiresDeveloperRole(request.model)
+	) {
+		return body;
+	}
+
+	return JSON.stringify({
+		...request,
+		messages: request.messages.map((message: {role?: string}) =>
+			message.role === 'system' ? {...message, role: 'developer'} : message,
+		),
+	});
+}
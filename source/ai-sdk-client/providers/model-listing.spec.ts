@@ -0,0 +1,166 @@
+import {createServer, type IncomingHttpHeaders, type Server} from 'node:http';
+import type {AddressInfo} from 'node:net';
+import test from 'ava';
+import {OllamaClient} from '@/ollama-client/ollama-client';
+import {AISDKClient} from '../ai-sdk-client.js';
+import {
+	parseModelsResponse,
+	parseOllamaTagsResponse,
+} from './model-listing.js';
+
+console.log('\nmodel-listing.spec.ts');
+
+let server: Server;
+let baseUrl: string;
+const requests: Array<{url?: string; headers: IncomingHttpHeaders}> = [];
+
+test.before(async () => {
+	// /v1/models answers like OpenRouter, /api/tags like Ollama, and
+	// /denied/models with a 401
+	server = createServer((req, res) => {
+		requests.push({url: req.url, headers: req.headers});
+		res.setHeader('content-type', 'application/json');
+		if (req.url === '/v1/models') {
+			res.end(
+				JSON.stringify({
+					object: 'list',
+					data: [
+						{id: 'gpt-4o', object: 'model', owned_by: 'openai'},
+						{id: 'qwen/qwen3-coder', name: 'Qwen3 Coder', context_length: 262144},
+					],
+				}),
+			);
+			return;
+		}
+		if (req.url === '/api/tags') {
+			res.end(
+				JSON.stringify({
+					models: [
+						{
+							name: 'llama3.1:8b',
+							details: {family: 'llama', parameter_size: '8.0B'},
+						},
+						{name: 'nomic-embed-text:latest'},
+					],
+				}),
+			);
+			return;
+		}
+		res.writeHead(401);
+		res.end(JSON.stringify({error: 'invalid api key'}));
+	});
+
+	await new Promise<void>(resolve => server.listen(0, '127.0.0.1', resolve));
+	baseUrl = `http://127.0.0.1:${(server.address() as AddressInfo).port}`;
+});
+
+test.after.always(() => {
+	server?.close();
+});
+
+test.serial('AISDKClient.listModels reads the OpenAI-style models endpoint', async t => {
+	const client = new AISDKClient({
+		name: 'openrouter',
+		type: 'openai',
+		models: ['gpt-4o'],
+		config: {
+			baseURL: `${baseUrl}/v1/`,
+			apiKey: 'sk-test',
+			organizationId: 'org-123',
+		},
+	});
+
+	const models = await client.listModels();
+
+	t.deepEqual(models, [
+		{id: 'gpt-4o'},
+		{id: 'qwen/qwen3-coder', name: 'Qwen3 Coder', contextLength: 262144},
+	]);
+	const {headers} = requests.at(-1)!;
+	t.is(headers.authorization, 'Bearer sk-test');
+	t.is(headers['openai-organization'], 'org-123');
+});
+
+test.serial('AISDKClient.listModels sends configured Anthropic headers', async t => {
+	const client = new AISDKClient({
+		name: 'anthropic-proxy',
+		type: 'openai',
+		models: ['claude-sonnet-4-5'],
+		config: {
+			baseURL: `${baseUrl}/v1`,
+			apiKey: 'sk-test',
+			anthropicVersion: '2024-10-22',
+			anthropicBeta: ['context-1m-2025-08-07', 'token-efficient-tools-2025-02-19'],
+		},
+	});
+
+	await client.listModels();
+
+	const {headers} = requests.at(-1)!;
+	t.is(headers['anthropic-version'], '2024-10-22');
+	t.is(
+		headers['anthropic-beta'],
+		'context-1m-2025-08-07,token-efficient-tools-2025-02-19',
+	);
+});
+
+test.serial('AISDKClient.listModels reports a rejected request', async t => {
+	const client = new AISDKClient({
+		name: 'TestProvider',
+		type: 'openai',
+		models: ['test-model'],
+		config: {baseURL: `${baseUrl}/denied`, apiKey: 'bad-key'},
+	});
+
+	await t.throwsAsync(client.listModels(), {
+		message: /Failed to list models \(401\): .*invalid api key/,
+	});
+});
+
+test.serial('OllamaClient.listModels reads /api/tags', async t => {
+	const client = new OllamaClient({
+		name: 'Ollama',
+		type: 'ollama',
+		models: ['llama3.1:8b'],
+		config: {baseURL: `${baseUrl}/v1`},
+	});
+
+	const models = await client.listModels();
+
+	t.is(requests.at(-1)?.url, '/api/tags');
+	t.deepEqual(models, [
+		{id: 'llama3.1:8b', family: 'llama', parameterSize: '8.0B'},
+		{id: 'nomic-embed-text:latest'},
+	]);
+});
+
+test('parseModelsResponse reads Anthropic display names', t => {
+	const models = parseModelsResponse({
+		data: [
+			{
+				type: 'model',
+				id: 'claude-sonnet-4-5',
+				display_name: 'Claude Sonnet 4.5',
+				created_at: '2025-09-29T00:00:00Z',
+			},
+		],
+		has_more: false,
+	});
+
+	t.deepEqual(models, [{id: 'claude-sonnet-4-5', name: 'Claude Sonnet 4.5'}]);
+});
+
+test('parseModelsResponse skips entries without an id', t => {
+	t.deepEqual(parseModelsResponse({data: [{id: ''}, {}, {id: ' m1 '}]}), [
+		{id: 'm1'},
+	]);
+});
+
+test('parseModelsResponse and parseOllamaTagsResponse reject malformed bodies', t => {
+	t.throws(() => parseModelsResponse({models: []}), {
+		message: /missing data array/,
+	});
+	t.throws(() => parseOllamaTagsResponse({data: []}), {
+		message: /missing models array/,
+	});
+});
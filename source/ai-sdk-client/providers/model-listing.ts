@@ -0,0 +1,145 @@
+import {type Dispatcher, fetch as undiciFetch} from 'undici';
+import type {AIProviderConfig, ProviderModelInfo} from '@/types/index';
+import {getProviderHeaders} from './provider-factory.js';
+
+interface OpenAIModelsResponse {
+	data?: Array<{
+		id?: string;
+		// OpenRouter, LM Studio and vLLM report these
+		name?: string;
+		context_length?: number;
+		max_model_len?: number;
+		// Anthropic
+		display_name?: string;
+	}>;
+}
+
+interface OllamaTagsResponse {
+	models?: Array<{
+		name?: string;
+		details?: {family?: string; parameter_size?: string};
+	}>;
+}
+
+// Used when the provider config does not set anthropicVersion
+const DEFAULT_ANTHROPIC_VERSION = '2023-06-01';
+
+function isAnthropicBaseURL(baseURL: string): boolean {
+	try {
+		return new URL(baseURL).hostname.endsWith('anthropic.com');
+	} catch {
+		return false;
+	}
+}
+
+/**
+ * Parses an OpenAI-style `GET /models` response, which Anthropic's
+ * `GET /v1/models` also follows. Context length is kept when the server
+ * reports it.
+ */
+export function parseModelsResponse(body: unknown): ProviderModelInfo[] {
+	const {data} = (body ?? {}) as OpenAIModelsResponse;
+	if (!Array.isArray(data)) {
+		throw new Error('Invalid models response: missing data array');
+	}
+
+	return data
+		.filter(model => typeof model?.id === 'string' && model.id.trim())
+		.map(model => {
+			const info: ProviderModelInfo = {id: (model.id as string).trim()};
+			const name = model.display_name || model.name;
+			if (name) {
+				info.name = name;
+			}
+			const contextLength = model.context_length ?? model.max_model_len;
+			if (typeof contextLength === 'number' && contextLength > 0) {
+				info.contextLength = contextLength;
+			}
+			return info;
+		});
+}
+
+/**
+ * Parses Ollama's `GET /api/tags` response
+ */
+export function parseOllamaTagsResponse(body: unknown): ProviderModelInfo[] {
+	const {models} = (body ?? {}) as OllamaTagsResponse;
+	if (!Array.isArray(models)) {
+		throw new Error('Invalid models response: missing models array');
+	}
+
+	return models
+		.filter(model => typeof model?.name === 'string' && model.name.trim())
+		.map(model => {
+			const info: ProviderModelInfo = {id: (model.name as string).trim()};
+			if (model.details?.family) {
+				info.family = model.details.family;
+			}
+			if (model.details?.parameter_size) {
+				info.parameterSize = model.details.parameter_size;
+			}
+			return info;
+		});
+}
+
+async function fetchModelsJSON(
+	url: string,
+	headers: Record<string, string>,
+	dispatcher: Dispatcher,
+): Promise<unknown> {
+	const response = await undiciFetch(url, {
+		method: 'GET',
+		headers: {Accept: 'application/json', ...headers},
+		dispatcher,
+	});
+	if (!response.ok) {
+		const errorText = await response.text();
+		throw new Error(
+			`Failed to list models (${response.status}): ${errorText || response.statusText}`,
+		);
+	}
+	return response.json();
+}
+
+/**
+ * Lists the models an OpenAI-compatible provider offers via `GET /models`.
+ * Anthropic's endpoint is called with its own auth headers.
+ */
+export async function listProviderModels(
+	providerConfig: AIProviderConfig,
+	dispatcher: Dispatcher,
+): Promise<ProviderModelInfo[]> {
+	const baseURL = (providerConfig.config.baseURL ?? '').replace(/\/+$/, '');
+	if (!baseURL) {
+		throw new Error(`Provider ${providerConfig.name} has no baseUrl`);
+	}
+
+	const apiKey = providerConfig.config.apiKey;
+	const headers: Record<string, string> = {
+		...getProviderHeaders(providerConfig),
+	};
+	if (isAnthropicBaseURL(baseURL)) {
+		headers['anthropic-version'] ??= DEFAULT_ANTHROPIC_VERSION;
+		if (apiKey) {
+			headers['x-api-key'] = apiKey;
+		}
+	} else if (apiKey) {
+		headers.Authorization = `Bearer ${apiKey}`;
+	}
+
+	return parseModelsResponse(
+		await fetchModelsJSON(`${baseURL}/models`, headers, dispatcher),
+	);
+}
+
+/**
+ * Lists the models pulled into an Ollama server via `GET /api/tags`
+ */
+export async function listOllamaModels(
+	baseURL: string,
+	dispatcher: Dispatcher,
+): Promise<ProviderModelInfo[]> {
+	return parseOllamaTagsResponse(
+		await fetchModelsJSON(`${baseURL}/api/tags`, {}, dispatcher),
+	);
+}
@@ -1,7 +1,7 @@
 import test from 'ava';
 import type {AIProviderConfig} from '@/types/index';
 import {Agent} from 'undici';
-import {createProvider} from './provider-factory.js';
+import {createProvider, getProviderHeaders} from './provider-factory.js';
 
 test('createProvider creates provider with basic config', t => {
 	const config: AIProviderConfig = {
@@ -69,3 +69,88 @@ test('createProvider handles provider with no baseURL', t => {
 
 	t.truthy(provider);
 });
+
+test('getProviderHeaders sends OpenAI organization and project when configured', t => {
+	const config: AIProviderConfig = {
+		name: 'openai',
+		type: 'openai',
+		models: ['gpt-5-codex'],
+		config: {
+			baseURL: 'https://api.openai.com/v1',
+			apiKey: 'test-key',
+			organizationId: 'org-123',
+			projectId: 'proj_456',
+		},
+	};
+
+	const headers = getProviderHeaders(config);
+
+	t.is(headers['OpenAI-Organization'], 'org-123');
+	t.is(headers['OpenAI-Project'], 'proj_456');
+});
+
+test('getProviderHeaders omits OpenAI organization and project by default', t => {
+	const config: AIProviderConfig = {
+		name: 'openai',
+		type: 'openai',
+		models: ['gpt-5-codex'],
+		config: {
+			baseURL: 'https://api.openai.com/v1',
+			apiKey: 'test-key',
+			organizationId: '',
+		},
+	};
+
+	const headers = getProviderHeaders(config);
+
+	t.false('OpenAI-Organization' in headers);
+	t.false('OpenAI-Project' in headers);
+});
+
+test('getProviderHeaders keeps OpenRouter attribution headers', t => {
+	const config: AIProviderConfig = {
+		name: 'OpenRouter',
+		type: 'openai',
+		models: ['test-model'],
+		config: {baseURL: 'https://openrouter.ai/api/v1', apiKey: 'test-key'},
+	};
+
+	t.deepEqual(getProviderHeaders(config), {
+		'HTTP-Referer': 'https://github.com/Nano-Collective/nanocoder',
+		'X-Title': 'Nanocoder',
+	});
+});
+
+test('getProviderHeaders sends the Anthropic version and beta features when configured', t => {
+	const config: AIProviderConfig = {
+		name: 'anthropic',
+		type: 'openai',
+		models: ['claude-sonnet-4-5'],
+		config: {
+			baseURL: 'https://api.anthropic.com/v1',
+			apiKey: 'test-key',
+			anthropicVersion: '2024-10-22',
+			anthropicBeta: ['context-1m-2025-08-07', ' token-efficient-tools-2025-02-19 ', ''],
+		},
+	};
+
+	t.deepEqual(getProviderHeaders(config), {
+		'anthropic-version': '2024-10-22',
+		'anthropic-beta': 'context-1m-2025-08-07,token-efficient-tools-2025-02-19',
+	});
+});
+
+test('getProviderHeaders omits Anthropic headers by default', t => {
+	const config: AIProviderConfig = {
+		name: 'anthropic',
+		type: 'openai',
+		models: ['claude-sonnet-4-5'],
+		config: {
+			baseURL: 'https://api.anthropic.com/v1',
+			apiKey: 'test-key',
+			anthropicBeta: [],
+		},
+	};
+
+	t.deepEqual(getProviderHeaders(config), {});
+});
@@ -1,13 +1,55 @@
 import {createOpenAICompatible} from '@ai-sdk/openai-compatible';
-import {type Agent, fetch as undiciFetch} from 'undici';
+import {type Dispatcher, fetch as undiciFetch} from 'undici';
 import type {AIProviderConfig} from '@/types/index';
+import {applyDeveloperRole} from './developer-role.js';
+
+/**
+ * Extra headers sent with every request to the provider: OpenRouter app
+ * attribution, the OpenAI organization and project when configured so that
+ * org- or project-scoped billing and quotas apply, and the Anthropic API
+ * version and beta features when configured
+ */
+export function getProviderHeaders(
+	providerConfig: AIProviderConfig,
+): Record<string, string> {
+	const {config} = providerConfig;
+	const headers: Record<string, string> = {};
+
+	if (providerConfig.name.toLowerCase() === 'openrouter') {
+		headers['HTTP-Referer'] = 'https://github.com/Nano-Collective/nanocoder';
+		headers['X-Title'] = 'Nanocoder';
+	}
+	if (typeof config.organizationId === 'string' && config.organizationId) {
+		headers['OpenAI-Organization'] = config.organizationId;
+	}
+	if (typeof config.projectId === 'string' && config.projectId) {
+		headers['OpenAI-Project'] = config.projectId;
+	}
+	if (
+		typeof config.anthropicVersion === 'string' &&
+		config.anthropicVersion
+	) {
+		headers['anthropic-version'] = config.anthropicVersion;
+	}
+	if (Array.isArray(config.anthropicBeta)) {
+		const betas = config.anthropicBeta
+			.filter((beta): beta is string => typeof beta === 'string')
+			.map(beta => beta.trim())
+			.filter(Boolean);
+		if (betas.length > 0) {
+			headers['anthropic-beta'] = betas.join(',');
+		}
+	}
+
+	return headers;
+}
 
 /**
  * Creates an OpenAI-compatible provider with custom fetch using undici
  */
 export function createProvider(
 	providerConfig: AIProviderConfig,
-	undiciAgent: Agent,
+	undiciAgent: Dispatcher,
 ): ReturnType<typeof createOpenAICompatible> {
 	const {config} = providerConfig;
 
@@ -16,26 +58,26 @@ export function createProvider(
 		url: string | URL | Request,
 		options?: RequestInit,
 	): Promise<Response> => {
+		// Reasoning models expect system prompts under the developer role
+		const body =
+			typeof options?.body === 'string'
+				? applyDeveloperRole(options.body)
+				: options?.body;
+
 		// Type cast to string | URL since undici's fetch accepts these types
 		// Request objects are converted to URL internally by the fetch spec
 		return undiciFetch(url as string | URL, {
 			...options,
+			body,
 			dispatcher: undiciAgent,
 		}) as Promise<Response>;
 	};
 
-	// Add OpenRouter-specific headers for app attribution
-	const headers: Record<string, string> = {};
-	if (providerConfig.name.toLowerCase() === 'openrouter') {
-		headers['HTTP-Referer'] = 'https://github.com/Nano-Collective/nanocoder';
-		headers['X-Title'] = 'Nanocoder';
-	}
-
 	return createOpenAICompatible({
 		name: providerConfig.name,
 		baseURL: config.baseURL ?? '',
 		apiKey: config.apiKey ?? 'dummy-key',
 		fetch: customFetch,
-		headers,
+		headers: getProviderHeaders(providerConfig),
 	});
 }
@@ -0,0 +1,24 @@
+import test from 'ava';
+import type {AIProviderConfig} from '@/types/index';
+import {getThinkingProviderOptions} from './thinking.js';
+
+console.log('\nthinking.spec.ts');
+
+function providerWith(config: AIProviderConfig['config']): AIProviderConfig {
+	return {name: 'Anthropic', type: 'openai', models: ['claude-sonnet-4'], config};
+}
+
+test('getThinkingProviderOptions sends the thinking config under the provider name', t => {
+	t.deepEqual(getThinkingProviderOptions(providerWith({thinkingBudget: 4096})), {
+		Anthropic: {thinking: {type: 'enabled', budget_tokens: 4096}},
+	});
+});
+
+test('getThinkingProviderOptions leaves thinking off without a budget', t => {
+	t.is(getThinkingProviderOptions(providerWith({})), undefined);
+	t.is(getThinkingProviderOptions(providerWith({thinkingBudget: 0})), undefined);
+	t.is(
+		getThinkingProviderOptions(providerWith({thinkingBudget: '4096'})),
+		undefined,
+	);
+});
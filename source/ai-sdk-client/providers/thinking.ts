@@ -0,0 +1,25 @@
+import type {generateText} from 'ai';
+import type {AIProviderConfig} from '@/types/index';
+
+type ProviderOptions = Parameters<typeof generateText>[0]['providerOptions'];
+
+/**
+ * Provider options that enable extended thinking when the provider sets a
+ * `thinkingBudget`. The OpenAI-compatible provider passes unknown options keyed
+ * by the provider name straight into the request body, which is where
+ * Anthropic's compatibility endpoint expects its `thinking` config.
+ */
+export function getThinkingProviderOptions(
+	providerConfig: AIProviderConfig,
+): ProviderOptions {
+	const budgetTokens = providerConfig.config.thinkingBudget;
+	if (typeof budgetTokens !== 'number' || budgetTokens <= 0) {
+		return undefined;
+	}
+
+	return {
+		[providerConfig.name]: {
+			thinking: {type: 'enabled', budget_tokens: budgetTokens},
+		},
+	};
+}
@@ -159,6 +159,9 @@ export default function App({
 		port: vscodePort,
 		currentModel: appState.currentModel,
 		currentProvider: appState.currentProvider,
+		client: appState.client,
+		messages: appState.messages,
+		onSessionLoaded: appState.updateMessages,
 		onPrompt: handleVSCodePrompt,
 	});
 
@@ -204,6 +207,15 @@ export default function App({
 		logger,
 	]);
 
+	// Let tools such as read_file count tokens with the current model's
+	// tokenizer
+	React.useEffect(() => {
+		appState.toolManager?.setCurrentModel(
+			appState.currentModel,
+			appState.currentProvider,
+		);
+	}, [appState.toolManager, appState.currentModel, appState.currentProvider]);
+
 	React.useEffect(() => {
 		if (appState.mcpInitialized) {
 			logger.info('MCP servers initialized', {
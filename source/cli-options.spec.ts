@@ -0,0 +1,143 @@
+import {spawnSync} from 'node:child_process';
+import {mkdtempSync, realpathSync, rmSync, writeFileSync} from 'node:fs';
+import {tmpdir} from 'node:os';
+import {join, resolve} from 'node:path';
+import test from 'ava';
+import {
+	applyGlobalPathOptions,
+	parseGlobalPathOptions,
+} from './cli-options';
+
+console.log('\ncli-options.spec.ts');
+
+test('parseGlobalPathOptions removes --config and --workspace', t => {
+	const options = parseGlobalPathOptions([
+		'--config',
+		'team.config.json',
+		'--workspace',
+		'../project',
+		'run',
+		'fix the tests',
+	]);
+
+	t.is(options.configPath, resolve('team.config.json'));
+	t.is(options.workspace, resolve('../project'));
+	t.deepEqual(options.args, ['run', 'fix the tests']);
+});
+
+test('parseGlobalPathOptions leaves other arguments alone', t => {
+	t.deepEqual(parseGlobalPathOptions(['--vscode', 'run', 'hi']), {
+		args: ['--vscode', 'run', 'hi'],
+	});
+});
+
+test('parseGlobalPathOptions rejects a flag without a value', t => {
+	t.throws(() => parseGlobalPathOptions(['--workspace']), {
+		message: '--workspace needs a value',
+	});
+});
+
+test.serial('applyGlobalPathOptions rejects paths that do not exist', t => {
+	const dir = mkdtempSync(join(tmpdir(), 'nanocoder-cli-options-'));
+
+	try {
+		t.throws(
+			() =>
+				applyGlobalPathOptions({
+					configPath: join(dir, 'missing.json'),
+					args: [],
+				}),
+			{message: `Config file not found: ${join(dir, 'missing.json')}`},
+		);
+		// A directory is not a config file, nor a file a workspace
+		t.throws(() => applyGlobalPathOptions({configPath: dir, args: []}), {
+			message: /Config file not found/,
+		});
+		writeFileSync(join(dir, 'file.txt'), '');
+		t.throws(
+			() =>
+				applyGlobalPathOptions({workspace: join(dir, 'file.txt'), args: []}),
+			{message: /Workspace directory not found/},
+		);
+		t.is(process.env.NANOCODER_CONFIG_FILE, undefined);
+	} finally {
+		rmSync(dir, {recursive: true, force: true});
+	}
+});
+
+test.serial('applyGlobalPathOptions moves into the workspace', t => {
+	const dir = mkdtempSync(join(tmpdir(), 'nanocoder-cli-workspace-'));
+	const originalCwd = process.cwd();
+
+	try {
+		applyGlobalPathOptions({workspace: dir, args: []});
+
+		t.is(process.cwd(), realpathSync(dir));
+	} finally {
+		process.chdir(originalCwd);
+		rmSync(dir, {recursive: true, force: true});
+	}
+});
+
+test.serial('nanocoder --config uses the given config file', t => {
+	t.timeout(60000);
+	const dir = mkdtempSync(join(tmpdir(), 'nanocoder-cli-config-file-'));
+	const configPath = join(dir, 'team.config.json');
+
+	try {
+		writeFileSync(
+			configPath,
+			JSON.stringify({
+				nanocoder: {
+					providers: [
+						{name: 'team-gateway', baseUrl: 'http://gateway:4000/v1', models: ['m1']},
+					],
+				},
+			}),
+		);
+
+		const result = spawnSync(
+			process.execPath,
+			[
+				'--import=tsx',
+				'--no-warnings',
+				join('source', 'cli.tsx'),
+				'--config',
+				configPath,
+				'config',
+			],
+			{
+				encoding: 'utf-8',
+				// Keep the user's real config directory out of the picture
+				env: {...process.env, NANOCODER_CONFIG_DIR: dir},
+			},
+		);
+
+		t.is(result.status, 0, result.stderr);
+		t.true(result.stdout.includes(`Config file: ${configPath}`));
+		t.regex(result.stdout, /"name": "team-gateway"/);
+	} finally {
+		rmSync(dir, {recursive: true, force: true});
+	}
+});
+
+test.serial('nanocoder --config fails when the file is missing', t => {
+	t.timeout(60000);
+	const missing = join(tmpdir(), 'nanocoder-no-such-config.json');
+
+	const result = spawnSync(
+		process.execPath,
+		[
+			'--import=tsx',
+			'--no-warnings',
+			join('source', 'cli.tsx'),
+			'--config',
+			missing,
+			'config',
+		],
+		{encoding: 'utf-8'},
+	);
+
+	t.is(result.status, 1);
+	t.is(result.stderr.trim(), `Error: Config file not found: ${missing}`);
+});
@@ -0,0 +1,63 @@
+import {statSync} from 'node:fs';
+import {resolve} from 'node:path';
+
+export interface GlobalPathOptions {
+	configPath?: string;
+	workspace?: string;
+	// Arguments left once --config and --workspace are removed
+	args: string[];
+}
+
+/**
+ * Pulls `--config <path>` and `--workspace <dir>` out of the CLI arguments.
+ * Paths are resolved against the directory nanocoder was started in.
+ */
+export function parseGlobalPathOptions(args: string[]): GlobalPathOptions {
+	const options: GlobalPathOptions = {args: []};
+
+	for (let i = 0; i < args.length; i++) {
+		const arg = args[i];
+		if (arg === '--config' || arg === '--workspace') {
+			const value = args[i + 1];
+			if (!value) {
+				throw new Error(`${arg} needs a value`);
+			}
+			options[arg === '--config' ? 'configPath' : 'workspace'] =
+				resolve(value);
+			i++;
+		} else {
+			options.args.push(arg);
+		}
+	}
+
+	return options;
+}
+
+function isKind(path: string, kind: 'file' | 'directory'): boolean {
+	try {
+		const stats = statSync(path); // nosemgrep
+		return kind === 'file' ? stats.isFile() : stats.isDirectory();
+	} catch {
+		return false;
+	}
+}
+
+/**
+ * Checks the paths exist, then points config loading at the config file and
+ * moves into the workspace. Must run before any config module is imported.
+ */
+export function applyGlobalPathOptions(options: GlobalPathOptions): void {
+	if (options.configPath && !isKind(options.configPath, 'file')) {
+		throw new Error(`Config file not found: ${options.configPath}`);
+	}
+	if (options.workspace && !isKind(options.workspace, 'directory')) {
+		throw new Error(`Workspace directory not found: ${options.workspace}`);
+	}
+
+	if (options.configPath) {
+		process.env.NANOCODER_CONFIG_FILE = options.configPath;
+	}
+	if (options.workspace) {
+		process.chdir(options.workspace);
+	}
+}
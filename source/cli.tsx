@@ -1,9 +1,29 @@
 #!/usr/bin/env node
-import {render} from 'ink';
-import App from '@/app';
+import {
+	applyGlobalPathOptions,
+	type GlobalPathOptions,
+	parseGlobalPathOptions,
+} from '@/cli-options';
+
+// --config and --workspace must be applied before the app (and with it the
+// config) is loaded, so everything else is imported dynamically below
+let pathOptions: GlobalPathOptions;
+try {
+	pathOptions = parseGlobalPathOptions(process.argv.slice(2));
+	applyGlobalPathOptions(pathOptions);
+} catch (error) {
+	console.error(`Error: ${error instanceof Error ? error.message : error}`);
+	process.exit(1);
+}
+
+const [{render}, {default: App}, {runCliCommand}] = await Promise.all([
+	import('ink'),
+	import('@/app'),
+	import('@/cli-commands'),
+]);
 
 // Parse CLI arguments
-const args = process.argv.slice(2);
+const args = pathOptions.args;
 const vscodeMode = args.includes('--vscode');
 
 // Extract VS Code port if specified
@@ -41,11 +61,22 @@ if (runCommandIndex !== -1 && args[runCommandIndex + 1]) {
 
 const nonInteractiveMode = runCommandIndex !== -1;
 
-render(
-	<App
-		vscodeMode={vscodeMode}
-		vscodePort={vscodePort}
-		nonInteractivePrompt={nonInteractivePrompt}
-		nonInteractiveMode={nonInteractiveMode}
-	/>,
-);
+// `config`, `chat` and `list-tools` print to stdout and exit without starting
+// the UI
+const subcommand = args[0];
+if (
+	subcommand === 'config' ||
+	subcommand === 'chat' ||
+	subcommand === 'list-tools'
+) {
+	process.exitCode = await runCliCommand(subcommand, args.slice(1));
+} else {
+	render(
+		<App
+			vscodeMode={vscodeMode}
+			vscodePort={vscodePort}
+			nonInteractivePrompt={nonInteractivePrompt}
+			nonInteractiveMode={nonInteractiveMode}
+		/>,
+	);
+}
@@ -2,7 +2,12 @@ import {existsSync, mkdirSync, rmSync, writeFileSync} from 'fs';
 import {tmpdir} from 'os';
 import {join} from 'path';
 import test from 'ava';
-import {ConfigurationError, createLLMClient} from './client-factory';
+import {
+	ConfigurationError,
+	createLLMClient,
+	createProviderClient,
+	toAIProviderConfig,
+} from './client-factory';
 import {reloadAppConfig} from '@/config/index';
 
 console.log('\nclient-factory.spec.ts');
@@ -73,6 +78,91 @@ test.after.always(() => {
 // ConfigurationError Tests
 // ============================================================================
 
+// ============================================================================
+// toAIProviderConfig
+// ============================================================================
+
+test('toAIProviderConfig: converts an OpenAI entry', t => {
+	const config = toAIProviderConfig({
+		name: 'openai',
+		baseUrl: 'https://api.openai.com/v1',
+		apiKey: 'sk-test',
+		models: ['gpt-4o'],
+		requestTimeout: 60000,
+		organizationId: 'org-1',
+	});
+
+	t.is(config.type, 'openai');
+	t.deepEqual(config.models, ['gpt-4o']);
+	t.is(config.requestTimeout, 60000);
+	t.is(config.config.baseURL, 'https://api.openai.com/v1');
+	t.is(config.config.apiKey, 'sk-test');
+	t.is(config.config.organizationId, 'org-1');
+});
+
+test('toAIProviderConfig: converts an Anthropic entry', t => {
+	const config = toAIProviderConfig({
+		name: 'anthropic',
+		baseUrl: 'https://api.anthropic.com/v1',
+		apiKey: 'sk-ant-test',
+		models: ['claude-sonnet-4-5'],
+		anthropicBeta: ['prompt-caching-2024-07-31'],
+		maxRetries: 5,
+	});
+
+	t.is(config.type, 'openai');
+	t.is(config.maxRetries, 5);
+	t.is(config.config.baseURL, 'https://api.anthropic.com/v1');
+	t.deepEqual(config.config.anthropicBeta, ['prompt-caching-2024-07-31']);
+});
+
+test('toAIProviderConfig: converts Ollama-style entries', t => {
+	// A bare Ollama URL means the native API
+	t.is(
+		toAIProviderConfig({
+			name: 'ollama',
+			baseUrl: 'http://localhost:11434',
+			models: ['qwen3'],
+		}).type,
+		'ollama',
+	);
+	// Its /v1 endpoint is OpenAI-compatible
+	t.is(
+		toAIProviderConfig({
+			name: 'ollama',
+			baseUrl: 'http://localhost:11434/v1',
+			models: ['qwen3'],
+		}).type,
+		'openai',
+	);
+	// The native client defaults to localhost, so no baseUrl is needed
+	const config = toAIProviderConfig({
+		name: 'ollama',
+		type: 'ollama',
+		models: ['qwen3'],
+		keepAlive: '10m',
+	});
+	t.is(config.type, 'ollama');
+	t.is(config.config.baseURL, undefined);
+	t.is(config.config.keepAlive, '10m');
+});
+
+test('toAIProviderConfig: fails clearly on missing fields', t => {
+	t.throws(
+		() => toAIProviderConfig({name: 'hosted', models: ['m']}),
+		{message: /Provider "hosted" is missing baseUrl/},
+	);
+	t.throws(
+		() =>
+			toAIProviderConfig({
+				name: ' ',
+				baseUrl: 'https://api.example.com/v1',
+				models: [],
+			}),
+		{message: 'Provider is missing a name'},
+	);
+});
+
 test('ConfigurationError: creates error with all properties', t => {
 	const error = new ConfigurationError(
 		'Test error message',
@@ -899,3 +989,43 @@ test.serial(
 		t.is(result.actualProvider, 'EmptyModelsProvider');
 	},
 );
+
+test.serial(
+	'createProviderClient: creates the named provider without falling back',
+	async t => {
+		globalThis.fetch = createMockFetch(true, 200);
+
+		const configDir = join(testDir, 'named-provider-test');
+		mkdirSync(configDir, {recursive: true});
+
+		createTestConfig(
+			{
+				nanocoder: {
+					providers: [
+						{
+							name: 'First',
+							baseUrl: 'http://localhost:8000/v1',
+							models: ['first-model'],
+						},
+						{
+							name: 'Second',
+							baseUrl: 'http://localhost:8001/v1',
+							models: ['second-model'],
+						},
+					],
+				},
+			},
+			configDir,
+		);
+
+		process.cwd = () => configDir;
+		reloadAppConfig();
+
+		const client = await createProviderClient('Second');
+		t.is(client.getCurrentModel(), 'second-model');
+
+		await t.throwsAsync(createProviderClient('Missing'), {
+			message: 'Unknown provider: Missing',
+		});
+	},
+);
@@ -4,7 +4,9 @@ import {AISDKClient} from '@/ai-sdk-client';
 import {appConfig, getClosestConfigFile} from '@/config/index';
 import {loadPreferences} from '@/config/preferences';
 import {TIMEOUT_PROVIDER_CONNECTION_MS} from '@/constants';
-import type {AIProviderConfig, LLMClient} from '@/types/index';
+import {OllamaClient} from '@/ollama-client';
+import type {AIProviderConfig, AppConfig, LLMClient} from '@/types/index';
+import {logWarning} from '@/utils/message-queue';
 
 // Custom error class for configuration errors that need special UI handling
 export class ConfigurationError extends Error {
@@ -86,11 +88,7 @@ async function createAISDKClient(
 				continue;
 			}
 
-			// Test provider connection
-			await testProviderConnection(providerConfig);
-
-			const client = await AISDKClient.create(providerConfig);
-
+			const client = await createClientFromConfig(providerConfig);
 			return {client, actualProvider: providerType};
 		} catch (error: unknown) {
 			const errorMessage =
@@ -115,24 +113,106 @@ async function createAISDKClient(
 	}
 }
 
+/**
+ * Creates a client for one configured provider, without falling back to the
+ * others the way createLLMClient does
+ * @throws Error when the provider isn't configured or can't be reached
+ */
+export async function createProviderClient(
+	provider: string,
+): Promise<LLMClient> {
+	const providerConfig = loadProviderConfigs().find(p => p.name === provider);
+	if (!providerConfig) {
+		throw new Error(`Unknown provider: ${provider}`);
+	}
+	return createClientFromConfig(providerConfig);
+}
+
+async function createClientFromConfig(
+	providerConfig: AIProviderConfig,
+): Promise<LLMClient> {
+	// Test provider connection
+	await testProviderConnection(providerConfig);
+
+	return providerConfig.type === 'ollama'
+		? OllamaClient.create(providerConfig)
+		: AISDKClient.create(providerConfig);
+}
+
+type ProviderEntry = NonNullable<AppConfig['providers']>[number];
+
+// Ollama's native API listens on 11434; its OpenAI-compatible API is under /v1
+const OLLAMA_NATIVE_URL_PATTERN = /:11434\/?$/;
+
+/**
+ * Client type for a provider entry: its explicit type, else Ollama's native
+ * client for a bare Ollama URL, else OpenAI-compatible
+ */
+function inferProviderType(provider: ProviderEntry): 'openai' | 'ollama' {
+	if (provider.type) {
+		return provider.type === 'ollama' ? 'ollama' : 'openai';
+	}
+	return provider.baseUrl && OLLAMA_NATIVE_URL_PATTERN.test(provider.baseUrl)
+		? 'ollama'
+		: 'openai';
+}
+
+/**
+ * Turns a provider entry from agents.config.json into the config a client
+ * is built from
+ * @throws Error naming the provider and the missing field
+ */
+export function toAIProviderConfig(provider: ProviderEntry): AIProviderConfig {
+	if (!provider.name?.trim()) {
+		throw new Error('Provider is missing a name');
+	}
+
+	const type = inferProviderType(provider);
+	// The Ollama client falls back to http://localhost:11434
+	if (type === 'openai' && !provider.baseUrl) {
+		throw new Error(
+			`Provider "${provider.name}" is missing baseUrl, e.g. "https://api.openai.com/v1"`,
+		);
+	}
+
+	return {
+		name: provider.name,
+		type,
+		models: provider.models || [],
+		requestTimeout: provider.requestTimeout,
+		socketTimeout: provider.socketTimeout,
+		maxRetries: provider.maxRetries,
+		logBodies: provider.logBodies,
+		autoMaxTokens: provider.autoMaxTokens,
+		maxToolTokens: provider.maxToolTokens,
+		toolCalling: provider.toolCalling,
+		connectionPool: provider.connectionPool,
+		config: {
+			baseURL: provider.baseUrl,
+			apiKey: provider.apiKey || 'dummy-key',
+			keepAlive: provider.keepAlive,
+			thinkingBudget: provider.thinkingBudget,
+			organizationId: provider.organizationId,
+			projectId: provider.projectId,
+			anthropicVersion: provider.anthropicVersion,
+			anthropicBeta: provider.anthropicBeta,
+		},
+	};
+}
+
 function loadProviderConfigs(): AIProviderConfig[] {
 	const providers: AIProviderConfig[] = [];
 
 	// Load providers from the new providers array structure
 	if (appConfig.providers) {
 		for (const provider of appConfig.providers) {
-			providers.push({
-				name: provider.name,
-				type: 'openai',
-				models: provider.models || [],
-				requestTimeout: provider.requestTimeout,
-				socketTimeout: provider.socketTimeout,
-				connectionPool: provider.connectionPool,
-				config: {
-					baseURL: provider.baseUrl,
-					apiKey: provider.apiKey || 'dummy-key',
-				},
-			});
+			try {
+				providers.push(toAIProviderConfig(provider));
+			} catch (error) {
+				logWarning(
+					`Skipping provider: ${error instanceof Error ? error.message : String(error)}`,
+				);
+			}
 		}
 	}
 
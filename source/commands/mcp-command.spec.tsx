@@ -1,8 +1,9 @@
 import test from 'ava';
 import React from 'react';
 import {renderWithTheme} from '../test-utils/render-with-theme.js';
+import {setToolManagerGetter} from '../message-handler';
 import {ToolManager} from '../tools/tool-manager';
-import {MCP} from './mcp';
+import {MCP, mcpCommand} from './mcp';
 
 console.log(`\nmcp-command.spec.tsx`);
 
@@ -14,6 +15,7 @@ test('MCP command: shows no servers when none connected', t => {
 	const mockToolManager = {
 		getConnectedServers: () => [],
 		getServerTools: () => [],
+		getServerStatus: () => [],
 		getServerInfo: () => undefined,
 	} as unknown as ToolManager;
 
@@ -34,6 +36,7 @@ test('MCP command: displays transport type icons', t => {
 		getServerTools: (serverName: string) => [
 			{name: `tool-${serverName}`, description: 'Test tool'},
 		],
+		getServerStatus: () => [],
 		getServerInfo: (serverName: string) => ({
 			name: serverName,
 			transport: serverName.includes('stdio')
@@ -66,6 +69,7 @@ test('MCP command: displays URLs for remote servers', t => {
 	const mockToolManager = {
 		getConnectedServers: () => ['remote-server'],
 		getServerTools: () => [{name: 'remote-tool', description: 'Remote tool'}],
+		getServerStatus: () => [],
 		getServerInfo: () => ({
 			name: 'remote-server',
 			transport: 'http',
@@ -88,6 +92,7 @@ test('MCP command: displays server descriptions', t => {
 	const mockToolManager = {
 		getConnectedServers: () => ['server-with-description'],
 		getServerTools: () => [{name: 'test-tool', description: 'Test tool'}],
+		getServerStatus: () => [],
 		getServerInfo: () => ({
 			name: 'server-with-description',
 			transport: 'stdio',
@@ -110,6 +115,7 @@ test('MCP command: displays server tags', t => {
 	const mockToolManager = {
 		getConnectedServers: () => ['server-with-tags'],
 		getServerTools: () => [{name: 'test-tool', description: 'Test tool'}],
+		getServerStatus: () => [],
 		getServerInfo: () => ({
 			name: 'server-with-tags',
 			transport: 'http',
@@ -136,6 +142,7 @@ test('MCP command: displays tool information correctly', t => {
 			{name: 'tool-2', description: 'Second tool'},
 			{name: 'tool-3', description: 'Third tool'},
 		],
+		getServerStatus: () => [],
 		getServerInfo: () => ({
 			name: 'multi-tool-server',
 			transport: 'stdio',
@@ -163,6 +170,7 @@ test('MCP command: handles singular tool count', t => {
 	const mockToolManager = {
 		getConnectedServers: () => ['single-tool-server'],
 		getServerTools: () => [{name: 'only-tool', description: 'Only tool'}],
+		getServerStatus: () => [],
 		getServerInfo: () => ({
 			name: 'single-tool-server',
 			transport: 'websocket',
@@ -185,6 +193,7 @@ test('MCP command: shows server count header', t => {
 	const mockToolManager = {
 		getConnectedServers: () => ['server-1', 'server-2', 'server-3'],
 		getServerTools: () => [],
+		getServerStatus: () => [],
 		getServerInfo: () => ({
 			name: 'test-server',
 			transport: 'stdio',
@@ -206,6 +215,7 @@ test('MCP command: shows configuration examples', t => {
 	const mockToolManager = {
 		getConnectedServers: () => [],
 		getServerTools: () => [],
+		getServerStatus: () => [],
 		getServerInfo: () => undefined,
 	} as unknown as ToolManager;
 
@@ -236,6 +246,7 @@ test('MCP command: uses transport type getTransportIcon function correctly', t =
 		const mockToolManager = {
 			getConnectedServers: () => ['test-server'],
 			getServerTools: () => [],
+			getServerStatus: () => [],
 			getServerInfo: () => ({
 				name: 'test-server',
 				transport: testCase.transport as any,
@@ -257,3 +268,133 @@ test('MCP command: uses transport type getTransportIcon function correctly', t =
 		);
 	}
 });
+
+test('MCP command: lists servers that failed to connect', t => {
+	const mockToolManager = {
+		getConnectedServers: () => [],
+		getServerTools: () => [],
+		getServerStatus: () => [
+			{
+				name: 'broken-server',
+				connected: false,
+				toolCount: 0,
+				lastError: 'spawn foo ENOENT',
+			},
+		],
+		getServerInfo: () => undefined,
+	} as unknown as ToolManager;
+
+	const {lastFrame} = renderWithTheme(<MCP toolManager={mockToolManager} />);
+
+	const output = lastFrame();
+	t.truthy(output);
+	t.regex(output!, /Failed MCP Servers \(1\)/);
+	t.regex(output!, /broken-server/);
+	t.regex(output!, /spawn foo ENOENT/);
+});
+
+// ============================================================================
+// Tests for /mcp raw
+// ============================================================================
+
+const metadata = {
+	provider: 'test',
+	model: 'test',
+	tokens: 0,
+	getMessageTokens: () => 0,
+};
+
+async function runRaw(args: string[]): Promise<string> {
+	const result = await mcpCommand.handler(['raw', ...args], [], metadata);
+	const {lastFrame} = renderWithTheme(result);
+	return lastFrame() || '';
+}
+
+test.serial('MCP command: raw requests need debug logging', async t => {
+	const previous = process.env.NANOCODER_LOG_LEVEL;
+	delete process.env.NANOCODER_LOG_LEVEL;
+
+	try {
+		t.regex(await runRaw(['docs', 'ping']), /NANOCODER_LOG_LEVEL=debug/);
+	} finally {
+		if (previous !== undefined) {
+			process.env.NANOCODER_LOG_LEVEL = previous;
+		}
+	}
+});
+
+test.serial('MCP command: raw sends the request and shows the result', async t => {
+	const previous = process.env.NANOCODER_LOG_LEVEL;
+	process.env.NANOCODER_LOG_LEVEL = 'debug';
+	const requests: unknown[][] = [];
+	setToolManagerGetter(
+		() =>
+			({
+				rawMCPRequest: async (...request: unknown[]) => {
+					requests.push(request);
+					return {level: 'debug'};
+				},
+			}) as unknown as ToolManager,
+	);
+
+	try {
+		const output = await runRaw([
+			'docs',
+			'logging/setLevel',
+			'{"level":',
+			'"debug"}',
+		]);
+
+		t.deepEqual(requests, [['docs', 'logging/setLevel', {level: 'debug'}]]);
+		t.regex(output, /"level": "debug"/);
+		t.regex(await runRaw(['docs']), /Usage: \/mcp raw/);
+	} finally {
+		setToolManagerGetter(() => null);
+		if (previous === undefined) {
+			delete process.env.NANOCODER_LOG_LEVEL;
+		} else {
+			process.env.NANOCODER_LOG_LEVEL = previous;
+		}
+	}
+});
+
+// ============================================================================
+// Tests for /mcp log-level
+// ============================================================================
+
+test.serial('MCP command: log-level sets a server log level', async t => {
+	const requests: unknown[][] = [];
+	setToolManagerGetter(
+		() =>
+			({
+				setMCPLogLevel: async (...request: unknown[]) => {
+					requests.push(request);
+					return request[0] === 'docs';
+				},
+			}) as unknown as ToolManager,
+	);
+
+	const run = async (args: string[]) => {
+		const result = await mcpCommand.handler(
+			['log-level', ...args],
+			[],
+			metadata,
+		);
+		return renderWithTheme(result).lastFrame() || '';
+	};
+
+	try {
+		t.regex(await run(['docs', 'warning']), /docs log level set to warning/);
+		t.regex(
+			await run(['search', 'debug']),
+			/search doesn't support setting a log level/,
+		);
+		t.regex(await run(['docs', 'loud']), /Usage: \/mcp log-level/);
+		t.deepEqual(requests, [
+			['docs', 'warning'],
+			['search', 'debug'],
+		]);
+	} finally {
+		setToolManagerGetter(() => null);
+	}
+});
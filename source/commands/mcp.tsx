@@ -1,11 +1,13 @@
 import {Box, Text} from 'ink';
 import React from 'react';
+import {ErrorMessage, InfoMessage} from '@/components/message-box';
 import {TitledBoxWithPreferences} from '@/components/ui/titled-box';
 import {useTerminalWidth} from '@/hooks/useTerminalWidth';
 import {useTheme} from '@/hooks/useTheme';
 import {getToolManager} from '@/message-handler';
 import {ToolManager} from '@/tools/tool-manager';
-import type {Command} from '@/types/index';
+import {type Command, MCP_LOG_LEVELS, type MCPLogLevel} from '@/types/index';
+import {formatError} from '@/utils/error-formatter';
 
 // Helper function to get transport icons
 function getTransportIcon(transportType: string): string {
@@ -29,6 +31,9 @@ export function MCP({toolManager}: MCPProps) {
 	const boxWidth = useTerminalWidth();
 	const {colors} = useTheme();
 	const connectedServers = toolManager?.getConnectedServers() || [];
+	const failedServers = (toolManager?.getServerStatus() || []).filter(
+		status => !status.connected,
+	);
 
 	return (
 		<TitledBoxWithPreferences
@@ -143,16 +148,143 @@ export function MCP({toolManager}: MCPProps) {
 					})}
 				</>
 			)}
+
+			{failedServers.length > 0 && (
+				<Box flexDirection="column">
+					<Text color={colors.error}>
+						Failed MCP Servers ({failedServers.length}):
+					</Text>
+					{failedServers.map(status => (
+						<Text key={status.name} color={colors.secondary}>
+							• <Text color={colors.error}>{status.name}</Text>
+							{status.lastError ? `: ${status.lastError}` : ''}
+						</Text>
+					))}
+				</Box>
+			)}
 		</TitledBoxWithPreferences>
 	);
 }
 
+const RAW_USAGE = 'Usage: /mcp raw <server> <method> [params as JSON]';
+
+/**
+ * Sends a JSON-RPC request the client doesn't wrap, e.g. logging/setLevel, to
+ * a server and shows the raw result. Only available with debug logging, as
+ * it bypasses the usual tool approval.
+ */
+async function rawRequest(
+	toolManager: ToolManager | null,
+	args: string[],
+): Promise<React.ReactElement> {
+	const key = `mcp-raw-${Date.now()}`;
+	if (process.env.NANOCODER_LOG_LEVEL !== 'debug') {
+		return React.createElement(ErrorMessage, {
+			key,
+			message: '/mcp raw is a debugging aid. Set NANOCODER_LOG_LEVEL=debug to use it.',
+			hideBox: true,
+		});
+	}
+
+	const [serverName, method, ...paramArgs] = args;
+	if (!serverName || !method) {
+		return React.createElement(ErrorMessage, {
+			key,
+			message: RAW_USAGE,
+			hideBox: true,
+		});
+	}
+	if (!toolManager) {
+		return React.createElement(ErrorMessage, {
+			key,
+			message: 'No MCP servers are connected',
+			hideBox: true,
+		});
+	}
+
+	try {
+		const params =
+			paramArgs.length > 0
+				? (JSON.parse(paramArgs.join(' ')) as Record<string, unknown>)
+				: undefined;
+		const result = await toolManager.rawMCPRequest(serverName, method, params);
+		return React.createElement(InfoMessage, {
+			key,
+			message: `${serverName} ${method}:\n${JSON.stringify(result, null, 2)}`,
+			hideBox: true,
+		});
+	} catch (error) {
+		return React.createElement(ErrorMessage, {
+			key,
+			message: `MCP request failed: ${formatError(error)}`,
+			hideBox: true,
+		});
+	}
+}
+
+const LOG_LEVEL_USAGE =
+	`Usage: /mcp log-level <server> <${MCP_LOG_LEVELS.join('|')}>`;
+
+function isLogLevel(level: string): level is MCPLogLevel {
+	return (MCP_LOG_LEVELS as readonly string[]).includes(level);
+}
+
+/**
+ * Sets how much a server logs through logging/setLevel. From then on the
+ * messages it sends are shown in the chat as well as logged.
+ */
+async function setLogLevel(
+	toolManager: ToolManager | null,
+	args: string[],
+): Promise<React.ReactElement> {
+	const key = `mcp-log-level-${Date.now()}`;
+	const [serverName, level] = args;
+	if (!serverName || !level || !isLogLevel(level)) {
+		return React.createElement(ErrorMessage, {
+			key,
+			message: LOG_LEVEL_USAGE,
+			hideBox: true,
+		});
+	}
+	if (!toolManager) {
+		return React.createElement(ErrorMessage, {
+			key,
+			message: 'No MCP servers are connected',
+			hideBox: true,
+		});
+	}
+
+	try {
+		const sent = await toolManager.setMCPLogLevel(serverName, level);
+		return React.createElement(InfoMessage, {
+			key,
+			message: sent
+				? `${serverName} log level set to ${level}`
+				: `${serverName} doesn't support setting a log level`,
+			hideBox: true,
+		});
+	} catch (error) {
+		return React.createElement(ErrorMessage, {
+			key,
+			message: `Failed to set log level: ${formatError(error)}`,
+			hideBox: true,
+		});
+	}
+}
+
 export const mcpCommand: Command = {
 	name: 'mcp',
 	description: 'Show connected MCP servers and their tools',
-	handler: (_args: string[], _messages, _metadata) => {
+	handler: (args: string[], _messages, _metadata) => {
 		const toolManager = getToolManager();
 
+		if (args[0]?.toLowerCase() === 'raw') {
+			return rawRequest(toolManager, args.slice(1));
+		}
+		if (args[0]?.toLowerCase() === 'log-level') {
+			return setLogLevel(toolManager, args.slice(1));
+		}
+
 		return Promise.resolve(
 			React.createElement(MCP, {
 				key: `mcp-${Date.now()}`,
@@ -2,7 +2,15 @@ import {existsSync, mkdirSync, readFileSync, rmSync, writeFileSync} from 'fs';
 import {tmpdir} from 'os';
 import {join} from 'path';
 import test from 'ava';
-import {confDirMap, getClosestConfigFile, reloadAppConfig} from './index';
+import type {AppConfig} from '@/types/index';
+import {
+	appConfig,
+	confDirMap,
+	getClosestConfigFile,
+	loadMergedAppConfig,
+	mergeAppConfigs,
+	reloadAppConfig,
+} from './index';
 
 console.log(`\nindex.spec.ts`);
 
@@ -169,3 +177,222 @@ test('loadAppConfig handles missing file gracefully', async t => {
 		process.chdir(originalCwd);
 	}
 });
+
+test('mergeAppConfigs unions providers by name with project taking precedence', t => {
+	const globalConfig: AppConfig = {
+		providers: [
+			{name: 'ollama', baseUrl: 'http://localhost:11434/v1', models: ['llama3']},
+			{name: 'openrouter', apiKey: 'global-key', models: ['gpt-4o']},
+		],
+	};
+	const projectConfig: AppConfig = {
+		providers: [
+			{name: 'openrouter', apiKey: 'project-key', models: ['claude']},
+			{name: 'lmstudio', baseUrl: 'http://localhost:1234/v1', models: ['qwen']},
+		],
+	};
+
+	const merged = mergeAppConfigs(globalConfig, projectConfig);
+
+	t.deepEqual(
+		merged.providers?.map(provider => provider.name),
+		['ollama', 'openrouter', 'lmstudio'],
+	);
+	t.is(merged.providers?.[1].apiKey, 'project-key');
+	t.deepEqual(merged.providers?.[1].models, ['claude']);
+});
+
+test('mergeAppConfigs keeps global MCP servers when the project adds its own', t => {
+	const merged = mergeAppConfigs(
+		{mcpServers: [{name: 'github', transport: 'stdio', command: 'gh-mcp'}]},
+		{mcpServers: [{name: 'docs', transport: 'http', url: 'http://docs'}]},
+	);
+
+	t.deepEqual(
+		merged.mcpServers?.map(server => server.name),
+		['github', 'docs'],
+	);
+});
+
+test('mergeAppConfigs lets project values override other keys', t => {
+	const merged = mergeAppConfigs(
+		{disabledTools: ['execute_bash'], providers: [{name: 'a', models: ['x']}]},
+		{disabledTools: ['fetch_url']},
+	);
+
+	t.deepEqual(merged.disabledTools, ['fetch_url']);
+	t.deepEqual(merged.providers, [{name: 'a', models: ['x']}]);
+});
+
+test.serial('loadMergedAppConfig reads the explicit config directory', t => {
+	const configDir = join(testDir, 'merged-config');
+	mkdirSync(configDir, {recursive: true});
+	writeFileSync(
+		join(configDir, 'agents.config.json'),
+		JSON.stringify({
+			nanocoder: {providers: [{name: 'ollama', models: ['llama3']}]},
+		}),
+		'utf-8',
+	);
+
+	const previousConfigDir = process.env.NANOCODER_CONFIG_DIR;
+	process.env.NANOCODER_CONFIG_DIR = configDir;
+
+	try {
+		const config = loadMergedAppConfig();
+
+		t.deepEqual(config.providers, [{name: 'ollama', models: ['llama3']}]);
+		t.deepEqual(config.mcpServers, []);
+		t.deepEqual(config.disabledTools, []);
+	} finally {
+		if (previousConfigDir === undefined) {
+			delete process.env.NANOCODER_CONFIG_DIR;
+		} else {
+			process.env.NANOCODER_CONFIG_DIR = previousConfigDir;
+		}
+	}
+});
+
+test.serial('reloadAppConfig loads the closest config', t => {
+	const configDir = join(testDir, 'reloaded-config');
+	mkdirSync(configDir, {recursive: true});
+	writeFileSync(
+		join(configDir, 'agents.config.json'),
+		JSON.stringify({
+			nanocoder: {
+				providers: [{name: 'ollama', models: ['llama3']}],
+				readFile: {maxFileBytes: 2048},
+			},
+		}),
+		'utf-8',
+	);
+
+	const previousConfigDir = process.env.NANOCODER_CONFIG_DIR;
+	process.env.NANOCODER_CONFIG_DIR = configDir;
+
+	try {
+		reloadAppConfig();
+
+		t.deepEqual(appConfig.providers, [{name: 'ollama', models: ['llama3']}]);
+		t.deepEqual(appConfig.mcpServers, []);
+		t.deepEqual(appConfig.readFile, {maxFileBytes: 2048});
+	} finally {
+		if (previousConfigDir === undefined) {
+			delete process.env.NANOCODER_CONFIG_DIR;
+		} else {
+			process.env.NANOCODER_CONFIG_DIR = previousConfigDir;
+		}
+		reloadAppConfig();
+	}
+});
+
+// Runs with a project config in the working directory and a legacy global
+// config in a stand-in home directory, then reloads the real config
+function withProjectAndGlobalConfig(
+	projectConfig: AppConfig,
+	globalConfig: AppConfig,
+	run: () => void,
+): void {
+	const projectDir = join(testDir, 'project');
+	const homeDir = join(testDir, 'home');
+	mkdirSync(projectDir, {recursive: true});
+	mkdirSync(homeDir, {recursive: true});
+	writeFileSync(
+		join(projectDir, 'agents.config.json'),
+		JSON.stringify({nanocoder: projectConfig}),
+		'utf-8',
+	);
+	writeFileSync(
+		join(homeDir, '.agents.config.json'),
+		JSON.stringify({nanocoder: globalConfig}),
+		'utf-8',
+	);
+
+	const previousCwd = process.cwd();
+	const previousEnv = {
+		HOME: process.env.HOME,
+		XDG_CONFIG_HOME: process.env.XDG_CONFIG_HOME,
+		NANOCODER_CONFIG_DIR: process.env.NANOCODER_CONFIG_DIR,
+	};
+	process.env.HOME = homeDir;
+	process.env.XDG_CONFIG_HOME = join(homeDir, '.config');
+	delete process.env.NANOCODER_CONFIG_DIR;
+	process.chdir(projectDir);
+
+	try {
+		run();
+	} finally {
+		process.chdir(previousCwd);
+		for (const [key, value] of Object.entries(previousEnv)) {
+			if (value === undefined) {
+				delete process.env[key];
+			} else {
+				process.env[key] = value;
+			}
+		}
+		reloadAppConfig();
+	}
+}
+
+const globalConfig: AppConfig = {
+	providers: [{name: 'global', models: ['g1']}],
+	mcpServers: [{name: 'github', transport: 'stdio', command: 'gh-mcp'}],
+};
+
+test.serial('reloadAppConfig loads only the closest config by default', t => {
+	withProjectAndGlobalConfig(
+		{providers: [{name: 'project', models: ['p1']}]},
+		globalConfig,
+		() => {
+			reloadAppConfig();
+
+			t.deepEqual(appConfig.providers, [{name: 'project', models: ['p1']}]);
+			t.deepEqual(appConfig.mcpServers, []);
+		},
+	);
+});
+
+test.serial('reloadAppConfig merges every config with mergeConfigs', t => {
+	withProjectAndGlobalConfig(
+		{mergeConfigs: true, providers: [{name: 'project', models: ['p1']}]},
+		globalConfig,
+		() => {
+			reloadAppConfig();
+
+			t.deepEqual(
+				appConfig.providers?.map(provider => provider.name),
+				['global', 'project'],
+			);
+			t.deepEqual(appConfig.mcpServers?.map(server => server.name), ['github']);
+		},
+	);
+});
+
+test.serial('NANOCODER_CONFIG_FILE overrides every agents.config.json location', t => {
+	const configFile = join(testDir, 'explicit', 'team.config.json');
+	mkdirSync(join(testDir, 'explicit'), {recursive: true});
+	writeFileSync(
+		configFile,
+		JSON.stringify({
+			nanocoder: {providers: [{name: 'team', models: ['m1']}]},
+		}),
+		'utf-8',
+	);
+
+	const previousConfigFile = process.env.NANOCODER_CONFIG_FILE;
+	process.env.NANOCODER_CONFIG_FILE = configFile;
+
+	try {
+		t.is(getClosestConfigFile('agents.config.json'), configFile);
+		t.is(confDirMap['agents.config.json'], configFile);
+		t.deepEqual(loadMergedAppConfig().providers, [
+			{name: 'team', models: ['m1']},
+		]);
+	} finally {
+		if (previousConfigFile === undefined) {
+			delete process.env.NANOCODER_CONFIG_FILE;
+		} else {
+			process.env.NANOCODER_CONFIG_FILE = previousConfigFile;
+		}
+	}
+});
@@ -28,6 +28,13 @@ export const confDirMap: Record<string, string> = {};
 
 // Find the closest config file for the requested configuration file
 export function getClosestConfigFile(fileName: string): string {
+	// An explicit config file (the --config flag) overrides every location
+	const explicitFile = process.env.NANOCODER_CONFIG_FILE;
+	if (fileName === 'agents.config.json' && explicitFile) {
+		confDirMap[fileName] = explicitFile;
+		return explicitFile;
+	}
+
 	try {
 		const configDir = getConfigPath();
 
@@ -98,30 +105,172 @@ function createDefaultConfFile(filePath: string, fileName: string): void {
 	}
 }
 
-// Function to load app configuration from agents.config.json if it exists
+// Reads the `nanocoder` section of an agents.config.json file, with
+// environment variables substituted
+function readNanocoderConfig(configPath: string): AppConfig | undefined {
+	const rawData = readFileSync(configPath, 'utf-8');
+	const agentsData = JSON.parse(rawData) as {nanocoder?: AppConfig};
+
+	// Apply environment variable substitution
+	const processedData = substituteEnvVars(agentsData);
+	return processedData.nanocoder;
+}
+
+// Function to load app configuration from agents.config.json if it exists.
+// With mergeConfigs set in that file, every agents.config.json is merged.
 function loadAppConfig(): AppConfig {
 	const agentsJsonPath = getClosestConfigFile('agents.config.json');
 
+	let nanocoderConfig: AppConfig | undefined;
 	try {
-		const rawData = readFileSync(agentsJsonPath, 'utf-8');
-		const agentsData = JSON.parse(rawData) as {nanocoder?: AppConfig};
-
-		// Apply environment variable substitution
-		const processedData = substituteEnvVars(agentsData);
-
-		if (processedData.nanocoder) {
-			return {
-				providers: processedData.nanocoder.providers ?? [],
-				mcpServers: processedData.nanocoder.mcpServers ?? [],
-			};
-		}
+		nanocoderConfig = readNanocoderConfig(agentsJsonPath);
 	} catch (error) {
 		logWarning(
 			`Failed to load agents.config.json: ${error instanceof Error ? error.message : String(error)}`,
 		);
 	}
 
-	return {};
+	if (!nanocoderConfig) {
+		return {};
+	}
+
+	if (nanocoderConfig.mergeConfigs) {
+		nanocoderConfig = loadMergedAppConfig();
+	}
+
+	return {
+		providers: nanocoderConfig.providers ?? [],
+		mcpServers: nanocoderConfig.mcpServers ?? [],
+		disabledTools: nanocoderConfig.disabledTools ?? [],
+		stopToolsOnError: nanocoderConfig.stopToolsOnError,
+		allowDuplicateToolCalls: nanocoderConfig.allowDuplicateToolCalls,
+		maxToolResultBytes: nanocoderConfig.maxToolResultBytes,
+		cacheToolResults: nanocoderConfig.cacheToolResults,
+		readFile: nanocoderConfig.readFile,
+		fetchUrl: nanocoderConfig.fetchUrl,
+		network: nanocoderConfig.network,
+	};
+}
+
+// Every existing location of a config file, lowest precedence first: the
+// user config directory, the legacy hidden file in $HOME, then the working
+// directory
+function getConfigFileLocations(fileName: string): string[] {
+	const explicitFile = process.env.NANOCODER_CONFIG_FILE;
+	if (fileName === 'agents.config.json' && explicitFile) {
+		return existsSync(explicitFile) ? [explicitFile] : []; // nosemgrep
+	}
+
+	const locations = [join(getConfigPath(), fileName)]; // nosemgrep
+
+	// Mirror getClosestConfigFile: an explicit config dir is the only source
+	if (!process.env.NANOCODER_CONFIG_DIR) {
+		locations.push(
+			join(homedir(), `.${fileName}`), // nosemgrep
+			join(process.cwd(), fileName), // nosemgrep
+		);
+	}
+
+	return locations.filter(location => existsSync(location)); // nosemgrep
+}
+
+function isPlainObject(value: unknown): value is Record<string, unknown> {
+	return typeof value === 'object' && value !== null && !Array.isArray(value);
+}
+
+function isNamedList(value: unknown[]): value is {name: string}[] {
+	return value.every(
+		entry => isPlainObject(entry) && typeof entry.name === 'string',
+	);
+}
+
+// Union two lists of named entries; an override entry replaces the base
+// entry with the same name in place, new entries are appended
+function mergeByName(
+	base: {name: string}[],
+	override: {name: string}[],
+): {name: string}[] {
+	const merged = [...base];
+	for (const entry of override) {
+		const index = merged.findIndex(existing => existing.name === entry.name);
+		if (index === -1) {
+			merged.push(entry);
+		} else {
+			merged[index] = entry;
+		}
+	}
+	return merged;
+}
+
+function deepMergeConfig(
+	base: Record<string, unknown>,
+	override: Record<string, unknown>,
+): Record<string, unknown> {
+	const merged = {...base};
+
+	for (const [key, value] of Object.entries(override)) {
+		const existing = merged[key];
+
+		if (
+			Array.isArray(existing) &&
+			Array.isArray(value) &&
+			existing.length > 0 &&
+			isNamedList(existing) &&
+			isNamedList(value)
+		) {
+			merged[key] = mergeByName(existing, value);
+		} else if (isPlainObject(existing) && isPlainObject(value)) {
+			merged[key] = deepMergeConfig(existing, value);
+		} else if (value !== undefined) {
+			merged[key] = value;
+		}
+	}
+
+	return merged;
+}
+
+/**
+ * Deep-merges two app configs. Values from `override` win on conflicts,
+ * except lists of named entries (providers, MCP and LSP servers) which are
+ * unioned by `name`.
+ */
+export function mergeAppConfigs(
+	base: AppConfig,
+	override: AppConfig,
+): AppConfig {
+	return deepMergeConfig(
+		base as Record<string, unknown>,
+		override as Record<string, unknown>,
+	) as AppConfig;
+}
+
+/**
+ * Loads agents.config.json from every location that has one and merges them,
+ * so a project config adds to the user's global providers and MCP servers
+ * rather than replacing them. The project config wins on conflicts.
+ */
+export function loadMergedAppConfig(): AppConfig {
+	let merged: AppConfig = {};
+
+	for (const configPath of getConfigFileLocations('agents.config.json')) {
+		try {
+			const nanocoderConfig = readNanocoderConfig(configPath);
+			if (nanocoderConfig) {
+				merged = mergeAppConfigs(merged, nanocoderConfig);
+			}
+		} catch (error) {
+			logWarning(
+				`Failed to load ${configPath}: ${error instanceof Error ? error.message : String(error)}`,
+			);
+		}
+	}
+
+	return {
+		...merged,
+		providers: merged.providers ?? [],
+		mcpServers: merged.mcpServers ?? [],
+		disabledTools: merged.disabledTools ?? [],
+	};
 }
 
 export let appConfig = loadAppConfig();
@@ -1,13 +1,26 @@
-import {existsSync, mkdirSync, readFileSync, rmSync, writeFileSync} from 'node:fs';
+import {
+	existsSync,
+	mkdirSync,
+	readFileSync,
+	realpathSync,
+	rmSync,
+	writeFileSync,
+} from 'node:fs';
 import {tmpdir} from 'node:os';
 import {join} from 'node:path';
 import test from 'ava';
 import {
 	getLastUsedModel,
+	getModelSettings,
+	isDirectoryTrusted,
 	loadPreferences,
 	resetPreferencesCache,
 	savePreferences,
+	setModelSettings,
+	trustDirectory,
+	untrustDirectory,
 	updateLastUsed,
+	validateModelSettings,
 } from './preferences';
 import type {UserPreferences} from '@/types/index';
 
@@ -596,3 +609,247 @@ test.serial('loadPreferences handles file with only whitespace', t => {
 		}
 	}
 });
+
+test.serial('setModelSettings round-trips through the preferences file', async t => {
+	const preferencesPath = getTestPreferencesPath();
+
+	try {
+		await setModelSettings('llama3', {
+			temperature: 0.2,
+			maxTokens: 2048,
+			topP: 0.9,
+		});
+
+		const parsed = JSON.parse(readFileSync(preferencesPath, 'utf-8'));
+		t.deepEqual(parsed.modelSettings, {
+			llama3: {temperature: 0.2, maxTokens: 2048, topP: 0.9},
+		});
+		t.deepEqual(getModelSettings('llama3'), {
+			temperature: 0.2,
+			maxTokens: 2048,
+			topP: 0.9,
+		});
+	} finally {
+		if (existsSync(preferencesPath)) {
+			rmSync(preferencesPath, {force: true});
+		}
+	}
+});
+
+test.serial('getModelSettings returns undefined for unknown models', t => {
+	const preferencesPath = getTestPreferencesPath();
+
+	try {
+		savePreferences({lastProvider: 'ollama'});
+
+		t.is(getModelSettings('unknown-model'), undefined);
+	} finally {
+		if (existsSync(preferencesPath)) {
+			rmSync(preferencesPath, {force: true});
+		}
+	}
+});
+
+test.serial('setModelSettings preserves other preferences and models', async t => {
+	const preferencesPath = getTestPreferencesPath();
+
+	try {
+		savePreferences({
+			lastProvider: 'ollama',
+			modelSettings: {llama3: {temperature: 0.5}},
+		});
+
+		await setModelSettings('qwen', {maxTokens: 1024});
+
+		const result = loadPreferences();
+		t.is(result.lastProvider, 'ollama');
+		t.deepEqual(result.modelSettings, {
+			llama3: {temperature: 0.5},
+			qwen: {maxTokens: 1024},
+		});
+	} finally {
+		if (existsSync(preferencesPath)) {
+			rmSync(preferencesPath, {force: true});
+		}
+	}
+});
+
+// Tests for model settings validation
+
+test('validateModelSettings accepts temperature at both ends of 0 to 2', t => {
+	t.notThrows(() => validateModelSettings({temperature: 0}));
+	t.notThrows(() => validateModelSettings({temperature: 2}));
+});
+
+test('validateModelSettings rejects temperature outside 0 to 2', t => {
+	t.throws(() => validateModelSettings({temperature: -0.1}), {
+		message: 'temperature must be between 0 and 2, got -0.1',
+	});
+	t.throws(() => validateModelSettings({temperature: 2.1}), {
+		message: 'temperature must be between 0 and 2, got 2.1',
+	});
+	t.throws(() => validateModelSettings({temperature: Number.NaN}), {
+		message: /got NaN/,
+	});
+});
+
+test('validateModelSettings checks topP is in (0, 1]', t => {
+	t.notThrows(() => validateModelSettings({topP: 1}));
+	t.throws(() => validateModelSettings({topP: 0}), {
+		message: 'topP must be greater than 0 and at most 1, got 0',
+	});
+	t.throws(() => validateModelSettings({topP: 1.5}), {
+		message: 'topP must be greater than 0 and at most 1, got 1.5',
+	});
+});
+
+test('validateModelSettings rejects maxTokens that is not a positive whole number', t => {
+	t.notThrows(() => validateModelSettings({maxTokens: 1}));
+	for (const maxTokens of [0, -1, 1.5]) {
+		t.throws(() => validateModelSettings({maxTokens}), {
+			message: `maxTokens must be a positive whole number, got ${maxTokens}`,
+		});
+	}
+});
+
+test('validateModelSettings checks maxTokens against the model output limit', t => {
+	t.notThrows(() => validateModelSettings({maxTokens: 8192}, 8192));
+	t.throws(() => validateModelSettings({maxTokens: 8193}, 8192), {
+		message: "maxTokens 8193 exceeds the model's output limit of 8192 tokens",
+	});
+	// An unknown limit leaves any positive value allowed
+	t.notThrows(() => validateModelSettings({maxTokens: 1_000_000}, 0));
+});
+
+test.serial('setModelSettings refuses invalid settings without saving', async t => {
+	const preferencesPath = getTestPreferencesPath();
+
+	try {
+		savePreferences({modelSettings: {llama3: {temperature: 0.5}}});
+
+		await t.throwsAsync(setModelSettings('llama3', {temperature: 5}), {
+			message: /temperature must be between 0 and 2/,
+		});
+		t.deepEqual(getModelSettings('llama3'), {temperature: 0.5});
+	} finally {
+		if (existsSync(preferencesPath)) {
+			rmSync(preferencesPath, {force: true});
+		}
+	}
+});
+
+// Tests for directory trust helpers
+
+test.serial('isDirectoryTrusted matches a trusted directory exactly', t => {
+	const preferencesPath = getTestPreferencesPath();
+	const project = join(testConfigDir, 'trusted-project');
+	mkdirSync(project, {recursive: true});
+
+	try {
+		savePreferences({trustedDirectories: [project]});
+
+		t.true(isDirectoryTrusted(project));
+		t.true(isDirectoryTrusted(`${project}/`));
+	} finally {
+		rmSync(project, {recursive: true, force: true});
+		if (existsSync(preferencesPath)) {
+			rmSync(preferencesPath, {force: true});
+		}
+	}
+});
+
+test.serial('isDirectoryTrusted matches subdirectories of a trusted directory', t => {
+	const preferencesPath = getTestPreferencesPath();
+	const project = join(testConfigDir, 'trusted-project');
+	const nested = join(project, 'src', 'components');
+	mkdirSync(nested, {recursive: true});
+
+	try {
+		savePreferences({trustedDirectories: [project]});
+
+		t.true(isDirectoryTrusted(nested));
+	} finally {
+		rmSync(project, {recursive: true, force: true});
+		if (existsSync(preferencesPath)) {
+			rmSync(preferencesPath, {force: true});
+		}
+	}
+});
+
+test.serial('isDirectoryTrusted rejects untrusted and sibling-prefixed paths', t => {
+	const preferencesPath = getTestPreferencesPath();
+	const project = join(testConfigDir, 'trusted-project');
+	const sibling = join(testConfigDir, 'trusted-project-other');
+	mkdirSync(project, {recursive: true});
+	mkdirSync(sibling, {recursive: true});
+
+	try {
+		savePreferences({trustedDirectories: [project]});
+
+		t.false(isDirectoryTrusted(sibling));
+		t.false(isDirectoryTrusted(testConfigDir));
+	} finally {
+		rmSync(project, {recursive: true, force: true});
+		rmSync(sibling, {recursive: true, force: true});
+		if (existsSync(preferencesPath)) {
+			rmSync(preferencesPath, {force: true});
+		}
+	}
+});
+
+test.serial('isDirectoryTrusted returns false without trusted directories', t => {
+	const preferencesPath = getTestPreferencesPath();
+
+	try {
+		savePreferences({});
+
+		t.false(isDirectoryTrusted(testConfigDir));
+	} finally {
+		if (existsSync(preferencesPath)) {
+			rmSync(preferencesPath, {force: true});
+		}
+	}
+});
+
+test.serial('trustDirectory stores the canonical path once', t => {
+	const preferencesPath = getTestPreferencesPath();
+	const project = join(testConfigDir, 'trusted-project');
+	mkdirSync(project, {recursive: true});
+
+	try {
+		t.true(trustDirectory(project));
+		t.false(trustDirectory(join(project, '..', 'trusted-project')));
+
+		t.deepEqual(loadPreferences().trustedDirectories, [realpathSync(project)]);
+	} finally {
+		rmSync(project, {recursive: true, force: true});
+		if (existsSync(preferencesPath)) {
+			rmSync(preferencesPath, {force: true});
+		}
+	}
+});
+
+test.serial('untrustDirectory removes only the given directory', t => {
+	const preferencesPath = getTestPreferencesPath();
+	const project = join(testConfigDir, 'trusted-project');
+	const other = join(testConfigDir, 'other-project');
+	mkdirSync(project, {recursive: true});
+	mkdirSync(other, {recursive: true});
+
+	try {
+		savePreferences({trustedDirectories: [project, other]});
+
+		t.true(untrustDirectory(project));
+		t.false(untrustDirectory(project));
+
+		t.false(isDirectoryTrusted(project));
+		t.true(isDirectoryTrusted(other));
+		t.deepEqual(loadPreferences().trustedDirectories, [other]);
+	} finally {
+		rmSync(project, {recursive: true, force: true});
+		rmSync(other, {recursive: true, force: true});
+		if (existsSync(preferencesPath)) {
+			rmSync(preferencesPath, {force: true});
+		}
+	}
+});
@@ -1,7 +1,9 @@
-import {readFileSync, writeFileSync} from 'fs';
+import {readFileSync, realpathSync, writeFileSync} from 'fs';
+import path from 'path';
 import type {TitleShape} from '@/components/ui/styled-title';
 import {getClosestConfigFile} from '@/config/index';
-import type {UserPreferences} from '@/types/index';
+import {getModelOutputLimit} from '@/models/models-dev-client';
+import type {ModelSettings, UserPreferences} from '@/types/index';
 import {logError} from '@/utils/message-queue';
 
 let PREFERENCES_PATH: string | null = null;
@@ -70,3 +72,146 @@ export function getLastUsedModel(provider: string): string | undefined {
 	const preferences = loadPreferences();
 	return preferences.providerModels?.[provider];
 }
+
+export function getModelSettings(model: string): ModelSettings | undefined {
+	const preferences = loadPreferences();
+	return preferences.modelSettings?.[model];
+}
+
+/**
+ * Checks generation settings before they reach a provider, which would
+ * otherwise reject them with an opaque error. With the model's output limit,
+ * maxTokens is also checked against it.
+ * @throws Error describing the first setting out of range
+ */
+export function validateModelSettings(
+	settings: ModelSettings,
+	outputLimit?: number,
+): void {
+	const {temperature, maxTokens, topP} = settings;
+
+	if (
+		temperature !== undefined &&
+		!(Number.isFinite(temperature) && temperature >= 0 && temperature <= 2)
+	) {
+		throw new Error(
+			`temperature must be between 0 and 2, got ${String(temperature)}`,
+		);
+	}
+
+	if (topP !== undefined && !(Number.isFinite(topP) && topP > 0 && topP <= 1)) {
+		throw new Error(
+			`topP must be greater than 0 and at most 1, got ${String(topP)}`,
+		);
+	}
+
+	if (maxTokens !== undefined) {
+		if (!Number.isInteger(maxTokens) || maxTokens <= 0) {
+			throw new Error(
+				`maxTokens must be a positive whole number, got ${String(maxTokens)}`,
+			);
+		}
+		if (outputLimit && outputLimit > 0 && maxTokens > outputLimit) {
+			throw new Error(
+				`maxTokens ${maxTokens} exceeds the model's output limit of ${outputLimit} tokens`,
+			);
+		}
+	}
+}
+
+/**
+ * Remembers generation settings for a model. maxTokens is checked against
+ * the model's output limit from models.dev when it is known.
+ * @throws Error when a setting is out of range; nothing is saved
+ */
+export async function setModelSettings(
+	model: string,
+	settings: ModelSettings,
+): Promise<void> {
+	// Only maxTokens needs the limit, so skip the lookup without it
+	const outputLimit =
+		settings.maxTokens !== undefined ? await getModelOutputLimit(model) : null;
+	validateModelSettings(settings, outputLimit ?? undefined);
+	const preferences = loadPreferences();
+	if (!preferences.modelSettings) {
+		preferences.modelSettings = {};
+	}
+	preferences.modelSettings[model] = settings;
+	savePreferences(preferences);
+}
+
+// Resolve symlinks where possible so aliases of the same directory compare
+// equal; fall back to a plain resolve for paths that no longer exist
+function canonicalizeDirectory(directory: string): string {
+	const resolved = path.resolve(directory); // nosemgrep
+	try {
+		return realpathSync(resolved);
+	} catch {
+		return resolved;
+	}
+}
+
+function isWithinDirectory(directory: string, parent: string): boolean {
+	const relative = path.relative(parent, directory);
+	return (
+		relative === '' ||
+		(relative !== '..' &&
+			!relative.startsWith(`..${path.sep}`) &&
+			!path.isAbsolute(relative))
+	);
+}
+
+/**
+ * Returns true if the directory is a trusted directory or lies inside one
+ */
+export function isDirectoryTrusted(directory: string): boolean {
+	const preferences = loadPreferences();
+	const canonicalDirectory = canonicalizeDirectory(directory);
+	return (preferences.trustedDirectories || []).some(trustedDir =>
+		isWithinDirectory(canonicalDirectory, canonicalizeDirectory(trustedDir)),
+	);
+}
+
+/**
+ * Adds the directory to the trusted list. Returns false if it was already
+ * listed.
+ */
+export function trustDirectory(directory: string): boolean {
+	const preferences = loadPreferences();
+	const trustedDirectories = preferences.trustedDirectories || [];
+	const canonicalDirectory = canonicalizeDirectory(directory);
+
+	if (
+		trustedDirectories.some(
+			trustedDir => canonicalizeDirectory(trustedDir) === canonicalDirectory,
+		)
+	) {
+		return false;
+	}
+
+	trustedDirectories.push(canonicalDirectory);
+	preferences.trustedDirectories = trustedDirectories;
+	savePreferences(preferences);
+	return true;
+}
+
+/**
+ * Removes the directory from the trusted list. Returns false if it was not
+ * listed.
+ */
+export function untrustDirectory(directory: string): boolean {
+	const preferences = loadPreferences();
+	const trustedDirectories = preferences.trustedDirectories || [];
+	const canonicalDirectory = canonicalizeDirectory(directory);
+
+	const remaining = trustedDirectories.filter(
+		trustedDir => canonicalizeDirectory(trustedDir) !== canonicalDirectory,
+	);
+	if (remaining.length === trustedDirectories.length) {
+		return false;
+	}
+
+	preferences.trustedDirectories = remaining;
+	savePreferences(preferences);
+	return true;
+}
@@ -28,16 +28,22 @@ export const PASTE_LARGE_CONTENT_THRESHOLD_CHARS = 150;
 // === CACHE CONFIGURATION ===
 export const CACHE_FILE_TTL_MS = 5000;
 export const CACHE_MODELS_EXPIRATION_MS = 7 * 24 * 60 * 60 * 1000; // 7 days
+export const CACHE_TOOL_RESULT_TTL_MS = 60_000;
+export const MAX_CACHED_TOOL_RESULTS = 100;
 export const MAX_FILE_READ_RETRIES = 3;
 
 // === LIMITS ===
 export const MAX_CHECKPOINT_FILES = 50;
 export const MAX_FIND_FILES_RESULTS = 100;
+export const MAX_LIST_DIRECTORY_ENTRIES = 1000; // Stop walking beyond this
+export const MAX_LIST_DIRECTORY_DEPTH = 10;
 export const MAX_SEARCH_RESULTS = 100;
+export const MAX_SEARCH_TOTAL_COUNT = 10_000; // Stop counting matches beyond this
 export const MAX_PROMPT_HISTORY_SIZE = 100;
 export const MAX_USAGE_SESSIONS = 100;
 export const MAX_DAILY_AGGREGATES = 30;
 export const MAX_WEB_SEARCH_QUERY_LENGTH = 500;
+export const MAX_LSP_QUEUED_FRAMES = 1000; // Sends beyond this fail rather than buffer
 
 // === DEFAULTS ===
 export const DEFAULT_FIND_FILES_RESULTS = 50;
@@ -54,8 +60,11 @@ export const BUFFER_GREP_MULTIPLIER = 3;
 export const FILE_READ_METADATA_THRESHOLD_LINES = 300;
 export const FILE_READ_CHUNKING_HINT_THRESHOLD_LINES = 500;
 export const FILE_READ_CHUNK_SIZE_LINES = 250;
+export const FILE_READ_CHUNK_TOKEN_BUDGET = 4000; // Suggested chunk size when the model's tokenizer is known
 export const CHARS_PER_TOKEN_ESTIMATE = 4;
 export const MAX_LINE_LENGTH_CHARS = 10_000; // Lines longer than this are likely minified/binary
+export const FILE_READ_STREAMING_THRESHOLD_BYTES = 10 * 1024 * 1024; // Larger files are streamed, never loaded whole
+export const MAX_FILE_READ_BYTES = 10 * 1024 * 1024; // Larger files are only read by line range
 
 // === TERMINAL AND UI ===
 export const PATH_LENGTH_NARROW_TERMINAL = 30;
@@ -73,6 +82,7 @@ export const HEAP_USAGE_CRITICAL_THRESHOLD = 0.95;
 // === OUTPUT TRUNCATION ===
 export const TRUNCATION_OUTPUT_LIMIT = 2000;
 export const TRUNCATION_DESCRIPTION_LENGTH = 100;
+export const MAX_TOOL_RESULT_BYTES = 100 * 1024; // Any tool result above this is cut before it reaches the model
 
 // === DELAYS ===
 export const DELAY_COMMAND_COMPLETE_MS = 100;
@@ -91,6 +101,7 @@ export const MAX_SECONDARY_LANGUAGES = 3;
 
 // === USAGE CALCULATOR ===
 export const TOKENS_PER_TOOL_ESTIMATE = 150;
+export const STREAMING_TOKEN_RECOUNT_CHARS = 256; // Streamed text past this is counted once and not again
 export const USAGE_SUCCESS_THRESHOLD_PERCENT = 70;
 export const USAGE_ERROR_THRESHOLD_PERCENT = 90;
 
@@ -100,6 +111,11 @@ export const BUFFER_FILE_LIST_BYTES = 10 * 1024 * 1024; // 10 MB
 
 // === FETCH URL ===
 export const MAX_URL_CONTENT_BYTES = 100_000; // ~100 KB
+export const MAX_FETCH_HTML_BYTES = 5 * 1024 * 1024; // HTML parsed per page
+export const DEFAULT_FETCH_URL_MAX_REDIRECTS = 5;
+export const TIMEOUT_FETCH_URL_MS = 30_000;
+export const DEFAULT_FETCH_URL_USER_AGENT =
+	'Mozilla/5.0 (compatible; Nanocoder; +https://github.com/Nano-Collective/nanocoder)';
 
 // === HEALTH MONITOR ===
 export const INTERVAL_HEALTH_CHECK_MS = 30_000;
@@ -121,6 +137,10 @@ export const MAX_TOOL_STEPS = 10;
 
 // === MCP ===
 export const TIMEOUT_MCP_DEFAULT_MS = 30_000;
+export const DELAY_MCP_STARTUP_RETRY_MS = 250;
+export const TIMEOUT_MCP_SHUTDOWN_MS = 2000;
+export const MAX_MCP_LOG_MESSAGES_PER_WINDOW = 20; // Server log messages shown in chat per window
+export const MCP_LOG_WINDOW_MS = 1000;
 
 // === CODEBASE ANALYSIS ===
 export const THRESHOLD_LARGE_CODEBASE_FILES = 500;
@@ -1,6 +1,7 @@
 import test from 'ava';
 import {processAssistantResponse} from './conversation-loop.js';
 import type {Message, ToolCall, ToolResult} from '@/types/core';
+import {validateConversation} from '@/utils/conversation-validation';
 
 // ============================================================================
 // Test Helpers and Mocks
@@ -123,6 +124,98 @@ test.serial('processAssistantResponse - handles unknown tool errors', async t =>
 	t.pass('Unknown tool handling requires injectable filterValidToolCalls');
 });
 
+// Client returning each response in turn and recording what it was sent
+const createSequenceClient = (
+	responses: Array<{toolCalls?: ToolCall[]; content?: string}>,
+	sent: Message[][],
+) => ({
+	chat: async (messages: Message[]) => {
+		sent.push(messages);
+		const response = responses[sent.length - 1] ?? {content: 'Done'};
+		return {
+			choices: [
+				{
+					message: {
+						content: response.content || '',
+						tool_calls: response.toolCalls || null,
+					},
+				},
+			],
+		};
+	},
+});
+
+const call = (id: string, name: string): ToolCall => ({
+	id,
+	function: {name, arguments: {}},
+});
+
+test.serial('processAssistantResponse - answers every call after an unknown tool', async t => {
+	const sent: Message[][] = [];
+	const params = createDefaultParams({
+		client: createSequenceClient(
+			[{toolCalls: [call('call_1', 'read_file'), call('call_2', 'nope')]}],
+			sent,
+		),
+		toolManager: createMockToolManager({tools: ['read_file']}),
+	});
+
+	await processAssistantResponse(params as any);
+
+	t.is(sent.length, 2);
+	const [, ...history] = sent[1];
+	t.notThrows(() => validateConversation(history));
+	const toolMessages = history.filter(message => message.role === 'tool');
+	t.deepEqual(
+		toolMessages.map(message => message.tool_call_id).sort(),
+		['call_1', 'call_2'],
+	);
+	t.regex(
+		toolMessages.find(message => message.tool_call_id === 'call_1')!.content,
+		/^Skipped: not run because nope failed/,
+	);
+});
+
+test.serial('processAssistantResponse - waits for approval before continuing a mixed batch', async t => {
+	const sent: Message[][] = [];
+	let confirmationMessages: Message[] = [];
+	let confirmationCalls: ToolCall[] = [];
+	const toolManager = {
+		...createMockToolManager({tools: ['read_file', 'write_file']}),
+		getToolEntry: (name: string) => ({
+			tool: {needsApproval: name === 'write_file'},
+		}),
+	};
+	const params = createDefaultParams({
+		client: createSequenceClient(
+			[
+				{
+					toolCalls: [
+						call('call_1', 'read_file'),
+						call('call_2', 'write_file'),
+					],
+				},
+			],
+			sent,
+		),
+		toolManager,
+		onStartToolConfirmationFlow: (calls: ToolCall[], messages: Message[]) => {
+			confirmationCalls = calls;
+			confirmationMessages = messages;
+		},
+	});
+
+	await processAssistantResponse(params as any);
+
+	// The model isn't asked again until write_file has a result
+	t.is(sent.length, 1);
+	t.deepEqual(
+		confirmationCalls.map(toolCall => toolCall.id),
+		['call_2'],
+	);
+	t.is(confirmationMessages.at(-1)?.tool_call_id, 'call_1');
+});
+
 // ============================================================================
 // Plan Mode Blocking Tests (lines 265-310)
 // ============================================================================
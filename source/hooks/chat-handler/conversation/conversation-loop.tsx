@@ -1,17 +1,30 @@
 import type React from 'react';
+import {wasTruncated} from '@/ai-sdk-client';
 import type {ConversationStateManager} from '@/app/utils/conversation-state';
 import AssistantMessage from '@/components/assistant-message';
-import {ErrorMessage} from '@/components/message-box';
+import {ErrorMessage, WarningMessage} from '@/components/message-box';
 import UserMessage from '@/components/user-message';
+import {appConfig} from '@/config/index';
+import {createTokenizer, StreamingTokenCounter} from '@/tokenization/index';
 import {parseToolCalls} from '@/tool-calling/index';
 import type {ToolManager} from '@/tools/tool-manager';
-import type {LLMClient, Message, ToolCall, ToolResult} from '@/types/core';
+import type {
+	LLMChatResponse,
+	LLMClient,
+	Message,
+	ToolCall,
+	ToolResult,
+} from '@/types/core';
 import {getLogger} from '@/utils/logging';
 import {MessageBuilder} from '@/utils/message-builder';
 import {parseToolArguments} from '@/utils/tool-args-parser';
 import {displayToolResult} from '@/utils/tool-result-display';
 import {filterValidToolCalls} from '../utils/tool-filters';
-import {executeToolsDirectly} from './tool-executor';
+import {
+	createSkippedResult,
+	executeToolsDirectly,
+	executeToolsWithConfirmation,
+} from './tool-executor';
 
 interface ProcessAssistantResponseParams {
 	systemMessage: Message;
@@ -84,34 +97,46 @@ export const processAssistantResponse = async (
 	setStreamingContent('');
 	setTokenCount(0);
 
-	const result = await client.chat(
-		[systemMessage, ...messages],
-		toolManager?.getAllTools() || {},
-		{
-			onToolExecuted: (toolCall: ToolCall, result: string) => {
-				// Display formatter for auto-executed tools (after execution with results)
-				void (async () => {
-					const toolResult: ToolResult = {
-						tool_call_id: toolCall.id,
-						role: 'tool' as const,
-						name: toolCall.function.name,
-						content: result,
-					};
-					await displayToolResult(
-						toolCall,
-						toolResult,
-						toolManager,
-						addToChatQueue,
-						getNextComponentKey,
-					);
-				})();
+	// Running count of the reply's tokens, shown while it is generated
+	const tokenizer = createTokenizer('', currentModel);
+	const tokenCounter = new StreamingTokenCounter(tokenizer);
+
+	let result: LLMChatResponse;
+	try {
+		result = await client.chat(
+			[systemMessage, ...messages],
+			toolManager?.getAllTools() || {},
+			{
+				onToken: (text: string) => {
+					setTokenCount(tokenCounter.append(text));
+				},
+				onToolExecuted: (toolCall: ToolCall, result: string) => {
+					// Display formatter for auto-executed tools (after execution with results)
+					void (async () => {
+						const toolResult: ToolResult = {
+							tool_call_id: toolCall.id,
+							role: 'tool' as const,
+							name: toolCall.function.name,
+							content: result,
+						};
+						await displayToolResult(
+							toolCall,
+							toolResult,
+							toolManager,
+							addToChatQueue,
+							getNextComponentKey,
+						);
+					})();
+				},
+				onFinish: () => {
+					setIsGenerating(false);
+				},
 			},
-			onFinish: () => {
-				setIsGenerating(false);
-			},
-		},
-		controller.signal,
-	);
+			controller.signal,
+		);
+	} finally {
+		tokenizer.free?.();
+	}
 
 	if (!result || !result.choices || result.choices.length === 0) {
 		throw new Error('No response received from model');
@@ -121,8 +146,25 @@ export const processAssistantResponse = async (
 	const toolCalls = message.tool_calls || null;
 	const fullContent = message.content || '';
 
+	// Let the user know the response hit the output token limit
+	if (wasTruncated(result)) {
+		addToChatQueue(
+			<WarningMessage
+				key={`truncated-response-${getNextComponentKey()}`}
+				message="Response was cut off at the model's max output tokens. Ask the model to continue if the answer is incomplete."
+				hideBox={true}
+			/>,
+		);
+	}
+
 	// Parse any tool calls from content for non-tool-calling models
 	const parseResult = parseToolCalls(fullContent);
+	if (parseResult.success && parseResult.format !== 'none') {
+		getLogger().debug('Parsed tool calls from content', {
+			format: parseResult.format,
+			toolCallCount: parseResult.toolCalls.length,
+		});
+	}
 
 	// Check for malformed tool calls and send error back to model for self-correction
 	if (!parseResult.success) {
@@ -189,18 +231,31 @@ export const processAssistantResponse = async (
 	const {validToolCalls, errorResults} = filterValidToolCalls(
 		allToolCalls,
 		toolManager,
+		{allowDuplicates: appConfig.allowDuplicateToolCalls},
+	);
+
+	// Calls to unknown tools stay in the assistant message, so the error
+	// results sent back for them answer a call the provider has seen
+	const unknownToolCallIds = new Set(
+		errorResults.map(result => result.tool_call_id),
+	);
+	const unknownToolCalls = allToolCalls.filter(
+		(toolCall, index) =>
+			unknownToolCallIds.has(toolCall.id) &&
+			allToolCalls.findIndex(other => other.id === toolCall.id) === index,
 	);
+	const answeredToolCalls = [...validToolCalls, ...unknownToolCalls];
 
 	// Add assistant message to conversation history only if it has content or tool_calls
 	// Empty assistant messages cause API errors: "Assistant message must have either content or tool_calls"
 	const assistantMsg: Message = {
 		role: 'assistant',
 		content: cleanedContent,
-		tool_calls: validToolCalls.length > 0 ? validToolCalls : undefined,
+		tool_calls: answeredToolCalls.length > 0 ? answeredToolCalls : undefined,
 	};
 
 	const hasValidAssistantMessage =
-		cleanedContent.trim() || validToolCalls.length > 0;
+		cleanedContent.trim() || answeredToolCalls.length > 0;
 
 	// Build updated messages array using MessageBuilder
 	const builder = new MessageBuilder(messages);
@@ -247,9 +302,15 @@ export const processAssistantResponse = async (
 			);
 		}
 
-		// Send error results back to model for self-correction
+		// Send error results back to model for self-correction. The valid calls
+		// in the same batch aren't run, but each still needs a result.
 		const errorBuilder = new MessageBuilder(updatedMessages);
-		errorBuilder.addToolResults(errorResults);
+		errorBuilder.addToolResults([
+			...errorResults,
+			...validToolCalls.map(toolCall =>
+				createSkippedResult(toolCall, errorResults[0].name),
+			),
+		]);
 		const updatedMessagesWithError = errorBuilder.build();
 		setMessages(updatedMessagesWithError);
 
@@ -360,6 +421,9 @@ export const processAssistantResponse = async (
 			}
 		}
 
+		// Messages the confirmation flow adds its results to
+		let messagesBeforeConfirmation = updatedMessages;
+
 		// Execute non-confirmation tools directly
 		if (toolsToExecuteDirectly.length > 0) {
 			const directResults = await executeToolsDirectly(
@@ -368,6 +432,7 @@ export const processAssistantResponse = async (
 				conversationStateManager,
 				addToChatQueue,
 				getNextComponentKey,
+				{stopOnError: appConfig.stopToolsOnError, signal: controller.signal},
 			);
 
 			// If we have results, continue the conversation with them
@@ -378,17 +443,49 @@ export const processAssistantResponse = async (
 				const updatedMessagesWithTools = directBuilder.build();
 				setMessages(updatedMessagesWithTools);
 
-				// Continue the main conversation loop with tool results as context
+				// Continue the main conversation loop with tool results as context,
+				// once the calls that need approval have results too
+				if (toolsNeedingConfirmation.length === 0) {
+					await processAssistantResponse({
+						...params,
+						messages: updatedMessagesWithTools,
+					});
+					return;
+				}
+				messagesBeforeConfirmation = updatedMessagesWithTools;
+			}
+		}
+
+		// Start confirmation flow only for tools that need it
+		if (toolsNeedingConfirmation.length > 0) {
+			// An embedder's confirmation handler answers in place of the prompt
+			const {getToolConfirmationHandler} = await import('@/message-handler');
+			const confirmationHandler = getToolConfirmationHandler();
+			if (confirmationHandler) {
+				const confirmedResults = await executeToolsWithConfirmation(
+					toolsNeedingConfirmation,
+					confirmationHandler,
+					toolManager,
+					conversationStateManager,
+					addToChatQueue,
+					getNextComponentKey,
+					{stopOnError: appConfig.stopToolsOnError, signal: controller.signal},
+				);
+
+				const confirmedBuilder = new MessageBuilder(
+					messagesBeforeConfirmation,
+				);
+				confirmedBuilder.addToolResults(confirmedResults);
+				const updatedMessagesWithTools = confirmedBuilder.build();
+				setMessages(updatedMessagesWithTools);
+
 				await processAssistantResponse({
 					...params,
 					messages: updatedMessagesWithTools,
 				});
 				return;
 			}
-		}
 
-		// Start confirmation flow only for tools that need it
-		if (toolsNeedingConfirmation.length > 0) {
 			// In non-interactive mode, exit when tool approval is required
 			if (nonInteractiveMode) {
 				const toolNames = toolsNeedingConfirmation
@@ -410,8 +507,8 @@ export const processAssistantResponse = async (
 					role: 'assistant',
 					content: errorMsg,
 				};
-				// Use updatedMessages which already includes auto-executed tool results
-				const errorBuilder = new MessageBuilder(updatedMessages);
+				// Already includes auto-executed and direct tool results
+				const errorBuilder = new MessageBuilder(messagesBeforeConfirmation);
 				errorBuilder.addMessage(errorMessage);
 				setMessages(errorBuilder.build());
 
@@ -426,7 +523,7 @@ export const processAssistantResponse = async (
 			// useToolHandler will add tool results
 			onStartToolConfirmationFlow(
 				toolsNeedingConfirmation,
-				updatedMessages, // Includes assistant message
+				messagesBeforeConfirmation, // Includes assistant message
 				assistantMsg,
 				systemMessage,
 			);
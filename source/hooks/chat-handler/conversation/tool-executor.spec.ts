@@ -1,5 +1,9 @@
 import test from 'ava';
-import {executeToolsDirectly} from './tool-executor.js';
+import {setToolRegistryGetter} from '@/message-handler';
+import {
+	executeToolsDirectly,
+	executeToolsWithConfirmation,
+} from './tool-executor.js';
 import type {ToolCall, ToolResult} from '@/types/core';
 
 // ============================================================================
@@ -271,3 +275,209 @@ test('executeToolsDirectly - handles tool with valid validation', async t => {
 
 	t.pass('Valid validation requires processToolUse mock');
 });
+
+// ============================================================================
+// Stop On Error
+// ============================================================================
+
+// Registers handlers where failing_tool throws, recording which tools ran
+function registerRecordingTools(): string[] {
+	const ran: string[] = [];
+	setToolRegistryGetter(() => ({
+		failing_tool: async () => {
+			ran.push('failing_tool');
+			throw new Error('could not create file');
+		},
+		passing_tool: async () => {
+			ran.push('passing_tool');
+			return 'ok';
+		},
+	}));
+	return ran;
+}
+
+const dependentToolCalls: ToolCall[] = [
+	{id: 'call_1', function: {name: 'failing_tool', arguments: {}}},
+	{id: 'call_2', function: {name: 'passing_tool', arguments: {}}},
+	{id: 'call_3', function: {name: 'passing_tool', arguments: {}}},
+];
+
+test.serial('executeToolsDirectly - stopOnError skips tools after a failure', async t => {
+	const ran = registerRecordingTools();
+	const stateUpdates: string[] = [];
+	const conversationStateManager = {
+		current: {
+			updateAfterToolExecution: (toolCall: ToolCall) =>
+				stateUpdates.push(toolCall.id),
+		},
+	};
+
+	const results = await executeToolsDirectly(
+		dependentToolCalls,
+		null,
+		conversationStateManager as any,
+		() => {},
+		() => 1,
+		{stopOnError: true},
+	);
+
+	t.deepEqual(ran, ['failing_tool']);
+	t.deepEqual(
+		results.map(result => result.tool_call_id),
+		['call_1', 'call_2', 'call_3'],
+	);
+	t.regex(results[0].content, /^Error: .*could not create file/);
+	t.regex(
+		results[1].content,
+		/^Skipped: not run because failing_tool failed/,
+	);
+	t.regex(results[2].content, /^Skipped:/);
+	t.deepEqual(stateUpdates, ['call_1', 'call_2', 'call_3']);
+});
+
+test.serial('executeToolsDirectly - runs every tool without stopOnError', async t => {
+	const ran = registerRecordingTools();
+
+	const results = await executeToolsDirectly(
+		dependentToolCalls,
+		null,
+		createMockConversationStateManager() as any,
+		() => {},
+		() => 1,
+	);
+
+	t.deepEqual(ran, ['failing_tool', 'passing_tool', 'passing_tool']);
+	t.is(results[1].content, 'ok');
+	t.is(results[2].content, 'ok');
+});
+
+test.serial('executeToolsDirectly - stopOnError ignores output that only looks like an error', async t => {
+	const ran: string[] = [];
+	setToolRegistryGetter(() => ({
+		// e.g. a grep over logs whose first match starts with "Error: "
+		search_logs: async () => {
+			ran.push('search_logs');
+			return 'Error: disk full (from app.log)';
+		},
+		passing_tool: async () => {
+			ran.push('passing_tool');
+			return 'ok';
+		},
+	}));
+
+	const results = await executeToolsDirectly(
+		[
+			{id: 'call_1', function: {name: 'search_logs', arguments: {}}},
+			{id: 'call_2', function: {name: 'passing_tool', arguments: {}}},
+		],
+		null,
+		createMockConversationStateManager() as any,
+		() => {},
+		() => 1,
+		{stopOnError: true},
+	);
+
+	t.deepEqual(ran, ['search_logs', 'passing_tool']);
+	t.is(results[1].content, 'ok');
+});
+
+test.serial('executeToolsDirectly - stopOnError stops after a validation failure', async t => {
+	const ran = registerRecordingTools();
+
+	const results = await executeToolsDirectly(
+		dependentToolCalls.slice(1),
+		createMockToolManager({
+			validatorResult: {valid: false, error: 'Validation failed: bad path'},
+		}) as any,
+		createMockConversationStateManager() as any,
+		() => {},
+		() => 1,
+		{stopOnError: true},
+	);
+
+	t.deepEqual(ran, []);
+	t.is(results.length, 2);
+	t.regex(results[1].content, /^Skipped: not run because passing_tool failed/);
+});
+
+// ============================================================================
+// Confirmation Handler
+// ============================================================================
+
+const confirmedToolCalls: ToolCall[] = [
+	{id: 'call_1', function: {name: 'passing_tool', arguments: {}}},
+	{id: 'call_2', function: {name: 'passing_tool', arguments: {}}},
+];
+
+function runWithConfirmation(
+	confirm: (toolCall: ToolCall) => Promise<boolean>,
+): Promise<ToolResult[]> {
+	return executeToolsWithConfirmation(
+		confirmedToolCalls,
+		confirm,
+		null,
+		createMockConversationStateManager() as any,
+		() => {},
+		() => 1,
+	);
+}
+
+test.serial('executeToolsWithConfirmation - runs tools the handler approves', async t => {
+	const ran = registerRecordingTools();
+	const asked: string[] = [];
+
+	const results = await runWithConfirmation(async toolCall => {
+		asked.push(toolCall.id);
+		return true;
+	});
+
+	t.deepEqual(asked, ['call_1', 'call_2']);
+	t.deepEqual(ran, ['passing_tool', 'passing_tool']);
+	t.deepEqual(
+		results.map(result => result.content),
+		['ok', 'ok'],
+	);
+});
+
+test.serial('executeToolsWithConfirmation - rejects tools the handler denies', async t => {
+	const ran = registerRecordingTools();
+
+	const results = await runWithConfirmation(async () => false);
+
+	t.deepEqual(ran, []);
+	t.deepEqual(
+		results.map(result => [result.tool_call_id, result.content]),
+		[
+			['call_1', 'Error: Tool call denied by user'],
+			['call_2', 'Error: Tool call denied by user'],
+		],
+	);
+});
+
+test.serial('executeToolsWithConfirmation - keeps call order when only some are approved', async t => {
+	const ran = registerRecordingTools();
+
+	const results = await runWithConfirmation(
+		async toolCall => toolCall.id === 'call_2',
+	);
+
+	t.deepEqual(ran, ['passing_tool']);
+	t.deepEqual(
+		results.map(result => [result.tool_call_id, result.content]),
+		[
+			['call_1', 'Error: Tool call denied by user'],
+			['call_2', 'ok'],
+		],
+	);
+});
+
+test.serial('executeToolsWithConfirmation - denies a tool when the handler fails', async t => {
+	const ran = registerRecordingTools();
+
+	const results = await runWithConfirmation(async () => {
+		throw new Error('prompt closed');
+	});
+
+	t.deepEqual(ran, []);
+	t.is(results[0].content, 'Error: Tool call denied by user');
+});
@@ -1,16 +1,45 @@
 import type React from 'react';
 import type {ConversationStateManager} from '@/app/utils/conversation-state';
 import {ErrorMessage} from '@/components/message-box';
+import type {ToolConfirmationHandler} from '@/message-handler';
 import type {ToolManager} from '@/tools/tool-manager';
 import type {ToolCall, ToolResult} from '@/types/core';
 import {formatError} from '@/utils/error-formatter';
+import {getLogger} from '@/utils/logging';
 import {parseToolArguments} from '@/utils/tool-args-parser';
 import {displayToolResult} from '@/utils/tool-result-display';
 
+export interface ToolExecutionOptions {
+	// Skip the remaining tools once one fails, for dependent sequences such as
+	// creating a file and then editing it
+	stopOnError?: boolean;
+	// Aborting stops a running tool that supports it, such as execute_bash
+	signal?: AbortSignal;
+}
+
+/**
+ * Result for a tool call left unrun after failedTool failed. Every tool call
+ * still needs a result for the model to see.
+ */
+export function createSkippedResult(
+	toolCall: ToolCall,
+	failedTool: string,
+): ToolResult {
+	return {
+		tool_call_id: toolCall.id,
+		role: 'tool' as const,
+		name: toolCall.function.name,
+		content: `Skipped: not run because ${failedTool} failed earlier in the same batch of tool calls`,
+	};
+}
+
 /**
  * Executes tools directly without confirmation.
  * Handles validation, execution, and error display.
  *
+ * With stopOnError, the first failing tool ends the batch and the rest are
+ * returned as skipped.
+ *
  * @returns Array of tool results from executed tools
  */
 export const executeToolsDirectly = async (
@@ -19,12 +48,35 @@ export const executeToolsDirectly = async (
 	conversationStateManager: React.MutableRefObject<ConversationStateManager>,
 	addToChatQueue: (component: React.ReactNode) => void,
 	getNextComponentKey: () => number,
+	options: ToolExecutionOptions = {},
 ): Promise<ToolResult[]> => {
 	// Import processToolUse here to avoid circular dependencies
 	const {processToolUse} = await import('@/message-handler');
 	const directResults: ToolResult[] = [];
+	// Name of the first tool that failed, if any
+	let failedTool: string | undefined;
+
+	for (const [index, toolCall] of toolsToExecuteDirectly.entries()) {
+		if (failedTool && options.stopOnError) {
+			const remaining = toolsToExecuteDirectly.slice(index);
+			for (const skippedCall of remaining) {
+				const skippedResult = createSkippedResult(skippedCall, failedTool);
+				directResults.push(skippedResult);
+				conversationStateManager.current.updateAfterToolExecution(
+					skippedCall,
+					skippedResult.content,
+				);
+			}
+			addToChatQueue(
+				<ErrorMessage
+					key={`tools-skipped-${toolCall.id}-${Date.now()}`}
+					message={`Skipped ${remaining.length} remaining tool call${remaining.length === 1 ? '' : 's'} because ${failedTool} failed`}
+					hideBox={true}
+				/>,
+			);
+			break;
+		}
 
-	for (const toolCall of toolsToExecuteDirectly) {
 		try {
 			// Run validator if available
 			const validator = toolManager?.getToolValidator(toolCall.function.name);
@@ -39,6 +91,7 @@ export const executeToolsDirectly = async (
 						role: 'tool' as const,
 						name: toolCall.function.name,
 						content: validationResult.error,
+						isError: true,
 					};
 					directResults.push(errorResult);
 
@@ -57,12 +110,17 @@ export const executeToolsDirectly = async (
 						/>,
 					);
 
+					failedTool ??= toolCall.function.name;
 					continue; // Skip to next tool
 				}
 			}
 
-			const result = await processToolUse(toolCall);
+			const result = await processToolUse(toolCall, options.signal);
 			directResults.push(result);
+			// processToolUse reports tool failures as error results
+			if (result.isError) {
+				failedTool ??= toolCall.function.name;
+			}
 
 			// Update conversation state with tool execution
 			conversationStateManager.current.updateAfterToolExecution(
@@ -79,12 +137,14 @@ export const executeToolsDirectly = async (
 				getNextComponentKey,
 			);
 		} catch (error) {
+			failedTool ??= toolCall.function.name;
 			// Handle tool execution errors
 			const errorResult: ToolResult = {
 				tool_call_id: toolCall.id,
 				role: 'tool' as const,
 				name: toolCall.function.name,
 				content: `Error: ${formatError(error)}`,
+				isError: true,
 			};
 			directResults.push(errorResult);
 
@@ -107,3 +167,83 @@ export const executeToolsDirectly = async (
 
 	return directResults;
 };
+
+/**
+ * Result for a tool call the confirmation handler turned down
+ */
+function createDeniedResult(toolCall: ToolCall): ToolResult {
+	return {
+		tool_call_id: toolCall.id,
+		role: 'tool' as const,
+		name: toolCall.function.name,
+		content: 'Error: Tool call denied by user',
+	};
+}
+
+/**
+ * Asks the confirmation handler about each tool call, then executes the
+ * approved ones as executeToolsDirectly does. Denied calls are not run and
+ * get a denial as their result. Results come back in call order.
+ *
+ * @returns Array of tool results, one per tool call
+ */
+export const executeToolsWithConfirmation = async (
+	toolCalls: ToolCall[],
+	confirm: ToolConfirmationHandler,
+	toolManager: ToolManager | null,
+	conversationStateManager: React.MutableRefObject<ConversationStateManager>,
+	addToChatQueue: (component: React.ReactNode) => void,
+	getNextComponentKey: () => number,
+	options: ToolExecutionOptions = {},
+): Promise<ToolResult[]> => {
+	const approved: ToolCall[] = [];
+	const resultsById = new Map<string, ToolResult>();
+
+	for (const toolCall of toolCalls) {
+		let isApproved = false;
+		try {
+			isApproved = await confirm(toolCall);
+		} catch (error) {
+			getLogger().warn('Tool confirmation handler failed, denying tool call', {
+				toolName: toolCall.function.name,
+				error: formatError(error),
+			});
+		}
+
+		if (isApproved) {
+			approved.push(toolCall);
+			continue;
+		}
+
+		const deniedResult = createDeniedResult(toolCall);
+		resultsById.set(toolCall.id, deniedResult);
+		conversationStateManager.current.updateAfterToolExecution(
+			toolCall,
+			deniedResult.content,
+		);
+		addToChatQueue(
+			<ErrorMessage
+				key={`tool-denied-${toolCall.id}-${Date.now()}`}
+				message={`Denied ${toolCall.function.name}`}
+				hideBox={true}
+			/>,
+		);
+	}
+
+	const approvedResults = await executeToolsDirectly(
+		approved,
+		toolManager,
+		conversationStateManager,
+		addToChatQueue,
+		getNextComponentKey,
+		options,
+	);
+	for (const result of approvedResults) {
+		resultsById.set(result.tool_call_id, result);
+	}
+
+	return toolCalls.flatMap(toolCall => {
+		const result = resultsById.get(toolCall.id);
+		return result ? [result] : [];
+	});
+};
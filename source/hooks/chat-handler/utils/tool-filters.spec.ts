@@ -114,6 +114,52 @@ test('filterValidToolCalls - deduplicates by function signature', t => {
 	t.is(validToolCalls[0].id, 'call_1'); // First one wins
 });
 
+test('filterValidToolCalls - collapses repeated read_file calls', t => {
+	const toolCalls: ToolCall[] = [
+		{
+			id: 'xml_call_0',
+			function: {name: 'read_file', arguments: {path: 'src/a.ts', start_line: 1}},
+		},
+		{
+			id: 'xml_call_1',
+			// Same arguments in a different order
+			function: {name: 'read_file', arguments: {start_line: 1, path: 'src/a.ts'}},
+		},
+	];
+
+	const {validToolCalls} = filterValidToolCalls(toolCalls, null);
+
+	t.is(validToolCalls.length, 1);
+	t.is(validToolCalls[0].id, 'xml_call_0');
+});
+
+test('filterValidToolCalls - keeps identical calls when duplicates are allowed', t => {
+	const toolCalls: ToolCall[] = [
+		{
+			id: 'call_1',
+			function: {name: 'read_file', arguments: {path: 'src/a.ts'}},
+		},
+		{
+			id: 'call_2',
+			function: {name: 'read_file', arguments: {path: 'src/a.ts'}},
+		},
+		{
+			id: 'call_2',
+			function: {name: 'read_file', arguments: {path: 'src/b.ts'}},
+		},
+	];
+
+	const {validToolCalls} = filterValidToolCalls(toolCalls, null, {
+		allowDuplicates: true,
+	});
+
+	// Repeated IDs are still dropped
+	t.deepEqual(
+		validToolCalls.map(call => call.id),
+		['call_1', 'call_2'],
+	);
+});
+
 test('filterValidToolCalls - allows different tool calls', t => {
 	const toolCalls: ToolCall[] = [
 		{
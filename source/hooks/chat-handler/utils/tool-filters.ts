@@ -1,5 +1,7 @@
 import type {ToolManager} from '@/tools/tool-manager';
 import type {ToolCall, ToolResult} from '@/types/core';
+import {getLogger} from '@/utils/logging';
+import {canonicalJSON} from '@/utils/tool-args-parser';
 
 /**
  * Filters out invalid tool calls and deduplicates by ID and function signature.
@@ -9,11 +11,13 @@ import type {ToolCall, ToolResult} from '@/types/core';
  * - Empty tool calls (missing id or function name)
  * - Tools that don't exist in the tool manager
  * - Duplicate tool call IDs (GPT-5 issue)
- * - Functionally identical tool calls (same tool + arguments)
+ * - Functionally identical tool calls (same tool + arguments), unless
+ *   allowDuplicates is set. Dropped duplicates are logged.
  */
 export const filterValidToolCalls = (
 	toolCalls: ToolCall[],
 	toolManager: ToolManager | null,
+	{allowDuplicates = false}: {allowDuplicates?: boolean} = {},
 ): {validToolCalls: ToolCall[]; errorResults: ToolResult[]} => {
 	const seenIds = new Set<string>();
 	const seenFunctionCalls = new Set<string>();
@@ -48,10 +52,14 @@ export const filterValidToolCalls = (
 		}
 
 		// Filter out functionally identical tool calls (same tool + args)
-		const functionSignature = `${toolCall.function.name}:${JSON.stringify(
+		const functionSignature = `${toolCall.function.name}:${canonicalJSON(
 			toolCall.function.arguments,
 		)}`;
-		if (seenFunctionCalls.has(functionSignature)) {
+		if (!allowDuplicates && seenFunctionCalls.has(functionSignature)) {
+			getLogger().warn('Dropped duplicate tool call', {
+				tool: toolCall.function.name,
+				toolCallId: toolCall.id,
+			});
 			continue;
 		}
 
@@ -21,7 +21,11 @@ import {
 	updateCommand,
 	usageCommand,
 } from '@/commands/index';
-import {ErrorMessage, InfoMessage} from '@/components/message-box';
+import {
+	ErrorMessage,
+	InfoMessage,
+	WarningMessage,
+} from '@/components/message-box';
 import {appConfig, reloadAppConfig} from '@/config/index';
 import {
 	getLastUsedModel,
@@ -31,15 +35,22 @@ import {
 import {CustomCommandExecutor} from '@/custom-commands/executor';
 import {CustomCommandLoader} from '@/custom-commands/loader';
 import {getLSPManager, type LSPInitResult} from '@/lsp/index';
+import {MCPLogLimiter} from '@/mcp/log-limiter';
 import {setToolManagerGetter, setToolRegistryGetter} from '@/message-handler';
 import {ToolManager} from '@/tools/tool-manager';
+import {ToolResultCache} from '@/tools/tool-result-cache';
 import type {CustomCommand} from '@/types/commands';
 import {
 	LLMClient,
 	LSPConnectionStatus,
 	MCPConnectionStatus,
 } from '@/types/core';
-import type {MCPInitResult, UpdateInfo, UserPreferences} from '@/types/index';
+import type {
+	MCPInitResult,
+	MCPServerLogMessage,
+	UpdateInfo,
+	UserPreferences,
+} from '@/types/index';
 import {checkForUpdates} from '@/utils/update-checker';
 
 interface UseAppInitializationProps {
@@ -132,9 +143,51 @@ export function useAppInitialization({
 		setCustomCommandsCount(customCommands.length);
 	};
 
+	// Show log messages from servers whose level was set with /mcp log-level
+	const showMCPServerLog = ({
+		serverName,
+		level,
+		logger,
+		data,
+	}: MCPServerLogMessage) => {
+		const source = logger ? `${serverName}/${logger}` : serverName;
+		const text = typeof data === 'string' ? data : JSON.stringify(data);
+		const Message =
+			level === 'debug' || level === 'info' || level === 'notice'
+				? InfoMessage
+				: level === 'warning'
+					? WarningMessage
+					: ErrorMessage;
+
+		addToChatQueue(
+			<Message
+				key={`mcp-log-${getNextComponentKey()}`}
+				message={`[${source}] ${level}: ${text}`}
+				hideBox={true}
+			/>,
+		);
+	};
+
+	// Say how many server log messages were left out of the chat
+	const showDroppedMCPServerLogs = (count: number) => {
+		addToChatQueue(
+			<WarningMessage
+				key={`mcp-log-dropped-${getNextComponentKey()}`}
+				message={`Dropped ${count} MCP server log message${count === 1 ? '' : 's'} to keep the chat readable`}
+				hideBox={true}
+			/>,
+		);
+	};
+
 	// Initialize MCP servers if configured
 	const initializeMCPServers = async (toolManager: ToolManager) => {
 		if (appConfig.mcpServers && appConfig.mcpServers.length > 0) {
+			// Only a burst of server log messages per second reaches the chat
+			const logLimiter = new MCPLogLimiter(
+				showMCPServerLog,
+				showDroppedMCPServerLogs,
+			);
+
 			// Initialize status array
 			const mcpStatus: MCPConnectionStatus[] = appConfig.mcpServers.map(
 				server => ({
@@ -167,7 +220,11 @@ export function useAppInitialization({
 			};
 
 			try {
-				await toolManager.initializeMCP(appConfig.mcpServers, onProgress);
+				await toolManager.initializeMCP(
+					appConfig.mcpServers,
+					onProgress,
+					message => logLimiter.push(message),
+				);
 			} catch (error) {
 				// Mark all pending servers as failed
 				mcpStatus.forEach((status, index) => {
@@ -342,7 +399,7 @@ export function useAppInitialization({
 			setClient(null);
 			setCurrentModel('');
 
-			const newToolManager = new ToolManager();
+			const newToolManager = new ToolManager(appConfig.disabledTools);
 			const newCustomCommandLoader = new CustomCommandLoader();
 			const newCustomCommandExecutor = new CustomCommandExecutor();
 
@@ -362,6 +419,10 @@ export function useAppInitialization({
 			// Set up the tool manager getter for commands that need it
 			setToolManagerGetter(() => newToolManager);
 
+			newToolManager.setResultCache(
+				appConfig.cacheToolResults ? new ToolResultCache() : null,
+			);
+
 			commandRegistry.register([
 				helpCommand,
 				exitCommand,
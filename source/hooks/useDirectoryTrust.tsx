@@ -1,6 +1,6 @@
 import path from 'path';
 import {useCallback, useEffect, useState} from 'react';
-import {loadPreferences, savePreferences} from '@/config/preferences';
+import {isDirectoryTrusted, trustDirectory} from '@/config/preferences';
 import {logError, logInfo} from '@/utils/message-queue';
 
 interface UseDirectoryTrustReturn {
@@ -31,16 +31,7 @@ export function useDirectoryTrust(
 				setIsLoading(true);
 				setError(null);
 
-				const preferences = loadPreferences();
-				const trustedDirectories = preferences.trustedDirectories || [];
-
-				// Normalize paths for comparison (resolve any relative path components)
-				const normalizedDirectory = path.resolve(directory); // nosemgrep
-				const isTrustedDir = trustedDirectories.some(
-					trustedDir => path.resolve(trustedDir) === normalizedDirectory, // nosemgrep
-				);
-
-				setIsTrusted(isTrustedDir);
+				setIsTrusted(isDirectoryTrusted(directory));
 			} catch (err) {
 				const errorMessage =
 					err instanceof Error ? err.message : 'Unknown error occurred';
@@ -60,22 +51,9 @@ export function useDirectoryTrust(
 		try {
 			setError(null);
 
-			const preferences = loadPreferences();
-			const trustedDirectories = preferences.trustedDirectories || [];
-
-			// Normalize the directory path before storing and checking
-			const normalizedDirectory = path.resolve(directory); // nosemgrep
-
-			// Only add if not already trusted (check using normalized paths)
-			if (
-				!trustedDirectories.some(
-					trustedDir => path.resolve(trustedDir) === normalizedDirectory, // nosemgrep
-				)
-			) {
-				trustedDirectories.push(normalizedDirectory);
-				preferences.trustedDirectories = trustedDirectories;
-				savePreferences(preferences);
-
+			// Only logs when the directory was not already trusted
+			if (trustDirectory(directory)) {
+				const normalizedDirectory = path.resolve(directory); // nosemgrep
 				logInfo(`Directory added to trusted list: ${normalizedDirectory}`);
 			}
 
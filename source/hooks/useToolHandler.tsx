@@ -4,7 +4,13 @@ import {ErrorMessage, InfoMessage} from '@/components/message-box';
 import {setCurrentMode as setCurrentModeContext} from '@/context/mode-context';
 import {ConversationContext} from '@/hooks/useAppState';
 import {getToolManager, processToolUse} from '@/message-handler';
-import {executeBashCommand, formatBashResultForLLM} from '@/tools/execute-bash';
+import type {ShellName} from '@/services/bash-executor';
+import {
+	executeBashCommand,
+	formatBashResultAsJSON,
+	formatBashResultForLLM,
+	type OutputTruncation,
+} from '@/tools/execute-bash';
 import {
 	DevelopmentMode,
 	LLMClient,
@@ -275,7 +281,10 @@ export function useToolHandler({
 				const commandStr = parsedArgs.command as string;
 
 				// Start execution first to get execution ID
-				const {executionId, promise} = executeBashCommand(commandStr);
+				const {executionId, promise} = executeBashCommand(
+					commandStr,
+					parsedArgs.shell as ShellName | undefined,
+				);
 
 				// Set as live component (renders outside Static for real-time updates)
 				setLiveComponent(
@@ -289,7 +298,13 @@ export function useToolHandler({
 
 				// Wait for execution to complete
 				const bashResult = await promise;
-				const llmContent = formatBashResultForLLM(bashResult);
+				const truncation = parsedArgs.truncate as
+					| OutputTruncation
+					| undefined;
+				const llmContent =
+					parsedArgs.structured === true
+						? formatBashResultAsJSON(bashResult, truncation)
+						: formatBashResultForLLM(bashResult, truncation);
 
 				result = {
 					tool_call_id: currentTool.id,
@@ -1,13 +1,31 @@
 import {readFile} from 'node:fs/promises';
 import {useCallback, useEffect, useRef, useState} from 'react';
+import {getToolManager} from '@/message-handler';
+import {ProviderRegistry} from '@/provider-registry';
+import type {
+	AISDKCoreTool,
+	LLMClient,
+	Message,
+	ToolCategory,
+} from '@/types/core';
+import {estimateCost} from '@/usage/cost';
+import {countModelTokens, getSessionHeadroom} from '@/usage/headroom';
+import {processPromptTemplate} from '@/utils/prompt-processor';
 import {DEFAULT_PORT, getVSCodeServer, VSCodeServer} from '@/vscode/index';
 import type {DiagnosticInfo} from '@/vscode/protocol';
+import {BridgeSessionStore} from '@/vscode/session-store';
+import {BridgeToolRunner} from '@/vscode/tool-runner';
 
 interface UseVSCodeServerProps {
 	enabled: boolean;
 	port?: number;
 	currentModel?: string;
 	currentProvider?: string;
+	client?: LLMClient | null;
+	// Current conversation, saved by session_save
+	messages?: Message[];
+	// Replaces the conversation with one restored by session_load
+	onSessionLoaded?: (messages: Message[]) => void;
 	onPrompt?: (
 		prompt: string,
 		context?: {
@@ -44,6 +62,9 @@ export function useVSCodeServer({
 	port = DEFAULT_PORT,
 	currentModel,
 	currentProvider,
+	client,
+	messages,
+	onSessionLoaded,
 	onPrompt,
 	onDiagnosticsReceived,
 }: UseVSCodeServerProps): UseVSCodeServerReturn {
@@ -57,6 +78,11 @@ export function useVSCodeServer({
 	const onDiagnosticsReceivedRef = useRef(onDiagnosticsReceived);
 	const currentModelRef = useRef(currentModel);
 	const currentProviderRef = useRef(currentProvider);
+	const clientRef = useRef(client);
+	const messagesRef = useRef(messages);
+	const onSessionLoadedRef = useRef(onSessionLoaded);
+	// Clients the extension can chat with; the default is the CLI's own
+	const providerRegistryRef = useRef(new ProviderRegistry());
 
 	// Keep refs up to date
 	useEffect(() => {
@@ -75,6 +101,24 @@ export function useVSCodeServer({
 		currentProviderRef.current = currentProvider;
 	}, [currentProvider]);
 
+	useEffect(() => {
+		clientRef.current = client;
+	}, [client]);
+
+	useEffect(() => {
+		if (client && currentProvider) {
+			providerRegistryRef.current.register(currentProvider, client, true);
+		}
+	}, [client, currentProvider]);
+
+	useEffect(() => {
+		messagesRef.current = messages;
+	}, [messages]);
+
+	useEffect(() => {
+		onSessionLoadedRef.current = onSessionLoaded;
+	}, [onSessionLoaded]);
+
 	// Initialize server on mount if enabled
 	useEffect(() => {
 		if (!enabled) {
@@ -84,6 +128,8 @@ export function useVSCodeServer({
 		const initServer = async () => {
 			const server = await getVSCodeServer(port);
 			serverRef.current = server;
+			const sessionStore = new BridgeSessionStore();
+			const toolRunner = new BridgeToolRunner();
 
 			// Set up callbacks using refs
 			server.onCallbacks({
@@ -93,6 +139,97 @@ export function useVSCodeServer({
 				onDiagnosticsResponse: diagnostics => {
 					onDiagnosticsReceivedRef.current?.(diagnostics);
 				},
+				onGetModels: async () => {
+					const listModels = clientRef.current?.listModels;
+					if (!listModels) {
+						throw new Error('The current provider cannot list its models');
+					}
+					return listModels.call(clientRef.current);
+				},
+				onDescribeTool: async name => {
+					return getToolManager()?.describeTool(name);
+				},
+				onListTools: async filter => {
+					return (
+						getToolManager()?.listTools({
+							...filter,
+							categories: filter.categories as ToolCategory[] | undefined,
+						}) ?? []
+					);
+				},
+				onGetMCPStatus: async () => getToolManager()?.getServerStatus() ?? [],
+				onMCPRawRequest: async ({server, method, params}) => {
+					const toolManager = getToolManager();
+					if (!toolManager) {
+						throw new Error('No MCP servers are connected');
+					}
+					return toolManager.rawMCPRequest(server, method, params);
+				},
+				onSaveSession: async id => {
+					await sessionStore.save(
+						id,
+						messagesRef.current ?? [],
+						currentProviderRef.current ?? '',
+						currentModelRef.current ?? '',
+					);
+				},
+				onLoadSession: async id => {
+					const messages = await sessionStore.load(id);
+					onSessionLoadedRef.current?.(messages);
+					return messages;
+				},
+				onListSessions: async () => sessionStore.list(),
+				onCountRemaining: async ({messages, draft}) => {
+					const client = clientRef.current;
+					if (!client) {
+						throw new Error('No model is connected');
+					}
+					return getSessionHeadroom({
+						provider: currentProviderRef.current ?? '',
+						client,
+						messages: [
+							{role: 'system', content: processPromptTemplate()},
+							...(messages ?? messagesRef.current ?? []),
+							...(draft ? [{role: 'user' as const, content: draft}] : []),
+						],
+						tools: getToolManager()?.getAllTools() ?? {},
+					});
+				},
+				onCountTokens: async ({model, provider, messages, tools}) => {
+					const allTools = getToolManager()?.getAllTools() ?? {};
+					const counted: Record<string, AISDKCoreTool> = {};
+					for (const name of tools ?? []) {
+						if (!allTools[name]) {
+							throw new Error(`Unknown tool: ${name}`);
+						}
+						counted[name] = allTools[name];
+					}
+					// The client knows its own model's window best
+					const client = clientRef.current;
+					const isCurrentModel = client?.getCurrentModel() === model;
+					return countModelTokens({
+						provider: provider ?? currentProviderRef.current ?? '',
+						model,
+						messages,
+						tools: counted,
+						maxContext: isCurrentModel ? client?.getContextSize() : undefined,
+					});
+				},
+				onEstimateCost: async ({model, usage}) => estimateCost(model, usage),
+				onInitProvider: async provider => {
+					const client = await providerRegistryRef.current.init(provider);
+					return {model: client.getCurrentModel()};
+				},
+				onChat: async ({provider, messages}) => {
+					const registry = providerRegistryRef.current;
+					const response = await registry.chat(messages, provider);
+					return {
+						provider: provider ?? registry.getDefaultProvider() ?? '',
+						content: response.choices[0]?.message.content ?? '',
+					};
+				},
+				onExecuteTools: async toolCalls => toolRunner.executeMany(toolCalls),
+				onCancelBash: id => toolRunner.cancel(id),
 				onConnect: () => {
 					setIsConnected(true);
 					setConnectionCount(server.getConnectionCount());
@@ -0,0 +1,110 @@
+import {Writable} from 'stream';
+import test from 'ava';
+import {FrameWriter} from './frame-writer';
+
+console.log(`\nframe-writer.spec.ts`);
+
+function frame(message: object): string {
+	const content = JSON.stringify(message);
+	return `Content-Length: ${Buffer.byteLength(content)}\r\n\r\n${content}`;
+}
+
+// Splits Content-Length framed output back into message bodies
+function parseFrames(output: string): string[] {
+	const bodies: string[] = [];
+	let rest = output;
+	while (rest.length > 0) {
+		const match = /^Content-Length: (\d+)\r\n\r\n/.exec(rest);
+		if (!match) {
+			throw new Error(`Bad frame header: ${rest.slice(0, 40)}`);
+		}
+		const start = match[0].length;
+		const length = Number(match[1]);
+		bodies.push(rest.slice(start, start + length));
+		rest = rest.slice(start + length);
+	}
+	return bodies;
+}
+
+// A reader that takes a while over each chunk and buffers very little
+function createSlowStream(chunks: string[]): Writable {
+	return new Writable({
+		highWaterMark: 64,
+		write(chunk: Buffer, _encoding, callback) {
+			chunks.push(chunk.toString());
+			setTimeout(callback, 1);
+		},
+	});
+}
+
+test('FrameWriter keeps frames whole and in order under concurrent sends', async t => {
+	const chunks: string[] = [];
+	const stream = createSlowStream(chunks);
+	const writer = new FrameWriter(stream);
+
+	const sends = Array.from({length: 200}, (_, id) =>
+		writer.send(
+			frame({
+				jsonrpc: '2.0',
+				id,
+				method: 'test',
+				params: {text: 'x'.repeat(id)},
+			}),
+		),
+	);
+	await Promise.all(sends);
+	await new Promise<void>(resolve => stream.end(resolve));
+
+	const bodies = parseFrames(chunks.join(''));
+	t.is(bodies.length, 200);
+	bodies.forEach((body, index) => {
+		const message = JSON.parse(body) as {id: number; params: {text: string}};
+		t.is(message.id, index);
+		t.is(message.params.text.length, index);
+	});
+	// Each frame went to the stream in one write
+	t.is(chunks.length, 200);
+});
+
+test('FrameWriter waits for the stream to drain', async t => {
+	const chunks: string[] = [];
+	const stream = createSlowStream(chunks);
+	const writer = new FrameWriter(stream);
+
+	// Larger than the buffer, so the stream asks the writer to wait
+	void writer.send('x'.repeat(100));
+	const second = writer.send('y');
+	t.deepEqual(chunks, ['x'.repeat(100)]);
+
+	await second;
+	t.deepEqual(chunks, ['x'.repeat(100), 'y']);
+});
+
+test('FrameWriter rejects frames once the stream has closed', async t => {
+	const stream = createSlowStream([]);
+	const writer = new FrameWriter(stream);
+	stream.destroy();
+
+	await t.throwsAsync(writer.send(frame({jsonrpc: '2.0', method: 'exit'})), {
+		message: 'Stream is closed',
+	});
+});
+
+test('FrameWriter rejects sends once the queue is full', async t => {
+	const chunks: string[] = [];
+	const stream = createSlowStream(chunks);
+	const writer = new FrameWriter(stream, 2);
+
+	// The first frame fills the buffer, so the next two wait in the queue
+	const sends = [
+		writer.send('x'.repeat(100)),
+		writer.send('y'),
+		writer.send('z'),
+	];
+	await t.throwsAsync(writer.send('overflow'), {
+		message: 'Write queue is full (2 frames)',
+	});
+
+	await Promise.all(sends);
+	t.deepEqual(chunks, ['x'.repeat(100), 'y', 'z']);
+});
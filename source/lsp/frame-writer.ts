@@ -0,0 +1,103 @@
+/**
+ * Serialized writer for framed messages
+ * Owns a writable stream so complete frames go out one at a time, in order
+ */
+
+import type {Writable} from 'stream';
+import {MAX_LSP_QUEUED_FRAMES} from '@/constants';
+
+/**
+ * Resolves once the stream can take more data, or has closed and never will
+ */
+function waitForDrain(stream: Writable): Promise<void> {
+	return new Promise((resolve, reject) => {
+		const cleanup = () => {
+			stream.off('drain', onDrain);
+			stream.off('close', onDrain);
+			stream.off('error', onError);
+		};
+		const onDrain = () => {
+			cleanup();
+			resolve();
+		};
+		const onError = (error: Error) => {
+			cleanup();
+			reject(error);
+		};
+		stream.on('drain', onDrain);
+		stream.on('close', onDrain);
+		stream.on('error', onError);
+	});
+}
+
+interface QueuedFrame {
+	frame: string;
+	resolve: () => void;
+	reject: (error: Error) => void;
+}
+
+/**
+ * Queues frames for a stream and writes each one whole, pausing whenever the
+ * stream's buffer is full until it drains. Senders never touch the stream
+ * themselves, so frames can't interleave and a slow reader holds back the
+ * queue instead of letting the buffer grow. The queue itself is capped, so a
+ * reader that stops reading makes sends fail rather than pile up.
+ */
+export class FrameWriter {
+	private queue: QueuedFrame[] = [];
+	private draining = false;
+
+	constructor(
+		readonly stream: Writable,
+		private readonly maxQueuedFrames = MAX_LSP_QUEUED_FRAMES,
+	) {}
+
+	/**
+	 * Queue a complete frame. When nothing is waiting it is written at once.
+	 * @returns Resolves once the stream has accepted the frame
+	 * @throws Error when the queue is full, or the stream has closed or fails
+	 * while draining
+	 */
+	send(frame: string): Promise<void> {
+		if (this.queue.length >= this.maxQueuedFrames) {
+			return Promise.reject(
+				new Error(`Write queue is full (${this.maxQueuedFrames} frames)`),
+			);
+		}
+		return new Promise((resolve, reject) => {
+			this.queue.push({frame, resolve, reject});
+			if (!this.draining) {
+				this.flush();
+			}
+		});
+	}
+
+	private flush(): void {
+		while (this.queue.length > 0) {
+			const {frame, resolve, reject} = this.queue.shift() as QueuedFrame;
+			if (this.stream.destroyed || this.stream.writableEnded) {
+				reject(new Error('Stream is closed'));
+				continue;
+			}
+
+			const hasRoom = this.stream.write(frame);
+			resolve();
+			if (!hasRoom) {
+				this.draining = true;
+				waitForDrain(this.stream).then(
+					() => {
+						this.draining = false;
+						this.flush();
+					},
+					(error: Error) => {
+						this.draining = false;
+						for (const queued of this.queue.splice(0)) {
+							queued.reject(error);
+						}
+					},
+				);
+				return;
+			}
+		}
+	}
+}
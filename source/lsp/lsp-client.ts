@@ -6,6 +6,7 @@
 import {ChildProcess, spawn} from 'child_process';
 import {EventEmitter} from 'events';
 import {createChildLogger} from '@/utils/logging';
+import {FrameWriter} from './frame-writer';
 import {
 	CodeAction,
 	CodeActionParams,
@@ -49,6 +50,7 @@ const logger = createChildLogger({module: 'lsp-client'});
 
 export class LSPClient extends EventEmitter {
 	private process: ChildProcess | null = null;
+	private writer: FrameWriter | null = null;
 	private buffer: string = '';
 	private requestId: number = 0;
 	private pendingRequests: Map<number | string, PendingRequest> = new Map();
@@ -111,7 +113,7 @@ export class LSPClient extends EventEmitter {
 			// Send shutdown request
 			await this.sendRequest(LSPMethods.Shutdown, null);
 			// Send exit notification
-			this.sendNotification(LSPMethods.Exit, null);
+			await this.sendNotification(LSPMethods.Exit, null);
 		} catch (error) {
 			// Errors during shutdown are expected and non-critical
 			logger.debug(
@@ -153,7 +155,11 @@ export class LSPClient extends EventEmitter {
 	/**
 	 * Open a text document
 	 */
-	openDocument(uri: string, languageId: string, text: string): void {
+	async openDocument(
+		uri: string,
+		languageId: string,
+		text: string,
+	): Promise<void> {
 		const version = 1;
 		this.openDocuments.set(uri, version);
 
@@ -166,13 +172,13 @@ export class LSPClient extends EventEmitter {
 			},
 		};
 
-		this.sendNotification(LSPMethods.DidOpen, params);
+		await this.sendNotification(LSPMethods.DidOpen, params);
 	}
 
 	/**
 	 * Update a text document
 	 */
-	updateDocument(uri: string, text: string): void {
+	async updateDocument(uri: string, text: string): Promise<void> {
 		const version = (this.openDocuments.get(uri) || 0) + 1;
 		this.openDocuments.set(uri, version);
 
@@ -181,20 +187,20 @@ export class LSPClient extends EventEmitter {
 			contentChanges: [{text}],
 		};
 
-		this.sendNotification(LSPMethods.DidChange, params);
+		await this.sendNotification(LSPMethods.DidChange, params);
 	}
 
 	/**
 	 * Close a text document
 	 */
-	closeDocument(uri: string): void {
+	async closeDocument(uri: string): Promise<void> {
 		this.openDocuments.delete(uri);
 
 		const params: {textDocument: TextDocumentIdentifier} = {
 			textDocument: {uri},
 		};
 
-		this.sendNotification(LSPMethods.DidClose, params);
+		await this.sendNotification(LSPMethods.DidClose, params);
 	}
 
 	/**
@@ -379,7 +385,7 @@ export class LSPClient extends EventEmitter {
 		)) as InitializeResult;
 
 		// Send initialized notification
-		this.sendNotification(LSPMethods.Initialized, {});
+		await this.sendNotification(LSPMethods.Initialized, {});
 
 		return result;
 	}
@@ -391,7 +397,7 @@ export class LSPClient extends EventEmitter {
 				return;
 			}
 
-			const id = ++this.requestId;
+			const id = this.nextRequestId();
 			const request: JsonRpcRequest = {
 				jsonrpc: '2.0',
 				id,
@@ -408,11 +414,35 @@ export class LSPClient extends EventEmitter {
 			}, 30000);
 
 			this.pendingRequests.set(id, {resolve, reject, method, timeoutId});
-			this.send(request);
+			// A frame that never reaches the server won't get a response
+			this.send(request).catch((error: unknown) => {
+				clearTimeout(timeoutId);
+				this.pendingRequests.delete(id);
+				reject(error instanceof Error ? error : new Error(String(error)));
+			});
 		});
 	}
 
-	private sendNotification(method: string, params: unknown): void {
+	/**
+	 * Next request id, wrapping before ids lose integer precision and skipping
+	 * any id still waiting for a response
+	 */
+	private nextRequestId(): number {
+		do {
+			this.requestId =
+				this.requestId >= Number.MAX_SAFE_INTEGER ? 1 : this.requestId + 1;
+		} while (this.pendingRequests.has(this.requestId));
+		return this.requestId;
+	}
+
+	/**
+	 * Send a notification. Write failures are logged rather than thrown, since
+	 * nothing waits on a notification's result.
+	 */
+	private async sendNotification(
+		method: string,
+		params: unknown,
+	): Promise<void> {
 		if (!this.process?.stdin) return;
 
 		const notification: JsonRpcNotification = {
@@ -421,13 +451,44 @@ export class LSPClient extends EventEmitter {
 			params,
 		};
 
-		this.send(notification);
+		try {
+			await this.send(notification);
+		} catch (error) {
+			logger.warn(
+				{server: this.config.name, error},
+				'Failed to write to LSP server',
+			);
+		}
+	}
+
+	/**
+	 * The writer that owns the server's stdin, made for each new process
+	 */
+	private getWriter(): FrameWriter | null {
+		const stdin = this.process?.stdin;
+		if (!stdin) {
+			return null;
+		}
+		if (this.writer?.stream !== stdin) {
+			this.writer = new FrameWriter(stdin);
+		}
+		return this.writer;
 	}
 
-	private send(message: JsonRpcRequest | JsonRpcNotification): void {
+	/**
+	 * Write a framed message to the server
+	 * @throws Error when the process isn't running or the write fails
+	 */
+	private async send(
+		message: JsonRpcRequest | JsonRpcNotification,
+	): Promise<void> {
+		const writer = this.getWriter();
+		if (!writer) {
+			throw new Error('LSP process not running');
+		}
 		const content = JSON.stringify(message);
 		const header = `Content-Length: ${Buffer.byteLength(content)}\r\n\r\n`;
-		this.process?.stdin?.write(header + content);
+		await writer.send(header + content);
 	}
 
 	private handleData(data: string): void {
@@ -474,17 +535,24 @@ export class LSPClient extends EventEmitter {
 	}
 
 	private handleMessage(message: JsonRpcResponse | JsonRpcNotification): void {
-		// Check if it's a response
-		if ('id' in message && message.id !== null) {
-			const pending = this.pendingRequests.get(message.id);
-			if (pending) {
-				clearTimeout(pending.timeoutId);
-				this.pendingRequests.delete(message.id);
-				if (message.error) {
-					pending.reject(new Error(message.error.message));
-				} else {
-					pending.resolve(message.result);
-				}
+		// Check if it's a response. Ids are matched exactly as sent, so a string
+		// id only answers a request sent with that same string.
+		if ('id' in message && !('method' in message)) {
+			const pending =
+				message.id === null ? undefined : this.pendingRequests.get(message.id);
+			if (!pending) {
+				logger.warn(
+					{server: this.config.name, id: message.id, error: message.error},
+					'LSP response for unknown request id',
+				);
+				return;
+			}
+			clearTimeout(pending.timeoutId);
+			this.pendingRequests.delete(message.id as number | string);
+			if (message.error) {
+				pending.reject(new Error(message.error.message));
+			} else {
+				pending.resolve(message.result);
 			}
 			return;
 		}
@@ -160,16 +160,16 @@ test('LSPManager - openDocument returns false for unsupported extension', async
 });
 
 // updateDocument tests
-test('LSPManager - updateDocument returns false when no client available', t => {
+test('LSPManager - updateDocument returns false when no client available', async t => {
 	const manager = new LSPManager();
-	const result = manager.updateDocument('/test/file.ts', 'new content');
+	const result = await manager.updateDocument('/test/file.ts', 'new content');
 	t.false(result);
 });
 
 // closeDocument tests
-test('LSPManager - closeDocument returns false when no client available', t => {
+test('LSPManager - closeDocument returns false when no client available', async t => {
 	const manager = new LSPManager();
-	const result = manager.closeDocument('/test/file.ts');
+	const result = await manager.closeDocument('/test/file.ts');
 	t.false(result);
 });
 
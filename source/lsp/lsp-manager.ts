@@ -210,7 +210,7 @@ export class LSPManager extends EventEmitter {
 		// Read content if not provided
 		const text = content ?? (await readFile(filePath, 'utf-8'));
 
-		client.openDocument(uri, languageId, text);
+		await client.openDocument(uri, languageId, text);
 		this.documentServers.set(uri, client.getCapabilities() ? 'active' : '');
 
 		return true;
@@ -219,12 +219,12 @@ export class LSPManager extends EventEmitter {
 	/**
 	 * Update a document in the language server
 	 */
-	updateDocument(filePath: string, content: string): boolean {
+	async updateDocument(filePath: string, content: string): Promise<boolean> {
 		const client = this.getClientForFile(filePath);
 		if (!client || !client.isReady()) return false;
 
 		const uri = this.fileToUri(filePath);
-		client.updateDocument(uri, content);
+		await client.updateDocument(uri, content);
 
 		return true;
 	}
@@ -232,12 +232,12 @@ export class LSPManager extends EventEmitter {
 	/**
 	 * Close a document in the language server
 	 */
-	closeDocument(filePath: string): boolean {
+	async closeDocument(filePath: string): Promise<boolean> {
 		const client = this.getClientForFile(filePath);
 		if (!client || !client.isReady()) return false;
 
 		const uri = this.fileToUri(filePath);
-		client.closeDocument(uri);
+		await client.closeDocument(uri);
 		this.documentServers.delete(uri);
 		this.diagnosticsCache.delete(uri);
 
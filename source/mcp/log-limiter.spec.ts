@@ -0,0 +1,71 @@
+import test from 'ava';
+import type {MCPServerLogMessage} from '@/types/mcp';
+import {MCPLogLimiter} from './log-limiter';
+
+console.log('\nlog-limiter.spec.ts');
+
+const log = (data: string): MCPServerLogMessage => ({
+	serverName: 'docs',
+	level: 'info',
+	data,
+});
+
+test('MCPLogLimiter shows messages up to the limit', t => {
+	const shown: unknown[] = [];
+	const dropped: number[] = [];
+	const limiter = new MCPLogLimiter(
+		message => shown.push(message.data),
+		count => dropped.push(count),
+		3,
+		60_000,
+	);
+
+	for (const data of ['a', 'b', 'c']) {
+		limiter.push(log(data));
+	}
+	limiter.flush();
+
+	t.deepEqual(shown, ['a', 'b', 'c']);
+	t.deepEqual(dropped, []);
+});
+
+test('MCPLogLimiter drops a flood and reports how many', t => {
+	const shown: unknown[] = [];
+	const dropped: number[] = [];
+	const limiter = new MCPLogLimiter(
+		message => shown.push(message.data),
+		count => dropped.push(count),
+		3,
+		60_000,
+	);
+
+	for (let i = 0; i < 1000; i++) {
+		limiter.push(log(String(i)));
+	}
+	limiter.flush();
+
+	t.deepEqual(shown, ['0', '1', '2']);
+	t.deepEqual(dropped, [997]);
+});
+
+test('MCPLogLimiter reports drops when the window ends', async t => {
+	const shown: unknown[] = [];
+	const dropped: number[] = [];
+	const limiter = new MCPLogLimiter(
+		message => shown.push(message.data),
+		count => dropped.push(count),
+		1,
+		50,
+	);
+
+	limiter.push(log('first'));
+	limiter.push(log('second'));
+	limiter.push(log('third'));
+	t.deepEqual(dropped, []);
+	await new Promise(resolve => setTimeout(resolve, 100));
+
+	t.deepEqual(dropped, [2]);
+	limiter.push(log('fourth'));
+	t.deepEqual(shown, ['first', 'fourth']);
+	limiter.flush();
+});
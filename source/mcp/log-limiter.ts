@@ -0,0 +1,59 @@
+import {MAX_MCP_LOG_MESSAGES_PER_WINDOW, MCP_LOG_WINDOW_MS} from '@/constants';
+import type {MCPServerLogMessage} from '@/types/mcp';
+
+/**
+ * Passes on at most maxPerWindow server log messages per window, so a chatty
+ * server can't flood the chat. Messages over the limit are dropped and
+ * counted, and the count is reported when the window ends.
+ */
+export class MCPLogLimiter {
+	private windowStart = 0;
+	private shown = 0;
+	private dropped = 0;
+	private flushTimer: ReturnType<typeof setTimeout> | undefined;
+
+	constructor(
+		private readonly show: (message: MCPServerLogMessage) => void,
+		private readonly reportDropped: (count: number) => void,
+		private readonly maxPerWindow = MAX_MCP_LOG_MESSAGES_PER_WINDOW,
+		private readonly windowMs = MCP_LOG_WINDOW_MS,
+	) {}
+
+	push(message: MCPServerLogMessage): void {
+		const now = Date.now();
+		if (now - this.windowStart >= this.windowMs) {
+			this.flush();
+			this.windowStart = now;
+			this.shown = 0;
+		}
+
+		if (this.shown < this.maxPerWindow) {
+			this.shown++;
+			this.show(message);
+			return;
+		}
+
+		this.dropped++;
+		if (!this.flushTimer) {
+			this.flushTimer = setTimeout(
+				() => this.flush(),
+				this.windowStart + this.windowMs - now,
+			);
+			// A pending report must not keep nanocoder running
+			this.flushTimer.unref?.();
+		}
+	}
+
+	/**
+	 * Report the messages dropped so far, if any
+	 */
+	flush(): void {
+		clearTimeout(this.flushTimer);
+		this.flushTimer = undefined;
+		if (this.dropped > 0) {
+			const count = this.dropped;
+			this.dropped = 0;
+			this.reportDropped(count);
+		}
+	}
+}
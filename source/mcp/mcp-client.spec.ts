@@ -1,4 +1,9 @@
+import {existsSync, mkdtempSync, rmSync, writeFileSync} from 'node:fs';
+import {tmpdir} from 'node:os';
+import {join} from 'node:path';
 import test from 'ava';
+import {asSchema} from 'ai';
+import type {MCPServerLogMessage} from '@/types/index';
 import {MCPClient} from './mcp-client';
 
 // ============================================================================
@@ -222,6 +227,53 @@ test('MCPClient.getAllTools: includes required parameters from schema', t => {
 	t.deepEqual(tools[0].function.parameters.required, ['arg1']);
 });
 
+// A schema with nested objects, array items, nested required lists and a
+// $ref into top-level $defs
+const nestedSchema = {
+	type: 'object',
+	properties: {
+		issue: {
+			type: 'object',
+			properties: {
+				title: {type: 'string', minLength: 1},
+				labels: {
+					type: 'array',
+					items: {type: 'string', enum: ['bug', 'docs']},
+				},
+				assignee: {$ref: '#/$defs/user'},
+			},
+			required: ['title'],
+			additionalProperties: false,
+		},
+	},
+	required: ['issue'],
+	additionalProperties: false,
+	$defs: {
+		user: {
+			type: 'object',
+			properties: {login: {type: 'string'}},
+			required: ['login'],
+		},
+	},
+};
+
+test('MCPClient.getAllTools: keeps a nested schema intact', t => {
+	const client = new MCPClient();
+
+	(client as any).serverTools.set('github', [
+		{
+			name: 'create_issue',
+			description: 'Create an issue',
+			inputSchema: structuredClone(nestedSchema),
+			serverName: 'github',
+		},
+	]);
+
+	const [tool] = client.getAllTools();
+
+	t.deepEqual(tool.function.parameters, nestedSchema);
+});
+
 test('MCPClient.getAllTools: handles multiple servers', t => {
 	const client = new MCPClient();
 
@@ -459,6 +511,164 @@ test('MCPClient.callTool: throws error when client not connected for server', as
 	);
 });
 
+test('MCPClient: marks server disconnected when its connection closes', t => {
+	const client = new MCPClient();
+	const mockClient = new MockClient();
+
+	(client as any).clients.set('test-server', mockClient);
+	(client as any).transports.set('test-server', mockTransport);
+	(client as any).serverTools.set('test-server', [
+		{name: 'tool1', inputSchema: {}, serverName: 'test-server'},
+	]);
+
+	(client as any).handleServerClosed('test-server', mockClient);
+
+	t.false(client.isServerConnected('test-server'));
+	const [status] = client.getServerStatus();
+	t.false(status.connected);
+	t.is(status.lastError, 'Connection closed unexpectedly');
+	// Tools stay routable so a reconnect can be attempted
+	t.true(client.getToolMapping().has('tool1'));
+});
+
+test('MCPClient.callTool: reconnects to a dead server when reconnect is enabled', async t => {
+	const client = new MCPClient();
+	const serverConfig = {
+		name: 'test-server',
+		transport: 'stdio' as const,
+		command: 'node',
+		reconnect: {enabled: true, maxAttempts: 3, backoffMs: 0},
+	};
+	let connectAttempts = 0;
+
+	(client as any).serverConfigs.set('test-server', serverConfig);
+	(client as any).serverTools.set('test-server', [
+		{name: 'test_tool', inputSchema: {}, serverName: 'test-server'},
+	]);
+	(client as any).connectToServer = async () => {
+		connectAttempts++;
+		if (connectAttempts < 2) {
+			throw new Error('spawn failed');
+		}
+		(client as any).clients.set('test-server', new MockClient());
+	};
+
+	const result = await client.callTool('test_tool', {});
+
+	t.is(connectAttempts, 2);
+	t.is(result, 'Test result');
+});
+
+test('MCPClient.callTool: does not reconnect when reconnect is disabled', async t => {
+	const client = new MCPClient();
+	let connectAttempts = 0;
+
+	(client as any).serverConfigs.set('test-server', {
+		name: 'test-server',
+		transport: 'stdio',
+		command: 'node',
+	});
+	(client as any).serverTools.set('test-server', [
+		{name: 'test_tool', inputSchema: {}, serverName: 'test-server'},
+	]);
+	(client as any).connectToServer = async () => {
+		connectAttempts++;
+	};
+
+	await t.throwsAsync(() => client.callTool('test_tool', {}), {
+		message: /No MCP client connected for server: test-server/,
+	});
+	t.is(connectAttempts, 0);
+});
+
+// Two servers that both offer a tool named "search"
+function createClientWithDuplicateTools(): MCPClient {
+	const client = new MCPClient();
+	for (const serverName of ['docs', 'code']) {
+		(client as any).clients.set(serverName, {
+			callTool: async ({name}: {name: string}) => ({
+				content: [{type: 'text', text: `${name} on ${serverName}`}],
+			}),
+		});
+		(client as any).serverTools.set(serverName, [
+			{name: 'search', inputSchema: {}, serverName},
+		]);
+	}
+	return client;
+}
+
+test('MCPClient.callToolOnServer: calls the tool on the named server', async t => {
+	const client = createClientWithDuplicateTools();
+
+	t.is(await client.callToolOnServer('docs', 'search', {}), 'search on docs');
+	t.is(await client.callToolOnServer('code', 'search', {}), 'search on code');
+});
+
+test('MCPClient.callToolOnServer: throws for a server that is not connected', async t => {
+	const client = createClientWithDuplicateTools();
+
+	await t.throwsAsync(() => client.callToolOnServer('web', 'search', {}), {
+		message: 'MCP server not connected: web',
+	});
+});
+
+test('MCPClient.callToolOnServer: throws for a tool the server lacks', async t => {
+	const client = createClientWithDuplicateTools();
+
+	await t.throwsAsync(() => client.callToolOnServer('docs', 'fetch', {}), {
+		message: 'MCP server "docs" has no tool: fetch',
+	});
+});
+
+test('MCPClient.callToolOnServer: throws when the server connection is gone', async t => {
+	const client = createClientWithDuplicateTools();
+	(client as any).clients.delete('docs');
+
+	await t.throwsAsync(() => client.callToolOnServer('docs', 'search', {}), {
+		message: 'No MCP client connected for server: docs',
+	});
+});
+
+function createClientReturning(content: unknown[]): MCPClient {
+	const client = new MCPClient();
+	(client as any).clients.set('test-server', {
+		callTool: async () => ({content}),
+	});
+	(client as any).serverTools.set('test-server', [
+		{name: 'test_tool', inputSchema: {}, serverName: 'test-server'},
+	]);
+	return client;
+}
+
+test('MCPClient.callTool: summarises audio content instead of inlining it', async t => {
+	const client = createClientReturning([
+		{type: 'audio', data: 'UklGRiQAAABXQVZF', mimeType: 'audio/wav'},
+	]);
+
+	const result = await client.callTool('test_tool', {});
+
+	t.is(result, '[audio content: audio/wav, 12 bytes]');
+});
+
+test('MCPClient.callTool: summarises image content instead of inlining it', async t => {
+	const client = createClientReturning([
+		{type: 'image', data: 'iVBORw0KGgo=', mimeType: 'image/png'},
+	]);
+
+	const result = await client.callTool('test_tool', {});
+
+	t.is(result, '[image content: image/png, 9 bytes]');
+});
+
+test('MCPClient.callTool: passes unknown content types through as JSON', async t => {
+	const content = {type: 'hologram', payload: {frames: 3}};
+	const client = createClientReturning([content]);
+
+	const result = await client.callTool('test_tool', {});
+
+	t.deepEqual(JSON.parse(result), content);
+});
+
 // ============================================================================
 // Tests for disconnect
 // ============================================================================
@@ -521,6 +731,24 @@ test('MCPClient.getNativeToolsRegistry: creates tools with needsApproval callbac
 	t.is(typeof registry.test_tool.needsApproval, 'function');
 });
 
+test('MCPClient.getNativeToolsRegistry: keeps a nested schema intact', async t => {
+	const client = new MCPClient();
+
+	(client as any).serverTools.set('github', [
+		{
+			name: 'create_issue',
+			description: 'Create an issue',
+			inputSchema: structuredClone(nestedSchema),
+			serverName: 'github',
+		},
+	]);
+
+	const registry = client.getNativeToolsRegistry();
+	const schema = await asSchema(registry.create_issue.inputSchema).jsonSchema;
+
+	t.deepEqual(schema, nestedSchema);
+});
+
 test('MCPClient.getNativeToolsRegistry: includes description with server prefix', t => {
 	const client = new MCPClient();
 
@@ -603,6 +831,48 @@ test('MCPClient.getServerInfo: returns info for connected server', t => {
 	t.deepEqual(serverInfo?.tags, ['test', 'demo']);
 });
 
+test('MCPClient.getServerStatus: returns empty array when no servers', t => {
+	const client = new MCPClient();
+
+	t.deepEqual(client.getServerStatus(), []);
+});
+
+test('MCPClient.getServerStatus: reports tool count for connected server', t => {
+	const client = new MCPClient();
+
+	(client as any).clients.set('test-server', new MockClient());
+	(client as any).serverTools.set('test-server', [
+		{name: 'tool1', inputSchema: {}, serverName: 'test-server'},
+		{name: 'tool2', inputSchema: {}, serverName: 'test-server'},
+		{name: 'tool3', inputSchema: {}, serverName: 'test-server'},
+	]);
+
+	const status = client.getServerStatus();
+
+	t.deepEqual(status, [
+		{name: 'test-server', connected: true, toolCount: 3, lastError: undefined},
+	]);
+});
+
+test('MCPClient.getServerStatus: includes servers that failed to connect', async t => {
+	const client = new MCPClient();
+
+	await t.throwsAsync(
+		async () =>
+			await client.connectToServer({
+				name: 'bad-server',
+				transport: 'stdio',
+			} as any),
+	);
+
+	const [status] = client.getServerStatus();
+
+	t.is(status.name, 'bad-server');
+	t.false(status.connected);
+	t.is(status.toolCount, 0);
+	t.regex(status.lastError!, /Invalid MCP server configuration/);
+});
+
 test('MCPClient.getServerInfo: includes URL for remote servers', t => {
 	const client = new MCPClient();
 
@@ -943,3 +1213,361 @@ test('MCPClient.connectToServer: validates websocket URL protocol', async t => {
 		{message: /websocket URL must use ws:\/\/ or wss:\/\/ protocol/i},
 	);
 });
+
+// ============================================================================
+// Tests for MCPClient - Startup Grace Period
+// ============================================================================
+
+// Minimal stdio MCP server that waits before answering initialize. Given a
+// marker path, the first launch exits immediately to simulate a server that
+// is not ready yet.
+const SLOW_SERVER_SCRIPT = `
+const fs = require('node:fs');
+const readline = require('node:readline');
+const [delay, marker] = process.argv.slice(2);
+if (marker && !fs.existsSync(marker)) {
+	fs.writeFileSync(marker, '');
+	process.exit(1);
+}
+const send = message => process.stdout.write(JSON.stringify(message) + '\\n');
+readline.createInterface({input: process.stdin}).on('line', line => {
+	const message = JSON.parse(line);
+	if (message.method === 'initialize') {
+		setTimeout(() => send({
+			jsonrpc: '2.0',
+			id: message.id,
+			result: {
+				protocolVersion: message.params.protocolVersion,
+				capabilities: {tools: {}, logging: {}},
+				serverInfo: {name: 'slow', version: '1.0.0'},
+			},
+		}), Number(delay));
+	} else if (message.method === 'tools/list') {
+		send({
+			jsonrpc: '2.0',
+			id: message.id,
+			result: {tools: [{name: 'slow_tool', inputSchema: {type: 'object'}}]},
+		});
+	} else if (message.method === 'custom/echo') {
+		send({jsonrpc: '2.0', id: message.id, result: {echo: message.params}});
+	} else if (message.method === 'logging/setLevel') {
+		send({jsonrpc: '2.0', id: message.id, result: {}});
+		send({
+			jsonrpc: '2.0',
+			method: 'notifications/message',
+			params: {level: 'info', logger: 'slow', data: 'level is ' + message.params.level},
+		});
+	}
+});
+`;
+
+function withSlowServer(
+	run: (scriptPath: string, dir: string) => Promise<void>,
+): () => Promise<void> {
+	return async () => {
+		const dir = mkdtempSync(join(tmpdir(), 'nanocoder-mcp-'));
+		const scriptPath = join(dir, 'slow-server.cjs');
+		writeFileSync(scriptPath, SLOW_SERVER_SCRIPT);
+		try {
+			await run(scriptPath, dir);
+		} finally {
+			rmSync(dir, {recursive: true, force: true});
+		}
+	};
+}
+
+test.serial('MCPClient: waits for a slow server within startupTimeout', async t => {
+	t.timeout(10000);
+	await withSlowServer(async scriptPath => {
+		const client = new MCPClient();
+
+		try {
+			const [result] = await client.connectToServers([
+				{
+					name: 'slow-server',
+					transport: 'stdio',
+					command: process.execPath,
+					args: [scriptPath, '500'],
+					startupTimeout: 5000,
+				},
+			]);
+
+			t.true(result.success, result.error);
+			t.is(result.toolCount, 1);
+			t.true((result.durationMs ?? 0) >= 500);
+		} finally {
+			await client.disconnect();
+		}
+	})();
+});
+
+test.serial('MCPClient: fails a server that is not ready within startupTimeout', async t => {
+	t.timeout(10000);
+	await withSlowServer(async scriptPath => {
+		const client = new MCPClient();
+
+		const [result] = await client.connectToServers([
+			{
+				name: 'slow-server',
+				transport: 'stdio',
+				command: process.execPath,
+				args: [scriptPath, '3000'],
+				startupTimeout: 500,
+			},
+		]);
+
+		t.false(result.success);
+		t.regex(
+			result.error ?? '',
+			/"slow-server" did not become ready within 500ms/,
+		);
+		t.is(typeof result.durationMs, 'number');
+		t.false(client.isServerConnected('slow-server'));
+	})();
+});
+
+test.serial('MCPClient: retries initialize when a starting server exits', async t => {
+	t.timeout(10000);
+	await withSlowServer(async (scriptPath, dir) => {
+		const client = new MCPClient();
+		const marker = join(dir, 'started');
+
+		try {
+			await client.connectToServer({
+				name: 'flaky-server',
+				transport: 'stdio',
+				command: process.execPath,
+				args: [scriptPath, '0', marker],
+				startupTimeout: 5000,
+			});
+
+			t.true(existsSync(marker));
+			t.true(client.isServerConnected('flaky-server'));
+		} finally {
+			await client.disconnect();
+		}
+	})();
+});
+
+test.serial('MCPClient: a server that hangs times out without holding up the rest', async t => {
+	t.timeout(10000);
+	await withSlowServer(async scriptPath => {
+		const client = new MCPClient();
+
+		try {
+			const results = await client.connectToServers([
+				{
+					name: 'hung-server',
+					transport: 'stdio',
+					command: process.execPath,
+					args: [scriptPath, '1500'],
+					timeout: 300,
+				},
+				{
+					name: 'ready-server',
+					transport: 'stdio',
+					command: process.execPath,
+					args: [scriptPath, '0'],
+				},
+			]);
+
+			const hung = results.find(r => r.serverName === 'hung-server');
+			const ready = results.find(r => r.serverName === 'ready-server');
+			t.false(hung?.success);
+			t.regex(hung?.error ?? '', /"hung-server" did not connect within 300ms/);
+			t.true(ready?.success, ready?.error);
+			t.is(ready?.toolCount, 1);
+
+			// Connecting late doesn't bring the server back
+			await new Promise(resolve => setTimeout(resolve, 1500));
+			t.false(client.isServerConnected('hung-server'));
+			t.true(client.isServerConnected('ready-server'));
+		} finally {
+			await client.disconnect();
+		}
+	})();
+});
+
+// Minimal stdio MCP server that, once its tools are listed, answers a request
+// that was never sent
+const STRAY_RESPONSE_SERVER_SCRIPT = `
+const readline = require('node:readline');
+const send = message => process.stdout.write(JSON.stringify(message) + '\\n');
+readline.createInterface({input: process.stdin}).on('line', line => {
+	const message = JSON.parse(line);
+	if (message.method === 'initialize') {
+		send({
+			jsonrpc: '2.0',
+			id: message.id,
+			result: {
+				protocolVersion: message.params.protocolVersion,
+				capabilities: {tools: {}},
+				serverInfo: {name: 'stray', version: '1.0.0'},
+			},
+		});
+	} else if (message.method === 'tools/list') {
+		send({jsonrpc: '2.0', id: message.id, result: {tools: []}});
+		send({jsonrpc: '2.0', id: 9999, result: {}});
+	}
+});
+`;
+
+test.serial('MCPClient: counts responses that match no pending request', async t => {
+	t.timeout(10000);
+	const dir = mkdtempSync(join(tmpdir(), 'nanocoder-mcp-'));
+	const scriptPath = join(dir, 'stray-server.cjs');
+	writeFileSync(scriptPath, STRAY_RESPONSE_SERVER_SCRIPT);
+	const client = new MCPClient();
+
+	try {
+		await client.connectToServer({
+			name: 'stray-server',
+			transport: 'stdio',
+			command: process.execPath,
+			args: [scriptPath],
+		});
+		// The stray response follows the tools/list reply
+		await new Promise(resolve => setTimeout(resolve, 100));
+
+		const [status] = client.getServerStatus();
+		t.true(status.connected);
+		t.is(status.droppedResponses, 1);
+	} finally {
+		await client.disconnect();
+		rmSync(dir, {recursive: true, force: true});
+	}
+});
+
+test('MCPClient.getServerStatus: leaves out droppedResponses when none were dropped', t => {
+	const client = new MCPClient();
+	(client as any).clients.set('test-server', new MockClient());
+
+	(client as any).handleServerError('test-server', new Error('Parse error'));
+
+	t.false('droppedResponses' in client.getServerStatus()[0]);
+});
+
+test.serial('MCPClient.rawRequest: returns the raw result of a custom method', async t => {
+	t.timeout(10000);
+	await withSlowServer(async scriptPath => {
+		const client = new MCPClient();
+
+		try {
+			const [result] = await client.connectToServers([
+				{
+					name: 'slow-server',
+					transport: 'stdio',
+					command: process.execPath,
+					args: [scriptPath, '0'],
+				},
+			]);
+			t.true(result.success, result.error);
+
+			const response = await client.rawRequest('slow-server', 'custom/echo', {
+				level: 'debug',
+			});
+
+			t.deepEqual(response, {echo: {level: 'debug'}});
+		} finally {
+			await client.disconnect();
+		}
+	})();
+});
+
+test('MCPClient.rawRequest: throws for a server that is not connected', async t => {
+	const client = new MCPClient();
+
+	await t.throwsAsync(() => client.rawRequest('missing', 'ping'), {
+		message: 'No MCP client connected for server: missing',
+	});
+});
+
+test.serial('MCPClient.setLogLevel: sets the level and forwards server logs', async t => {
+	t.timeout(10000);
+	await withSlowServer(async scriptPath => {
+		const messages: MCPServerLogMessage[] = [];
+		let onMessage = () => {};
+		const received = new Promise<void>(resolve => {
+			onMessage = resolve;
+		});
+		const client = new MCPClient(message => {
+			messages.push(message);
+			onMessage();
+		});
+
+		try {
+			const [result] = await client.connectToServers([
+				{
+					name: 'slow-server',
+					transport: 'stdio',
+					command: process.execPath,
+					args: [scriptPath, '0'],
+				},
+			]);
+			t.true(result.success, result.error);
+
+			t.true(await client.setLogLevel('slow-server', 'debug'));
+			await received;
+
+			t.deepEqual(messages, [
+				{
+					serverName: 'slow-server',
+					level: 'info',
+					logger: 'slow',
+					data: 'level is debug',
+				},
+			]);
+		} finally {
+			await client.disconnect();
+		}
+	})();
+});
+
+test('MCPClient.setLogLevel: only sends logging/setLevel with the logging capability', async t => {
+	const client = new MCPClient();
+	const sent: Array<{serverName: string; level: string}> = [];
+	const mockClient = (serverName: string, capabilities: object) => ({
+		getServerCapabilities: () => capabilities,
+		setLoggingLevel: async (level: string) => {
+			sent.push({serverName, level});
+			return {};
+		},
+	});
+	(client as any).clients.set(
+		'with-logging',
+		mockClient('with-logging', {tools: {}, logging: {}}),
+	);
+	(client as any).clients.set(
+		'without-logging',
+		mockClient('without-logging', {tools: {}}),
+	);
+
+	t.true(await client.setLogLevel('with-logging', 'warning'));
+	t.false(await client.setLogLevel('without-logging', 'warning'));
+	t.deepEqual(sent, [{serverName: 'with-logging', level: 'warning'}]);
+});
+
+test('MCPClient.setLogLevel: server logs reach the chat only once a level is set', async t => {
+	const messages: MCPServerLogMessage[] = [];
+	const client = new MCPClient(message => messages.push(message));
+	(client as any).clients.set('docs', {
+		getServerCapabilities: () => ({tools: {}, logging: {}}),
+		setLoggingLevel: async () => ({}),
+	});
+	const params = {level: 'info', logger: 'index', data: 'indexed 12 pages'};
+
+	(client as any).handleServerLog('docs', params);
+	t.deepEqual(messages, []);
+
+	await client.setLogLevel('docs', 'info');
+	(client as any).handleServerLog('docs', params);
+	t.deepEqual(messages, [{serverName: 'docs', ...params}]);
+});
+
+test('MCPClient.setLogLevel: throws for a server that is not connected', async t => {
+	const client = new MCPClient();
+
+	await t.throwsAsync(() => client.setLogLevel('missing', 'debug'), {
+		message: 'No MCP client connected for server: missing',
+	});
+});
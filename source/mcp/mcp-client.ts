@@ -2,6 +2,11 @@ import {Client} from '@modelcontextprotocol/sdk/client/index.js';
 import {StdioClientTransport} from '@modelcontextprotocol/sdk/client/stdio.js';
 import {StreamableHTTPClientTransport} from '@modelcontextprotocol/sdk/client/streamableHttp.js';
 import {WebSocketClientTransport} from '@modelcontextprotocol/sdk/client/websocket.js';
+import {
+	type LoggingMessageNotification,
+	LoggingMessageNotificationSchema,
+	ResultSchema,
+} from '@modelcontextprotocol/sdk/types.js';
 
 // Union type for all supported client transports
 type ClientTransport =
@@ -10,11 +15,18 @@ type ClientTransport =
 	| StreamableHTTPClientTransport;
 
 import {dynamicTool} from 'ai';
+import {
+	DELAY_MCP_STARTUP_RETRY_MS,
+	TIMEOUT_MCP_DEFAULT_MS,
+} from '@/constants';
 import {getCurrentMode} from '@/context/mode-context';
 import type {
 	AISDKCoreTool,
 	MCPInitResult,
+	MCPLogLevel,
 	MCPServer,
+	MCPServerLogMessage,
+	MCPServerStatus,
 	MCPTool,
 	Tool,
 	ToolParameterSchema,
@@ -30,15 +42,65 @@ import {
 } from '@/utils/logging';
 import {TransportFactory} from './transport-factory.js';
 
+type LoggerMethod = 'debug' | 'info' | 'warn' | 'error';
+
+// Logger method each MCP log level is recorded with
+const LOGGER_METHODS: Record<MCPLogLevel, LoggerMethod> = {
+	debug: 'debug',
+	info: 'info',
+	notice: 'info',
+	warning: 'warn',
+	error: 'error',
+	critical: 'error',
+	alert: 'error',
+	emergency: 'error',
+};
+
+/**
+ * Renders an MCP tool result content block as text for the model. Image and
+ * audio blocks are summarised rather than inlined, as their base64 payloads
+ * would flood the context. Unrecognised block types are passed through as
+ * JSON so newer servers don't break tool calls.
+ */
+function formatContentBlock(content: Record<string, unknown>): string {
+	switch (content.type) {
+		case 'text':
+			return typeof content.text === 'string' ? content.text : '';
+		case 'image':
+		case 'audio': {
+			const data = typeof content.data === 'string' ? content.data : '';
+			const mimeType =
+				typeof content.mimeType === 'string' ? content.mimeType : 'unknown';
+			// base64 encodes 3 bytes in every 4 characters
+			const bytes = Math.floor((data.length * 3) / 4);
+			return `[${content.type} content: ${mimeType}, ${bytes.toLocaleString()} bytes]`;
+		}
+		default:
+			return JSON.stringify(content);
+	}
+}
+
 export class MCPClient {
 	private clients: Map<string, Client> = new Map();
 	private transports: Map<string, ClientTransport> = new Map();
 	private serverTools: Map<string, MCPTool[]> = new Map();
 	private serverConfigs: Map<string, MCPServer> = new Map();
+	private serverErrors: Map<string, string> = new Map();
+	// Responses per server that matched no pending request, e.g. one that
+	// arrived after its request timed out
+	private droppedResponses: Map<string, number> = new Map();
+	// Servers whose log level the user set; only their log messages are
+	// passed to onServerLog
+	private logLevels: Map<string, MCPLogLevel> = new Map();
 	private isConnected: boolean = false;
 	private logger = getLogger();
 
-	constructor() {
+	/**
+	 * @param onServerLog Receives the log messages of servers whose level was
+	 * set with setLogLevel, so they can be shown to the user. Every message is
+	 * written to the log either way.
+	 */
+	constructor(private onServerLog?: (message: MCPServerLogMessage) => void) {
 		this.logger.debug('MCP client initialized');
 	}
 
@@ -84,33 +146,28 @@ export class MCPClient {
 					duration: `${finalMetrics.duration.toFixed(2)}ms`,
 					correlationId,
 				});
-				throw new Error(
-					`Invalid MCP server configuration for "${
-						normalizedServer.name
-					}": ${validation.errors.join(', ')}`,
-				);
+				const message = `Invalid MCP server configuration for "${
+					normalizedServer.name
+				}": ${validation.errors.join(', ')}`;
+				this.serverErrors.set(normalizedServer.name, message);
+				throw new Error(message);
 			}
 
 			try {
-				// Create transport using the factory
-				const transport = TransportFactory.createTransport(normalizedServer);
-
-				this.logger.debug('MCP transport created', {
-					serverName: normalizedServer.name,
-					transportType: normalizedServer.transport,
-				});
-
-				// Create and connect client
-				const client = new Client({
-					name: 'nanocoder-mcp-client',
-					version: '1.0.0',
-				});
-
-				this.logger.debug('MCP client created, attempting connection', {
-					serverName: normalizedServer.name,
-				});
-
-				await client.connect(transport);
+				const {client, transport} =
+					await this.connectWithStartupGrace(normalizedServer);
+
+				// Mark the server as disconnected if its transport dies mid-session
+				// (e.g. a stdio child process exits)
+				client.onclose = () =>
+					this.handleServerClosed(normalizedServer.name, client);
+				client.onerror = error =>
+					this.handleServerError(normalizedServer.name, error);
+				client.setNotificationHandler(
+					LoggingMessageNotificationSchema,
+					notification =>
+						this.handleServerLog(normalizedServer.name, notification.params),
+				);
 
 				this.logger.info('MCP server connected successfully', {
 					serverName: normalizedServer.name,
@@ -132,6 +189,7 @@ export class MCPClient {
 				}));
 
 				this.serverTools.set(normalizedServer.name, tools);
+				this.serverErrors.delete(normalizedServer.name);
 
 				const finalMetrics = endMetrics(metrics);
 
@@ -145,6 +203,11 @@ export class MCPClient {
 					correlationId,
 				});
 			} catch (error) {
+				this.serverErrors.set(
+					normalizedServer.name,
+					error instanceof Error ? error.message : String(error),
+				);
+
 				const finalMetrics = endMetrics(metrics);
 				this.logger.error('Failed to connect to MCP server', {
 					serverName: normalizedServer.name,
@@ -163,6 +226,128 @@ export class MCPClient {
 		}, correlationId);
 	}
 
+	/**
+	 * Creates a transport and client and performs the initialize handshake.
+	 * With a startupTimeout, initialize may take up to that long, and failed
+	 * attempts (e.g. a remote server still refusing connections) are retried
+	 * with a fresh transport until it expires.
+	 */
+	private async connectWithStartupGrace(
+		server: MCPServer,
+	): Promise<{client: Client; transport: ClientTransport}> {
+		const {startupTimeout} = server;
+		const deadline = Date.now() + (startupTimeout ?? 0);
+
+		for (let attempt = 1; ; attempt++) {
+			// Create transport using the factory
+			const transport = TransportFactory.createTransport(server);
+
+			this.logger.debug('MCP transport created', {
+				serverName: server.name,
+				transportType: server.transport,
+			});
+
+			// Create and connect client
+			const client = new Client({
+				name: 'nanocoder-mcp-client',
+				version: '1.0.0',
+			});
+
+			this.logger.debug('MCP client created, attempting connection', {
+				serverName: server.name,
+				attempt,
+			});
+
+			try {
+				await client.connect(
+					transport,
+					startupTimeout
+						? {timeout: Math.max(deadline - Date.now(), 1)}
+						: undefined,
+				);
+				return {client, transport};
+			} catch (error) {
+				// Stops a spawned stdio process from outliving the failed attempt
+				await client.close().catch(() => undefined);
+
+				if (!startupTimeout) {
+					throw error;
+				}
+				if (deadline - Date.now() <= DELAY_MCP_STARTUP_RETRY_MS) {
+					const message =
+						error instanceof Error ? error.message : String(error);
+					throw new Error(
+						`MCP server "${server.name}" did not become ready within ${startupTimeout}ms: ${message}`,
+					);
+				}
+
+				this.logger.debug('MCP server not ready, retrying initialize', {
+					serverName: server.name,
+					attempt,
+					error: error instanceof Error ? error.message : error,
+				});
+				await new Promise(resolve =>
+					setTimeout(resolve, DELAY_MCP_STARTUP_RETRY_MS),
+				);
+			}
+		}
+	}
+
+	/**
+	 * Connects to a server, giving up after its timeout (plus any startup grace
+	 * period) so one hanging server can't hold up the rest. A connection that
+	 * completes after the timeout is closed rather than used.
+	 */
+	private async connectWithTimeout(server: MCPServer): Promise<void> {
+		const timeoutMs =
+			(server.timeout ?? TIMEOUT_MCP_DEFAULT_MS) + (server.startupTimeout ?? 0);
+		const timeoutMessage = `MCP server "${server.name}" did not connect within ${timeoutMs}ms`;
+		const connecting = this.connectToServer(server);
+
+		let timer: NodeJS.Timeout | undefined;
+		const timedOut = new Promise<never>((_resolve, reject) => {
+			timer = setTimeout(() => reject(new Error(timeoutMessage)), timeoutMs);
+		});
+
+		try {
+			await Promise.race([connecting, timedOut]);
+		} catch (error) {
+			if (error instanceof Error && error.message === timeoutMessage) {
+				this.serverErrors.set(server.name, timeoutMessage);
+				connecting.then(
+					() => this.dropLateConnection(server.name, timeoutMessage),
+					() => undefined,
+				);
+			}
+			throw error;
+		} finally {
+			clearTimeout(timer);
+		}
+	}
+
+	/**
+	 * Closes a server that connected after connectToServers gave up on it
+	 */
+	private async dropLateConnection(
+		serverName: string,
+		error: string,
+	): Promise<void> {
+		const client = this.clients.get(serverName);
+		this.clients.delete(serverName);
+		this.transports.delete(serverName);
+		this.serverTools.delete(serverName);
+		this.serverConfigs.delete(serverName);
+		this.serverErrors.set(serverName, error);
+
+		this.logger.warn('Closing MCP server that connected after its timeout', {
+			serverName,
+		});
+		if (client) {
+			client.onclose = undefined;
+			await client.close().catch(() => undefined);
+		}
+	}
+
 	async connectToServers(
 		servers: MCPServer[],
 		onProgress?: (result: MCPInitResult) => void,
@@ -180,16 +365,18 @@ export class MCPClient {
 		return await withNewCorrelationContext(async () => {
 			// Connect to servers in parallel for better performance
 			const connectionPromises = servers.map(async server => {
+				const startedAt = Date.now();
 				try {
 					// Normalize server configuration for backward compatibility
 					const normalizedServer = this.normalizeServerConfig(server);
 
-					await this.connectToServer(normalizedServer);
+					await this.connectWithTimeout(normalizedServer);
 					const tools = this.serverTools.get(normalizedServer.name) || [];
 					const result: MCPInitResult = {
 						serverName: normalizedServer.name,
 						success: true,
 						toolCount: tools.length,
+						durationMs: Date.now() - startedAt,
 					};
 					results.push(result);
 
@@ -207,6 +394,7 @@ export class MCPClient {
 						serverName: normalizedServer.name,
 						success: false,
 						error: error instanceof Error ? error.message : String(error),
+						durationMs: Date.now() - startedAt,
 					};
 
 					this.logger.error('MCP server connection failed in batch', {
@@ -262,11 +450,14 @@ export class MCPClient {
 			for (const mcpTool of serverTools) {
 				// Convert MCP tool to nanocoder Tool format
 				// Use the original tool name for better model compatibility
+				// The schema is kept whole: properties can $ref top-level $defs, and
+				// keywords like additionalProperties constrain the arguments
 				const schema = mcpTool.inputSchema as
 					| {
 							type?: string;
 							properties?: Record<string, unknown>;
 							required?: string[];
+							[keyword: string]: unknown;
 					  }
 					| undefined;
 
@@ -278,6 +469,7 @@ export class MCPClient {
 							? `[MCP:${serverName}] ${mcpTool.description}`
 							: `MCP tool from ${serverName}`,
 						parameters: {
+							...schema,
 							type: 'object',
 							properties: (schema?.properties || {}) as Record<
 								string,
@@ -364,11 +556,13 @@ export class MCPClient {
 		name: string;
 		tool: AISDKCoreTool;
 		handler: (args: Record<string, unknown>) => Promise<string>;
+		category: 'mcp';
 	}> {
 		const entries: Array<{
 			name: string;
 			tool: AISDKCoreTool;
 			handler: (args: Record<string, unknown>) => Promise<string>;
+			category: 'mcp';
 		}> = [];
 
 		// Get native tools once to avoid redundant calls
@@ -391,6 +585,7 @@ export class MCPClient {
 						name: toolName,
 						tool: coreTool,
 						handler,
+						category: 'mcp',
 					});
 				}
 			}
@@ -421,14 +616,180 @@ export class MCPClient {
 			throw new Error(`MCP tool not found: ${toolName}`);
 		}
 
-		const client = this.clients.get(mapping.serverName);
+		return this.callToolOnServer(
+			mapping.serverName,
+			mapping.originalName,
+			args,
+		);
+	}
+
+	/**
+	 * Call a tool on a named server, bypassing the tool name mapping, for when
+	 * more than one server offers a tool with the same name
+	 */
+	async callToolOnServer(
+		serverName: string,
+		toolName: string,
+		args: Record<string, unknown>,
+	): Promise<string> {
+		const tools = this.serverTools.get(serverName);
+		if (!tools) {
+			throw new Error(`MCP server not connected: ${serverName}`);
+		}
+		if (!tools.some(tool => tool.name === toolName)) {
+			throw new Error(`MCP server "${serverName}" has no tool: ${toolName}`);
+		}
+
+		const client =
+			this.clients.get(serverName) ?? (await this.reconnectServer(serverName));
 		if (!client) {
-			throw new Error(
-				`No MCP client connected for server: ${mapping.serverName}`,
+			throw new Error(`No MCP client connected for server: ${serverName}`);
+		}
+
+		return this.executeToolCall(client, toolName, args);
+	}
+
+	/**
+	 * Send any JSON-RPC request to a named server and return its raw result,
+	 * for debugging methods the client doesn't wrap, such as logging/setLevel
+	 * or completion/complete
+	 */
+	async rawRequest(
+		serverName: string,
+		method: string,
+		params?: Record<string, unknown>,
+	): Promise<Record<string, unknown>> {
+		const client = this.clients.get(serverName);
+		if (!client) {
+			throw new Error(`No MCP client connected for server: ${serverName}`);
+		}
+
+		this.logger.debug('Sending raw MCP request', {serverName, method});
+		return await client.request({method, params}, ResultSchema);
+	}
+
+	/**
+	 * Set how verbose a server's log messages are with logging/setLevel.
+	 * Servers that don't advertise the logging capability are skipped.
+	 * @returns Whether the request was sent
+	 * @throws Error when the server isn't connected or rejects the request
+	 */
+	async setLogLevel(serverName: string, level: MCPLogLevel): Promise<boolean> {
+		const client = this.clients.get(serverName);
+		if (!client) {
+			throw new Error(`No MCP client connected for server: ${serverName}`);
+		}
+
+		if (!client.getServerCapabilities()?.logging) {
+			this.logger.debug('MCP server has no logging capability', {
+				serverName,
+				level,
+			});
+			return false;
+		}
+
+		await client.setLoggingLevel(level);
+		this.logLevels.set(serverName, level);
+		this.logger.debug('MCP server log level set', {serverName, level});
+		return true;
+	}
+
+	/**
+	 * Drops a server whose connection closed unexpectedly. Its config and tool
+	 * list are kept so callTool can still route to it and attempt a reconnect.
+	 */
+	private handleServerClosed(serverName: string, client: Client): void {
+		// Ignore stale clients replaced by a reconnect
+		if (this.clients.get(serverName) !== client) {
+			return;
+		}
+
+		this.clients.delete(serverName);
+		this.transports.delete(serverName);
+		this.serverErrors.set(serverName, 'Connection closed unexpectedly');
+
+		this.logger.warn('MCP server connection closed unexpectedly', {
+			serverName,
+		});
+	}
+
+	/**
+	 * Records protocol errors the SDK reports outside of any request. A response
+	 * to an id nobody is waiting for is dropped by the SDK, so it is counted.
+	 */
+	private handleServerError(serverName: string, error: Error): void {
+		if (/unknown message ID/i.test(error.message)) {
+			const dropped = (this.droppedResponses.get(serverName) ?? 0) + 1;
+			this.droppedResponses.set(serverName, dropped);
+			this.logger.warn('Dropped MCP response with no matching request', {
+				serverName,
+				droppedResponses: dropped,
+			});
+			return;
+		}
+
+		this.logger.warn('MCP server protocol error', {
+			serverName,
+			error: error.message,
+		});
+	}
+
+	private handleServerLog(
+		serverName: string,
+		params: LoggingMessageNotification['params'],
+	): void {
+		const message: MCPServerLogMessage = {
+			serverName,
+			level: params.level,
+			logger: params.logger,
+			data: params.data,
+		};
+		this.logger[LOGGER_METHODS[params.level]]('MCP server log message', {
+			...message,
+		});
+		if (this.logLevels.has(serverName)) {
+			this.onServerLog?.(message);
+		}
+	}
+
+	/**
+	 * Reconnects to a disconnected server with exponential backoff, if the
+	 * server's reconnect option is enabled
+	 */
+	private async reconnectServer(
+		serverName: string,
+	): Promise<Client | undefined> {
+		const serverConfig = this.serverConfigs.get(serverName);
+		if (!serverConfig?.reconnect?.enabled) {
+			return undefined;
+		}
+
+		const {maxAttempts, backoffMs} = serverConfig.reconnect;
+
+		for (let attempt = 1; attempt <= maxAttempts; attempt++) {
+			await new Promise(resolve =>
+				setTimeout(resolve, backoffMs * 2 ** (attempt - 1)),
 			);
+
+			this.logger.info('Reconnecting to MCP server', {
+				serverName,
+				attempt,
+				maxAttempts,
+			});
+
+			try {
+				await this.connectToServer(serverConfig);
+				return this.clients.get(serverName);
+			} catch (error) {
+				this.logger.warn('MCP server reconnect attempt failed', {
+					serverName,
+					attempt,
+					error: error instanceof Error ? error.message : error,
+				});
+			}
 		}
 
-		return this.executeToolCall(client, mapping.originalName, args);
+		return undefined;
 	}
 
 	private async executeToolCall(
@@ -468,34 +829,19 @@ export class MCPClient {
 					Array.isArray(result.content) &&
 					result.content.length > 0
 				) {
-					const content = result.content[0] as
-						| {type: 'text'; text?: string}
-						| Record<string, unknown>;
-					if ('type' in content && content.type === 'text') {
-						const textContent = content as {type: 'text'; text?: string};
-						const responseText = textContent.text || '';
-
-						const finalMetrics = endMetrics(metrics);
-						this.logger.info('MCP tool execution completed', {
-							toolName,
-							responseLength: responseText.length,
-							duration: `${finalMetrics.duration.toFixed(2)}ms`,
-							correlationId,
-						});
-
-						return responseText;
-					}
-					const jsonResponse = JSON.stringify(content);
+					const content = result.content[0] as Record<string, unknown>;
+					const responseText = formatContentBlock(content);
 
 					const finalMetrics = endMetrics(metrics);
-					this.logger.info('MCP tool execution completed (JSON)', {
+					this.logger.info('MCP tool execution completed', {
 						toolName,
-						responseLength: jsonResponse.length,
+						contentType: content.type,
+						responseLength: responseText.length,
 						duration: `${finalMetrics.duration.toFixed(2)}ms`,
 						correlationId,
 					});
 
-					return jsonResponse;
+					return responseText;
 				}
 
 				const finalMetrics = endMetrics(metrics);
@@ -547,6 +893,8 @@ export class MCPClient {
 
 			for (const [serverName, client] of this.clients.entries()) {
 				try {
+					// Intentional shutdown, not a dead server
+					client.onclose = undefined;
 					await client.close();
 					successfulDisconnections++;
 
@@ -573,6 +921,8 @@ export class MCPClient {
 			this.transports.clear();
 			this.serverTools.clear();
 			this.serverConfigs.clear();
+			this.serverErrors.clear();
+			this.droppedResponses.clear();
 			this.isConnected = false;
 
 			this.logger.info('MCP client disconnection completed', {
@@ -596,6 +946,28 @@ export class MCPClient {
 		return this.serverTools.get(serverName) || [];
 	}
 
+	/**
+	 * Gets connection health and tool counts for every server that was
+	 * connected or attempted, including servers that failed to connect
+	 */
+	getServerStatus(): MCPServerStatus[] {
+		const names = new Set([
+			...this.clients.keys(),
+			...this.serverErrors.keys(),
+		]);
+
+		return Array.from(names).map(name => {
+			const droppedResponses = this.droppedResponses.get(name);
+			return {
+				name,
+				connected: this.clients.has(name),
+				toolCount: this.serverTools.get(name)?.length || 0,
+				lastError: this.serverErrors.get(name),
+				...(droppedResponses && {droppedResponses}),
+			};
+		});
+	}
+
 	/**
 	 * Gets server information including transport type and URL for remote servers
 	 */
@@ -0,0 +1,60 @@
+import test from 'ava';
+import {GracefulStdioClientTransport} from './stdio-transport';
+
+console.log(`\nstdio-transport.spec.ts`);
+
+// Mock server that reports when its SIGTERM handler is installed
+function startMockServer(onSigterm: string, shutdownTimeout?: number) {
+	const transport = new GracefulStdioClientTransport(
+		{
+			command: process.execPath,
+			args: [
+				'-e',
+				`process.on('SIGTERM', () => { ${onSigterm} });
+				process.stderr.write('ready');
+				setInterval(() => {}, 1000);`,
+			],
+			stderr: 'pipe',
+		},
+		shutdownTimeout,
+	);
+	const ready = new Promise(resolve => transport.stderr?.once('data', resolve));
+	return {transport, ready};
+}
+
+function isRunning(pid: number): boolean {
+	try {
+		process.kill(pid, 0);
+		return true;
+	} catch {
+		return false;
+	}
+}
+
+test('GracefulStdioClientTransport.close: lets a server exit on SIGTERM', async t => {
+	t.timeout(10000);
+	const {transport, ready} = startMockServer('process.exit(0);', 5000);
+	await transport.start();
+	await ready;
+	const pid = transport.pid as number;
+
+	const started = Date.now();
+	await transport.close();
+
+	t.false(transport.forceKilled);
+	t.false(isRunning(pid));
+	t.true(Date.now() - started < 5000);
+});
+
+test('GracefulStdioClientTransport.close: kills a server that ignores SIGTERM', async t => {
+	t.timeout(10000);
+	const {transport, ready} = startMockServer('', 200);
+	await transport.start();
+	await ready;
+	const pid = transport.pid as number;
+
+	await transport.close();
+
+	t.true(transport.forceKilled);
+	t.false(isRunning(pid));
+});
@@ -0,0 +1,68 @@
+import {
+	StdioClientTransport,
+	type StdioServerParameters,
+} from '@modelcontextprotocol/sdk/client/stdio.js';
+import {TIMEOUT_MCP_SHUTDOWN_MS} from '@/constants';
+
+const EXIT_POLL_INTERVAL_MS = 50;
+
+function isRunning(pid: number): boolean {
+	try {
+		// Signal 0 only checks that the process exists
+		process.kill(pid, 0);
+		return true;
+	} catch {
+		return false;
+	}
+}
+
+async function waitForExit(pid: number, timeoutMs: number): Promise<boolean> {
+	const deadline = Date.now() + timeoutMs;
+	while (isRunning(pid)) {
+		if (Date.now() >= deadline) {
+			return false;
+		}
+		await new Promise(resolve => setTimeout(resolve, EXIT_POLL_INTERVAL_MS));
+	}
+	return true;
+}
+
+/**
+ * Stdio transport that stops its server with SIGTERM and only falls back to
+ * SIGKILL once the grace period runs out, so the server can remove its temp
+ * files and sockets. On Windows, where there is no SIGTERM, the SDK's own
+ * shutdown is used.
+ */
+export class GracefulStdioClientTransport extends StdioClientTransport {
+	/** Whether the last close had to SIGKILL a server that ignored SIGTERM */
+	forceKilled = false;
+
+	constructor(
+		server: StdioServerParameters,
+		private readonly shutdownTimeout = TIMEOUT_MCP_SHUTDOWN_MS,
+	) {
+		super(server);
+	}
+
+	async close(): Promise<void> {
+		const pid = this.pid;
+		if (pid !== null && process.platform !== 'win32' && isRunning(pid)) {
+			try {
+				process.kill(pid, 'SIGTERM');
+			} catch {
+				// Already gone
+			}
+
+			if (!(await waitForExit(pid, this.shutdownTimeout))) {
+				this.forceKilled = true;
+				try {
+					process.kill(pid, 'SIGKILL');
+				} catch {
+					// Exited just after the deadline
+				}
+			}
+		}
+
+		await super.close();
+	}
+}
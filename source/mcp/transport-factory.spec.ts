@@ -404,3 +404,113 @@ test('TransportFactory.validateServerConfig: validates existing command (node)',
 	t.true(result.valid);
 	t.is(result.errors.length, 0);
 });
+
+test('TransportFactory.validateServerConfig: detects missing working directory', t => {
+	const server: MCPServer = {
+		name: 'test-stdio-missing-cwd',
+		transport: 'stdio',
+		command: 'node',
+		cwd: '/nonexistent/nanocoder-mcp-cwd',
+	};
+
+	const result = TransportFactory.validateServerConfig(server);
+
+	t.false(result.valid);
+	t.true(
+		result.errors.some(error =>
+			error.includes("Working directory '/nonexistent/nanocoder-mcp-cwd'"),
+		),
+	);
+});
+
+// ============================================================================
+// Tests for TransportFactory.buildStdioEnvironment
+// ============================================================================
+
+test.serial('TransportFactory.buildStdioEnvironment: inherits the parent environment with overrides', t => {
+	process.env.NANOCODER_MCP_TEST_VAR = 'parent';
+
+	try {
+		const env = TransportFactory.buildStdioEnvironment({
+			name: 'inherit',
+			transport: 'stdio',
+			command: 'node',
+			inheritEnv: true,
+			env: {LOG_LEVEL: 'debug'},
+		});
+
+		t.is(env?.NANOCODER_MCP_TEST_VAR, 'parent');
+		t.is(env?.LOG_LEVEL, 'debug');
+	} finally {
+		delete process.env.NANOCODER_MCP_TEST_VAR;
+	}
+});
+
+test.serial('TransportFactory.buildStdioEnvironment: isolates the server when inheritEnv is false', t => {
+	process.env.NANOCODER_MCP_TEST_VAR = 'parent';
+
+	try {
+		const env = TransportFactory.buildStdioEnvironment({
+			name: 'isolated',
+			transport: 'stdio',
+			command: 'node',
+			inheritEnv: false,
+			env: {LOG_LEVEL: 'debug'},
+		});
+
+		t.is(env?.NANOCODER_MCP_TEST_VAR, undefined);
+		t.is(env?.LOG_LEVEL, 'debug');
+		t.is(env?.PATH, process.env.PATH);
+	} finally {
+		delete process.env.NANOCODER_MCP_TEST_VAR;
+	}
+});
+
+test('TransportFactory.buildStdioEnvironment: keeps the existing defaults', t => {
+	const withEnv = TransportFactory.buildStdioEnvironment({
+		name: 'with-env',
+		transport: 'stdio',
+		command: 'node',
+		env: {LOG_LEVEL: 'debug'},
+	});
+	t.is(withEnv?.PATH, process.env.PATH);
+	t.is(withEnv?.LOG_LEVEL, 'debug');
+
+	// Without env the SDK applies its own default environment
+	t.is(
+		TransportFactory.buildStdioEnvironment({
+			name: 'no-env',
+			transport: 'stdio',
+			command: 'node',
+		}),
+		undefined,
+	);
+});
+
+test.serial('TransportFactory.buildStdioEnvironment: adds no logging variables of its own', t => {
+	const names = ['LOG_LEVEL', 'DEBUG', 'VERBOSE'];
+	const saved = names.map(name => process.env[name]);
+	for (const name of names) {
+		delete process.env[name];
+	}
+
+	try {
+		const env = TransportFactory.buildStdioEnvironment({
+			name: 'quiet',
+			transport: 'stdio',
+			command: 'node',
+			env: {API_KEY: 'secret'},
+		});
+
+		for (const name of names) {
+			t.is(env?.[name], undefined);
+		}
+		t.is(env?.API_KEY, 'secret');
+	} finally {
+		names.forEach((name, index) => {
+			if (saved[index] !== undefined) {
+				process.env[name] = saved[index];
+			}
+		});
+	}
+});
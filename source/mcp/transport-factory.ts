@@ -1,10 +1,14 @@
-import {StdioClientTransport} from '@modelcontextprotocol/sdk/client/stdio.js';
+import {
+	getDefaultEnvironment,
+	StdioClientTransport,
+} from '@modelcontextprotocol/sdk/client/stdio.js';
 import {StreamableHTTPClientTransport} from '@modelcontextprotocol/sdk/client/streamableHttp.js';
 import {WebSocketClientTransport} from '@modelcontextprotocol/sdk/client/websocket.js';
 import {execFileSync} from 'child_process';
-import {accessSync, constants as fsConstants} from 'fs';
+import {accessSync, constants as fsConstants, statSync} from 'fs';
 import {logWarning} from '@/utils/message-queue';
 import type {MCPServer, MCPTransportType} from '../types/mcp.js';
+import {GracefulStdioClientTransport} from './stdio-transport';
 
 /**
  * Installation instructions for common MCP server dependencies
@@ -74,6 +78,14 @@ function getInstallHint(command: string): string {
 	);
 }
 
+function isDirectory(path: string): boolean {
+	try {
+		return statSync(path).isDirectory(); // nosemgrep
+	} catch {
+		return false;
+	}
+}
+
 // Union type for all supported client transports
 type ClientTransport =
 	| StdioClientTransport
@@ -124,13 +136,36 @@ export class TransportFactory {
 			args = ['--native-tls', ...args];
 		}
 
-		return new StdioClientTransport({
-			command: server.command,
-			args,
-			env: server.env
-				? ({...process.env, ...server.env} as Record<string, string>)
-				: undefined,
-		});
+		return new GracefulStdioClientTransport(
+			{
+				command: server.command,
+				args,
+				env: TransportFactory.buildStdioEnvironment(server),
+				cwd: server.cwd,
+			},
+			server.shutdownTimeout,
+		);
+	}
+
+	/**
+	 * Environment for a stdio server process. With inheritEnv the configured
+	 * env is overlaid on nanocoder's full environment; with inheritEnv false
+	 * it is overlaid on the SDK's minimal one (PATH, HOME, USER and the like).
+	 * Unset, the full environment is used only when env is configured.
+	 * @returns The environment, or undefined to let the SDK pick its default
+	 */
+	static buildStdioEnvironment(
+		server: MCPServer,
+	): Record<string, string> | undefined {
+		const inheritEnv = server.inheritEnv ?? Boolean(server.env);
+		if (!inheritEnv && !server.env) {
+			return undefined;
+		}
+
+		const base = inheritEnv
+			? (process.env as Record<string, string>)
+			: getDefaultEnvironment();
+		return {...base, ...server.env};
 	}
 
 	/**
@@ -239,6 +274,9 @@ export class TransportFactory {
 					const hint = getInstallHint(server.command);
 					errors.push(`Command '${server.command}' not found.\n\n${hint}`);
 				}
+				if (server.cwd && !isDirectory(server.cwd)) {
+					errors.push(`Working directory '${server.cwd}' does not exist`);
+				}
 				break;
 
 			case 'websocket':
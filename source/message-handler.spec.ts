@@ -262,6 +262,7 @@ test('processToolUse - catches and returns handler errors as tool result', async
 	t.is(result.role, 'tool');
 	t.is(result.name, 'failing_tool');
 	t.is(result.content, 'Error: Handler execution failed');
+	t.true(result.isError);
 });
 
 test('processToolUse - catches non-Error exceptions', async t => {
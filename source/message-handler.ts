@@ -23,7 +23,29 @@ export function getToolManager(): ToolManager | null {
 	return toolManagerGetter ? toolManagerGetter() : null;
 }
 
-export async function processToolUse(toolCall: ToolCall): Promise<ToolResult> {
+/**
+ * Decides whether a tool call that needs approval may run. Resolving true
+ * approves it; false or a rejection denies it.
+ */
+export type ToolConfirmationHandler = (toolCall: ToolCall) => Promise<boolean>;
+
+// Set by an embedder to approve tool calls without the interactive prompt
+let toolConfirmationHandler: ToolConfirmationHandler | null = null;
+
+export function setToolConfirmationHandler(
+	handler: ToolConfirmationHandler | null,
+) {
+	toolConfirmationHandler = handler;
+}
+
+export function getToolConfirmationHandler(): ToolConfirmationHandler | null {
+	return toolConfirmationHandler;
+}
+
+export async function processToolUse(
+	toolCall: ToolCall,
+	signal?: AbortSignal,
+): Promise<ToolResult> {
 	// Handle XML validation errors by throwing (will be caught and returned as error ToolResult)
 	if (toolCall.function.name === '__xml_validation_error__') {
 		const args = toolCall.function.arguments as {error: string};
@@ -47,7 +69,7 @@ export async function processToolUse(toolCall: ToolCall): Promise<ToolResult> {
 			toolCall.function.arguments,
 			{strict: true},
 		);
-		const result = await handler(parsedArgs);
+		const result = await handler(parsedArgs, signal);
 		return {
 			tool_call_id: toolCall.id,
 			role: 'tool',
@@ -62,6 +84,7 @@ export async function processToolUse(toolCall: ToolCall): Promise<ToolResult> {
 			role: 'tool',
 			name: toolCall.function.name,
 			content: errorMessage,
+			isError: true,
 		};
 	}
 }
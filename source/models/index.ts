@@ -1 +1,5 @@
-export {getModelContextLimit} from './models-dev-client.js';
+export {
+	getModelContextLimit,
+	getModelCost,
+	getModelOutputLimit,
+} from './models-dev-client.js';
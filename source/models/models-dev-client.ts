@@ -285,6 +285,28 @@ async function findModelByName(modelName: string): Promise<ModelInfo | null> {
 	return null;
 }
 
+/**
+ * Strip :cloud or -cloud suffix if present (Ollama cloud models)
+ */
+function normalizeModelId(modelId: string): string {
+	return modelId.endsWith(':cloud') || modelId.endsWith('-cloud')
+		? modelId.slice(0, -6) // Remove ":cloud" or "-cloud"
+		: modelId;
+}
+
+/**
+ * Look up a model in models.dev by exact ID, then by partial name
+ */
+async function findModelInfo(modelId: string): Promise<ModelInfo | null> {
+	const normalizedModelId = normalizeModelId(modelId);
+
+	// Try exact ID match first, then partial name match
+	return (
+		(await findModelById(normalizedModelId)) ??
+		(await findModelByName(normalizedModelId))
+	);
+}
+
 /**
  * Get context limit for a model
  * Returns null if model not found and no fallback available
@@ -299,27 +321,16 @@ export async function getModelContextLimit(
 		return ollamaLimitOriginal;
 	}
 
-	// Strip :cloud or -cloud suffix if present (Ollama cloud models)
-	const normalizedModelId =
-		modelId.endsWith(':cloud') || modelId.endsWith('-cloud')
-			? modelId.slice(0, -6) // Remove ":cloud" or "-cloud"
-			: modelId;
-
-	// Try exact ID match first
-	let modelInfo = await findModelById(normalizedModelId);
-
-	// Try partial name match if exact match fails
-	if (!modelInfo) {
-		modelInfo = await findModelByName(normalizedModelId);
-	}
-
 	// If found in models.dev, return that
+	const modelInfo = await findModelInfo(modelId);
 	if (modelInfo) {
 		return modelInfo.contextLimit;
 	}
 
 	// Fall back to Ollama model defaults with normalized ID
-	const ollamaLimit = getOllamaFallbackContextLimit(normalizedModelId);
+	const ollamaLimit = getOllamaFallbackContextLimit(
+		normalizeModelId(modelId),
+	);
 	if (ollamaLimit) {
 		return ollamaLimit;
 	}
@@ -327,3 +338,25 @@ export async function getModelContextLimit(
 	// No context limit found
 	return null;
 }
+
+/**
+ * Get the maximum output tokens for a model
+ * Returns null if the model is not in models.dev or has no output limit
+ */
+export async function getModelOutputLimit(
+	modelId: string,
+): Promise<number | null> {
+	const modelInfo = await findModelInfo(modelId);
+	return modelInfo?.outputLimit || null;
+}
+
+/**
+ * Get a model's prices in USD per million input and output tokens
+ * Returns null if the model is not in models.dev
+ */
+export async function getModelCost(
+	modelId: string,
+): Promise<ModelInfo['cost'] | null> {
+	const modelInfo = await findModelInfo(modelId);
+	return modelInfo?.cost ?? null;
+}
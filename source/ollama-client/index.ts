@@ -0,0 +1 @@
+export {OllamaClient} from './ollama-client.js';
@@ -0,0 +1,65 @@
+import test from 'ava';
+import {parseNDJSONStream} from './ndjson-parser.js';
+
+async function* fromChunks(
+	chunks: Array<Uint8Array | string>,
+): AsyncGenerator<Uint8Array | string> {
+	for (const chunk of chunks) {
+		yield chunk;
+	}
+}
+
+async function collect<T>(iterable: AsyncIterable<T>): Promise<T[]> {
+	const items: T[] = [];
+	for await (const item of iterable) {
+		items.push(item);
+	}
+	return items;
+}
+
+test('parseNDJSONStream parses one object per line', async t => {
+	const stream = fromChunks([
+		'{"message":{"content":"Hel"},"done":false}\n',
+		'{"message":{"content":"lo"},"done":false}\n',
+		'{"done":true,"prompt_eval_count":12,"eval_count":2}\n',
+	]);
+
+	const objects = await collect(parseNDJSONStream<Record<string, unknown>>(stream));
+
+	t.is(objects.length, 3);
+	t.deepEqual(objects[0], {message: {content: 'Hel'}, done: false});
+	t.is(objects[2].eval_count, 2);
+});
+
+test('parseNDJSONStream buffers lines split across chunks', async t => {
+	const stream = fromChunks(['{"a":', '1}\n{"b"', ':2}\n']);
+
+	const objects = await collect(parseNDJSONStream(stream));
+
+	t.deepEqual(objects, [{a: 1}, {b: 2}]);
+});
+
+test('parseNDJSONStream decodes multi-byte characters split across chunks', async t => {
+	const bytes = new TextEncoder().encode('{"text":"héllo"}\n');
+	// Split inside the two-byte "é"
+	const splitAt = bytes.indexOf(0xc3) + 1;
+	const stream = fromChunks([bytes.slice(0, splitAt), bytes.slice(splitAt)]);
+
+	const objects = await collect(parseNDJSONStream<{text: string}>(stream));
+
+	t.is(objects[0].text, 'héllo');
+});
+
+test('parseNDJSONStream skips blank lines and flushes trailing object', async t => {
+	const stream = fromChunks(['\n{"a":1}\n\n', '{"done":true}']);
+
+	const objects = await collect(parseNDJSONStream(stream));
+
+	t.deepEqual(objects, [{a: 1}, {done: true}]);
+});
+
+test('parseNDJSONStream throws on invalid JSON', async t => {
+	const stream = fromChunks(['not json\n']);
+
+	await t.throwsAsync(() => collect(parseNDJSONStream(stream)));
+});
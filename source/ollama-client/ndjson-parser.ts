@@ -0,0 +1,35 @@
+/**
+ * Parses a newline-delimited JSON (NDJSON) byte stream into objects.
+ *
+ * Ollama's native API streams one JSON object per line rather than using
+ * server-sent events, so chunks may split a line (or a multi-byte character)
+ * at any point. Partial lines are buffered until their newline arrives.
+ */
+export async function* parseNDJSONStream<T>(
+	stream: AsyncIterable<Uint8Array | string>,
+): AsyncGenerator<T> {
+	const decoder = new TextDecoder();
+	let buffer = '';
+
+	for await (const chunk of stream) {
+		buffer +=
+			typeof chunk === 'string' ? chunk : decoder.decode(chunk, {stream: true});
+
+		let newlineIndex = buffer.indexOf('\n');
+		while (newlineIndex !== -1) {
+			const line = buffer.slice(0, newlineIndex).trim();
+			buffer = buffer.slice(newlineIndex + 1);
+			if (line) {
+				yield JSON.parse(line) as T;
+			}
+			newlineIndex = buffer.indexOf('\n');
+		}
+	}
+
+	// Flush any trailing object that wasn't newline-terminated
+	buffer += decoder.decode();
+	const rest = buffer.trim();
+	if (rest) {
+		yield JSON.parse(rest) as T;
+	}
+}
@@ -0,0 +1,203 @@
+import {createServer} from 'node:http';
+import type {AddressInfo} from 'node:net';
+import test from 'ava';
+import {jsonSchema, tool} from '@/types/core';
+import type {Message} from '@/types/index';
+import {
+	convertToOllamaMessages,
+	getOllamaBaseURL,
+	OllamaClient,
+} from './ollama-client.js';
+
+test('getOllamaBaseURL strips the OpenAI-compatible /v1 suffix', t => {
+	t.is(getOllamaBaseURL('http://localhost:11434/v1'), 'http://localhost:11434');
+	t.is(getOllamaBaseURL('http://localhost:11434/v1/'), 'http://localhost:11434');
+	t.is(getOllamaBaseURL('http://gpu-box:11434'), 'http://gpu-box:11434');
+});
+
+test('getOllamaBaseURL defaults to the local Ollama port', t => {
+	t.is(getOllamaBaseURL(undefined), 'http://localhost:11434');
+});
+
+test('convertToOllamaMessages sends developer messages as system messages', t => {
+	const converted = convertToOllamaMessages([
+		{role: 'developer', content: 'Be terse'},
+		{role: 'user', content: 'Hi'},
+	]);
+
+	t.is(converted[0].role, 'system');
+	t.is(converted[0].content, 'Be terse');
+});
+
+test('convertToOllamaMessages maps tool calls and tool results', t => {
+	const messages: Message[] = [
+		{role: 'user', content: 'Read the file'},
+		{
+			role: 'assistant',
+			content: '',
+			tool_calls: [
+				{id: 'call_1', function: {name: 'read_file', arguments: {path: 'a.ts'}}},
+			],
+		},
+		{role: 'tool', content: 'contents', tool_call_id: 'call_1', name: 'read_file'},
+	];
+
+	const converted = convertToOllamaMessages(messages);
+
+	t.deepEqual(converted[1].tool_calls, [
+		{function: {name: 'read_file', arguments: {path: 'a.ts'}}},
+	]);
+	t.is(converted[2].role, 'tool');
+	t.is(converted[2].tool_name, 'read_file');
+});
+
+test('OllamaClient uses the first configured model', t => {
+	const client = new OllamaClient({
+		name: 'Ollama',
+		type: 'ollama',
+		models: ['qwen2.5-coder:14b', 'llama3.2'],
+		config: {baseURL: 'http://localhost:11434/v1'},
+	});
+
+	t.is(client.getCurrentModel(), 'qwen2.5-coder:14b');
+	client.setModel('llama3.2');
+	t.is(client.getCurrentModel(), 'llama3.2');
+});
+
+test('OllamaClient rejects an already-aborted request', async t => {
+	const client = new OllamaClient({
+		name: 'Ollama',
+		type: 'ollama',
+		models: ['llama3.2'],
+		config: {},
+	});
+	const controller = new AbortController();
+	controller.abort();
+
+	await t.throwsAsync(() => client.chat([], {}, {}, controller.signal), {
+		message: 'Operation was cancelled',
+	});
+});
+
+test('OllamaClient returns thinking separately from the answer', async t => {
+	let requestBody: Record<string, unknown> = {};
+	const server = createServer((req, res) => {
+		let raw = '';
+		req.on('data', chunk => {
+			raw += chunk;
+		});
+		req.on('end', () => {
+			requestBody = JSON.parse(raw);
+			res.writeHead(200, {'content-type': 'application/x-ndjson'});
+			for (const chunk of [
+				{message: {role: 'assistant', content: '', thinking: 'The user '}},
+				{message: {role: 'assistant', content: '', thinking: 'says hi.'}},
+				{message: {role: 'assistant', content: 'Hello!'}},
+				{done: true, done_reason: 'stop', prompt_eval_count: 5, eval_count: 7},
+			]) {
+				res.write(`${JSON.stringify(chunk)}\n`);
+			}
+			res.end();
+		});
+	});
+	await new Promise<void>(resolve => server.listen(0, '127.0.0.1', resolve));
+	const {port} = server.address() as AddressInfo;
+
+	try {
+		const client = new OllamaClient({
+			name: 'Ollama',
+			type: 'ollama',
+			models: ['qwen3'],
+			config: {baseURL: `http://127.0.0.1:${port}`, thinkingBudget: 1024},
+		});
+		const reasoningDeltas: string[] = [];
+
+		const response = await client.chat([{role: 'user', content: 'hi'}], {}, {
+			onReasoning: delta => reasoningDeltas.push(delta),
+		});
+
+		t.is(requestBody.think, true);
+		t.is(response.reasoning, 'The user says hi.');
+		t.is(response.choices[0].message.content, 'Hello!');
+		t.deepEqual(reasoningDeltas, ['The user ', 'says hi.']);
+	} finally {
+		server.close();
+	}
+});
+
+// Serves one empty answer per request and records each request body
+async function withCapturingServer(
+	run: (baseURL: string, bodies: Record<string, unknown>[]) => Promise<void>,
+): Promise<void> {
+	const bodies: Record<string, unknown>[] = [];
+	const server = createServer((req, res) => {
+		let raw = '';
+		req.on('data', chunk => {
+			raw += chunk;
+		});
+		req.on('end', () => {
+			bodies.push(JSON.parse(raw));
+			res.writeHead(200, {'content-type': 'application/x-ndjson'});
+			res.write(
+				`${JSON.stringify({message: {role: 'assistant', content: 'ok'}})}\n`,
+			);
+			res.end(`${JSON.stringify({done: true, done_reason: 'stop'})}\n`);
+		});
+	});
+	await new Promise<void>(resolve => server.listen(0, '127.0.0.1', resolve));
+	const {port} = server.address() as AddressInfo;
+
+	try {
+		await run(`http://127.0.0.1:${port}`, bodies);
+	} finally {
+		server.close();
+	}
+}
+
+test('OllamaClient applies the tool choice by which tools it sends', async t => {
+	const tools = {
+		read_file: tool({
+			description: 'Read a file',
+			inputSchema: jsonSchema<{path: string}>({
+				type: 'object',
+				properties: {path: {type: 'string'}},
+				required: ['path'],
+			}),
+		}),
+		list_directory: tool({
+			description: 'List a directory',
+			inputSchema: jsonSchema<Record<string, never>>({
+				type: 'object',
+				properties: {},
+			}),
+		}),
+	};
+	const messages: Message[] = [{role: 'user', content: 'hi'}];
+
+	await withCapturingServer(async (baseURL, bodies) => {
+		const client = new OllamaClient({
+			name: 'Ollama',
+			type: 'ollama',
+			models: ['llama3.2'],
+			config: {baseURL},
+		});
+
+		await client.chat(messages, tools, {}, undefined, {toolChoice: 'auto'});
+		await client.chat(messages, tools, {}, undefined, {toolChoice: 'none'});
+		await client.chat(messages, tools, {}, undefined, {
+			toolChoice: {type: 'tool', toolName: 'read_file'},
+		});
+
+		const toolNames = bodies.map(body =>
+			((body.tools ?? []) as Array<{function: {name: string}}>).map(
+				ollamaTool => ollamaTool.function.name,
+			),
+		);
+		t.deepEqual(toolNames, [
+			['read_file', 'list_directory'],
+			[],
+			['read_file'],
+		]);
+		t.false('tools' in bodies[1]);
+	});
+});
@@ -0,0 +1,412 @@
+import {asSchema} from 'ai';
+import {type Dispatcher, fetch as undiciFetch} from 'undici';
+import {resolveAutoMaxTokens} from '@/ai-sdk-client/chat/max-tokens';
+import {getBodyLogFields} from '@/ai-sdk-client/chat/request-logging';
+import {getToolsForChoice} from '@/ai-sdk-client/chat/tool-choice';
+import {generateToolCallId} from '@/ai-sdk-client/converters/tool-converter';
+import {listOllamaModels} from '@/ai-sdk-client/providers/model-listing';
+import {TIMEOUT_SOCKET_DEFAULT_MS} from '@/constants';
+import {getModelContextLimit, getModelOutputLimit} from '@/models/index.js';
+import type {
+	AIProviderConfig,
+	AISDKCoreTool,
+	ChatOptions,
+	LLMChatResponse,
+	LLMClient,
+	Message,
+	ProviderModelInfo,
+	StreamCallbacks,
+} from '@/types/index';
+import {type ChatStreamEvent, collectChatStream} from '@/utils/chat-stream';
+import {
+	repairDanglingToolCalls,
+	validateConversation,
+} from '@/utils/conversation-validation';
+import {createHttpDispatcher} from '@/utils/http-dispatcher';
+import {
+	endMetrics,
+	generateCorrelationId,
+	getLogger,
+	startMetrics,
+} from '@/utils/logging';
+import {parseNDJSONStream} from './ndjson-parser.js';
+
+interface OllamaMessage {
+	role: Message['role'];
+	content: string;
+	thinking?: string;
+	tool_calls?: Array<{
+		function: {name: string; arguments: Record<string, unknown>};
+	}>;
+	tool_name?: string;
+}
+
+interface OllamaTool {
+	type: 'function';
+	function: {
+		name: string;
+		description: string;
+		parameters: unknown;
+	};
+}
+
+/**
+ * A single line of an Ollama /api/chat NDJSON stream
+ */
+export interface OllamaChatChunk {
+	message?: OllamaMessage;
+	done?: boolean;
+	done_reason?: string;
+	prompt_eval_count?: number;
+	eval_count?: number;
+	error?: string;
+}
+
+/**
+ * Maps Ollama's NDJSON chunks to provider-neutral chat stream events
+ */
+export async function* toChatStreamEvents(
+	chunks: AsyncIterable<OllamaChatChunk>,
+): AsyncGenerator<ChatStreamEvent> {
+	for await (const chunk of chunks) {
+		if (chunk.error) {
+			throw new Error(`Ollama error: ${chunk.error}`);
+		}
+
+		if (chunk.message?.thinking) {
+			yield {type: 'reasoning', text: chunk.message.thinking};
+		}
+
+		if (chunk.message?.content) {
+			yield {type: 'text', text: chunk.message.content};
+		}
+
+		for (const ollamaToolCall of chunk.message?.tool_calls || []) {
+			yield {
+				type: 'tool-call',
+				toolCall: {
+					id: generateToolCallId(),
+					function: {
+						name: ollamaToolCall.function.name,
+						arguments: ollamaToolCall.function.arguments || {},
+					},
+				},
+			};
+		}
+
+		if (chunk.done) {
+			yield {
+				type: 'finish',
+				finishReason: chunk.done_reason,
+				usage: {
+					prompt_tokens: chunk.prompt_eval_count ?? 0,
+					completion_tokens: chunk.eval_count ?? 0,
+				},
+			};
+		}
+	}
+}
+
+/**
+ * Strips the OpenAI-compatible suffix so configs written for the /v1 shim
+ * (e.g. http://localhost:11434/v1) also work against the native API
+ */
+export function getOllamaBaseURL(baseURL?: string): string {
+	return (baseURL || 'http://localhost:11434')
+		.replace(/\/+$/, '')
+		.replace(/\/v1$/, '');
+}
+
+/**
+ * Converts nanocoder messages to Ollama's native chat message format
+ */
+export function convertToOllamaMessages(messages: Message[]): OllamaMessage[] {
+	return messages.map(message => {
+		const ollamaMessage: OllamaMessage = {
+			// Ollama has no developer role
+			role: message.role === 'developer' ? 'system' : message.role,
+			content: message.content || '',
+		};
+
+		if (message.tool_calls && message.tool_calls.length > 0) {
+			ollamaMessage.tool_calls = message.tool_calls.map(toolCall => ({
+				function: {
+					name: toolCall.function.name,
+					arguments: toolCall.function.arguments,
+				},
+			}));
+		}
+
+		if (message.role === 'tool' && message.name) {
+			ollamaMessage.tool_name = message.name;
+		}
+
+		return ollamaMessage;
+	});
+}
+
+/**
+ * Converts AI SDK tools to Ollama's function tool definitions
+ */
+async function convertToOllamaTools(
+	tools: Record<string, AISDKCoreTool>,
+): Promise<OllamaTool[]> {
+	const ollamaTools: OllamaTool[] = [];
+
+	for (const [name, coreTool] of Object.entries(tools)) {
+		const parameters = await asSchema(coreTool.inputSchema).jsonSchema;
+		ollamaTools.push({
+			type: 'function',
+			function: {
+				name,
+				description: coreTool.description || '',
+				parameters,
+			},
+		});
+	}
+
+	return ollamaTools;
+}
+
+/**
+ * LLM client for Ollama's native /api/chat endpoint.
+ *
+ * Unlike the OpenAI-compatible shim, the native API supports Ollama-specific
+ * options such as keep_alive. Responses are streamed as NDJSON. Tool calls are
+ * returned to the conversation loop rather than auto-executed.
+ */
+export class OllamaClient implements LLMClient {
+	private currentModel: string;
+	private availableModels: string[];
+	private providerConfig: AIProviderConfig;
+	private undiciAgent: Dispatcher;
+	private cachedContextSize: number;
+	private cachedOutputLimit: number;
+	private baseURL: string;
+
+	constructor(providerConfig: AIProviderConfig) {
+		const logger = getLogger();
+
+		this.providerConfig = providerConfig;
+		this.availableModels = providerConfig.models;
+		this.currentModel = providerConfig.models[0] || '';
+		this.cachedContextSize = 0;
+		this.cachedOutputLimit = 0;
+		this.baseURL = getOllamaBaseURL(providerConfig.config.baseURL);
+
+		logger.info('Ollama client initializing', {
+			models: this.availableModels,
+			defaultModel: this.currentModel,
+			provider: providerConfig.name,
+		});
+
+		const {requestTimeout, socketTimeout} = providerConfig;
+		const effectiveSocketTimeout = socketTimeout ?? requestTimeout;
+		const resolvedSocketTimeout =
+			effectiveSocketTimeout === -1
+				? 0
+				: (effectiveSocketTimeout ?? TIMEOUT_SOCKET_DEFAULT_MS);
+
+		this.undiciAgent = createHttpDispatcher({
+			connect: {
+				timeout: resolvedSocketTimeout,
+			},
+			bodyTimeout: resolvedSocketTimeout,
+			headersTimeout: resolvedSocketTimeout,
+			keepAliveTimeout: providerConfig.connectionPool?.idleTimeout,
+			keepAliveMaxTimeout:
+				providerConfig.connectionPool?.cumulativeMaxIdleTimeout,
+		});
+
+		void this.updateContextSize();
+	}
+
+	static create(providerConfig: AIProviderConfig): Promise<OllamaClient> {
+		return Promise.resolve(new OllamaClient(providerConfig));
+	}
+
+	private async updateContextSize(): Promise<void> {
+		try {
+			const contextSize = await getModelContextLimit(this.currentModel);
+			this.cachedContextSize = contextSize || 0;
+			const outputLimit = await getModelOutputLimit(this.currentModel);
+			this.cachedOutputLimit = outputLimit || 0;
+		} catch (error) {
+			getLogger().debug('Failed to get model context size', {
+				model: this.currentModel,
+				error,
+			});
+			this.cachedContextSize = 0;
+			this.cachedOutputLimit = 0;
+		}
+	}
+
+	setModel(model: string): void {
+		const previousModel = this.currentModel;
+		this.currentModel = model;
+
+		getLogger().info('Model changed', {
+			previousModel,
+			newModel: model,
+			provider: this.providerConfig.name,
+		});
+
+		void this.updateContextSize();
+	}
+
+	getCurrentModel(): string {
+		return this.currentModel;
+	}
+
+	getContextSize(): number {
+		return this.cachedContextSize;
+	}
+
+	getAvailableModels(): Promise<string[]> {
+		return Promise.resolve(this.availableModels);
+	}
+
+	/**
+	 * List the models pulled into the Ollama server
+	 */
+	listModels(): Promise<ProviderModelInfo[]> {
+		return listOllamaModels(this.baseURL, this.undiciAgent);
+	}
+
+	async chat(
+		messages: Message[],
+		tools: Record<string, AISDKCoreTool>,
+		callbacks: StreamCallbacks,
+		signal?: AbortSignal,
+		options: ChatOptions = {},
+	): Promise<LLMChatResponse> {
+		const logger = getLogger();
+
+		if (signal?.aborted) {
+			throw new Error('Operation was cancelled');
+		}
+
+		const metrics = startMetrics();
+		const correlationId = generateCorrelationId();
+
+		logger.info('Ollama chat request starting', {
+			model: this.currentModel,
+			messageCount: messages.length,
+			toolCount: Object.keys(tools).length,
+			correlationId,
+			provider: this.providerConfig.name,
+		});
+
+		// A restored history can end mid-batch; answer what was left unanswered
+		const conversation = repairDanglingToolCalls(messages);
+		validateConversation(conversation);
+
+		const body: Record<string, unknown> = {
+			model: this.currentModel,
+			messages: convertToOllamaMessages(conversation),
+			stream: true,
+		};
+		// Ollama has no tool_choice, so a choice is applied by which tools are
+		// sent; 'required' can't be enforced
+		const requestTools = getToolsForChoice(tools, options.toolChoice);
+		if (Object.keys(requestTools).length > 0) {
+			body.tools = await convertToOllamaTools(requestTools);
+		}
+		// Ollama has no thinking budget; any budget just turns thinking on
+		if (this.providerConfig.config.thinkingBudget !== undefined) {
+			body.think = true;
+		}
+		if (this.providerConfig.config.keepAlive !== undefined) {
+			body.keep_alive = this.providerConfig.config.keepAlive;
+		}
+		const maxOutputTokens = await resolveAutoMaxTokens(
+			this.providerConfig,
+			this.currentModel,
+			{
+				messages: conversation,
+				tools: requestTools,
+				contextSize: this.cachedContextSize,
+				outputLimit: this.cachedOutputLimit,
+			},
+		);
+		if (maxOutputTokens !== undefined) {
+			body.options = {num_predict: maxOutputTokens};
+		}
+
+		logger.debug('Ollama request prepared', {
+			messageCount: messages.length,
+			toolCount: Object.keys(tools).length,
+			...getBodyLogFields(this.providerConfig, {messages: body.messages}),
+		});
+
+		let statusCode: number | undefined;
+
+		try {
+			const response = await undiciFetch(`${this.baseURL}/api/chat`, {
+				method: 'POST',
+				headers: {'Content-Type': 'application/json'},
+				body: JSON.stringify(body),
+				signal,
+				dispatcher: this.undiciAgent,
+			});
+
+			statusCode = response.status;
+			if (!response.ok || !response.body) {
+				const errorText = await response.text();
+				throw new Error(
+					`Ollama request failed (${response.status}): ${errorText || response.statusText}`,
+				);
+			}
+
+			const chatResponse = await collectChatStream(
+				toChatStreamEvents(parseNDJSONStream<OllamaChatChunk>(response.body)),
+				callbacks,
+			);
+			const {content, tool_calls: toolCalls} =
+				chatResponse.choices[0].message;
+
+			const finalMetrics = endMetrics(metrics);
+			logger.info('Ollama chat request completed', {
+				model: this.currentModel,
+				duration: `${finalMetrics.duration.toFixed(2)}ms`,
+				responseLength: content.length,
+				toolCallsFound: toolCalls?.length ?? 0,
+				usage: chatResponse.usage,
+				statusCode,
+				...getBodyLogFields(this.providerConfig, {response: content}),
+				correlationId,
+				provider: this.providerConfig.name,
+			});
+
+			return chatResponse;
+		} catch (error) {
+			if (
+				signal?.aborted ||
+				(error instanceof Error && error.name === 'AbortError')
+			) {
+				logger.info('Ollama chat request cancelled by user', {
+					model: this.currentModel,
+					correlationId,
+				});
+				throw new Error('Operation was cancelled');
+			}
+
+			logger.error('Ollama chat request failed', {
+				model: this.currentModel,
+				statusCode,
+				error: error instanceof Error ? error.message : error,
+				correlationId,
+				provider: this.providerConfig.name,
+			});
+			throw error;
+		}
+	}
+
+	clearContext(): Promise<void> {
+		getLogger().debug('Ollama client context cleared', {
+			model: this.currentModel,
+			provider: this.providerConfig.name,
+		});
+		return Promise.resolve();
+	}
+}
@@ -0,0 +1,87 @@
+import test from 'ava';
+import {ProviderRegistry} from './provider-registry';
+import type {LLMClient, Message} from '@/types/index';
+
+console.log('\nprovider-registry.spec.ts');
+
+// A client that answers every chat with its own provider name
+function createFakeClient(provider: string): LLMClient {
+	return {
+		getCurrentModel: () => `${provider}-model`,
+		setModel: () => {},
+		getContextSize: () => 4096,
+		getAvailableModels: async () => [`${provider}-model`],
+		chat: async () => ({
+			choices: [{message: {role: 'assistant', content: `from ${provider}`}}],
+		}),
+		clearContext: async () => {},
+	};
+}
+
+function createRegistry(): {registry: ProviderRegistry; created: string[]} {
+	const created: string[] = [];
+	const registry = new ProviderRegistry(async provider => {
+		created.push(provider);
+		return createFakeClient(provider);
+	});
+	return {registry, created};
+}
+
+const messages: Message[] = [{role: 'user', content: 'hello'}];
+
+test('init registers clients by name and routes chats to each', async t => {
+	const {registry, created} = createRegistry();
+	await registry.init('openrouter');
+	await registry.init('ollama');
+
+	const first = await registry.chat(messages, 'openrouter');
+	const second = await registry.chat(messages, 'ollama');
+
+	t.deepEqual(created, ['openrouter', 'ollama']);
+	t.is(first.choices[0].message.content, 'from openrouter');
+	t.is(second.choices[0].message.content, 'from ollama');
+	t.deepEqual(registry.getProviderNames(), ['openrouter', 'ollama']);
+});
+
+test('chats without a provider go to the default client', async t => {
+	const {registry} = createRegistry();
+	await registry.init('openrouter');
+	await registry.init('ollama');
+
+	t.is(registry.getDefaultProvider(), 'openrouter');
+	const response = await registry.chat(messages);
+	t.is(response.choices[0].message.content, 'from openrouter');
+
+	registry.register('lmstudio', createFakeClient('lmstudio'), true);
+	const switched = await registry.chat(messages);
+	t.is(switched.choices[0].message.content, 'from lmstudio');
+});
+
+test('init does not overwrite other providers', async t => {
+	const {registry} = createRegistry();
+	const current = createFakeClient('current');
+	registry.register('openrouter', current, true);
+	await registry.init('ollama');
+
+	t.is(registry.get('openrouter'), current);
+	t.is(registry.getDefaultProvider(), 'openrouter');
+});
+
+test('get throws for a provider that was never initialized', t => {
+	const {registry} = createRegistry();
+	t.throws(() => registry.get(), {message: 'No provider is initialized'});
+	registry.register('ollama', createFakeClient('ollama'));
+	t.throws(() => registry.get('openrouter'), {
+		message: 'Provider "openrouter" is not initialized',
+	});
+});
+
+test('init rejects when the provider cannot be created', async t => {
+	const registry = new ProviderRegistry(async provider => {
+		throw new Error(`Unknown provider: ${provider}`);
+	});
+	await t.throwsAsync(registry.init('missing'), {
+		message: 'Unknown provider: missing',
+	});
+	t.deepEqual(registry.getProviderNames(), []);
+});
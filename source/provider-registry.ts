@@ -0,0 +1,75 @@
+import {createProviderClient} from '@/client-factory';
+import type {LLMChatResponse, LLMClient, Message} from '@/types/index';
+
+/**
+ * Clients for several providers, by name, so requests can pick which one
+ * answers. Requests that don't name a provider go to the default client.
+ */
+export class ProviderRegistry {
+	private clients = new Map<string, LLMClient>();
+	private defaultProvider: string | null = null;
+
+	constructor(
+		private createClient: (
+			provider: string,
+		) => Promise<LLMClient> = createProviderClient,
+	) {}
+
+	/**
+	 * Registers a client under a name, replacing any client already there.
+	 * The first client registered becomes the default.
+	 */
+	register(provider: string, client: LLMClient, makeDefault = false): void {
+		this.clients.set(provider, client);
+		if (makeDefault || this.defaultProvider === null) {
+			this.defaultProvider = provider;
+		}
+	}
+
+	/**
+	 * Creates a client for a configured provider and registers it, leaving
+	 * the default alone unless the registry was empty
+	 */
+	async init(provider: string): Promise<LLMClient> {
+		const client = await this.createClient(provider);
+		this.register(provider, client);
+		return client;
+	}
+
+	/**
+	 * The client for a provider, or the default client when none is named
+	 * @throws Error when no such client is registered
+	 */
+	get(provider?: string): LLMClient {
+		const name = provider ?? this.defaultProvider;
+		const client = name === null ? undefined : this.clients.get(name);
+		if (!client) {
+			throw new Error(
+				provider
+					? `Provider "${provider}" is not initialized`
+					: 'No provider is initialized',
+			);
+		}
+		return client;
+	}
+
+	/**
+	 * Sends a one-shot chat, without tools, to the named provider's client or
+	 * the default one
+	 */
+	chat(
+		messages: Message[],
+		provider?: string,
+		signal?: AbortSignal,
+	): Promise<LLMChatResponse> {
+		return this.get(provider).chat(messages, {}, {}, signal);
+	}
+
+	getDefaultProvider(): string | null {
+		return this.defaultProvider;
+	}
+
+	getProviderNames(): string[] {
+		return [...this.clients.keys()];
+	}
+}
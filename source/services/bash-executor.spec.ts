@@ -1,5 +1,10 @@
 import test from 'ava';
-import {BashExecutor} from './bash-executor';
+import {
+	BashExecutor,
+	getShellInvocation,
+	isShellAvailable,
+	type ShellName,
+} from './bash-executor';
 
 console.log(`\nbash-executor.spec.ts`);
 
@@ -159,6 +164,7 @@ test('cancel - resolves the promise with cancelled state', async t => {
 
 	t.true(result.isComplete);
 	t.is(result.error, 'Cancelled by user');
+	t.true(result.cancelled);
 });
 
 test('cancel - emits complete event with error', async t => {
@@ -357,3 +363,48 @@ test('complete event - provides immutable state copy', async t => {
 	// But be different objects
 	t.not(eventState, promiseResult);
 });
+
+// Shell selection tests
+test('getShellInvocation - uses -c for POSIX shells', t => {
+	t.deepEqual(getShellInvocation('echo hi', 'bash'), {
+		file: 'bash',
+		args: ['-c', 'echo hi'],
+	});
+	t.deepEqual(getShellInvocation('echo hi', 'sh').args, ['-c', 'echo hi']);
+});
+
+test('getShellInvocation - uses -Command for pwsh and /c for cmd', t => {
+	t.deepEqual(getShellInvocation('dir', 'pwsh').args, [
+		'-NoProfile',
+		'-Command',
+		'dir',
+	]);
+	t.deepEqual(getShellInvocation('dir', 'cmd').args, ['/c', 'dir']);
+});
+
+test('execute - runs bashisms when bash is selected', async t => {
+	if (!isShellAvailable('bash')) {
+		t.pass('bash not available');
+		return;
+	}
+
+	const executor = createExecutor();
+	const {promise} = executor.execute(
+		'[[ "abc" == a* ]] && echo "matched"',
+		'bash',
+	);
+	const result = await promise;
+
+	t.is(result.exitCode, 0);
+	t.true(result.fullOutput.includes('matched'));
+});
+
+test('execute - returns error for unsupported shell', async t => {
+	const executor = createExecutor();
+	const {promise} = executor.execute('echo hi', 'fish' as ShellName);
+	const result = await promise;
+
+	t.true(result.isComplete);
+	t.regex(result.error!, /Shell "fish" is not available/);
+	t.false(executor.hasActiveExecutions());
+});
@@ -1,4 +1,4 @@
-import {type ChildProcess, spawn} from 'node:child_process';
+import {type ChildProcess, execFileSync, spawn} from 'node:child_process';
 import {randomUUID} from 'node:crypto';
 import {EventEmitter} from 'node:events';
 import {platform} from 'node:process';
@@ -10,6 +10,62 @@ import {
 
 const isWindows = platform === 'win32';
 
+export type ShellName = 'sh' | 'bash' | 'zsh' | 'pwsh' | 'cmd';
+
+export const SUPPORTED_SHELLS: ShellName[] = [
+	'sh',
+	'bash',
+	'zsh',
+	'pwsh',
+	'cmd',
+];
+
+const shellAvailability = new Map<ShellName, boolean>();
+
+/**
+ * Checks whether a shell is on the PATH. Results are cached per process.
+ */
+export function isShellAvailable(shell: ShellName): boolean {
+	const cached = shellAvailability.get(shell);
+	if (cached !== undefined) return cached;
+
+	let available: boolean;
+	try {
+		execFileSync(isWindows ? 'where' : 'which', [shell], {stdio: 'ignore'});
+		available = true;
+	} catch {
+		available = false;
+	}
+
+	shellAvailability.set(shell, available);
+	return available;
+}
+
+/**
+ * Default shell: bash if available, else sh on Unix; cmd on Windows
+ */
+function getDefaultShell(): ShellName {
+	if (isWindows) return 'cmd';
+	return isShellAvailable('bash') ? 'bash' : 'sh';
+}
+
+/**
+ * Builds the executable and arguments to run a command in the given shell
+ */
+export function getShellInvocation(
+	command: string,
+	shell: ShellName,
+): {file: string; args: string[]} {
+	switch (shell) {
+		case 'pwsh':
+			return {file: 'pwsh', args: ['-NoProfile', '-Command', command]};
+		case 'cmd':
+			return {file: 'cmd', args: ['/c', command]};
+		default:
+			return {file: shell, args: ['-c', command]};
+	}
+}
+
 export interface BashExecutionState {
 	executionId: string;
 	command: string;
@@ -19,6 +75,7 @@ export interface BashExecutionState {
 	isComplete: boolean;
 	exitCode: number | null;
 	error: string | null;
+	cancelled?: boolean; // Set when cancel() stopped the command
 }
 
 interface ExecutionEntry {
@@ -31,7 +88,10 @@ interface ExecutionEntry {
 export class BashExecutor extends EventEmitter {
 	private executions = new Map<string, ExecutionEntry>();
 
-	execute(command: string): {
+	execute(
+		command: string,
+		shell?: ShellName,
+	): {
 		executionId: string;
 		promise: Promise<BashExecutionState>;
 	} {
@@ -48,9 +108,20 @@ export class BashExecutor extends EventEmitter {
 			error: null,
 		};
 
-		const proc = isWindows
-			? spawn('cmd', ['/c', command])
-			: spawn('sh', ['-c', command]);
+		if (
+			shell &&
+			(!SUPPORTED_SHELLS.includes(shell) || !isShellAvailable(shell))
+		) {
+			state.isComplete = true;
+			state.error = `Shell "${shell}" is not available. Supported shells: ${SUPPORTED_SHELLS.join(', ')}`;
+			return {executionId, promise: Promise.resolve({...state})};
+		}
+
+		const {file, args} = getShellInvocation(
+			command,
+			shell ?? getDefaultShell(),
+		);
+		const proc = spawn(file, args);
 
 		// Collect output
 		proc.stdout.on('data', (data: Buffer) => {
@@ -115,6 +186,7 @@ export class BashExecutor extends EventEmitter {
 		execution.process.kill('SIGTERM');
 		execution.state.isComplete = true;
 		execution.state.error = 'Cancelled by user';
+		execution.state.cancelled = true;
 		this.emit('complete', {...execution.state});
 
 		// Resolve the promise with the cancelled state
@@ -17,15 +17,19 @@ import {FileSnapshotService} from './file-snapshot';
 
 /**
  * Service for managing conversation checkpoints.
- * Checkpoints are stored in .nanocoder/checkpoints/ within the workspace root.
+ * Checkpoints are stored in .nanocoder/checkpoints/ within the workspace root,
+ * or in another directory under .nanocoder/ for a separate set of them.
  */
 export class CheckpointManager {
 	private readonly checkpointsDir: string;
 	private readonly fileSnapshotService: FileSnapshotService;
 
-	constructor(workspaceRoot: string = process.cwd()) {
+	constructor(
+		workspaceRoot: string = process.cwd(),
+		directory: string = 'checkpoints',
+	) {
 		// nosemgrep
-		this.checkpointsDir = path.join(workspaceRoot, '.nanocoder', 'checkpoints'); // nosemgrep
+		this.checkpointsDir = path.join(workspaceRoot, '.nanocoder', directory); // nosemgrep
 		this.fileSnapshotService = new FileSnapshotService(workspaceRoot);
 	}
 
@@ -1 +1,7 @@
-export {createTokenizer} from './tokenizer-factory.js';
+export {
+	countRequestTokens,
+	getAvailableOutputBudget,
+	wouldOverflow,
+} from './request-counter.js';
+export {StreamingTokenCounter} from './streaming-counter.js';
+export {countMessageTokens, createTokenizer} from './tokenizer-factory.js';
@@ -0,0 +1,87 @@
+/**
+ * Tests for request-counter.ts
+ */
+
+import type {Message} from '@/types/core.js';
+import test from 'ava';
+import {
+	countRequestTokens,
+	getAvailableOutputBudget,
+	wouldOverflow,
+} from './request-counter.js';
+import {AnthropicTokenizer} from './tokenizers/anthropic-tokenizer.js';
+import {FallbackTokenizer} from './tokenizers/fallback-tokenizer.js';
+import {OpenAITokenizer} from './tokenizers/openai-tokenizer.js';
+
+console.log(`\nrequest-counter.spec.ts`);
+
+const messages: Message[] = [
+	{role: 'system', content: 'You are a helpful assistant.'},
+	{role: 'user', content: 'Hello, world!'},
+];
+
+test('countRequestTokens returns 0 for no messages', t => {
+	t.is(countRequestTokens([], new OpenAITokenizer('gpt-4')), 0);
+});
+
+test('countRequestTokens adds request overhead to message counts', t => {
+	const tokenizer = new OpenAITokenizer('gpt-4');
+	const messageTotal =
+		tokenizer.countTokens(messages[0]) + tokenizer.countTokens(messages[1]);
+
+	t.is(
+		countRequestTokens(messages, tokenizer),
+		messageTotal + tokenizer.getRequestOverhead(),
+	);
+});
+
+test('countTokens applies each tokenizer family its own message overhead', t => {
+	const message: Message = {role: 'user', content: 'Hello, world!'};
+	const openai = new OpenAITokenizer('gpt-4');
+	const anthropic = new AnthropicTokenizer('claude-3');
+
+	t.is(openai.getMessageOverhead(), 4);
+	t.is(anthropic.getMessageOverhead(), 3);
+	t.is(
+		openai.countTokens(message),
+		openai.encode('Hello, world!') + openai.encode('user') + 4,
+	);
+	t.is(
+		anthropic.countTokens(message),
+		anthropic.encode('Hello, world!') + anthropic.encode('user') + 3,
+	);
+});
+
+test('countRequestTokens has no fixed overhead for the fallback tokenizer', t => {
+	const tokenizer = new FallbackTokenizer();
+
+	t.is(
+		countRequestTokens(messages, tokenizer),
+		tokenizer.countTokens(messages[0]) + tokenizer.countTokens(messages[1]),
+	);
+});
+
+test('getAvailableOutputBudget returns remaining context', t => {
+	const tokenizer = new FallbackTokenizer();
+	const used = countRequestTokens(messages, tokenizer);
+
+	t.is(getAvailableOutputBudget(messages, tokenizer, used + 100), 100);
+	t.is(getAvailableOutputBudget(messages, tokenizer, used + 100, 40), 60);
+});
+
+test('getAvailableOutputBudget never goes negative on a full context', t => {
+	const tokenizer = new FallbackTokenizer();
+	const used = countRequestTokens(messages, tokenizer);
+
+	t.is(getAvailableOutputBudget(messages, tokenizer, used - 5), 0);
+});
+
+test('wouldOverflow detects a near-full context', t => {
+	const tokenizer = new FallbackTokenizer();
+	const used = countRequestTokens(messages, tokenizer);
+	const maxContext = used + 10;
+
+	t.false(wouldOverflow(messages, tokenizer, maxContext, 10));
+	t.true(wouldOverflow(messages, tokenizer, maxContext, 11));
+	t.true(wouldOverflow(messages, tokenizer, maxContext, 10, 1));
+});
@@ -0,0 +1,54 @@
+/**
+ * Request-level token counting
+ * Sums per-message counts and adds the tokenizer's fixed per-request overhead
+ */
+
+import type {Message} from '@/types/core';
+import type {Tokenizer} from '../types/tokenization.js';
+
+/**
+ * Count the tokens a full request will use before the model generates output
+ */
+export function countRequestTokens(
+	messages: Message[],
+	tokenizer: Tokenizer,
+): number {
+	if (messages.length === 0) {
+		return 0;
+	}
+
+	let total = tokenizer.getRequestOverhead();
+	for (const message of messages) {
+		total += tokenizer.countTokens(message);
+	}
+
+	return total;
+}
+
+/**
+ * How many tokens are left for the model's output, e.g. when choosing
+ * max_tokens. Tool definitions count against the context window too.
+ */
+export function getAvailableOutputBudget(
+	messages: Message[],
+	tokenizer: Tokenizer,
+	maxContextTokens: number,
+	toolDefinitionTokens = 0,
+): number {
+	const used = countRequestTokens(messages, tokenizer) + toolDefinitionTokens;
+	return Math.max(0, maxContextTokens - used);
+}
+
+/**
+ * Whether asking for desiredOutputTokens would exceed the context window
+ */
+export function wouldOverflow(
+	messages: Message[],
+	tokenizer: Tokenizer,
+	maxContextTokens: number,
+	desiredOutputTokens: number,
+	toolDefinitionTokens = 0,
+): boolean {
+	const used = countRequestTokens(messages, tokenizer) + toolDefinitionTokens;
+	return used + desiredOutputTokens > maxContextTokens;
+}
@@ -0,0 +1,63 @@
+/**
+ * Tests for streaming-counter.ts
+ */
+
+import test from 'ava';
+import {StreamingTokenCounter} from './streaming-counter.js';
+import {AnthropicTokenizer} from './tokenizers/anthropic-tokenizer.js';
+import {FallbackTokenizer} from './tokenizers/fallback-tokenizer.js';
+import {OpenAITokenizer} from './tokenizers/openai-tokenizer.js';
+
+console.log(`\nstreaming-counter.spec.ts`);
+
+const text =
+	'The quick brown fox jumps over the lazy dog. ' +
+	'Streaming responses arrive in small pieces, often splitting words\n' +
+	'and punctuation across chunks: function countTokens(text) { return 42; }';
+
+// Split text into uneven chunks, cutting through words
+function toDeltas(source: string, sizes: number[]): string[] {
+	const deltas: string[] = [];
+	let offset = 0;
+	let index = 0;
+	while (offset < source.length) {
+		const size = sizes[index++ % sizes.length];
+		deltas.push(source.slice(offset, offset + size));
+		offset += size;
+	}
+	return deltas;
+}
+
+test('StreamingTokenCounter matches a one-shot count for OpenAI', t => {
+	const tokenizer = new OpenAITokenizer('gpt-4');
+	const counter = new StreamingTokenCounter(tokenizer, 20);
+
+	for (const delta of toDeltas(text, [3, 7, 1, 12])) {
+		counter.append(delta);
+	}
+
+	const expected = tokenizer.encode(text);
+	t.true(Math.abs(counter.getCount() - expected) <= 2);
+	tokenizer.free?.();
+});
+
+test('StreamingTokenCounter matches a one-shot count for Anthropic', t => {
+	const tokenizer = new AnthropicTokenizer('claude-3');
+	const counter = new StreamingTokenCounter(tokenizer, 20);
+
+	for (const delta of toDeltas(text, [5, 2, 9])) {
+		counter.append(delta);
+	}
+
+	const expected = tokenizer.encode(text);
+	t.true(Math.abs(counter.getCount() - expected) <= 2);
+});
+
+test('StreamingTokenCounter returns the running count from append', t => {
+	const counter = new StreamingTokenCounter(new FallbackTokenizer());
+
+	t.is(counter.getCount(), 0);
+	t.is(counter.append('abcd'), 1);
+	t.is(counter.append('efgh'), 2);
+	t.is(counter.getCount(), 2);
+});
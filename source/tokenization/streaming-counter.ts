@@ -0,0 +1,56 @@
+/**
+ * Incremental token counting for streamed output
+ * Keeps a running total without recounting the whole text on every chunk
+ */
+
+import {STREAMING_TOKEN_RECOUNT_CHARS} from '@/constants';
+import type {Tokenizer} from '../types/tokenization.js';
+
+/**
+ * Running token count of text that arrives in pieces. Only the text after the
+ * last word boundary is recounted as deltas arrive; once it grows past
+ * recountChars, everything before its last whitespace is counted for good.
+ * Tokenizers split words at whitespace, so the total stays close to counting
+ * the full text at once.
+ */
+export class StreamingTokenCounter {
+	private settledTokens = 0;
+	private pending = '';
+	private count = 0;
+
+	constructor(
+		private readonly tokenizer: Tokenizer,
+		private readonly recountChars = STREAMING_TOKEN_RECOUNT_CHARS,
+	) {}
+
+	/**
+	 * Add a delta of streamed text
+	 * @returns The token count of all text appended so far
+	 */
+	append(delta: string): number {
+		this.pending += delta;
+
+		if (this.pending.length > this.recountChars) {
+			// Cut before the last whitespace, which begins the word still growing
+			const boundary = this.pending.search(/\s\S*$/);
+			if (boundary > 0) {
+				this.settledTokens += this.tokenizer.encode(
+					this.pending.slice(0, boundary),
+				);
+				this.pending = this.pending.slice(boundary);
+			}
+		}
+
+		this.count =
+			this.settledTokens +
+			(this.pending ? this.tokenizer.encode(this.pending) : 0);
+		return this.count;
+	}
+
+	/**
+	 * The token count of all text appended so far
+	 */
+	getCount(): number {
+		return this.count;
+	}
+}
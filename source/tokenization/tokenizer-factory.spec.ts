@@ -3,7 +3,9 @@
  */
 
 import test from 'ava';
+import type {Message} from '../types/core.js';
 import {
+	countMessageTokens,
 	createTokenizer,
 	createTokenizerForProvider,
 } from './tokenizer-factory.js';
@@ -168,3 +170,33 @@ test('createTokenizerForProvider works without model ID', t => {
 	const tokenizer = createTokenizerForProvider('openai');
 	t.true(tokenizer instanceof OpenAITokenizer);
 });
+
+// Test countMessageTokens
+const sizedMessage: Message = {
+	role: 'user',
+	content: 'How many tokens does this message take up?',
+};
+
+test('countMessageTokens uses the OpenAI tokenizer for GPT models', t => {
+	const count = countMessageTokens(sizedMessage, 'gpt-4o');
+
+	t.true(count > 0);
+	t.is(count, new OpenAITokenizer('gpt-4o').countTokens(sizedMessage));
+});
+
+test('countMessageTokens uses the Anthropic tokenizer for Claude models', t => {
+	const count = countMessageTokens(sizedMessage, 'claude-sonnet-4-5');
+
+	t.true(count > 0);
+	t.is(
+		count,
+		new AnthropicTokenizer('claude-sonnet-4-5').countTokens(sizedMessage),
+	);
+});
+
+test('countMessageTokens takes the provider name into account', t => {
+	t.is(
+		countMessageTokens(sizedMessage, 'custom-model', 'openai'),
+		new OpenAITokenizer('custom-model').countTokens(sizedMessage),
+	);
+});
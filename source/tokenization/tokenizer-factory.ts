@@ -3,6 +3,7 @@
  * Creates appropriate tokenizer based on provider and model
  */
 
+import type {Message} from '../types/core.js';
 import type {Tokenizer, TokenizerProvider} from '../types/tokenization.js';
 import {AnthropicTokenizer} from './tokenizers/anthropic-tokenizer.js';
 import {FallbackTokenizer} from './tokenizers/fallback-tokenizer.js';
@@ -93,6 +94,23 @@ export function createTokenizer(
 	}
 }
 
+/**
+ * Count the tokens in a single message, using the tokenizer for its model.
+ * The provider name is optional and only helps when the model ID is ambiguous.
+ */
+export function countMessageTokens(
+	message: Message,
+	modelId: string,
+	providerName = '',
+): number {
+	const tokenizer = createTokenizer(providerName, modelId);
+	try {
+		return tokenizer.countTokens(message);
+	} finally {
+		tokenizer.free?.();
+	}
+}
+
 /**
  * Create a tokenizer with explicit provider
  */
@@ -30,11 +30,20 @@ export class AnthropicTokenizer implements Tokenizer {
 		const content = message.content || '';
 		const role = message.role || '';
 
+		return (
+			this.encode(content) + this.encode(role) + this.getMessageOverhead()
+		);
+	}
+
+	getMessageOverhead(): number {
 		// Anthropic format includes role in the message structure
 		// Approximate overhead for message formatting
-		const messageOverhead = 3;
+		return 3;
+	}
 
-		return this.encode(content) + this.encode(role) + messageOverhead;
+	getRequestOverhead(): number {
+		// Approximate overhead for the assistant turn the reply is written into
+		return 3;
 	}
 
 	getName(): string {
@@ -20,6 +20,15 @@ export class FallbackTokenizer implements Tokenizer {
 		return this.encode(content) + Math.ceil(role.length / this.CHARS_PER_TOKEN);
 	}
 
+	getMessageOverhead(): number {
+		// Role is estimated from its length rather than a fixed overhead
+		return 0;
+	}
+
+	getRequestOverhead(): number {
+		return 0;
+	}
+
 	getName(): string {
 		return 'fallback';
 	}
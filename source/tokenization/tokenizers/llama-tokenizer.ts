@@ -28,11 +28,21 @@ export class LlamaTokenizer implements Tokenizer {
 		const content = message.content || '';
 		const role = message.role || '';
 
+		return (
+			this.encode(content) + this.encode(role) + this.getMessageOverhead()
+		);
+	}
+
+	getMessageOverhead(): number {
 		// Llama format: <|start_header_id|>role<|end_header_id|>content<|eot_id|>
 		// Approximate overhead for message formatting
-		const messageOverhead = 6;
+		return 6;
+	}
 
-		return this.encode(content) + this.encode(role) + messageOverhead;
+	getRequestOverhead(): number {
+		// <|begin_of_text|> plus the <|start_header_id|>assistant<|end_header_id|>
+		// header the reply is generated after
+		return 5;
 	}
 
 	getName(): string {
@@ -219,3 +219,35 @@ test('OpenAITokenizer countTokens handles missing role', t => {
 	// Should handle gracefully by using empty string for role
 	t.true(count >= 4); // At least overhead + content
 });
+
+test('OpenAITokenizer resolves known model encodings', t => {
+	t.is(new OpenAITokenizer('gpt-4').getEncodingName(), 'cl100k_base');
+	t.is(new OpenAITokenizer('gpt-4o').getEncodingName(), 'o200k_base');
+});
+
+test('OpenAITokenizer falls back to cl100k_base for unknown models', t => {
+	const tokenizer = new OpenAITokenizer('my-custom-gpt');
+
+	t.is(tokenizer.getEncodingName(), 'cl100k_base');
+});
+
+test('OpenAITokenizer.withEncoding pins the encoding', t => {
+	const tokenizer = OpenAITokenizer.withEncoding('my-custom-gpt', 'o200k_base');
+
+	t.is(tokenizer.getEncodingName(), 'o200k_base');
+	t.true(tokenizer.encode('Hello, world!') > 0);
+});
+
+test.serial('OpenAITokenizer.setEncodingOverride applies to new tokenizers', t => {
+	OpenAITokenizer.setEncodingOverride('my-custom-gpt', 'o200k_base');
+
+	try {
+		t.is(new OpenAITokenizer('my-custom-gpt').getEncodingName(), 'o200k_base');
+		// Other models are unaffected
+		t.is(new OpenAITokenizer('gpt-4').getEncodingName(), 'cl100k_base');
+	} finally {
+		OpenAITokenizer.clearEncodingOverrides();
+	}
+
+	t.is(new OpenAITokenizer('my-custom-gpt').getEncodingName(), 'cl100k_base');
+});
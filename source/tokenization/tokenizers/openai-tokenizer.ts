@@ -3,25 +3,85 @@
  * Supports GPT-3.5, GPT-4, and other OpenAI models
  */
 
-import {encoding_for_model, get_encoding, type TiktokenModel} from 'tiktoken';
+import {
+	get_encoding,
+	get_encoding_name_for_model,
+	type TiktokenEncoding,
+	type TiktokenModel,
+} from 'tiktoken';
 import type {Message} from '@/types/core';
 import type {Tokenizer} from '../../types/tokenization';
 
+/**
+ * Model ID -> encoding overrides for fine-tuned or new models whose names
+ * tiktoken doesn't recognise
+ */
+const encodingOverrides = new Map<string, TiktokenEncoding>();
+
+/**
+ * Resolve the encoding for a model: explicit override first, then tiktoken's
+ * model table, then cl100k_base
+ */
+function resolveEncodingName(modelId: string): TiktokenEncoding {
+	const override = encodingOverrides.get(modelId);
+	if (override) {
+		return override;
+	}
+
+	try {
+		return get_encoding_name_for_model(modelId as TiktokenModel);
+	} catch {
+		return 'cl100k_base';
+	}
+}
+
 /**
  * OpenAI tokenizer using tiktoken for accurate token counting
  */
 export class OpenAITokenizer implements Tokenizer {
 	private encoding: ReturnType<typeof get_encoding>;
+	private encodingName: TiktokenEncoding;
 	private modelName: string;
 
-	constructor(modelId?: string) {
+	constructor(modelId?: string, encodingName?: TiktokenEncoding) {
 		this.modelName = modelId || 'gpt-4';
+		this.encodingName = encodingName ?? resolveEncodingName(this.modelName);
+		this.encoding = get_encoding(this.encodingName);
+	}
 
-		try {
-			this.encoding = encoding_for_model(modelId as TiktokenModel);
-		} catch {
-			this.encoding = get_encoding('cl100k_base');
-		}
+	/**
+	 * Create a tokenizer pinned to a specific encoding, ignoring the model name
+	 */
+	static withEncoding(
+		modelId: string,
+		encodingName: TiktokenEncoding,
+	): OpenAITokenizer {
+		return new OpenAITokenizer(modelId, encodingName);
+	}
+
+	/**
+	 * Pin the encoding used for a model ID in all tokenizers created afterwards
+	 * (e.g. "my-custom-gpt" -> "o200k_base")
+	 */
+	static setEncodingOverride(
+		modelId: string,
+		encodingName: TiktokenEncoding,
+	): void {
+		encodingOverrides.set(modelId, encodingName);
+	}
+
+	/**
+	 * Remove all encoding overrides
+	 */
+	static clearEncodingOverrides(): void {
+		encodingOverrides.clear();
+	}
+
+	/**
+	 * Get the resolved tiktoken encoding name (useful for debugging counts)
+	 */
+	getEncodingName(): TiktokenEncoding {
+		return this.encodingName;
 	}
 
 	encode(text: string): number {
@@ -37,11 +97,19 @@ export class OpenAITokenizer implements Tokenizer {
 		const content = message.content || '';
 		const role = message.role || '';
 
-		// OpenAI format: each message has overhead for role markers
-		// <|im_start|>role\ncontent<|im_end|>
-		const messageOverhead = 4; // Approximate overhead per message
+		return (
+			this.encode(content) + this.encode(role) + this.getMessageOverhead()
+		);
+	}
+
+	getMessageOverhead(): number {
+		// ChatML framing per message: <|im_start|>role\ncontent<|im_end|>\n
+		return 4;
+	}
 
-		return this.encode(content) + this.encode(role) + messageOverhead;
+	getRequestOverhead(): number {
+		// Every reply is primed with <|im_start|>assistant<|im_sep|>
+		return 3;
 	}
 
 	getName(): string {
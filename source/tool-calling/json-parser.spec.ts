@@ -637,3 +637,29 @@ test('parseJSONToolCalls: handles null in arguments', t => {
 	t.is(calls.length, 1);
 	t.deepEqual(calls[0].function.arguments, {optionalValue: null});
 });
+
+test('parseJSONToolCalls: treats empty string arguments as empty object', t => {
+	const calls = parseJSONToolCalls('{"name": "get_status", "arguments": ""}');
+
+	t.is(calls.length, 1);
+	t.is(calls[0].function.name, 'get_status');
+	t.deepEqual(calls[0].function.arguments, {});
+});
+
+test('parseJSONToolCalls: treats whitespace-only arguments as empty object', t => {
+	const calls = parseJSONToolCalls('{"name": "get_status", "arguments": "  "}');
+
+	t.is(calls.length, 1);
+	t.deepEqual(calls[0].function.arguments, {});
+});
+
+test('detectMalformedJSONToolCall: allows empty string arguments', t => {
+	t.is(
+		detectMalformedJSONToolCall('{"name": "get_status", "arguments": ""}'),
+		null,
+	);
+	t.is(
+		detectMalformedJSONToolCall('{"name": "get_status", "arguments": "  "}'),
+		null,
+	);
+});
@@ -27,8 +27,10 @@ export function detectMalformedJSONToolCall(
 			hint: 'Tool calls must include both "name" and "arguments" fields',
 		},
 		{
-			// Malformed arguments (not an object)
-			regex: /\{\s*"name"\s*:\s*"[^"]+"\s*,\s*"arguments"\s*:\s*"[^"]*"\s*\}/,
+			// Malformed arguments (not an object). Empty strings are allowed and
+			// treated as no arguments.
+			regex:
+				/\{\s*"name"\s*:\s*"[^"]+"\s*,\s*"arguments"\s*:\s*"[^"]*[^"\s][^"]*"\s*\}/,
 			error: 'Invalid tool call: "arguments" must be an object, not a string',
 			hint: 'Use {"name": "tool_name", "arguments": {...}} format',
 		},
@@ -54,6 +56,17 @@ function getCorrectJSONFormatExamples(_specificHint: string): string {
 	return `Please use the native tool calling format provided by the system. The tools are already available to you - call them directly using the function calling interface.`;
 }
 
+/**
+ * Normalizes parsed tool call arguments. Some models emit `"arguments": ""`
+ * for tools that take no parameters, which is treated as an empty object.
+ */
+function normalizeArguments(args: unknown): Record<string, unknown> {
+	if (typeof args === 'string' && args.trim() === '') {
+		return {};
+	}
+	return (args || {}) as Record<string, unknown>;
+}
+
 /**
  * Parses JSON-formatted tool calls from content
  * This is an internal function - use tool-parser.ts for public API
@@ -88,7 +101,7 @@ export function parseJSONToolCalls(content: string): ToolCall[] {
 					id: `call_${Date.now()}`,
 					function: {
 						name: parsed.name || '',
-						arguments: parsed.arguments || {},
+						arguments: normalizeArguments(parsed.arguments),
 					},
 				};
 				extractedCalls.push(toolCall);
@@ -114,7 +127,7 @@ export function parseJSONToolCalls(content: string): ToolCall[] {
 					id: `call_${Date.now()}_${extractedCalls.length}`,
 					function: {
 						name: parsed.name || '',
-						arguments: parsed.arguments || {},
+						arguments: normalizeArguments(parsed.arguments),
 					},
 				};
 				extractedCalls.push(toolCall);
@@ -146,7 +159,7 @@ export function parseJSONToolCalls(content: string): ToolCall[] {
 					id: `call_${Date.now()}_${extractedCalls.length}`,
 					function: {
 						name: name || '',
-						arguments: args as Record<string, unknown>,
+						arguments: normalizeArguments(args),
 					},
 				});
 			} catch {
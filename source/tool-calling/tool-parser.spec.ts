@@ -24,6 +24,24 @@ test('parseToolCalls: successfully parses valid XML tool call', t => {
 	}
 });
 
+test('parseToolCalls: parses a tool call named in a tag attribute', t => {
+	const content =
+		'Checking the file.\n<tool_call name="read_file">{"path": "/path/to/file.txt"}</tool_call>';
+
+	const result = parseToolCalls(content);
+
+	t.true(result.success);
+	if (result.success) {
+		t.is(result.format, 'xml');
+		t.is(result.toolCalls.length, 1);
+		t.is(result.toolCalls[0].function.name, 'read_file');
+		t.deepEqual(result.toolCalls[0].function.arguments, {
+			path: '/path/to/file.txt',
+		});
+		t.is(result.cleanedContent, 'Checking the file.');
+	}
+});
+
 test('parseToolCalls: detects malformed XML with attribute syntax', t => {
 	const content = `
 <function=read_file>
@@ -393,3 +411,48 @@ The actual response.`;
 		t.regex(result.cleanedContent, /The actual response/);
 	}
 });
+
+// Format detection
+
+test('parseToolCalls: reports xml format', t => {
+	const result = parseToolCalls('<read_file>\n<path>a.txt</path>\n</read_file>');
+
+	t.true(result.success);
+	if (result.success) {
+		t.is(result.format, 'xml');
+	}
+});
+
+test('parseToolCalls: reports json format', t => {
+	const result = parseToolCalls(
+		'{"name": "read_file", "arguments": {"path": "a.txt"}}',
+	);
+
+	t.true(result.success);
+	if (result.success) {
+		t.is(result.format, 'json');
+	}
+});
+
+test('parseToolCalls: reports fenced-json format', t => {
+	const content = `\`\`\`json
+{"name": "read_file", "arguments": {"path": "a.txt"}}
+\`\`\``;
+
+	const result = parseToolCalls(content);
+
+	t.true(result.success);
+	if (result.success) {
+		t.is(result.toolCalls.length, 1);
+		t.is(result.format, 'fenced-json');
+	}
+});
+
+test('parseToolCalls: reports none when no tool calls found', t => {
+	const result = parseToolCalls('Just a plain answer.');
+
+	t.true(result.success);
+	if (result.success) {
+		t.is(result.format, 'none');
+	}
+});
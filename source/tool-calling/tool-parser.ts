@@ -38,6 +38,11 @@ function normalizeWhitespace(content: string): string {
 	);
 }
 
+/**
+ * Which content-embedded tool call format was detected
+ */
+type ToolCallFormat = 'xml' | 'json' | 'fenced-json' | 'none';
+
 /**
  * Result of parsing tool calls from content
  */
@@ -46,6 +51,7 @@ type ParseResult =
 			success: true;
 			toolCalls: ToolCall[];
 			cleanedContent: string;
+			format: ToolCallFormat;
 	  }
 	| {
 			success: false;
@@ -85,6 +91,7 @@ export function parseToolCalls(content: string): ParseResult {
 				success: true,
 				toolCalls: convertedCalls,
 				cleanedContent,
+				format: 'xml',
 			};
 		}
 	}
@@ -108,6 +115,9 @@ export function parseToolCalls(content: string): ParseResult {
 			success: true,
 			toolCalls: jsonCalls,
 			cleanedContent,
+			format: /```(?:json)?\s*\n?\s*\{/.test(strippedContent)
+				? 'fenced-json'
+				: 'json',
 		};
 	}
 
@@ -116,5 +126,6 @@ export function parseToolCalls(content: string): ParseResult {
 		success: true,
 		toolCalls: [],
 		cleanedContent: normalizeWhitespace(strippedContent),
+		format: 'none',
 	};
 }
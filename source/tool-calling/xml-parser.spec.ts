@@ -660,3 +660,64 @@ test('parseToolCalls - allows valid tool calls with HTML-like parameter content'
 	t.is(result[0].toolName, 'create_file');
 	t.truthy(result[0].parameters.content);
 });
+
+test('parseToolCalls - treats empty arguments wrapper as no arguments', t => {
+	const content = '<get_status><arguments></arguments></get_status>';
+
+	const calls = XMLToolCallParser.parseToolCalls(content);
+
+	t.is(calls.length, 1);
+	t.is(calls[0].toolName, 'get_status');
+	t.deepEqual(calls[0].parameters, {});
+});
+
+// Attribute-style calls and alternate wrapper tags
+test('parseToolCalls - parses a name attribute with JSON arguments', t => {
+	const content =
+		'<tool_call name="read_file">{"path": "/test/file.txt", "offset": 10}</tool_call>';
+	const result = XMLToolCallParser.parseToolCalls(content);
+
+	t.is(result.length, 1);
+	t.is(result[0].toolName, 'read_file');
+	t.deepEqual(result[0].parameters, {path: '/test/file.txt', offset: 10});
+});
+
+test('parseToolCalls - parses a name attribute with parameter tags or no body', t => {
+	const content = `<invoke name='read_file'><path>/a.txt</path></invoke>
+<invoke name="list_files"></invoke>`;
+	const result = XMLToolCallParser.parseToolCalls(content);
+
+	t.deepEqual(result, [
+		{toolName: 'read_file', parameters: {path: '/a.txt'}},
+		{toolName: 'list_files', parameters: {}},
+	]);
+});
+
+test('parseToolCalls - accepts alternate wrapper tag names', t => {
+	const content =
+		'<function_call><read_file><path>/test/file.txt</path></read_file></function_call>';
+	const result = XMLToolCallParser.parseToolCalls(content);
+
+	t.is(result.length, 1);
+	t.is(result[0].toolName, 'read_file');
+	t.deepEqual(result[0].parameters, {path: '/test/file.txt'});
+});
+
+test('parseToolCalls - only recognizes the configured wrapper tags', t => {
+	const content = '<call name="read_file">{"path": "/a.txt"}</call>';
+
+	t.deepEqual(XMLToolCallParser.parseToolCalls(content), []);
+	t.deepEqual(XMLToolCallParser.parseToolCalls(content, ['call']), [
+		{toolName: 'read_file', parameters: {path: '/a.txt'}},
+	]);
+});
+
+test('removeToolCallsFromContent - removes attribute-style calls', t => {
+	const content =
+		'Let me read it.\n<function_call name="read_file">{"path": "/a.txt"}</function_call>\nDone.';
+
+	t.is(
+		XMLToolCallParser.removeToolCallsFromContent(content),
+		'Let me read it.\n\nDone.',
+	);
+});
@@ -5,18 +5,69 @@ interface ParsedToolCall {
 	parameters: Record<string, unknown>;
 }
 
+/**
+ * Wrapper tags models put around tool calls
+ */
+export const DEFAULT_TOOL_CALL_TAGS = ['tool_call', 'function_call', 'invoke'];
+
 /**
  * Parses XML-formatted tool calls from non-function-calling models
- * Expected format: <tool_name><param1>value1</param1><param2>value2</param2></tool_name>
+ * Expected formats:
+ * - <tool_name><param1>value1</param1><param2>value2</param2></tool_name>,
+ *   optionally inside a wrapper tag such as <tool_call>
+ * - <tool_call name="tool_name">{"param1": "value1"}</tool_call>, with any
+ *   of the wrapper tags
  */
 export class XMLToolCallParser {
 	private static readonly TOOL_CALL_REGEX = /<(\w+)>(.*?)<\/\1>/gs;
 	private static readonly PARAMETER_REGEX = /<(\w+)>(.*?)<\/\1>/gs;
 
+	/**
+	 * Matches <tag name="tool">arguments</tag> for any of the given tags
+	 */
+	private static attributeCallRegex(tags: string[]): RegExp {
+		return new RegExp(
+			`<(${tags.join('|')})\\s+name\\s*=\\s*["']([\\w.-]+)["'][^>]*>([\\s\\S]*?)<\\/\\1>`,
+			'g',
+		);
+	}
+
+	/**
+	 * Matches bare opening and closing wrapper tags
+	 */
+	private static wrapperTagRegex(tags: string[]): RegExp {
+		return new RegExp(`<\\/?(?:${tags.join('|')})>`, 'g');
+	}
+
+	/**
+	 * Arguments of an attribute-style call: a JSON object, or parameter tags
+	 */
+	private static parseAttributeArguments(
+		body: string,
+	): Record<string, unknown> {
+		const trimmed = body.trim();
+		if (trimmed === '') {
+			return {};
+		}
+		try {
+			const parsed = JSON.parse(trimmed) as unknown;
+			if (parsed && typeof parsed === 'object' && !Array.isArray(parsed)) {
+				return parsed as Record<string, unknown>;
+			}
+		} catch {
+			// Not JSON, so read it as parameter tags below
+		}
+		return this.parseParameters(trimmed);
+	}
+
 	/**
 	 * Extracts tool calls from text content containing XML-formatted tool calls
+	 * @param tags - Wrapper tag names to recognize around tool calls
 	 */
-	static parseToolCalls(content: string): ParsedToolCall[] {
+	static parseToolCalls(
+		content: string,
+		tags: string[] = DEFAULT_TOOL_CALL_TAGS,
+	): ParsedToolCall[] {
 		const toolCalls: ParsedToolCall[] = [];
 		let match;
 
@@ -27,16 +78,30 @@ export class XMLToolCallParser {
 			processedContent = codeBlockMatch[1].trim();
 		}
 
+		// Take out calls that name the tool in an attribute
+		processedContent = processedContent.replace(
+			this.attributeCallRegex(tags),
+			(_match, _tag: string, toolName: string, body: string) => {
+				toolCalls.push({
+					toolName,
+					parameters: this.parseAttributeArguments(body),
+				});
+				return '';
+			},
+		);
+
 		// Remove <tool_call> wrapper tags if present
-		processedContent = processedContent.replace(/<\/?tool_call>/g, '').trim();
+		processedContent = processedContent
+			.replace(this.wrapperTagRegex(tags), '')
+			.trim();
 
 		// Find all tool call blocks
 		this.TOOL_CALL_REGEX.lastIndex = 0; // Reset regex state
 		while ((match = this.TOOL_CALL_REGEX.exec(processedContent)) !== null) {
 			const [fullMatch, toolName, innerXml] = match;
 
-			// Skip if this is a generic "tool_call" tag that slipped through
-			if (toolName === 'tool_call') {
+			// Skip if this is a generic wrapper tag that slipped through
+			if (tags.includes(toolName)) {
 				continue;
 			}
 
@@ -149,6 +214,11 @@ export class XMLToolCallParser {
 			// Trim whitespace from parameter value
 			const trimmedValue = paramValue.trim();
 
+			// An empty <arguments></arguments> wrapper means no arguments
+			if (paramName === 'arguments' && trimmedValue === '') {
+				continue;
+			}
+
 			// Try to parse as JSON for complex objects/arrays
 			try {
 				parameters[paramName] = JSON.parse(trimmedValue) as unknown;
@@ -176,8 +246,12 @@ export class XMLToolCallParser {
 
 	/**
 	 * Removes XML tool call blocks from content, leaving only the text
+	 * @param tags - Wrapper tag names to recognize around tool calls
 	 */
-	static removeToolCallsFromContent(content: string): string {
+	static removeToolCallsFromContent(
+		content: string,
+		tags: string[] = DEFAULT_TOOL_CALL_TAGS,
+	): string {
 		let cleanedContent = content;
 
 		// Remove all markdown code blocks that contain XML tool calls (using global flag)
@@ -187,7 +261,10 @@ export class XMLToolCallParser {
 				if (blockContent) {
 					// Reset regex and check if this block contains XML tool calls
 					this.TOOL_CALL_REGEX.lastIndex = 0;
-					if (this.TOOL_CALL_REGEX.test(blockContent)) {
+					if (
+						this.TOOL_CALL_REGEX.test(blockContent) ||
+						this.attributeCallRegex(tags).test(blockContent)
+					) {
 						// This code block contains XML tool calls, remove it entirely
 						return '';
 					}
@@ -198,11 +275,12 @@ export class XMLToolCallParser {
 		);
 
 		// Remove XML tool calls that aren't in code blocks
+		cleanedContent = cleanedContent.replace(this.attributeCallRegex(tags), '');
 		this.TOOL_CALL_REGEX.lastIndex = 0;
 		cleanedContent = cleanedContent.replace(this.TOOL_CALL_REGEX, '');
 
 		// Remove any <tool_call> wrapper tags that may be left behind
-		cleanedContent = cleanedContent.replace(/<\/?tool_call>/g, '');
+		cleanedContent = cleanedContent.replace(this.wrapperTagRegex(tags), '');
 
 		// Clean up whitespace artifacts left by removed tool calls
 		cleanedContent = cleanedContent
@@ -222,9 +300,12 @@ export class XMLToolCallParser {
 	/**
 	 * Checks if content contains XML-formatted tool calls
 	 */
-	static hasToolCalls(content: string): boolean {
+	static hasToolCalls(
+		content: string,
+		tags: string[] = DEFAULT_TOOL_CALL_TAGS,
+	): boolean {
 		// Use parseToolCalls with validation to ensure we only detect valid tool calls
-		const toolCalls = this.parseToolCalls(content);
+		const toolCalls = this.parseToolCalls(content, tags);
 		return toolCalls.length > 0;
 	}
 
@@ -3,7 +3,7 @@ import {render} from 'ink-testing-library';
 import React from 'react';
 import {themes} from '../config/themes';
 import {ThemeContext} from '../hooks/useTheme';
-import {executeBashTool} from './execute-bash';
+import {executeBashTool, truncateOutput} from './execute-bash';
 
 // ============================================================================
 // Test Helpers
@@ -352,3 +352,172 @@ test('execute_bash handles whitespace-only output', async t => {
 	t.truthy(result);
 	t.is(typeof result, 'string');
 });
+
+// ============================================================================
+// Tests for execute_bash Tool Handler - Structured Output
+// ============================================================================
+
+test('execute_bash structured output returns JSON with exit code and streams', async t => {
+	const result = await executeBashTool.tool.execute!(
+		{command: 'echo "out"; echo "err" >&2; exit 3', structured: true},
+		{toolCallId: 'test', messages: []},
+	);
+
+	const parsed = JSON.parse(result);
+	t.deepEqual(parsed, {
+		exit_code: 3,
+		stdout: 'out\n',
+		stderr: 'err\n',
+		truncated: false,
+		cancelled: false,
+	});
+});
+
+test('execute_bash structured output flags truncation', async t => {
+	const result = await executeBashTool.tool.execute!(
+		{
+			command:
+				'seq 1 100 | while read i; do echo "This is a long line of text that repeats many times"; done',
+			structured: true,
+		},
+		{toolCallId: 'test', messages: []},
+	);
+
+	const parsed = JSON.parse(result);
+	t.is(parsed.exit_code, 0);
+	t.true(parsed.truncated);
+	t.is(parsed.stdout.length, 2000);
+});
+
+test('execute_bash defaults to plain text output', async t => {
+	const result = await executeBashTool.tool.execute!(
+		{command: 'echo "plain"'},
+		{toolCallId: 'test', messages: []},
+	);
+
+	t.throws(() => JSON.parse(result));
+	t.true(result.includes('plain'));
+});
+
+// ============================================================================
+// Tests for execute_bash - Truncation Strategies
+// ============================================================================
+
+// Numbered lines, so the kept boundaries are easy to identify
+const NUMBERED_OUTPUT = Array.from(
+	{length: 500},
+	(_, i) => `line ${String(i + 1).padStart(3, '0')}`,
+).join('\n');
+
+test('truncateOutput keeps output under the limit whole', t => {
+	for (const truncation of ['head', 'tail', 'middle'] as const) {
+		t.is(truncateOutput('short', truncation, 10), 'short');
+	}
+});
+
+test('truncateOutput head keeps the first characters', t => {
+	const result = truncateOutput(NUMBERED_OUTPUT, 'head', 100);
+
+	t.is(result, NUMBERED_OUTPUT.slice(0, 100));
+	t.true(result.startsWith('line 001'));
+});
+
+test('truncateOutput tail keeps the last characters', t => {
+	const result = truncateOutput(NUMBERED_OUTPUT, 'tail', 100);
+
+	t.is(result, NUMBERED_OUTPUT.slice(-100));
+	t.true(result.endsWith('line 500'));
+});
+
+test('truncateOutput middle keeps both ends around an omission marker', t => {
+	const result = truncateOutput(NUMBERED_OUTPUT, 'middle', 100);
+	const omitted = NUMBERED_OUTPUT.length - 100;
+
+	t.is(
+		result,
+		`${NUMBERED_OUTPUT.slice(0, 50)}\n[... ${omitted} bytes omitted ...]\n${NUMBERED_OUTPUT.slice(-50)}`,
+	);
+});
+
+test('execute_bash tail truncation keeps the end of the output and the exit code', async t => {
+	const result = await executeBashTool.tool.execute!(
+		{
+			command: 'seq 1 1000; exit 2',
+			truncate: 'tail',
+		},
+		{toolCallId: 'test', messages: []},
+	);
+
+	t.true(result.startsWith('EXIT_CODE: 2\n'));
+	t.true(result.includes('[Output truncated'));
+	t.true(result.endsWith('999\n1000\n'));
+	t.false(result.includes('\n1\n2\n'));
+});
+
+test('execute_bash middle truncation keeps both ends of the output', async t => {
+	const result = await executeBashTool.tool.execute!(
+		{command: 'seq 1 1000', truncate: 'middle'},
+		{toolCallId: 'test', messages: []},
+	);
+
+	t.true(result.includes('EXIT_CODE: 0\n1\n2\n3\n'));
+	t.regex(result, /\[\.\.\. \d+ bytes omitted \.\.\.\]/);
+	t.true(result.endsWith('999\n1000\n'));
+});
+
+test('execute_bash structured output applies the truncation strategy', async t => {
+	const result = await executeBashTool.tool.execute!(
+		{command: 'seq 1 1000', structured: true, truncate: 'tail'},
+		{toolCallId: 'test', messages: []},
+	);
+
+	const parsed = JSON.parse(result);
+	t.true(parsed.truncated);
+	t.is(parsed.stdout.length, 2000);
+	t.true(parsed.stdout.endsWith('999\n1000\n'));
+});
+
+test('execute_bash validator rejects an unknown truncation strategy', async t => {
+	const result = await executeBashTool.validator!({
+		command: 'echo hi',
+		truncate: 'sideways',
+	} as any);
+
+	t.false(result.valid);
+	if (!result.valid) {
+		t.true(result.error.includes('Use one of: head, tail, middle'));
+	}
+});
+
+// ============================================================================
+// Tests for execute_bash Tool Handler - Cancellation
+// ============================================================================
+
+test('execute_bash stops the command when the signal aborts', async t => {
+	const controller = new AbortController();
+	const started = Date.now();
+	setTimeout(() => controller.abort(), 100);
+
+	const result = await executeBashTool.tool.execute!(
+		{command: 'sleep 10', structured: true},
+		{toolCallId: 'test', messages: [], abortSignal: controller.signal},
+	);
+
+	t.true(Date.now() - started < 5000);
+	const parsed = JSON.parse(result);
+	t.true(parsed.cancelled);
+	t.is(parsed.error, 'Cancelled by user');
+	t.is(parsed.exit_code, null);
+});
+
+test('execute_bash cancels at once when the signal has already aborted', async t => {
+	const controller = new AbortController();
+	controller.abort();
+
+	const result = await executeBashTool.tool.execute!(
+		{command: 'sleep 10'},
+		{toolCallId: 'test', messages: [], abortSignal: controller.signal},
+	);
+
+	t.true(result.startsWith('Error: Cancelled by user'));
+});
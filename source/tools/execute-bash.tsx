@@ -4,7 +4,13 @@ import React from 'react';
 import BashProgress from '@/components/bash-progress';
 import {TRUNCATION_OUTPUT_LIMIT} from '@/constants';
 import {useTheme} from '@/hooks/useTheme';
-import {type BashExecutionState, bashExecutor} from '@/services/bash-executor';
+import {
+	type BashExecutionState,
+	bashExecutor,
+	isShellAvailable,
+	type ShellName,
+	SUPPORTED_SHELLS,
+} from '@/services/bash-executor';
 import type {NanocoderToolExport} from '@/types/core';
 import {jsonSchema, tool} from '@/types/core';
 
@@ -13,72 +19,181 @@ import {jsonSchema, tool} from '@/types/core';
  * This is the internal implementation used by both the tool and direct !command mode.
  *
  * @param command - The bash command to execute
+ * @param shell - Shell to run the command in (defaults to bash, else sh; cmd on Windows)
  * @returns Object containing executionId and promise for the result
  */
-export function executeBashCommand(command: string): {
+export function executeBashCommand(
+	command: string,
+	shell?: ShellName,
+): {
 	executionId: string;
 	promise: Promise<BashExecutionState>;
 } {
-	return bashExecutor.execute(command);
+	return bashExecutor.execute(command, shell);
 }
 
+export type OutputTruncation = 'head' | 'tail' | 'middle';
+
+export const OUTPUT_TRUNCATIONS: OutputTruncation[] = [
+	'head',
+	'tail',
+	'middle',
+];
+
+const TRUNCATION_NOTE =
+	'[Output truncated. Use more specific commands to see full output]';
+
 /**
- * Format bash execution result for LLM context
+ * Cut output down to limit characters: keep the start (head), the end (tail),
+ * or both ends around a marker counting the bytes left out (middle)
  */
-export function formatBashResultForLLM(result: BashExecutionState): string {
-	let fullOutput = '';
-	const exitCodeInfo =
-		result.exitCode !== null ? `EXIT_CODE: ${result.exitCode}\n` : '';
+export function truncateOutput(
+	output: string,
+	truncation: OutputTruncation = 'head',
+	limit = TRUNCATION_OUTPUT_LIMIT,
+): string {
+	if (output.length <= limit) {
+		return output;
+	}
 
-	if (result.stderr) {
-		fullOutput = `${exitCodeInfo}STDERR:\n${result.stderr}\nSTDOUT:\n${result.fullOutput}`;
-	} else {
-		fullOutput = `${exitCodeInfo}${result.fullOutput}`;
+	switch (truncation) {
+		case 'tail':
+			return output.slice(output.length - limit);
+		case 'middle': {
+			const headLength = Math.ceil(limit / 2);
+			const tailStart = output.length - (limit - headLength);
+			const omitted = Buffer.byteLength(output.slice(headLength, tailStart));
+			return `${output.slice(0, headLength)}\n[... ${omitted} bytes omitted ...]\n${output.slice(tailStart)}`;
+		}
+		default:
+			return output.slice(0, limit);
 	}
+}
+
+/**
+ * Format bash execution result for LLM context. The exit code and any error
+ * are always kept; the output is truncated with the given strategy.
+ */
+export function formatBashResultForLLM(
+	result: BashExecutionState,
+	truncation: OutputTruncation = 'head',
+): string {
+	let prefix =
+		result.exitCode !== null ? `EXIT_CODE: ${result.exitCode}\n` : '';
 
 	// Handle errors
 	if (result.error) {
-		fullOutput = `Error: ${result.error}\n${fullOutput}`;
+		prefix = `Error: ${result.error}\n${prefix}`;
 	}
 
+	const output = result.stderr
+		? `STDERR:\n${result.stderr}\nSTDOUT:\n${result.fullOutput}`
+		: result.fullOutput;
+
 	// Limit the context for LLM to prevent overwhelming the model
-	const llmContext =
-		fullOutput.length > TRUNCATION_OUTPUT_LIMIT
-			? fullOutput.substring(0, TRUNCATION_OUTPUT_LIMIT) +
-				'\n... [Output truncated. Use more specific commands to see full output]'
-			: fullOutput;
+	if (output.length <= TRUNCATION_OUTPUT_LIMIT) {
+		return `${prefix}${output}`;
+	}
 
-	return llmContext;
+	const truncated = truncateOutput(output, truncation);
+	switch (truncation) {
+		case 'tail':
+			return `${prefix}... ${TRUNCATION_NOTE}\n${truncated}`;
+		case 'middle':
+			return `${prefix}${truncated}`;
+		default:
+			return `${prefix}${truncated}\n... ${TRUNCATION_NOTE}`;
+	}
+}
+
+/**
+ * Format bash execution result as JSON for callers that branch on the exit
+ * code instead of parsing the human-readable form
+ */
+export function formatBashResultAsJSON(
+	result: BashExecutionState,
+	truncation: OutputTruncation = 'head',
+): string {
+	return JSON.stringify({
+		exit_code: result.exitCode,
+		stdout: truncateOutput(result.fullOutput, truncation),
+		stderr: truncateOutput(result.stderr, truncation),
+		truncated:
+			result.fullOutput.length > TRUNCATION_OUTPUT_LIMIT ||
+			result.stderr.length > TRUNCATION_OUTPUT_LIMIT,
+		cancelled: result.cancelled ?? false,
+		...(result.error ? {error: result.error} : {}),
+	});
+}
+
+interface ExecuteBashArgs {
+	command: string;
+	structured?: boolean;
+	shell?: ShellName;
+	truncate?: OutputTruncation;
 }
 
 /**
  * Tool execute function - called by the tool system
  * Note: For streaming tools, the tool handler will use executeBashCommand directly
- * and this function serves as a fallback/compatibility layer
+ * and this function serves as a fallback/compatibility layer.
+ * Aborting the signal kills the command and returns a cancelled result.
  */
-const executeExecuteBash = async (args: {command: string}): Promise<string> => {
-	const {promise} = bashExecutor.execute(args.command);
-	const result = await promise;
-	return formatBashResultForLLM(result);
+const executeExecuteBash = async (
+	args: ExecuteBashArgs,
+	signal?: AbortSignal,
+): Promise<string> => {
+	const {executionId, promise} = bashExecutor.execute(args.command, args.shell);
+	const cancel = () => bashExecutor.cancel(executionId);
+	if (signal?.aborted) {
+		cancel();
+	}
+	signal?.addEventListener('abort', cancel, {once: true});
+
+	let result: BashExecutionState;
+	try {
+		result = await promise;
+	} finally {
+		signal?.removeEventListener('abort', cancel);
+	}
+	return args.structured
+		? formatBashResultAsJSON(result, args.truncate)
+		: formatBashResultForLLM(result, args.truncate);
 };
 
 const executeBashCoreTool = tool({
 	description:
 		'Execute a bash command and return the output (use for running commands)',
-	inputSchema: jsonSchema<{command: string}>({
+	inputSchema: jsonSchema<ExecuteBashArgs>({
 		type: 'object',
 		properties: {
 			command: {
 				type: 'string',
 				description: 'The bash command to execute.',
 			},
+			shell: {
+				type: 'string',
+				enum: SUPPORTED_SHELLS,
+				description:
+					'Shell to run the command in (default: bash if available, else sh; cmd on Windows).',
+			},
+			structured: {
+				type: 'boolean',
+				description:
+					'Return a JSON object with exit_code, stdout, stderr, truncated and cancelled fields instead of plain text (default: false).',
+			},
+			truncate: {
+				type: 'string',
+				enum: OUTPUT_TRUNCATIONS,
+				description: `Which part of long output to keep when it exceeds ${TRUNCATION_OUTPUT_LIMIT} characters: "head" keeps the start (default), "tail" keeps the end, "middle" keeps both ends. Use "tail" or "middle" for builds and test runs, where errors and summaries come last.`,
+			},
 		},
 		required: ['command'],
 	}),
 	// High risk: bash commands always require approval in all modes
 	needsApproval: true,
-	execute: async (args, _options) => {
-		return await executeExecuteBash(args);
+	execute: async (args, options) => {
+		return await executeExecuteBash(args, options?.abortSignal);
 	},
 });
 
@@ -124,9 +239,9 @@ const executeBashStreamingFormatter = (
 	return <BashProgress executionId={executionId} command={args.command} />;
 };
 
-const executeBashValidator = (args: {
-	command: string;
-}): Promise<{valid: true} | {valid: false; error: string}> => {
+const executeBashValidator = (
+	args: ExecuteBashArgs,
+): Promise<{valid: true} | {valid: false; error: string}> => {
 	const command = args.command?.trim();
 
 	// Check if command is empty
@@ -156,12 +271,35 @@ const executeBashValidator = (args: {
 		}
 	}
 
+	if (args.truncate && !OUTPUT_TRUNCATIONS.includes(args.truncate)) {
+		return Promise.resolve({
+			valid: false,
+			error: `⚒ Unknown truncate "${args.truncate}". Use one of: ${OUTPUT_TRUNCATIONS.join(', ')}`,
+		});
+	}
+
+	if (args.shell) {
+		if (!SUPPORTED_SHELLS.includes(args.shell)) {
+			return Promise.resolve({
+				valid: false,
+				error: `⚒ Unsupported shell "${args.shell}". Supported shells: ${SUPPORTED_SHELLS.join(', ')}`,
+			});
+		}
+		if (!isShellAvailable(args.shell)) {
+			return Promise.resolve({
+				valid: false,
+				error: `⚒ Shell "${args.shell}" was not found on this system`,
+			});
+		}
+	}
+
 	return Promise.resolve({valid: true});
 };
 
 export const executeBashTool: NanocoderToolExport = {
 	name: 'execute_bash' as const,
 	tool: executeBashCoreTool,
+	category: 'shell',
 	formatter: executeBashFormatter,
 	streamingFormatter: executeBashStreamingFormatter,
 	validator: executeBashValidator,
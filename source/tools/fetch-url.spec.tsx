@@ -1,3 +1,5 @@
+import {createServer, type Server} from 'node:http';
+import type {AddressInfo} from 'node:net';
 import test from 'ava';
 import {render} from 'ink-testing-library';
 import React from 'react';
@@ -19,12 +21,22 @@ if (typeof File === 'undefined') {
 
 // Dynamically import to avoid loading undici in test environment
 let fetchUrlTool: any;
+let buildFetchHeaders: any;
+let formatFetchedContent: any;
+let readResponseText: any;
+let fetchWithPolicy: any;
+let appConfig: any;
 
 test.before(async () => {
 	// Only import when we need it, and handle the case where undici might not work
 	try {
 		const module = await import('./fetch-url.js');
 		fetchUrlTool = module.fetchUrlTool;
+		buildFetchHeaders = module.buildFetchHeaders;
+		formatFetchedContent = module.formatFetchedContent;
+		readResponseText = module.readResponseText;
+		fetchWithPolicy = (await import('../utils/url-policy.js')).fetchWithPolicy;
+		appConfig = (await import('../config/index.js')).appConfig;
 	} catch (error) {
 		// If undici fails to load (e.g., in CI), we'll skip handler tests
 		console.warn('Failed to load fetch-url module:', error);
@@ -339,3 +351,414 @@ test('formatter calculates token estimate correctly', t => {
 	t.truthy(output);
 	t.regex(output!, /~25 tokens/);
 });
+
+// ============================================================================
+// Request headers
+// ============================================================================
+
+test.serial('buildFetchHeaders sends a default user agent', t => {
+	if (!buildFetchHeaders) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const headers = buildFetchHeaders(new URL('https://example.com/'));
+
+	t.regex(headers['user-agent'], /Nanocoder/);
+	t.truthy(headers.accept);
+});
+
+test.serial('buildFetchHeaders layers config and per-call headers', t => {
+	if (!buildFetchHeaders) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const previous = appConfig.fetchUrl;
+	appConfig.fetchUrl = {
+		userAgent: 'ConfiguredAgent/2.0',
+		headers: {
+			'example.com': {'X-Team': 'platform', Authorization: 'Bearer config'},
+			'api.example.com': {'X-Team': 'api'},
+		},
+	};
+
+	try {
+		const headers = buildFetchHeaders(new URL('https://api.example.com/'), {
+			authorization: 'Bearer call',
+		});
+
+		t.is(headers['user-agent'], 'ConfiguredAgent/2.0');
+		// The more specific host wins
+		t.is(headers['x-team'], 'api');
+		t.is(headers.authorization, 'Bearer call');
+		t.false('Authorization' in headers);
+	} finally {
+		appConfig.fetchUrl = previous;
+	}
+});
+
+test.serial('custom headers are sent with the request', async t => {
+	if (!buildFetchHeaders) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	// Echo server returns the request headers as JSON
+	const server: Server = createServer((req, res) => {
+		res.writeHead(200, {'content-type': 'application/json'});
+		res.end(JSON.stringify(req.headers));
+	});
+	await new Promise<void>(resolve => server.listen(0, '127.0.0.1', resolve));
+	const {port} = server.address() as AddressInfo;
+
+	try {
+		const {response} = await fetchWithPolicy(
+			`http://127.0.0.1:${port}/`,
+			{allowedHosts: ['127.0.0.1']},
+			{
+				headers: buildFetchHeaders(new URL(`http://127.0.0.1:${port}/`), {
+					'User-Agent': 'CustomAgent/1.0',
+					'X-Api-Key': 'secret',
+				}),
+			},
+		);
+		const echoed = (await response.json()) as Record<string, string>;
+
+		t.is(echoed['user-agent'], 'CustomAgent/1.0');
+		t.is(echoed['x-api-key'], 'secret');
+	} finally {
+		server.close();
+	}
+});
+
+test.serial('configured headers only go to their host', async t => {
+	if (!fetchUrlTool) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	// Echo server returns the request headers as JSON
+	const server: Server = createServer((req, res) => {
+		res.writeHead(200, {'content-type': 'application/json'});
+		res.end(JSON.stringify(req.headers));
+	});
+	await new Promise<void>(resolve => server.listen(0, '127.0.0.1', resolve));
+	const {port} = server.address() as AddressInfo;
+	const previous = appConfig.fetchUrl;
+
+	try {
+		appConfig.fetchUrl = {
+			allowedHosts: ['127.0.0.1'],
+			headers: {'api.example.com': {'X-Api-Key': 'secret'}},
+		};
+		const offList = await fetchUrlTool.tool.execute(
+			{url: `http://127.0.0.1:${port}/`, format: 'raw'},
+			{toolCallId: 'test', messages: []},
+		);
+		t.false(offList.includes('secret'));
+
+		appConfig.fetchUrl = {
+			allowedHosts: ['127.0.0.1'],
+			headers: {'127.0.0.1': {'X-Api-Key': 'secret'}},
+		};
+		const listed = await fetchUrlTool.tool.execute(
+			{url: `http://127.0.0.1:${port}/`, format: 'raw'},
+			{toolCallId: 'test', messages: []},
+		);
+		t.true(listed.includes('"x-api-key":"secret"'));
+	} finally {
+		appConfig.fetchUrl = previous;
+		server.close();
+	}
+});
+
+test.serial('configured headers are dropped on a redirect to another origin', async t => {
+	if (!fetchUrlTool) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	// The echo server returns the request headers as JSON; the other server
+	// redirects to it
+	const echo: Server = createServer((req, res) => {
+		res.writeHead(200, {'content-type': 'application/json'});
+		res.end(JSON.stringify(req.headers));
+	});
+	await new Promise<void>(resolve => echo.listen(0, '127.0.0.1', resolve));
+	const echoPort = (echo.address() as AddressInfo).port;
+	const redirect: Server = createServer((_req, res) => {
+		res.writeHead(302, {location: `http://127.0.0.1:${echoPort}/`});
+		res.end();
+	});
+	await new Promise<void>(resolve => redirect.listen(0, '127.0.0.1', resolve));
+	const {port} = redirect.address() as AddressInfo;
+	const previous = appConfig.fetchUrl;
+	appConfig.fetchUrl = {
+		allowedHosts: ['127.0.0.1'],
+		headers: {'127.0.0.1': {'X-Api-Key': 'secret'}},
+	};
+
+	try {
+		const result = await fetchUrlTool.tool.execute(
+			{url: `http://127.0.0.1:${port}/`, format: 'raw'},
+			{toolCallId: 'test', messages: []},
+		);
+		t.true(result.includes(`"host":"127.0.0.1:${echoPort}"`));
+		t.false(result.includes('secret'));
+	} finally {
+		appConfig.fetchUrl = previous;
+		echo.close();
+		redirect.close();
+	}
+});
+
+test('formatter shows header names but not their values', t => {
+	if (!fetchUrlTool) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const component = fetchUrlTool.formatter!({
+		url: 'https://api.example.com',
+		headers: {Authorization: 'Bearer super-secret-token'},
+	});
+
+	const {lastFrame} = render(
+		<MockThemeProvider>{component}</MockThemeProvider>,
+	);
+
+	const output = lastFrame();
+	t.regex(output!, /Authorization/);
+	t.notRegex(output!, /super-secret-token/);
+});
+
+// ============================================================================
+// Output formats
+// ============================================================================
+
+const FORMAT_FIXTURE = `<html>
+<head><title>Signup</title><style>.x { color: red; }</style></head>
+<body>
+	<h1>Create an account</h1>
+	<p>Fill in the <strong>form</strong> below.</p>
+	<form data-endpoint="/api/signup"><input name="email" type="email"></form>
+	<script>trackPageView();</script>
+</body>
+</html>`;
+
+test('formatFetchedContent converts to markdown by default', async t => {
+	if (!formatFetchedContent) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const content = await formatFetchedContent(FORMAT_FIXTURE);
+
+	t.regex(content, /#\s*Create an account/);
+	t.false(content.includes('<h1>'));
+	t.false(content.includes('trackPageView'));
+});
+
+test('formatFetchedContent returns visible text for text format', async t => {
+	if (!formatFetchedContent) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const content = await formatFetchedContent(FORMAT_FIXTURE, {format: 'text'});
+
+	t.is(content, 'Create an account\nFill in the form below.');
+});
+
+test('formatFetchedContent returns the original HTML for raw format', async t => {
+	if (!formatFetchedContent) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const content = await formatFetchedContent(FORMAT_FIXTURE, {format: 'raw'});
+
+	t.is(content, FORMAT_FIXTURE);
+	t.true(content.includes('data-endpoint="/api/signup"'));
+});
+
+test('formatFetchedContent rejects unknown formats', async t => {
+	if (!formatFetchedContent) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	await t.throwsAsync(() => formatFetchedContent('<p>hi</p>', {format: 'pdf'}), {
+		message: /Unknown format "pdf"/,
+	});
+});
+
+test('validator rejects unknown formats', async t => {
+	if (!fetchUrlTool) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const result = await fetchUrlTool.validator!({
+		url: 'https://example.com',
+		format: 'xml',
+	});
+
+	t.false(result.valid);
+	if (!result.valid) {
+		t.true(result.error.includes('Use one of: markdown, text, raw'));
+	}
+});
+
+// ============================================================================
+// HTML size guard
+// ============================================================================
+
+test('readResponseText reads a page under the limit in full', async t => {
+	if (!readResponseText) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const {Response} = await import('undici');
+	const html = '<html><body><p>héllo</p></body></html>';
+
+	const result = await readResponseText(new Response(html), 1024);
+
+	t.deepEqual(result, {text: html, truncated: false});
+});
+
+test('readResponseText stops reading oversized HTML at the limit', async t => {
+	if (!readResponseText) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const {Response} = await import('undici');
+	const html = `<html><body>${'<div>spam</div>'.repeat(100_000)}</body></html>`;
+
+	const result = await readResponseText(new Response(html), 64 * 1024);
+
+	t.true(result.truncated);
+	t.is(result.text.length, 64 * 1024);
+	t.true(html.startsWith(result.text));
+});
+
+test('readResponseText keeps multi-byte characters whole', async t => {
+	if (!readResponseText) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const {Response} = await import('undici');
+	const bytes = new TextEncoder().encode('a€b€');
+	// '€' is three bytes; deliver it split across two chunks
+	const split = new ReadableStream<Uint8Array>({
+		start(controller) {
+			controller.enqueue(bytes.subarray(0, 2));
+			controller.enqueue(bytes.subarray(2));
+			controller.close();
+		},
+	});
+
+	const whole = await readResponseText(new Response(split), 1024);
+	// The cap falls inside the second '€'
+	const capped = await readResponseText(new Response(bytes), 6);
+
+	t.deepEqual(whole, {text: 'a€b€', truncated: false});
+	t.deepEqual(capped, {text: 'a€b', truncated: true});
+});
+
+// ============================================================================
+// Structured responses
+// ============================================================================
+
+// Serves one fixed body with the given content type from 127.0.0.1, which
+// the tool is allowed to reach for the duration of the run
+async function fetchFromLocalServer(
+	contentType: string,
+	body: string,
+	args: Record<string, unknown> = {},
+): Promise<string> {
+	const server: Server = createServer((_req, res) => {
+		res.writeHead(200, {'content-type': contentType});
+		res.end(body);
+	});
+	await new Promise<void>(resolve => server.listen(0, '127.0.0.1', resolve));
+	const {port} = server.address() as AddressInfo;
+	const previous = appConfig.fetchUrl;
+	appConfig.fetchUrl = {allowedHosts: ['127.0.0.1']};
+
+	try {
+		return await fetchUrlTool.tool.execute!(
+			{url: `http://127.0.0.1:${port}/`, ...args},
+			{toolCallId: 'test', messages: []},
+		);
+	} finally {
+		appConfig.fetchUrl = previous;
+		server.close();
+	}
+}
+
+test.serial('fetch_url pretty-prints a JSON API response', async t => {
+	if (!fetchUrlTool) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const body = JSON.stringify({user: {id: 7, tags: ['admin']}});
+
+	const content = await fetchFromLocalServer(
+		'application/json; charset=utf-8',
+		body,
+	);
+
+	t.is(content, JSON.stringify(JSON.parse(body), null, 2));
+});
+
+test.serial('fetch_url returns JSON unformatted for raw format', async t => {
+	if (!fetchUrlTool) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const body = JSON.stringify({ok: true});
+
+	const content = await fetchFromLocalServer('application/json', body, {
+		format: 'raw',
+	});
+
+	t.is(content, body);
+});
+
+test.serial('fetch_url pretty-prints an XML response', async t => {
+	if (!fetchUrlTool) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const content = await fetchFromLocalServer(
+		'application/xml',
+		'<?xml version="1.0"?><feed><entry><title>First</title><link href="/1"/></entry></feed>',
+	);
+
+	t.is(
+		content,
+		[
+			'<?xml version="1.0"?>',
+			'<feed>',
+			'  <entry>',
+			'    <title>First</title>',
+			'    <link href="/1"/>',
+			'  </entry>',
+			'</feed>',
+		].join('\n'),
+	);
+});
+
+test.serial('fetch_url keeps a plain-text response as-is', async t => {
+	if (!fetchUrlTool) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const body = '# not a heading\n<b>not markup</b>\n';
+
+	const content = await fetchFromLocalServer('text/plain', body);
+
+	t.is(content, body);
+});
+
+test.serial('fetch_url converts an HTML response to markdown', async t => {
+	if (!fetchUrlTool) {
+		t.pass('Skipping test - fetch-url module not available');
+		return;
+	}
+	const content = await fetchFromLocalServer('text/html', FORMAT_FIXTURE);
+
+	t.regex(content, /#\s*Create an account/);
+	t.false(content.includes('<h1>'));
+});
@@ -1,35 +1,309 @@
 import {convertToMarkdown} from '@nanocollective/get-md';
+import * as cheerio from 'cheerio';
 import {Box, Text} from 'ink';
 import React from 'react';
+import type {Response} from 'undici';
 
-import {MAX_URL_CONTENT_BYTES} from '@/constants';
+import {appConfig} from '@/config/index';
+import {
+	DEFAULT_FETCH_URL_USER_AGENT,
+	MAX_FETCH_HTML_BYTES,
+	MAX_URL_CONTENT_BYTES,
+	TIMEOUT_FETCH_URL_MS,
+} from '@/constants';
 import {useTheme} from '@/hooks/useTheme';
 import type {NanocoderToolExport} from '@/types/core';
 import {jsonSchema, tool} from '@/types/core';
+import {extractMainContent} from '@/utils/readability';
 import {calculateTokens} from '@/utils/token-calculator';
+import {
+	checkUrlAllowed,
+	fetchWithPolicy,
+	matchesHost,
+} from '@/utils/url-policy';
+
+export type FetchFormat = 'markdown' | 'text' | 'raw';
+
+const FETCH_FORMATS: readonly FetchFormat[] = ['markdown', 'text', 'raw'];
 
 interface FetchArgs {
 	url: string;
+	headers?: Record<string, string>;
+	readability?: boolean;
+	format?: FetchFormat;
+}
+
+// Elements that start a new line when a page is read as plain text
+const TEXT_BLOCK_SELECTOR =
+	'p, div, section, article, header, footer, li, tr, h1, h2, h3, h4, h5, h6, pre, blockquote';
+
+function checkFetchFormat(format: unknown): string | undefined {
+	if (format === undefined || FETCH_FORMATS.includes(format as FetchFormat)) {
+		return undefined;
+	}
+	return `Unknown format "${String(format)}". Use one of: ${FETCH_FORMATS.join(', ')}`;
+}
+
+function extractVisibleText(html: string): string {
+	const $ = cheerio.load(html);
+	$('script, style, noscript, template, head').remove();
+	$('br').replaceWith('\n');
+	$(TEXT_BLOCK_SELECTOR).append('\n');
+
+	return $.root()
+		.text()
+		.split('\n')
+		.map(line => line.replace(/\s+/g, ' ').trim())
+		.filter(line => line.length > 0)
+		.join('\n');
+}
+
+type BodyKind = 'html' | 'json' | 'xml' | 'text';
+
+/**
+ * Decides how to treat a response body from its Content-Type. Anything
+ * unrecognised, or a missing header, is handled as HTML.
+ */
+function getBodyKind(contentType = ''): BodyKind {
+	const mediaType = contentType.split(';')[0].trim().toLowerCase();
+	if (mediaType === 'application/json' || mediaType.endsWith('+json')) {
+		return 'json';
+	}
+	if (mediaType === 'application/xhtml+xml') {
+		return 'html';
+	}
+	if (
+		mediaType === 'application/xml' ||
+		mediaType === 'text/xml' ||
+		mediaType.endsWith('+xml')
+	) {
+		return 'xml';
+	}
+	return mediaType === 'text/plain' ? 'text' : 'html';
+}
+
+// A body cut off at the size limit won't parse, so it is returned as-is
+function prettyPrintJson(body: string): string {
+	try {
+		return JSON.stringify(JSON.parse(body), null, 2);
+	} catch {
+		return body;
+	}
+}
+
+// CDATA sections, comments, tags and the text between them
+const XML_TOKEN_PATTERN =
+	/<!\[CDATA\[[\s\S]*?\]\]>|<!--[\s\S]*?-->|<[^>]+>|[^<]+/g;
+
+/**
+ * Puts each XML element on its own line, indented by depth. An element that
+ * holds only text stays on one line.
+ */
+export function prettyPrintXml(body: string): string {
+	const tokens = body.match(XML_TOKEN_PATTERN) ?? [];
+	const lines: string[] = [];
+	let depth = 0;
+
+	const isOpening = (token: string) =>
+		/^<[^/?!]/.test(token) && !token.endsWith('/>');
+
+	for (let i = 0; i < tokens.length; i++) {
+		const token = tokens[i].trim();
+		if (!token) {
+			continue;
+		}
+		const indent = '  '.repeat(depth);
+
+		const text = tokens[i + 1]?.trim();
+		const closing = tokens[i + 2]?.trim();
+		if (
+			isOpening(token) &&
+			text &&
+			!text.startsWith('<') &&
+			closing?.startsWith('</')
+		) {
+			lines.push(`${indent}${token}${text}${closing}`);
+			i += 2;
+		} else if (token.startsWith('</')) {
+			depth = Math.max(0, depth - 1);
+			lines.push(`${'  '.repeat(depth)}${token}`);
+		} else {
+			lines.push(`${indent}${token}`);
+			if (isOpening(token)) {
+				depth++;
+			}
+		}
+	}
+
+	return lines.join('\n');
+}
+
+/**
+ * Reads a response body as text, stopping after maxBytes so a huge or
+ * endless page can't exhaust memory or stall parsing
+ */
+export async function readResponseText(
+	response: Response,
+	maxBytes = MAX_FETCH_HTML_BYTES,
+): Promise<{text: string; truncated: boolean}> {
+	if (!response.body) {
+		return {text: '', truncated: false};
+	}
+
+	const reader = response.body.getReader();
+	const decoder = new TextDecoder();
+	let text = '';
+	let bytes = 0;
+	try {
+		while (true) {
+			const {done, value} = await reader.read();
+			if (done) {
+				return {text: text + decoder.decode(), truncated: false};
+			}
+			const remaining = maxBytes - bytes;
+			if (value.byteLength > remaining) {
+				// Not flushed: a character cut off at the cap stays buffered in the
+				// decoder and is dropped, rather than decoded as U+FFFD
+				text += decoder.decode(value.subarray(0, remaining), {stream: true});
+				return {text, truncated: true};
+			}
+			bytes += value.byteLength;
+			text += decoder.decode(value, {stream: true});
+		}
+	} finally {
+		// Stop downloading whatever is left
+		void reader.cancel().catch(() => undefined);
+	}
+}
+
+/**
+ * Turns a fetched page into the requested format: markdown (default), the
+ * visible text only, or the HTML as received. With readability, the page is
+ * first reduced to its main content. JSON and XML responses are pretty-printed
+ * and plain text is kept as-is, unless the raw body was asked for.
+ */
+export async function formatFetchedContent(
+	html: string,
+	{
+		format = 'markdown',
+		readability = false,
+		contentType,
+	}: Omit<FetchArgs, 'url'> & {contentType?: string} = {},
+): Promise<string> {
+	const formatError = checkFetchFormat(format);
+	if (formatError) {
+		throw new Error(formatError);
+	}
+
+	const kind = getBodyKind(contentType);
+	if (kind !== 'html') {
+		if (format === 'raw' || kind === 'text') {
+			return html;
+		}
+		return kind === 'json' ? prettyPrintJson(html) : prettyPrintXml(html);
+	}
+
+	const source = readability ? extractMainContent(html) : html;
+	switch (format) {
+		case 'raw':
+			return source;
+		case 'text':
+			return extractVisibleText(source);
+		default:
+			return (await convertToMarkdown(source)).markdown;
+	}
+}
+
+/**
+ * Configured headers for a URL's host: those listed under the host itself
+ * or a parent domain, with more specific hosts winning
+ */
+function getConfiguredHeaders(url: URL): Record<string, string> {
+	const hosts = Object.entries(appConfig.fetchUrl?.headers ?? {})
+		.filter(([host]) => matchesHost(url.hostname, [host]))
+		.sort(([a], [b]) => a.length - b.length);
+
+	const headers: Record<string, string> = {};
+	for (const [, hostHeaders] of hosts) {
+		// Headers listed without a host would go to every site
+		if (typeof hostHeaders !== 'object' || hostHeaders === null) {
+			continue;
+		}
+		for (const [name, value] of Object.entries(hostHeaders)) {
+			headers[name.toLowerCase()] = value;
+		}
+	}
+	return headers;
+}
+
+/**
+ * Builds request headers for a URL: defaults, then the headers configured
+ * for its host, then per-call headers. Names are lower-cased so overrides
+ * replace rather than duplicate.
+ */
+export function buildFetchHeaders(
+	url: URL,
+	callHeaders: Record<string, string> = {},
+): Record<string, string> {
+	const {userAgent} = appConfig.fetchUrl ?? {};
+	const configHeaders = getConfiguredHeaders(url);
+	const merged: Record<string, string> = {
+		'user-agent': userAgent ?? DEFAULT_FETCH_URL_USER_AGENT,
+		accept: 'text/html, text/plain;q=0.9, */*;q=0.8',
+	};
+
+	for (const [name, value] of [
+		...Object.entries(configHeaders),
+		...Object.entries(callHeaders),
+	]) {
+		merged[name.toLowerCase()] = value;
+	}
+
+	return merged;
 }
 
 const executeFetchUrl = async (args: FetchArgs): Promise<string> => {
 	// Validate URL
+	let url: URL;
 	try {
-		new URL(args.url);
+		url = new URL(args.url);
 	} catch {
 		throw new Error(`Invalid URL: ${args.url}`);
 	}
 
 	try {
-		// Use get-md to convert URL to LLM-friendly markdown
-		const result = await convertToMarkdown(args.url);
+		// Fetch the page ourselves so every redirect hop is checked against the
+		// configured policy, then convert it to the requested format. Headers
+		// configured for this host are not sent on to another origin.
+		const {response} = await fetchWithPolicy(
+			args.url,
+			appConfig.fetchUrl ?? {},
+			{
+				headers: buildFetchHeaders(url, args.headers),
+				signal: AbortSignal.timeout(TIMEOUT_FETCH_URL_MS),
+			},
+			Object.keys(getConfiguredHeaders(url)),
+		);
 
-		const content = result.markdown;
+		if (!response.ok) {
+			throw new Error(`HTTP ${response.status}: ${response.statusText}`);
+		}
+
+		const {text: html, truncated: htmlTruncated} =
+			await readResponseText(response);
+		let content = await formatFetchedContent(html, {
+			...args,
+			contentType: response.headers.get('content-type') ?? undefined,
+		});
 
 		if (!content || content.length === 0) {
 			throw new Error('No content returned from URL');
 		}
 
+		if (htmlTruncated) {
+			content += `\n\n[Page truncated - only the first ${MAX_FETCH_HTML_BYTES} bytes were read]`;
+		}
+
 		// Limit content size to prevent context overflow
 		if (content.length > MAX_URL_CONTENT_BYTES) {
 			const truncated = content.substring(0, MAX_URL_CONTENT_BYTES);
@@ -52,6 +326,23 @@ const fetchUrlCoreTool = tool({
 				type: 'string',
 				description: 'The URL to fetch content from.',
 			},
+			headers: {
+				type: 'object',
+				additionalProperties: {type: 'string'},
+				description:
+					'Optional HTTP headers to send, e.g. an Authorization header for APIs that need one. Overrides configured headers with the same name.',
+			},
+			readability: {
+				type: 'boolean',
+				description:
+					'Extract only the main article content, dropping navigation, headers, footers, sidebars and ads. Use for documentation pages and blog posts. Default: false.',
+			},
+			format: {
+				type: 'string',
+				enum: [...FETCH_FORMATS],
+				description:
+					'Output format for HTML pages: "markdown" (default), "text" for the visible text only, or "raw" for the original HTML, e.g. to see form fields or data attributes that markdown drops. JSON and XML responses are pretty-printed and plain text is returned as-is unless "raw" is given. Size limits apply to every format.',
+			},
 		},
 		required: ['url'],
 	}),
@@ -64,9 +355,11 @@ const fetchUrlCoreTool = tool({
 
 function FetchUrlFormatterComponent({
 	url,
+	headerNames,
 	result,
 }: {
 	url: string;
+	headerNames: string[];
 	result?: string;
 }): React.ReactElement {
 	const {colors} = useTheme();
@@ -89,6 +382,13 @@ function FetchUrlFormatterComponent({
 					<Text color={colors.text}>{url}</Text>
 				</Box>
 			</Box>
+			{headerNames.length > 0 && (
+				<Box>
+					<Text color={colors.secondary}>Headers: </Text>
+					{/* Names only - values may hold credentials */}
+					<Text color={colors.text}>{headerNames.join(', ')}</Text>
+				</Box>
+			)}
 			{result && (
 				<>
 					<Box>
@@ -113,38 +413,32 @@ const fetchUrlFormatter = (
 	result?: string,
 ): React.ReactElement => {
 	return (
-		<FetchUrlFormatterComponent url={args.url || 'unknown'} result={result} />
+		<FetchUrlFormatterComponent
+			url={args.url || 'unknown'}
+			headerNames={Object.keys(args.headers ?? {})}
+			result={result}
+		/>
 	);
 };
 
 const fetchUrlValidator = (
 	args: FetchArgs,
 ): Promise<{valid: true} | {valid: false; error: string}> => {
+	const formatError = checkFetchFormat(args.format);
+	if (formatError) {
+		return Promise.resolve({valid: false, error: `⚒ ${formatError}`});
+	}
+
 	// Validate URL format
 	try {
 		const parsedUrl = new URL(args.url);
 
-		// Check for valid protocol
-		if (!['http:', 'https:'].includes(parsedUrl.protocol)) {
-			return Promise.resolve({
-				valid: false,
-				error: `Invalid URL protocol "${parsedUrl.protocol}". Only http: and https: are supported.`,
-			});
-		}
-
-		// Check for localhost/internal IPs (security consideration)
-		const hostname = parsedUrl.hostname.toLowerCase();
-		if (
-			hostname === 'localhost' ||
-			hostname === '127.0.0.1' ||
-			hostname === '0.0.0.0' ||
-			hostname.startsWith('192.168.') ||
-			hostname.startsWith('10.') ||
-			hostname.match(/^172\.(1[6-9]|2[0-9]|3[0-1])\./)
-		) {
+		// Check protocol, host lists and internal/private addresses
+		const refusal = checkUrlAllowed(parsedUrl, appConfig.fetchUrl ?? {});
+		if (refusal) {
 			return Promise.resolve({
 				valid: false,
-				error: `⚒ Cannot fetch from internal/private network address: ${hostname}`,
+				error: `⚒ ${refusal}`,
 			});
 		}
 
@@ -160,6 +454,7 @@ const fetchUrlValidator = (
 export const fetchUrlTool: NanocoderToolExport = {
 	name: 'fetch_url' as const,
 	tool: fetchUrlCoreTool,
+	category: 'web',
 	formatter: fetchUrlFormatter,
 	validator: fetchUrlValidator,
 };
@@ -1,4 +1,4 @@
-import {mkdirSync, rmSync, writeFileSync} from 'node:fs';
+import {mkdirSync, rmSync, symlinkSync, writeFileSync} from 'node:fs';
 import {join} from 'node:path';
 import test from 'ava';
 import {render} from 'ink-testing-library';
@@ -759,6 +759,59 @@ test.serial('find_files handles patterns with subdirectory prefix', async t => {
 	}
 });
 
+// A small Rust project: a root file, files directly in src and one nested
+async function findInRustProject(pattern: string): Promise<string> {
+	const testDir = join(process.cwd(), 'test-find-basename-temp');
+	const originalCwd = process.cwd();
+
+	try {
+		mkdirSync(join(testDir, 'src', 'nested'), {recursive: true});
+		mkdirSync(join(testDir, 'lib'), {recursive: true});
+		writeFileSync(join(testDir, 'build.rs'), 'fn main() {}');
+		writeFileSync(join(testDir, 'src', 'foo.rs'), 'fn foo() {}');
+		writeFileSync(join(testDir, 'src', 'nested', 'bar.rs'), 'fn bar() {}');
+		writeFileSync(join(testDir, 'lib', 'foo.rs'), 'fn foo() {}');
+
+		process.chdir(testDir);
+		return await findFilesTool.tool.execute!(
+			{pattern, maxResults: 50},
+			{toolCallId: 'test', messages: []},
+		);
+	} finally {
+		process.chdir(originalCwd);
+		rmSync(testDir, {recursive: true, force: true});
+	}
+}
+
+test.serial('find_files matches a pattern without a slash at any depth', async t => {
+	t.timeout(10000);
+	const result = await findInRustProject('*.rs');
+
+	t.true(result.includes('build.rs'));
+	t.true(result.includes('src/foo.rs'));
+	t.true(result.includes('src/nested/bar.rs'));
+	t.true(result.includes('lib/foo.rs'));
+});
+
+test.serial('find_files matches a pattern with a slash from the root', async t => {
+	t.timeout(10000);
+	const result = await findInRustProject('src/*.rs');
+
+	t.true(result.includes('src/foo.rs'));
+	t.false(result.includes('src/nested/bar.rs'));
+	t.false(result.includes('build.rs'));
+	t.false(result.includes('lib/foo.rs'));
+});
+
+test.serial('find_files finds an exact path with a slash', async t => {
+	t.timeout(10000);
+	const result = await findInRustProject('src/foo.rs');
+
+	t.true(result.includes('Found 1 match'));
+	t.true(result.includes('src/foo.rs'));
+	t.false(result.includes('lib/foo.rs'));
+});
+
 test.serial('find_files handles symlinks gracefully', async t => {
 	t.timeout(10000);
 	const testDir = join(process.cwd(), 'test-find-symlink-temp');
@@ -922,3 +975,109 @@ test.serial(
 		}
 	},
 );
+
+test.serial('find_files finds gitignored files only when respectGitignore is false', async t => {
+	t.timeout(10000);
+	const testDir = join(process.cwd(), 'test-find-gitignore-override-temp');
+
+	try {
+		mkdirSync(testDir, {recursive: true});
+		writeFileSync(join(testDir, '.gitignore'), 'secret.env\n');
+		writeFileSync(join(testDir, 'secret.env'), 'KEY=value');
+
+		const originalCwd = process.cwd();
+
+		try {
+			process.chdir(testDir);
+
+			const respected = await findFilesTool.tool.execute!(
+				{pattern: '*.env'},
+				{toolCallId: 'test', messages: []},
+			);
+			t.false(respected.includes('secret.env'));
+
+			const overridden = await findFilesTool.tool.execute!(
+				{pattern: '*.env', respectGitignore: false},
+				{toolCallId: 'test', messages: []},
+			);
+			t.true(overridden.includes('secret.env'));
+		} finally {
+			process.chdir(originalCwd);
+		}
+	} finally {
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
+
+test.serial('find_files excludes hidden files when includeHidden is false', async t => {
+	t.timeout(10000);
+	const testDir = join(process.cwd(), 'test-find-hidden-temp');
+
+	try {
+		mkdirSync(join(testDir, '.config'), {recursive: true});
+		writeFileSync(join(testDir, '.config', 'app.json'), '{}');
+		writeFileSync(join(testDir, '.eslintrc.json'), '{}');
+		writeFileSync(join(testDir, 'package.json'), '{}');
+
+		const originalCwd = process.cwd();
+
+		try {
+			process.chdir(testDir);
+
+			const withHidden = await findFilesTool.tool.execute!(
+				{pattern: '*.json'},
+				{toolCallId: 'test', messages: []},
+			);
+			t.true(withHidden.includes('.eslintrc.json'));
+			t.true(withHidden.includes('.config/app.json'));
+
+			const withoutHidden = await findFilesTool.tool.execute!(
+				{pattern: '*.json', includeHidden: false},
+				{toolCallId: 'test', messages: []},
+			);
+			t.false(withoutHidden.includes('.eslintrc.json'));
+			t.false(withoutHidden.includes('.config/app.json'));
+			t.true(withoutHidden.includes('package.json'));
+		} finally {
+			process.chdir(originalCwd);
+		}
+	} finally {
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
+
+test.serial('find_files follows symlinked directories only when followSymlinks is true', async t => {
+	t.timeout(10000);
+	const testDir = join(process.cwd(), 'test-find-follow-symlinks-temp');
+
+	try {
+		mkdirSync(join(testDir, 'shared'), {recursive: true});
+		mkdirSync(join(testDir, 'app'), {recursive: true});
+		writeFileSync(join(testDir, 'shared', 'util.ts'), 'content');
+		symlinkSync(join(testDir, 'shared'), join(testDir, 'app', 'linked'), 'dir');
+		// Loop back to the root to make sure cycles are handled
+		symlinkSync(testDir, join(testDir, 'app', 'loop'), 'dir');
+
+		const originalCwd = process.cwd();
+
+		try {
+			process.chdir(testDir);
+
+			const notFollowed = await findFilesTool.tool.execute!(
+				{pattern: 'app/**/*.ts'},
+				{toolCallId: 'test', messages: []},
+			);
+			t.false(notFollowed.includes('app/linked/util.ts'));
+
+			const followed = await findFilesTool.tool.execute!(
+				{pattern: 'app/**/*.ts', followSymlinks: true},
+				{toolCallId: 'test', messages: []},
+			);
+			t.true(followed.includes('app/linked/util.ts'));
+		} finally {
+			process.chdir(originalCwd);
+		}
+	} finally {
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
@@ -12,18 +12,58 @@ import {
 import {ThemeContext} from '@/hooks/useTheme';
 import type {NanocoderToolExport} from '@/types/core';
 import {jsonSchema, tool} from '@/types/core';
+import {getOverflowedStdout} from '@/utils/exec-output';
 import {DEFAULT_IGNORE_DIRS, loadGitignore} from '@/utils/gitignore-loader';
+import {formatLossyPathNote} from '@/utils/text-decoding';
 import {calculateTokens} from '@/utils/token-calculator';
 
 const execFileAsync = promisify(execFile);
 
 /**
- * Find files matching a glob pattern using find command
+ * Run find and return its output. find exits with code 1 on non-fatal errors
+ * (symlink loops, unreadable directories) but still prints every path it
+ * could walk, so that partial output is kept. On an enormous tree the output
+ * is cut off at BUFFER_FIND_FILES_BYTES rather than failing.
+ */
+async function runFind(findArgs: string[], cwd: string): Promise<string> {
+	try {
+		const {stdout} = await execFileAsync('find', findArgs, {
+			cwd,
+			maxBuffer: BUFFER_FIND_FILES_BYTES,
+		});
+		return stdout;
+	} catch (error: unknown) {
+		const overflowed = getOverflowedStdout(error);
+		if (overflowed !== undefined) {
+			return overflowed;
+		}
+		if (
+			error instanceof Error &&
+			'code' in error &&
+			error.code === 1 &&
+			'stdout' in error &&
+			typeof error.stdout === 'string'
+		) {
+			return error.stdout;
+		}
+		throw error;
+	}
+}
+
+/**
+ * Find files matching a glob pattern using find command. Like find -name, a
+ * pattern without a slash matches names at any depth; one with a slash is
+ * matched against the path from the project root.
  */
 async function findFilesByPattern(
 	pattern: string,
 	cwd: string,
 	maxResults: number,
+	options: {
+		respectGitignore: boolean;
+		includeHidden: boolean;
+		followSymlinks: boolean;
+	},
 ): Promise<{files: string[]; truncated: boolean}> {
 	try {
 		const ig = loadGitignore(cwd);
@@ -73,11 +113,13 @@ async function findFilesByPattern(
 			findArgs[0] = `./${dirPath}`;
 			// Only descend one level (maxdepth 1) to match the specific directory
 			findArgs.push('-maxdepth', '1', '-name', filePattern);
-		} else if (pattern.includes('*')) {
-			// Simple pattern like *.ts
-			findArgs.push('-name', pattern);
+		} else if (pattern.includes('/')) {
+			// Exact path like source/tools/index.ts, relative to the project root
+			const path = pattern.replace(/^\.\//, '').replace(/\/+$/, '');
+			findArgs.push('-path', `./${path}`);
 		} else {
-			// Exact path or directory name
+			// Pattern without a slash, like *.ts or package.json: matched against
+			// the name alone, at any depth
 			findArgs.push('-name', pattern);
 		}
 
@@ -88,11 +130,17 @@ async function findFilesByPattern(
 			findArgs.push('-not', '-path', exclusion);
 		}
 
-		// Execute find command with array-based arguments
-		const {stdout} = await execFileAsync('find', findArgs, {
+		// Exclude dotfiles and anything under a dot-directory
+		if (!options.includeHidden) {
+			findArgs.push('-not', '-path', '*/.*');
+		}
+
+		// Execute find command with array-based arguments. -L follows symlinks;
+		// find detects directory loops itself and skips them.
+		const stdout = await runFind(
+			options.followSymlinks ? ['-L', ...findArgs] : findArgs,
 			cwd,
-			maxBuffer: BUFFER_FIND_FILES_BYTES,
-		});
+		);
 
 		const allPaths = stdout
 			.trim()
@@ -104,7 +152,7 @@ async function findFilesByPattern(
 		// Filter using gitignore and limit results
 		const paths: string[] = [];
 		for (const path of allPaths) {
-			if (!ig.ignores(path)) {
+			if (!options.respectGitignore || !ig.ignores(path)) {
 				paths.push(path);
 
 				if (paths.length >= maxResults) {
@@ -128,6 +176,9 @@ async function findFilesByPattern(
 interface FindFilesArgs {
 	pattern: string;
 	maxResults?: number;
+	respectGitignore?: boolean;
+	includeHidden?: boolean;
+	followSymlinks?: boolean;
 }
 
 const executeFindFiles = async (args: FindFilesArgs): Promise<string> => {
@@ -142,6 +193,11 @@ const executeFindFiles = async (args: FindFilesArgs): Promise<string> => {
 			args.pattern,
 			cwd,
 			maxResults,
+			{
+				respectGitignore: args.respectGitignore ?? true,
+				includeHidden: args.includeHidden ?? true,
+				followSymlinks: args.followSymlinks ?? false,
+			},
 		);
 
 		if (files.length === 0) {
@@ -153,6 +209,11 @@ const executeFindFiles = async (args: FindFilesArgs): Promise<string> => {
 		}:\n\n`;
 		output += files.join('\n');
 
+		const lossyNote = formatLossyPathNote(files);
+		if (lossyNote) {
+			output += `\n\n${lossyNote}`;
+		}
+
 		return output;
 	} catch (error: unknown) {
 		const errorMessage =
@@ -163,20 +224,35 @@ const executeFindFiles = async (args: FindFilesArgs): Promise<string> => {
 
 const findFilesCoreTool = tool({
 	description:
-		'Find files and directories by path pattern. AUTO-ACCEPTED (no user approval needed). Use this INSTEAD OF bash find/locate/ls commands for file discovery. Examples: "*.tsx" (all .tsx files), "src/**/*.ts" (recursive in src/), "*.{ts,tsx}" (multiple extensions), "package.json" (exact file), "*config*" (files containing "config"), "source/tools/*.ts" (specific directory). Excludes node_modules, .git, dist, build automatically.',
+		'Find files and directories by path pattern. AUTO-ACCEPTED (no user approval needed). Use this INSTEAD OF bash find/locate/ls commands for file discovery. Examples: "*.tsx" (all .tsx files), "src/**/*.ts" (recursive in src/), "*.{ts,tsx}" (multiple extensions), "package.json" (exact file), "*config*" (files containing "config"), "source/tools/*.ts" (specific directory). A pattern without a slash matches file names at any depth, so "*.ts" also finds "src/a/b.ts"; a pattern with a slash matches the path from the project root, so "src/*.ts" only finds files directly in src/. Excludes node_modules, .git, dist, build automatically.',
 	inputSchema: jsonSchema<FindFilesArgs>({
 		type: 'object',
 		properties: {
 			pattern: {
 				type: 'string',
 				description:
-					'Glob pattern to match file and directory paths. Examples: "*.tsx" (all .tsx files), "src/**/*.ts" (recursive in src/), "*.{ts,tsx}" (multiple extensions), "package.json" (exact file), "*config*" (files containing "config"), "source/tools/*.ts" (specific directory)',
+					'Glob pattern to match file and directory paths. Without a slash it matches names at any depth; with one, the path from the project root. Examples: "*.tsx" (all .tsx files), "src/**/*.ts" (recursive in src/), "*.{ts,tsx}" (multiple extensions), "package.json" (exact file), "*config*" (files containing "config"), "source/tools/*.ts" (specific directory)',
 			},
 			maxResults: {
 				type: 'number',
 				description:
 					'Maximum number of results to return (default: 50, max: 100)',
 			},
+			respectGitignore: {
+				type: 'boolean',
+				description:
+					'Skip files matched by .gitignore (default: true). Set to false to find ignored files such as build artifacts or .env. node_modules, .git, dist and build are always excluded.',
+			},
+			includeHidden: {
+				type: 'boolean',
+				description:
+					'Include dotfiles and files in dot-directories (default: true)',
+			},
+			followSymlinks: {
+				type: 'boolean',
+				description:
+					'Descend into symlinked directories, e.g. shared packages in a monorepo (default: false)',
+			},
 		},
 		required: ['pattern'],
 	}),
@@ -256,5 +332,21 @@ const findFilesFormatter = (
 export const findFilesTool: NanocoderToolExport = {
 	name: 'find_files' as const,
 	tool: findFilesCoreTool,
+	category: 'search',
+	idempotent: true,
+	examples: [
+		{
+			description: 'All TypeScript files under src, at any depth',
+			arguments: {pattern: 'src/**/*.ts'},
+		},
+		{
+			description: 'Files with either extension, anywhere in the project',
+			arguments: {pattern: '*.{ts,tsx}'},
+		},
+		{
+			description: 'Ignored files such as .env, which are skipped by default',
+			arguments: {pattern: '.env*', respectGitignore: false},
+		},
+	],
 	formatter: findFilesFormatter,
 };
@@ -442,6 +442,7 @@ const validator = async (
 export const gitBranchSuggestTool: NanocoderToolExport = {
 	name: 'git_branch_suggest' as const,
 	tool: gitBranchSuggestCoreTool,
+	category: 'other',
 	formatter,
 	validator,
 };
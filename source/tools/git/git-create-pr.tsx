@@ -552,6 +552,7 @@ const validator = async (
 export const gitCreatePRTool: NanocoderToolExport = {
 	name: 'git_create_pr' as const,
 	tool: gitCreatePRCoreTool,
+	category: 'other',
 	formatter,
 	validator,
 };
@@ -432,6 +432,7 @@ const validator = async (
 export const gitSmartCommitTool: NanocoderToolExport = {
 	name: 'git_smart_commit' as const,
 	tool: gitSmartCommitCoreTool,
+	category: 'other',
 	formatter,
 	validator,
 };
@@ -398,6 +398,7 @@ const validator = async (
 export const gitStatusEnhancedTool: NanocoderToolExport = {
 	name: 'git_status_enhanced' as const,
 	tool: gitStatusEnhancedCoreTool,
+	category: 'other',
 	formatter,
 	validator,
 };
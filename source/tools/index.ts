@@ -19,6 +19,9 @@ import type {
 	AISDKCoreTool,
 	NanocoderToolExport,
 	StreamingFormatter,
+	ToolCategory,
+	ToolContext,
+	ToolExample,
 	ToolHandler,
 } from '@/types/index';
 
@@ -53,13 +56,15 @@ export const toolRegistry: Record<string, ToolHandler> = Object.fromEntries(
 		t.name,
 		// Extract the execute function from the AI SDK tool
 		// biome-ignore lint/suspicious/noExplicitAny: Dynamic typing required
-		async (args: any) => {
+		async (args: any, signal?: AbortSignal, context?: ToolContext) => {
 			// Call the tool's execute function with a dummy options object
 			// The actual options will be provided by AI SDK during automatic execution
 			// biome-ignore lint/suspicious/noExplicitAny: Dynamic typing required
 			return await (t.tool as any).execute(args, {
 				toolCallId: 'manual',
 				messages: [],
+				abortSignal: signal,
+				experimental_context: context,
 			});
 		},
 	]),
@@ -126,3 +131,18 @@ export const toolStreamingFormatters: Record<string, StreamingFormatter> =
 		},
 		{} as Record<string, StreamingFormatter>,
 	);
+
+// Export category registry for UI grouping and approval policy
+export const toolCategories: Record<string, ToolCategory> = Object.fromEntries(
+	allTools.map(t => [t.name, t.category ?? 'other']),
+);
+
+// Few-shot examples for the tools that have them
+export const toolExamples: Record<string, ToolExample[]> = Object.fromEntries(
+	allTools.filter(t => t.examples).map(t => [t.name, t.examples ?? []]),
+);
+
+// Names of the tools whose results may be cached
+export const idempotentTools: ReadonlySet<string> = new Set(
+	allTools.filter(t => t.idempotent).map(t => t.name),
+);
@@ -1,4 +1,4 @@
-import {mkdirSync, rmSync, writeFileSync} from 'node:fs';
+import {mkdirSync, rmSync, symlinkSync, writeFileSync} from 'node:fs';
 import {join} from 'node:path';
 import test from 'ava';
 import {render} from 'ink-testing-library';
@@ -580,3 +580,101 @@ test('list_directory tool has handler function', t => {
 test('list_directory tool has formatter function', t => {
 	t.is(typeof listDirectoryTool.formatter, 'function');
 });
+
+test.serial('list_directory caps entries on a deeply nested tree', async t => {
+	t.timeout(30000);
+	const originalCwd = process.cwd();
+	const testDir = join(originalCwd, 'test-listdir-cap-temp');
+
+	try {
+		// 10 levels of 120 files each is well over the entry cap
+		let levelDir = testDir;
+		for (let depth = 0; depth < 10; depth++) {
+			levelDir = join(levelDir, `level${depth}`);
+			mkdirSync(levelDir, {recursive: true});
+			for (let i = 0; i < 120; i++) {
+				writeFileSync(join(levelDir, `file${i}.txt`), '');
+			}
+		}
+
+		process.chdir(testDir);
+
+		const result = await listDirectoryTool.tool.execute!(
+			{recursive: true, maxDepth: 10, tree: true},
+			{toolCallId: 'test', messages: []},
+		);
+
+		t.true(result.includes('[Results truncated at 1000 entries'));
+		const listedPaths = result
+			.split('\n')
+			.filter(line => line.includes('level'));
+		t.is(listedPaths.length, 1000);
+	} finally {
+		process.chdir(originalCwd);
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
+
+test.serial('list_directory does not follow symlinked directories', async t => {
+	t.timeout(10000);
+	const originalCwd = process.cwd();
+	const testDir = join(originalCwd, 'test-listdir-symlink-loop-temp');
+
+	try {
+		mkdirSync(join(testDir, 'a'), {recursive: true});
+		writeFileSync(join(testDir, 'a', 'file.ts'), 'content');
+		// a/loop points back at its parent, a cycle if it were followed
+		symlinkSync(testDir, join(testDir, 'a', 'loop'));
+
+		process.chdir(testDir);
+
+		const result = await listDirectoryTool.tool.execute!(
+			{recursive: true, maxDepth: 10, tree: true},
+			{toolCallId: 'test', messages: []},
+		);
+
+		t.true(result.includes(join('a', 'loop')));
+		t.false(result.includes(join('a', 'loop', 'a')));
+		t.false(result.includes('Results truncated'));
+	} finally {
+		process.chdir(originalCwd);
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
+
+test.serial('list_directory notes names that are not valid UTF-8', async t => {
+	// Linux file systems accept any bytes in a name; macOS and Windows don't
+	if (process.platform !== 'linux') {
+		t.pass('Skipped on non-Linux platform');
+		return;
+	}
+
+	const originalCwd = process.cwd();
+	const testDir = join(originalCwd, 'test-listdir-non-utf8-temp');
+	// "bad\xff", a name with a byte that isn't valid UTF-8
+	const badName = Buffer.concat([
+		Buffer.from(`${testDir}/`),
+		Buffer.from([0x62, 0x61, 0x64, 0xff]),
+	]);
+
+	try {
+		mkdirSync(testDir, {recursive: true});
+		writeFileSync(join(testDir, 'good.txt'), 'content');
+		mkdirSync(badName);
+
+		process.chdir(testDir);
+
+		// Recursing must skip the directory it can't open rather than fail
+		const result = await listDirectoryTool.tool.execute!(
+			{recursive: true},
+			{toolCallId: 'test', messages: []},
+		);
+
+		t.true(result.includes('good.txt'));
+		t.true(result.includes('bad�'));
+		t.true(result.includes("[1 path contains bytes that aren't valid UTF-8"));
+	} finally {
+		process.chdir(originalCwd);
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
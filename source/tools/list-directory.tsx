@@ -4,11 +4,16 @@ import {Box, Text} from 'ink';
 import React from 'react';
 
 import ToolMessage from '@/components/tool-message';
+import {
+	MAX_LIST_DIRECTORY_DEPTH,
+	MAX_LIST_DIRECTORY_ENTRIES,
+} from '@/constants';
 import {ThemeContext} from '@/hooks/useTheme';
 import type {NanocoderToolExport} from '@/types/core';
 import {jsonSchema, tool} from '@/types/core';
 import {loadGitignore} from '@/utils/gitignore-loader';
 import {isValidFilePath, resolveFilePath} from '@/utils/path-validation';
+import {formatLossyPathNote, isLossyPath} from '@/utils/text-decoding';
 import {calculateTokens} from '@/utils/token-calculator';
 
 interface ListDirectoryArgs {
@@ -31,7 +36,10 @@ const executeListDirectory = async (
 ): Promise<string> => {
 	const dirPath = args.path || '.';
 	const recursive = args.recursive ?? false;
-	const maxDepth = args.maxDepth ?? 3;
+	const maxDepth = Math.min(
+		Math.max(args.maxDepth ?? 3, 1),
+		MAX_LIST_DIRECTORY_DEPTH,
+	);
 	const tree = args.tree ?? false;
 	const showHiddenFiles = args.showHiddenFiles ?? false;
 
@@ -48,7 +56,10 @@ const executeListDirectory = async (
 
 	try {
 		const entries: DirectoryEntry[] = [];
+		let truncated = false;
 
+		// Symlinks are listed but never followed, so the walk cannot loop; the
+		// entry cap bounds it on enormous trees
 		const walkDirectory = async (
 			currentPath: string,
 			relativeTo: string,
@@ -60,6 +71,11 @@ const executeListDirectory = async (
 				const items = await readdir(currentPath, {withFileTypes: true});
 
 				for (const item of items) {
+					if (entries.length >= MAX_LIST_DIRECTORY_ENTRIES) {
+						truncated = true;
+						return;
+					}
+
 					// Skip hidden files unless showHiddenFiles is true
 					if (
 						!showHiddenFiles &&
@@ -104,8 +120,14 @@ const executeListDirectory = async (
 						size,
 					});
 
-					// Recurse into directories if enabled
-					if (recursive && item.isDirectory() && depth < maxDepth) {
+					// Recurse into directories if enabled. A lossily decoded name
+					// doesn't name the directory on disk, so it can't be opened.
+					if (
+						recursive &&
+						item.isDirectory() &&
+						depth < maxDepth &&
+						!isLossyPath(item.name)
+					) {
 						await walkDirectory(fullPath, relativePath, depth + 1);
 					}
 				}
@@ -168,6 +190,17 @@ const executeListDirectory = async (
 			output += `\n[Tree format: flat paths]`;
 		}
 
+		const lossyNote = formatLossyPathNote(
+			entries.map(entry => entry.relativePath),
+		);
+		if (lossyNote) {
+			output += `\n${lossyNote}`;
+		}
+
+		if (truncated) {
+			output += `\n[Results truncated at ${MAX_LIST_DIRECTORY_ENTRIES} entries - list a subdirectory or lower maxDepth to see the rest]`;
+		}
+
 		return output;
 	} catch (error: unknown) {
 		if (error instanceof Error && 'code' in error && error.code === 'ENOENT') {
@@ -328,5 +361,7 @@ const listDirectoryFormatter = (
 export const listDirectoryTool: NanocoderToolExport = {
 	name: 'list_directory' as const,
 	tool: listDirectoryCoreTool,
+	category: 'file-read',
+	idempotent: true,
 	formatter: listDirectoryFormatter,
 };
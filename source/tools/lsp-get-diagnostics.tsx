@@ -320,6 +320,7 @@ const getDiagnosticsValidator = async (
 export const getDiagnosticsTool: NanocoderToolExport = {
 	name: 'lsp_get_diagnostics' as const,
 	tool: getDiagnosticsCoreTool,
+	category: 'other',
 	formatter: getDiagnosticsFormatter,
 	validator: getDiagnosticsValidator,
 };
@@ -3,8 +3,10 @@ import {join} from 'node:path';
 import test from 'ava';
 import {render} from 'ink-testing-library';
 import React from 'react';
+import {appConfig} from '../config/index';
 import {themes} from '../config/themes';
 import {ThemeContext} from '../hooks/useTheme';
+import {computeContentHash} from '../utils/content-hash';
 import {readFileTool} from './read-file';
 
 // ============================================================================
@@ -13,6 +15,10 @@ import {readFileTool} from './read-file';
 
 console.log(`\nread-file.spec.tsx – ${React.version}`);
 
+// Footer read_file adds after content, with the whole file's hash
+const hashNote = (fileContent: string) =>
+	`\n\n[Hash: ${computeContentHash(fileContent)} (pass as expected_hash when editing)]`;
+
 // Create a mock theme provider for tests
 function TestThemeProvider({children}: {children: React.ReactNode}) {
 	const themeContextValue = {
@@ -289,6 +295,59 @@ test.serial('read_file provides chunk suggestions for large files', async t => {
 	}
 });
 
+test.serial(
+	'read_file sizes chunks to a token budget with a model tokenizer',
+	async t => {
+		t.timeout(10000);
+		const testDir = join(process.cwd(), 'test-read-token-chunks-temp');
+		const firstChunkEnd = (result: string) =>
+			Number(/start_line: 1, end_line: (\d+)/.exec(result)?.[1]);
+
+		try {
+			mkdirSync(testDir, {recursive: true});
+			const dense = Array.from(
+				{length: 1000},
+				(_, i) =>
+					`const value${i} = compute(alpha${i}, beta${i}, gamma${i}, delta${i});`,
+			).join('\n');
+			writeFileSync(join(testDir, 'dense.ts'), dense);
+			writeFileSync(join(testDir, 'sparse.ts'), Array(1000).fill('}').join('\n'));
+
+			// The ToolManager passes the current model as the tool context
+			const options = {
+				toolCallId: 'test',
+				messages: [],
+				experimental_context: {model: {model: 'gpt-4', provider: ''}},
+			};
+			const denseResult = await readFileTool.tool.execute!(
+				{path: join(testDir, 'dense.ts')},
+				options,
+			);
+			const sparseResult = await readFileTool.tool.execute!(
+				{path: join(testDir, 'sparse.ts')},
+				options,
+			);
+
+			t.regex(denseResult, /Tokens: [\d,]+ \(openai-gpt-4 tokenizer\)/);
+			t.regex(denseResult, /chunks of up to ~4,000 tokens/);
+			// Long lines of code fill the budget in far fewer than 250 lines
+			t.true(firstChunkEnd(denseResult) < 250);
+			// A file of short lines fits the budget in one chunk
+			t.is(firstChunkEnd(sparseResult), 1000);
+
+			// Without a model, chunks go back to a fixed line count
+			const estimated = await readFileTool.tool.execute!(
+				{path: join(testDir, 'sparse.ts')},
+				{toolCallId: 'test', messages: []},
+			);
+			t.regex(estimated, /Estimated tokens: ~/);
+			t.is(firstChunkEnd(estimated), 250);
+		} finally {
+			rmSync(testDir, {recursive: true, force: true});
+		}
+	},
+);
+
 // ============================================================================
 // Tests for read_file Handler - Line Range Reading
 // ============================================================================
@@ -849,6 +908,61 @@ test.serial('read_file returns content without line numbers', async t => {
 	}
 });
 
+test.serial('read_file prefixes line numbers when line_numbers is true', async t => {
+	t.timeout(10000);
+	const testDir = join(process.cwd(), 'test-read-linenums-temp');
+
+	try {
+		mkdirSync(testDir, {recursive: true});
+		writeFileSync(join(testDir, 'test.ts'), 'line1\nline2\nline3\nline4');
+
+		const result = await readFileTool.tool.execute!(
+			{
+				path: join(testDir, 'test.ts'),
+				start_line: 2,
+				end_line: 3,
+				line_numbers: true,
+			},
+			{toolCallId: 'test', messages: []},
+		);
+
+		t.is(
+			result,
+			`   2→line2\n   3→line3${hashNote('line1\nline2\nline3\nline4')}`,
+		);
+	} finally {
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
+
+test.serial('read_file returns exact source range when line_numbers is false', async t => {
+	t.timeout(10000);
+	const testDir = join(process.cwd(), 'test-read-raw-range-temp');
+
+	try {
+		mkdirSync(testDir, {recursive: true});
+		const source = 'const a = 1;\n\tindented();\n  // comment →\nend';
+		writeFileSync(join(testDir, 'test.ts'), source);
+
+		const result = await readFileTool.tool.execute!(
+			{
+				path: join(testDir, 'test.ts'),
+				start_line: 2,
+				end_line: 3,
+				line_numbers: false,
+			},
+			{toolCallId: 'test', messages: []},
+		);
+
+		t.is(
+			result,
+			source.split('\n').slice(1, 3).join('\n') + hashNote(source),
+		);
+	} finally {
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
+
 // ============================================================================
 // Tests for read_file Tool Configuration
 // ============================================================================
@@ -906,6 +1020,29 @@ test.serial('read_file metadata_only returns file info without content', async t
 	}
 });
 
+test.serial('read_file metadata_only reports the content hash', async t => {
+	t.timeout(10000);
+	const testDir = join(process.cwd(), 'test-meta-hash');
+
+	try {
+		mkdirSync(testDir, {recursive: true});
+		const source = 'const x = 1;\nconst y = 2;';
+		writeFileSync(join(testDir, 'test.ts'), source);
+
+		const result = await readFileTool.tool.execute!(
+			{
+				path: join(testDir, 'test.ts'),
+				metadata_only: true,
+			},
+			{toolCallId: 'test', messages: []},
+		);
+
+		t.true(result.includes(`Hash: ${computeContentHash(source)}`));
+	} finally {
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
+
 test.serial('read_file metadata_only handles directories', async t => {
 	t.timeout(10000);
 	const testDir = join(process.cwd(), 'test-meta-dir');
@@ -952,3 +1089,144 @@ test.serial('read_file metadata_only shows last modified time', async t => {
 		rmSync(testDir, {recursive: true, force: true});
 	}
 });
+
+test.serial('read_file rejects non-UTF-8 files by default', async t => {
+	t.timeout(10000);
+	const testDir = join(process.cwd(), 'test-latin1-strict');
+
+	try {
+		mkdirSync(testDir, {recursive: true});
+		writeFileSync(
+			join(testDir, 'legacy.txt'),
+			Buffer.from('café\nnaïve\n', 'latin1'),
+		);
+
+		await t.throwsAsync(
+			readFileTool.tool.execute!(
+				{path: join(testDir, 'legacy.txt')},
+				{toolCallId: 'test', messages: []},
+			),
+			{message: /is not valid UTF-8.*lossy=true/},
+		);
+	} finally {
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
+
+test.serial('read_file with lossy replaces invalid bytes and adds a note', async t => {
+	t.timeout(10000);
+	const testDir = join(process.cwd(), 'test-latin1-lossy');
+
+	try {
+		mkdirSync(testDir, {recursive: true});
+		writeFileSync(
+			join(testDir, 'legacy.txt'),
+			Buffer.from('café\nnaïve\n', 'latin1'),
+		);
+
+		const result = await readFileTool.tool.execute!(
+			{path: join(testDir, 'legacy.txt'), lossy: true},
+			{toolCallId: 'test', messages: []},
+		);
+
+		t.true(result.startsWith('caf\uFFFD\nna\uFFFDve'));
+		t.regex(result, /\[Note: File is not valid UTF-8/);
+	} finally {
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
+
+test.serial('read_file metadata_only reports invalid encodings', async t => {
+	t.timeout(10000);
+	const testDir = join(process.cwd(), 'test-latin1-meta');
+
+	try {
+		mkdirSync(testDir, {recursive: true});
+		writeFileSync(join(testDir, 'legacy.txt'), Buffer.from('café\n', 'latin1'));
+
+		const result = await readFileTool.tool.execute!(
+			{path: join(testDir, 'legacy.txt'), metadata_only: true},
+			{toolCallId: 'test', messages: []},
+		);
+
+		t.regex(result, /Encoding: Not valid UTF-8/);
+	} finally {
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
+
+test.serial('read_file streams a narrow range from a very large file', async t => {
+	t.timeout(60000);
+	const testDir = join(process.cwd(), 'test-read-large-temp');
+
+	try {
+		mkdirSync(testDir, {recursive: true});
+		// ~12 MB, above the streaming threshold
+		const lineCount = 300_000;
+		const lines = Array.from(
+			{length: lineCount},
+			(_, i) => `log line ${i + 1} ${'-'.repeat(24)}`,
+		);
+		writeFileSync(join(testDir, 'big.log'), lines.join('\n'));
+
+		const result = await readFileTool.tool.execute!(
+			{
+				path: join(testDir, 'big.log'),
+				start_line: 150_000,
+				end_line: 150_002,
+				line_numbers: true,
+			},
+			{toolCallId: 'test', messages: []},
+		);
+
+		t.is(
+			result,
+			[150_000, 150_001, 150_002]
+				.map(n => `${n}→log line ${n} ${'-'.repeat(24)}`)
+				.join('\n') + hashNote(lines.join('\n')),
+		);
+
+		const metadata = await readFileTool.tool.execute!(
+			{path: join(testDir, 'big.log')},
+			{toolCallId: 'test', messages: []},
+		);
+
+		t.true(metadata.includes(`Total lines: ${lineCount.toLocaleString()}`));
+	} finally {
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
+
+test.serial('read_file returns only metadata for a file above maxFileBytes', async t => {
+	const testDir = join(process.cwd(), 'test-read-limit-temp');
+	const previous = appConfig.readFile;
+	appConfig.readFile = {maxFileBytes: 1024};
+
+	try {
+		mkdirSync(testDir, {recursive: true});
+		const lines = Array.from({length: 200}, (_, i) => `row ${i + 1}`);
+		writeFileSync(join(testDir, 'over-limit.txt'), lines.join('\n'));
+
+		const result = await readFileTool.tool.execute!(
+			{path: join(testDir, 'over-limit.txt')},
+			{toolCallId: 'test', messages: []},
+		);
+
+		t.regex(result, /File too large to read whole \(limit: 1,024 bytes\)/);
+		t.regex(result, /specify start_line\/end_line/);
+		// Scanned for metadata, not loaded
+		t.false(result.includes('row 1\n'));
+		t.true(result.includes('Total lines: 200'));
+		t.true(result.includes(`Hash: ${computeContentHash(lines.join('\n'))}`));
+
+		const range = await readFileTool.tool.execute!(
+			{path: join(testDir, 'over-limit.txt'), start_line: 199, end_line: 200},
+			{toolCallId: 'test', messages: []},
+		);
+
+		t.is(range, `row 199\nrow 200${hashNote(lines.join('\n'))}`);
+	} finally {
+		appConfig.readFile = previous;
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
@@ -1,30 +1,274 @@
 import {constants} from 'node:fs';
-import {access, lstat, readFile} from 'node:fs/promises';
+import {access, lstat, stat} from 'node:fs/promises';
 import {resolve} from 'node:path';
 import {Box, Text} from 'ink';
 import React from 'react';
 
 import ToolMessage from '@/components/tool-message';
+import {appConfig} from '@/config/index';
 import {
+	CHARS_PER_TOKEN_ESTIMATE,
 	FILE_READ_CHUNK_SIZE_LINES,
+	FILE_READ_CHUNK_TOKEN_BUDGET,
 	FILE_READ_CHUNKING_HINT_THRESHOLD_LINES,
 	FILE_READ_METADATA_THRESHOLD_LINES,
+	MAX_FILE_READ_BYTES,
 	MAX_LINE_LENGTH_CHARS,
 } from '@/constants';
 import {ThemeContext} from '@/hooks/useTheme';
-import type {NanocoderToolExport} from '@/types/core';
+import {createTokenizer} from '@/tokenization/index';
+import type {NanocoderToolExport, ToolContext} from '@/types/core';
 import {jsonSchema, tool} from '@/types/core';
+import {hashFile} from '@/utils/content-hash';
 import {getCachedFileContent} from '@/utils/file-cache';
+import {
+	type FileSummary,
+	getFileSummary,
+	readLineRange,
+	scanFile,
+	shouldStreamFile,
+} from '@/utils/file-streaming';
 import {getFileType} from '@/utils/file-type-detector';
 import {isValidFilePath, resolveFilePath} from '@/utils/path-validation';
 import {calculateTokens} from '@/utils/token-calculator';
 
-const executeReadFile = async (args: {
+interface ReadFileArgs {
 	path: string;
 	start_line?: number;
 	end_line?: number;
 	metadata_only?: boolean;
-}): Promise<string> => {
+	line_numbers?: boolean;
+	lossy?: boolean;
+}
+
+// Refuse to silently mangle legacy encodings such as Latin-1
+function getLossyNote(
+	args: ReadFileArgs,
+	file: {lossy: boolean; encoding: string},
+): string {
+	if (file.lossy && !args.lossy) {
+		throw new Error(
+			`File "${args.path}" is not valid ${file.encoding.toUpperCase()}. Call read_file again with lossy=true to replace invalid bytes with U+FFFD.`,
+		);
+	}
+	return file.lossy
+		? `\n\n[Note: File is not valid ${file.encoding.toUpperCase()} - invalid bytes were replaced with U+FFFD]`
+		: '';
+}
+
+interface LineRange {
+	start: number;
+	end: number;
+}
+
+interface LargeFileSummary {
+	totalLines: number;
+	size: number;
+	tokens: number;
+	hash: string;
+	chunks: LineRange[];
+	// Set when tokens were counted by the model's tokenizer, not estimated
+	tokenizer?: string;
+}
+
+/**
+ * Splits a file into ranges of whole lines that each fit the token budget,
+ * where a line over the budget on its own gets a range to itself
+ */
+function sizeChunksToBudget(lineTokens: number[], budget: number): LineRange[] {
+	const chunks: LineRange[] = [];
+	let start = 1;
+	let tokens = 0;
+
+	lineTokens.forEach((count, index) => {
+		if (tokens > 0 && tokens + count > budget) {
+			chunks.push({start, end: index});
+			start = index + 1;
+			tokens = 0;
+		}
+		tokens += count;
+	});
+	if (start <= lineTokens.length) {
+		chunks.push({start, end: lineTokens.length});
+	}
+	return chunks;
+}
+
+function fixedSizeChunks(totalLines: number): LineRange[] {
+	const chunks: LineRange[] = [];
+	for (
+		let start = 1;
+		start <= totalLines;
+		start += FILE_READ_CHUNK_SIZE_LINES
+	) {
+		chunks.push({
+			start,
+			end: Math.min(start + FILE_READ_CHUNK_SIZE_LINES - 1, totalLines),
+		});
+	}
+	return chunks;
+}
+
+/**
+ * Counts a cached file's tokens and plans its chunks. With the current model
+ * from the tool context, its tokenizer sizes chunks to
+ * FILE_READ_CHUNK_TOKEN_BUDGET; without one, tokens are estimated from
+ * characters and chunks are FILE_READ_CHUNK_SIZE_LINES long.
+ */
+function summarizeLines(
+	content: string,
+	lines: string[],
+	model: ToolContext['model'],
+): Pick<LargeFileSummary, 'tokens' | 'chunks' | 'tokenizer'> {
+	if (!model) {
+		return {
+			tokens: calculateTokens(content),
+			chunks: fixedSizeChunks(lines.length),
+		};
+	}
+
+	const tokenizer = createTokenizer(model.provider, model.model);
+	try {
+		const lineTokens = lines.map(line => tokenizer.encode(`${line}\n`));
+		return {
+			tokens: lineTokens.reduce((sum, count) => sum + count, 0),
+			chunks: sizeChunksToBudget(lineTokens, FILE_READ_CHUNK_TOKEN_BUDGET),
+			tokenizer: tokenizer.getName(),
+		};
+	} finally {
+		tokenizer.free?.();
+	}
+}
+
+function formatChunk(args: ReadFileArgs, chunk: LineRange): string {
+	return `read_file({path: "${args.path}", start_line: ${chunk.start}, end_line: ${chunk.end}})`;
+}
+
+function formatLargeFileMetadata(
+	args: ReadFileArgs,
+	absPath: string,
+	summary: LargeFileSummary,
+): string {
+	const {totalLines, chunks} = summary;
+	// Detect file type from extension
+	const fileType = getFileType(absPath);
+
+	let output = `File: ${args.path}\n`;
+	output += `Type: ${fileType}\n`;
+	output += `Total lines: ${totalLines.toLocaleString()}\n`;
+	output += `Size: ${summary.size.toLocaleString()} bytes\n`;
+	output += summary.tokenizer
+		? `Tokens: ${summary.tokens.toLocaleString()} (${summary.tokenizer} tokenizer)\n`
+		: `Estimated tokens: ~${summary.tokens.toLocaleString()}\n`;
+	output += `Hash: ${summary.hash} (pass as expected_hash when editing)\n\n`;
+
+	if (totalLines <= FILE_READ_CHUNKING_HINT_THRESHOLD_LINES) {
+		output += `[Medium file - To read specific sections, call read_file with start_line and end_line]\n`;
+		output += `[To read entire file progressively, make multiple calls:]\n`;
+		for (const chunk of chunks) {
+			output += `  - ${formatChunk(args, chunk)}\n`;
+		}
+	} else {
+		output += `[Large file - Choose one approach:]\n`;
+		output += `[1. Targeted read: Use search_files to find code, then read specific ranges]\n`;
+		output += summary.tokenizer
+			? `[2. Progressive read: Read file in chunks of up to ~${FILE_READ_CHUNK_TOKEN_BUDGET.toLocaleString()} tokens]\n`
+			: `[2. Progressive read: Read file in chunks (recommended chunk size: 200-300 lines)]\n`;
+		output += `   Example chunks for ${totalLines} lines:\n`;
+		for (const chunk of chunks.slice(0, 3)) {
+			output += `   - ${formatChunk(args, chunk)}\n`;
+		}
+		if (chunks.length > 3) {
+			output += `   ... and ${chunks.length - 3} more chunks to complete the file\n`;
+		}
+	}
+
+	return output;
+}
+
+function formatTooLargeFile(
+	args: ReadFileArgs,
+	absPath: string,
+	size: number,
+	maxFileBytes: number,
+	summary: FileSummary,
+): string {
+	const tokens = Math.ceil(summary.charCount / CHARS_PER_TOKEN_ESTIMATE);
+	let output = `File: ${args.path}\n`;
+	output += `Type: ${getFileType(absPath)}\n`;
+	output += `Total lines: ${summary.lineCount.toLocaleString()}\n`;
+	output += `Size: ${size.toLocaleString()} bytes\n`;
+	output += `Estimated tokens: ~${tokens.toLocaleString()}\n`;
+	output += `Hash: ${summary.hash} (pass as expected_hash when editing)\n\n`;
+	output += `[File too large to read whole (limit: ${maxFileBytes.toLocaleString()} bytes) - specify start_line/end_line to read a section]\n`;
+	output += `  - read_file({path: "${args.path}", start_line: 1, end_line: ${FILE_READ_CHUNK_SIZE_LINES}})\n`;
+	return output;
+}
+
+function formatLines(
+	args: ReadFileArgs,
+	linesToReturn: string[],
+	startLine: number,
+	hash: string,
+	lossyNote: string,
+): string {
+	// The hash of the whole file, which edit tools check as expected_hash
+	const hashNote = `\n\n[Hash: ${hash} (pass as expected_hash when editing)]`;
+
+	if (args.line_numbers) {
+		return (
+			linesToReturn
+				.map(
+					(line, index) => `${String(startLine + index).padStart(4)}→${line}`,
+				)
+				.join('\n') +
+			lossyNote +
+			hashNote
+		);
+	}
+
+	// Return content without line numbers for clean content-based editing
+	return linesToReturn.join('\n') + lossyNote + hashNote;
+}
+
+/**
+ * Reads a file too large for the cache. Ranged reads stream the file and stop
+ * at end_line; anything else gets metadata from a streaming scan, with a note
+ * to read by line range once it is over the readFile.maxFileBytes limit.
+ */
+async function readLargeFile(
+	args: ReadFileArgs,
+	absPath: string,
+	size: number,
+	maxFileBytes: number,
+): Promise<string> {
+	if (args.start_line === undefined && args.end_line === undefined) {
+		const summary = await scanFile(absPath);
+		if (size > maxFileBytes) {
+			return formatTooLargeFile(args, absPath, size, maxFileBytes, summary);
+		}
+		getLossyNote(args, summary);
+		// Too large to tokenize line by line, so chunks are a fixed size
+		return formatLargeFileMetadata(args, absPath, {
+			totalLines: summary.lineCount,
+			size,
+			tokens: Math.ceil(summary.charCount / CHARS_PER_TOKEN_ESTIMATE),
+			hash: summary.hash,
+			chunks: fixedSizeChunks(summary.lineCount),
+		});
+	}
+
+	const startLine = args.start_line ? Math.max(1, args.start_line) : 1;
+	const range = await readLineRange(absPath, startLine, args.end_line);
+	const lossyNote = getLossyNote(args, range);
+	const hash = await hashFile(absPath);
+	return formatLines(args, range.lines, startLine, hash, lossyNote);
+}
+
+const executeReadFile = async (
+	args: ReadFileArgs,
+	model?: ToolContext['model'],
+): Promise<string> => {
 	const absPath = resolve(args.path);
 
 	try {
@@ -56,26 +300,24 @@ const executeReadFile = async (args: {
 
 				// Try to detect encoding and line count
 				try {
-					const cached = await getCachedFileContent(absPath);
-					const lines = cached.lines;
-					const content = cached.content;
+					const summary = await getFileSummary(absPath);
 
-					output += `Lines: ${lines.length.toLocaleString()}\n`;
-					output += `Estimated Tokens: ~${calculateTokens(content).toLocaleString()}\n`;
+					const tokens = Math.ceil(
+						summary.charCount / CHARS_PER_TOKEN_ESTIMATE,
+					);
+					output += `Lines: ${summary.lineCount.toLocaleString()}\n`;
+					output += `Estimated Tokens: ~${tokens.toLocaleString()}\n`;
 
 					// Detect file type from extension
 					const fileType = getFileType(absPath);
 					output += `File Type: ${fileType}\n`;
 
-					// Detect likely encoding (simple heuristic)
-					let encoding = 'UTF-8';
-					try {
-						// Try to read as UTF-8
-						await readFile(absPath, 'utf-8');
-					} catch {
-						encoding = 'Binary/Unknown';
-					}
-					output += `Encoding: ${encoding}\n`;
+					// Encoding detected from the BOM while decoding
+					const encoding = summary.encoding.toUpperCase();
+					output += summary.lossy
+						? `Encoding: Not valid ${encoding} (use lossy=true to read)\n`
+						: `Encoding: ${encoding}\n`;
+					output += `Hash: ${summary.hash} (pass as expected_hash when editing)\n`;
 				} catch (error: unknown) {
 					// If we can't read it, mark as not readable
 					output += `Readable: no\n`;
@@ -94,8 +336,17 @@ const executeReadFile = async (args: {
 			return output;
 		}
 
+		// Very large files are streamed instead of loaded into the cache
+		const {size} = await stat(absPath);
+		const maxFileBytes =
+			appConfig.readFile?.maxFileBytes ?? MAX_FILE_READ_BYTES;
+		if (shouldStreamFile(size) || size > maxFileBytes) {
+			return await readLargeFile(args, absPath, size, maxFileBytes);
+		}
+
 		const cached = await getCachedFileContent(absPath);
 		const content = cached.content;
+		const lossyNote = getLossyNote(args, cached);
 
 		// Check if file is empty (0 tokens)
 		if (content.length === 0) {
@@ -104,8 +355,6 @@ const executeReadFile = async (args: {
 
 		const lines = cached.lines;
 		const totalLines = lines.length;
-		const fileSize = content.length;
-		const estimatedTokens = calculateTokens(content);
 
 		// Progressive disclosure: metadata first for files >300 lines
 		// Small files can be read directly without ranges
@@ -115,38 +364,12 @@ const executeReadFile = async (args: {
 			totalLines > FILE_READ_METADATA_THRESHOLD_LINES
 		) {
 			// Return metadata only for medium/large files
-			// Detect file type from extension
-			const fileType = getFileType(absPath);
-
-			let output = `File: ${args.path}\n`;
-			output += `Type: ${fileType}\n`;
-			output += `Total lines: ${totalLines.toLocaleString()}\n`;
-			output += `Size: ${fileSize.toLocaleString()} bytes\n`;
-			output += `Estimated tokens: ~${estimatedTokens.toLocaleString()}\n\n`;
-
-			if (totalLines <= FILE_READ_CHUNKING_HINT_THRESHOLD_LINES) {
-				output += `[Medium file - To read specific sections, call read_file with start_line and end_line]\n`;
-				output += `[To read entire file progressively, make multiple calls:]\n`;
-				output += `  - read_file({path: "${args.path}", start_line: 1, end_line: ${FILE_READ_CHUNK_SIZE_LINES}})\n`;
-				output += `  - read_file({path: "${args.path}", start_line: ${FILE_READ_CHUNK_SIZE_LINES + 1}, end_line: ${totalLines}})\n`;
-			} else {
-				output += `[Large file - Choose one approach:]\n`;
-				output += `[1. Targeted read: Use search_files to find code, then read specific ranges]\n`;
-				output += `[2. Progressive read: Read file in chunks (recommended chunk size: 200-300 lines)]\n`;
-				output += `   Example chunks for ${totalLines} lines:\n`;
-				const chunkSize = FILE_READ_CHUNK_SIZE_LINES;
-				const numChunks = Math.ceil(totalLines / chunkSize);
-				for (let i = 0; i < Math.min(numChunks, 3); i++) {
-					const start = i * chunkSize + 1;
-					const end = Math.min((i + 1) * chunkSize, totalLines);
-					output += `   - read_file({path: "${args.path}", start_line: ${start}, end_line: ${end}})\n`;
-				}
-				if (numChunks > 3) {
-					output += `   ... and ${numChunks - 3} more chunks to complete the file\n`;
-				}
-			}
-
-			return output;
+			return formatLargeFileMetadata(args, absPath, {
+				totalLines,
+				size: content.length,
+				hash: cached.hash,
+				...summarizeLines(content, lines, model),
+			});
 		}
 
 		// Line ranges specified - read and return content
@@ -158,8 +381,7 @@ const executeReadFile = async (args: {
 		// Extract the lines to return
 		const linesToReturn = lines.slice(startLine - 1, endLine);
 
-		// Return content without line numbers for clean content-based editing
-		return linesToReturn.join('\n');
+		return formatLines(args, linesToReturn, startLine, cached.hash, lossyNote);
 	} catch (error: unknown) {
 		// Handle file not found and other filesystem errors
 		if (
@@ -178,12 +400,14 @@ const executeReadFile = async (args: {
 
 const readFileCoreTool = tool({
 	description:
-		'Read file contents with line numbers. AUTO-ACCEPTED (no user approval needed). Use this INSTEAD OF bash cat/head/tail/less commands. PROGRESSIVE DISCLOSURE: Files ≤300 lines return content directly. Files >300 lines return metadata first - then call again with start_line/end_line to read specific sections. Use metadata_only=true for file info (size, lines, type) without reading content. Always prefer this over bash for any file reading.',
+		'Read file contents. AUTO-ACCEPTED (no user approval needed). Use this INSTEAD OF bash cat/head/tail/less commands. PROGRESSIVE DISCLOSURE: Files ≤300 lines return content directly. Files >300 lines return metadata first - then call again with start_line/end_line to read specific sections. Content reads end with the whole file's content hash; metadata_only=true returns file info (size, lines, type, content hash) without reading content. Pass the hash as expected_hash to write_file/string_replace to refuse the edit if the file changed since. Use line_numbers=true to prefix each line with its line number. Always prefer this over bash for any file reading.',
 	inputSchema: jsonSchema<{
 		path: string;
 		start_line?: number;
 		end_line?: number;
 		metadata_only?: boolean;
+		line_numbers?: boolean;
+		lossy?: boolean;
 	}>({
 		type: 'object',
 		properties: {
@@ -204,7 +428,17 @@ const readFileCoreTool = tool({
 			metadata_only: {
 				type: 'boolean',
 				description:
-					'Optional: If true, returns only file metadata (size, line count, type, encoding, modification time) without content. Useful for quickly checking file properties.',
+					'Optional: If true, returns only file metadata (size, line count, type, encoding, content hash, modification time) without content. Useful for quickly checking file properties.',
+			},
+			line_numbers: {
+				type: 'boolean',
+				description:
+					'Optional: If true, prefixes each line with its line number (e.g. "  12→code"). Defaults to false, which returns the exact file content for the range.',
+			},
+			lossy: {
+				type: 'boolean',
+				description:
+					'Optional: If true, files that are not valid UTF-8 (e.g. Latin-1) are read with invalid bytes replaced by U+FFFD instead of failing. Defaults to false.',
 			},
 		},
 		required: ['path'],
@@ -217,10 +451,17 @@ const readFileCoreTool = tool({
 			start_line?: number;
 			end_line?: number;
 			metadata_only?: boolean;
+			line_numbers?: boolean;
+			lossy?: boolean;
+		},
+		options: {
+			toolCallId: string;
+			messages: unknown[];
+			experimental_context?: unknown;
 		},
-		_options: {toolCallId: string; messages: unknown[]},
 	) => {
-		return await executeReadFile(args);
+		const context = options.experimental_context as ToolContext | undefined;
+		return await executeReadFile(args, context?.model);
 	},
 });
 
@@ -328,10 +569,8 @@ const readFileFormatter = async (
 		const path = args.path || args.file_path;
 		if (path && typeof path === 'string') {
 			const absPath = resolve(path);
-			const cached = await getCachedFileContent(absPath);
-			const content = cached.content;
-			const lines = cached.lines;
-			const totalLines = lines.length;
+			const summary = await getFileSummary(absPath);
+			const totalLines = summary.lineCount;
 
 			// Detect if this was a metadata-only response
 			const isMetadataOnly =
@@ -350,7 +589,7 @@ const readFileFormatter = async (
 			let tokens: number;
 			if (isMetadataOnly) {
 				// For metadata, show estimated tokens of the FULL FILE
-				tokens = calculateTokens(content);
+				tokens = Math.ceil(summary.charCount / CHARS_PER_TOKEN_ESTIMATE);
 			} else {
 				// For content reads, show tokens of what was actually returned
 				tokens = result ? calculateTokens(result) : 0;
@@ -371,6 +610,28 @@ const readFileFormatter = async (
 	return <ReadFileFormatter args={args} fileInfo={fileInfo} />;
 };
 
+// Lines the validator scans for minified content. Streamed files read
+// without a range only return metadata, so there is nothing to scan.
+async function getLinesToCheck(
+	absPath: string,
+	startLine: number,
+	args: {start_line?: number; end_line?: number},
+): Promise<string[]> {
+	const {size} = await stat(absPath);
+	if (shouldStreamFile(size)) {
+		if (args.start_line === undefined && args.end_line === undefined) {
+			return [];
+		}
+		return (await readLineRange(absPath, startLine, args.end_line)).lines;
+	}
+
+	const cached = await getCachedFileContent(absPath);
+	const endLine = args.end_line
+		? Math.min(cached.lines.length, args.end_line)
+		: cached.lines.length;
+	return cached.lines.slice(startLine - 1, endLine);
+}
+
 const readFileValidator = async (args: {
 	path: string;
 	start_line?: number;
@@ -424,8 +685,7 @@ const readFileValidator = async (args: {
 
 		// Check if end_line exceeds file length
 		if (args.end_line !== undefined) {
-			const cached = await getCachedFileContent(absPath);
-			const totalLines = cached.lines.length;
+			const totalLines = (await getFileSummary(absPath)).lineCount;
 
 			if (args.end_line > totalLines) {
 				return {
@@ -438,18 +698,15 @@ const readFileValidator = async (args: {
 		// Check for minified/binary content (very long lines)
 		// Skip this check for metadata_only requests
 		if (!args.metadata_only) {
-			const cached = await getCachedFileContent(absPath);
 			const startLine = args.start_line ? Math.max(1, args.start_line) : 1;
-			const endLine = args.end_line
-				? Math.min(cached.lines.length, args.end_line)
-				: cached.lines.length;
+			const lines = await getLinesToCheck(absPath, startLine, args);
 
-			for (let i = startLine - 1; i < endLine; i++) {
-				const line = cached.lines[i];
+			for (let i = 0; i < lines.length; i++) {
+				const line = lines[i];
 				if (line && line.length > MAX_LINE_LENGTH_CHARS) {
 					return {
 						valid: false,
-						error: `⚒ File "${args.path}" contains minified or binary content (line ${i + 1} has ${line.length.toLocaleString()} characters). This file cannot be read as it would consume excessive tokens without providing useful information.`,
+						error: `⚒ File "${args.path}" contains minified or binary content (line ${startLine + i} has ${line.length.toLocaleString()} characters). This file cannot be read as it would consume excessive tokens without providing useful information.`,
 					};
 				}
 			}
@@ -480,6 +737,8 @@ const readFileValidator = async (args: {
 export const readFileTool: NanocoderToolExport = {
 	name: 'read_file' as const,
 	tool: readFileCoreTool,
+	category: 'file-read',
+	idempotent: true,
 	formatter: readFileFormatter,
 	validator: readFileValidator,
 };
@@ -5,7 +5,13 @@ import {render} from 'ink-testing-library';
 import React from 'react';
 import {themes} from '../config/themes';
 import {ThemeContext} from '../hooks/useTheme';
-import {searchFileContentsTool} from './search-file-contents';
+import {
+	parseRipgrepJson,
+	type SearchBackend,
+	type SearchOptions,
+	searchFileContentsTool,
+	setSearchBackend,
+} from './search-file-contents';
 
 // ============================================================================
 // Test Helpers
@@ -549,6 +555,40 @@ test.serial('search_file_contents enforces max cap of 100 results', async t => {
 	}
 });
 
+test.serial('search_file_contents reports total matches when truncated', async t => {
+	t.timeout(10000);
+	const testDir = join(process.cwd(), 'test-search-total-count-temp');
+
+	try {
+		mkdirSync(testDir, {recursive: true});
+
+		// A single file with 412 matching lines
+		const lines = Array.from(
+			{length: 412},
+			(_, i) => `const manyMatches${i} = ${i};`,
+		);
+		writeFileSync(join(testDir, 'many.ts'), lines.join('\n'));
+
+		const originalCwd = process.cwd();
+
+		try {
+			process.chdir(testDir);
+
+			const result = await searchFileContentsTool.tool.execute!(
+				{query: 'manyMatches', maxResults: 30},
+				{toolCallId: 'test', messages: []},
+			);
+
+			const firstLine = result.split('\n')[0];
+			t.is(firstLine, 'Found 30 matches (showing first 30 of 412 matches):');
+		} finally {
+			process.chdir(originalCwd);
+		}
+	} finally {
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
+
 test.serial(
 	'search_file_contents respects maxResults when less than cap',
 	async t => {
@@ -1317,3 +1357,140 @@ test.serial('search_file_contents handles whitespace in query', async t => {
 		rmSync(testDir, {recursive: true, force: true});
 	}
 });
+
+// ============================================================================
+// Structured Results
+// ============================================================================
+
+test('parseRipgrepJson extracts match events', t => {
+	const output = [
+		'{"type":"begin","data":{"path":{"text":"./src/app.ts"}}}',
+		'{"type":"match","data":{"path":{"text":"./src/app.ts"},"lines":{"text":"  const handler = 1;\\n"},"line_number":12,"absolute_offset":200,"submatches":[{"match":{"text":"handler"},"start":8,"end":15}]}}',
+		'{"type":"end","data":{"path":{"text":"./src/app.ts"},"stats":{}}}',
+		'{"type":"summary","data":{"stats":{}}}',
+	].join('\n');
+
+	t.deepEqual(parseRipgrepJson(output), [
+		{file: 'src/app.ts', line: 12, column: 9, text: '  const handler = 1;'},
+	]);
+});
+
+test('parseRipgrepJson converts byte offsets to character columns', t => {
+	// "héllo " is 7 bytes but 6 characters
+	const output =
+		'{"type":"match","data":{"path":{"text":"./a.txt"},"lines":{"text":"héllo world\\r\\n"},"line_number":1,"submatches":[{"match":{"text":"world"},"start":7,"end":12}]}}';
+
+	const [match] = parseRipgrepJson(output);
+	t.is(match.column, 7);
+	t.is(match.text, 'héllo world');
+	t.is(match.text.slice(match.column - 1), 'world');
+});
+
+test('parseRipgrepJson decodes base64 paths and skips malformed lines', t => {
+	const path = Buffer.from('./odd.txt').toString('base64');
+	const output = [
+		'not json',
+		`{"type":"match","data":{"path":{"bytes":"${path}"},"lines":{"text":"x\\n"},"line_number":3,"submatches":[{"match":{"text":"x"},"start":0,"end":1}]}}`,
+	].join('\n');
+
+	t.deepEqual(parseRipgrepJson(output), [
+		{file: 'odd.txt', line: 3, column: 1, text: 'x'},
+	]);
+});
+
+test.serial('search_file_contents returns structured JSON results', async t => {
+	const testDir = join(process.cwd(), 'test-search-structured-temp');
+
+	try {
+		mkdirSync(testDir, {recursive: true});
+		writeFileSync(
+			join(testDir, 'test.ts'),
+			'first line\n  const needle = 1;\n',
+		);
+
+		const originalCwd = process.cwd();
+
+		try {
+			process.chdir(testDir);
+
+			const result = await searchFileContentsTool.tool.execute!(
+				{query: 'needle', structured: true},
+				{toolCallId: 'test', messages: []},
+			);
+
+			t.deepEqual(JSON.parse(result), [
+				{file: 'test.ts', line: 2, column: 9, text: '  const needle = 1;'},
+			]);
+
+			const empty = await searchFileContentsTool.tool.execute!(
+				{query: 'nonexistentXYZ', structured: true},
+				{toolCallId: 'test', messages: []},
+			);
+			t.deepEqual(JSON.parse(empty), []);
+		} finally {
+			process.chdir(originalCwd);
+		}
+	} finally {
+		rmSync(testDir, {recursive: true, force: true});
+	}
+});
+
+test.serial('search_file_contents uses an injected search backend', async t => {
+	const calls: Array<{query: string; options: SearchOptions}> = [];
+	const indexBackend: SearchBackend = {
+		name: 'code-index',
+		async search(query, options) {
+			calls.push({query, options});
+			return {
+				matches: [
+					{file: 'src/auth.ts', line: 12, column: 7, text: 'const token = load();'},
+					{file: 'node_modules/lib/index.js', line: 1, column: 1, text: 'token'},
+					{file: 'src/session.ts', line: 40, column: 3, text: '  token.refresh();'},
+				],
+			};
+		},
+	};
+	setSearchBackend(indexBackend);
+
+	try {
+		const result = await searchFileContentsTool.tool.execute!(
+			{query: 'token', caseSensitive: true, structured: true},
+			{toolCallId: 'test', messages: []},
+		);
+
+		t.deepEqual(calls, [
+			{
+				query: 'token',
+				options: {cwd: process.cwd(), caseSensitive: true, structured: true},
+			},
+		]);
+		// Ignored paths are still filtered out of the backend's results
+		t.deepEqual(JSON.parse(result), [
+			{file: 'src/auth.ts', line: 12, column: 7, text: 'const token = load();'},
+			{file: 'src/session.ts', line: 40, column: 3, text: '  token.refresh();'},
+		]);
+	} finally {
+		setSearchBackend(null);
+	}
+});
+
+test.serial('search_file_contents reports errors from a search backend', async t => {
+	setSearchBackend({
+		name: 'offline-index',
+		async search() {
+			throw new Error('index server unavailable');
+		},
+	});
+
+	try {
+		await t.throwsAsync(
+			searchFileContentsTool.tool.execute!(
+				{query: 'anything'},
+				{toolCallId: 'test', messages: []},
+			),
+			{message: /index server unavailable/},
+		);
+	} finally {
+		setSearchBackend(null);
+	}
+});
@@ -9,113 +9,342 @@ import {
 	BUFFER_GREP_MULTIPLIER,
 	DEFAULT_SEARCH_RESULTS,
 	MAX_SEARCH_RESULTS,
+	MAX_SEARCH_TOTAL_COUNT,
 } from '@/constants';
 import {ThemeContext} from '@/hooks/useTheme';
 import type {NanocoderToolExport} from '@/types/core';
 import {jsonSchema, tool} from '@/types/core';
+import {getOverflowedStdout} from '@/utils/exec-output';
 import {DEFAULT_IGNORE_DIRS, loadGitignore} from '@/utils/gitignore-loader';
+import {formatLossyPathNote, isLossyPath} from '@/utils/text-decoding';
 import {calculateTokens} from '@/utils/token-calculator';
 
 const execFileAsync = promisify(execFile);
 
-interface SearchMatch {
+// Truncate long lines to prevent token explosion
+const MAX_CONTENT_LENGTH = 300;
+
+export interface SearchMatch {
 	file: string;
 	line: number;
-	content: string;
+	/** 1-based character column of the first match on the line */
+	column: number;
+	/** The matching line, without its line terminator */
+	text: string;
+}
+
+/** A match as returned in structured mode */
+export interface StructuredSearchMatch {
+	file: string;
+	line: number;
+	column: number;
+	text: string;
+	/** Set when file isn't valid UTF-8 and can't be passed back as written */
+	lossyPath?: true;
 }
 
 /**
- * Search file contents using grep
+ * Converts a byte offset into a UTF-8 line into a 1-based character column
  */
-async function searchFileContents(
-	query: string,
-	cwd: string,
-	maxResults: number,
-	caseSensitive: boolean,
-): Promise<{matches: SearchMatch[]; truncated: boolean}> {
-	try {
-		const ig = loadGitignore(cwd);
+function byteOffsetToColumn(text: string, byteOffset: number): number {
+	const prefix = Buffer.from(text, 'utf-8').subarray(0, byteOffset);
+	return prefix.toString('utf-8').length + 1;
+}
+
+/**
+ * Parses `rg --json` output into matches. Only `match` events are used; paths
+ * or lines that are not valid UTF-8 (reported as base64 `bytes`) are decoded
+ * lossily.
+ */
+export function parseRipgrepJson(output: string): SearchMatch[] {
+	const matches: SearchMatch[] = [];
 
-		// Build grep arguments array to prevent command injection
-		const grepArgs: string[] = [
-			'-rn', // recursive with line numbers
-			'-E', // extended regex
-		];
+	for (const line of output.split('\n')) {
+		if (!line.trim()) {
+			continue;
+		}
 
-		// Add case sensitivity flag
-		if (!caseSensitive) {
-			grepArgs.push('-i');
+		let event: {
+			type?: string;
+			data?: {
+				path?: {text?: string; bytes?: string};
+				lines?: {text?: string; bytes?: string};
+				line_number?: number;
+				submatches?: Array<{start: number}>;
+			};
+		};
+		try {
+			event = JSON.parse(line);
+		} catch {
+			continue;
 		}
 
-		// Add include and exclude patterns
-		grepArgs.push('--include=*');
-		// Dynamically add exclusions from DEFAULT_IGNORE_DIRS
-		for (const dir of DEFAULT_IGNORE_DIRS) {
-			grepArgs.push(`--exclude-dir=${dir}`);
+		const data = event.data;
+		if (event.type !== 'match' || !data?.path || !data.lines) {
+			continue;
 		}
 
-		// Add the search query (no escaping needed with array-based args)
-		grepArgs.push(query);
+		const file = (
+			data.path.text ??
+			Buffer.from(data.path.bytes ?? '', 'base64').toString()
+		).replace(/^\.\//, '');
+		const text = (
+			data.lines.text ??
+			Buffer.from(data.lines.bytes ?? '', 'base64').toString()
+		).replace(/\r?\n$/, '');
+		const start = data.submatches?.[0]?.start ?? 0;
+
+		matches.push({
+			file,
+			line: data.line_number ?? 0,
+			column: byteOffsetToColumn(text, start),
+			text,
+		});
+	}
+
+	return matches;
+}
+
+/**
+ * Parses `grep -rn` output into matches. grep has no column output, so the
+ * column is located with the equivalent JavaScript regex, falling back to 1.
+ */
+function parseGrepOutput(
+	output: string,
+	query: string,
+	caseSensitive: boolean,
+): SearchMatch[] {
+	let pattern: RegExp | undefined;
+	try {
+		pattern = new RegExp(query, caseSensitive ? '' : 'i');
+	} catch {
+		pattern = undefined;
+	}
 
-		// Add search path
-		grepArgs.push('.');
+	const matches: SearchMatch[] = [];
+	for (const line of output.trim().split('\n').filter(Boolean)) {
+		const match = line.match(/^\.\/(.+?):(\d+):(.*)$/);
+		if (match) {
+			const index = pattern ? match[3].search(pattern) : -1;
+			matches.push({
+				file: match[1],
+				line: parseInt(match[2], 10),
+				column: index >= 0 ? index + 1 : 1,
+				text: match[3],
+			});
+		}
+	}
+	return matches;
+}
 
-		// Execute grep command with array-based arguments
-		const {stdout} = await execFileAsync('grep', grepArgs, {
+/**
+ * Runs a search command, keeping the output read up to the buffer cap on an
+ * enormous tree rather than failing. Exit code 1 means no matches for both
+ * grep and ripgrep.
+ */
+async function runSearchCommand(
+	command: string,
+	args: string[],
+	cwd: string,
+): Promise<{stdout: string; outputCapped: boolean}> {
+	try {
+		const {stdout} = await execFileAsync(command, args, {
 			cwd,
 			maxBuffer: BUFFER_FIND_FILES_BYTES * BUFFER_GREP_MULTIPLIER,
 		});
-
-		const matches: SearchMatch[] = [];
-		const lines = stdout.trim().split('\n').filter(Boolean);
-
-		for (const line of lines) {
-			const match = line.match(/^\.\/(.+?):(\d+):(.*)$/);
-			if (match) {
-				const filePath = match[1];
-
-				// Skip files ignored by gitignore
-				if (ig.ignores(filePath)) {
-					continue;
-				}
-
-				// Truncate long lines to prevent token explosion
-				const MAX_CONTENT_LENGTH = 300;
-				let content = match[3].trim();
-				if (content.length > MAX_CONTENT_LENGTH) {
-					content = content.slice(0, MAX_CONTENT_LENGTH) + '…';
-				}
-
-				matches.push({
-					file: filePath,
-					line: parseInt(match[2], 10),
-					content,
-				});
-
-				// Stop once we have enough matches
-				if (matches.length >= maxResults) {
-					break;
-				}
-			}
+		return {stdout, outputCapped: false};
+	} catch (error: unknown) {
+		const overflowed = getOverflowedStdout(error);
+		if (overflowed !== undefined) {
+			return {stdout: overflowed, outputCapped: true};
 		}
+		if (error instanceof Error && 'code' in error && error.code === 1) {
+			return {stdout: '', outputCapped: false};
+		}
+		throw error;
+	}
+}
 
-		return {
-			matches,
-			truncated: lines.length >= maxResults || matches.length >= maxResults,
-		};
+async function runGrep(
+	query: string,
+	cwd: string,
+	caseSensitive: boolean,
+): Promise<{matches: SearchMatch[]; outputCapped: boolean}> {
+	// Build grep arguments array to prevent command injection
+	const grepArgs: string[] = [
+		'-rn', // recursive with line numbers
+		'-E', // extended regex
+	];
+
+	// Add case sensitivity flag
+	if (!caseSensitive) {
+		grepArgs.push('-i');
+	}
+
+	// Add include and exclude patterns
+	grepArgs.push('--include=*');
+	// Dynamically add exclusions from DEFAULT_IGNORE_DIRS
+	for (const dir of DEFAULT_IGNORE_DIRS) {
+		grepArgs.push(`--exclude-dir=${dir}`);
+	}
+
+	// Add the search query (no escaping needed with array-based args)
+	grepArgs.push(query);
+
+	// Add search path
+	grepArgs.push('.');
+
+	const {stdout, outputCapped} = await runSearchCommand('grep', grepArgs, cwd);
+	return {
+		matches: parseGrepOutput(stdout, query, caseSensitive),
+		outputCapped,
+	};
+}
+
+/**
+ * Searches with ripgrep's JSON output, which gives exact match offsets.
+ * Returns null if ripgrep is not installed.
+ */
+async function runRipgrep(
+	query: string,
+	cwd: string,
+	caseSensitive: boolean,
+): Promise<{matches: SearchMatch[]; outputCapped: boolean} | null> {
+	const rgArgs: string[] = [
+		'--json',
+		'--hidden',
+		caseSensitive ? '--case-sensitive' : '--ignore-case',
+	];
+	for (const dir of DEFAULT_IGNORE_DIRS) {
+		rgArgs.push('--glob', `!${dir}`);
+	}
+	rgArgs.push('-e', query, '.');
+
+	try {
+		const {stdout, outputCapped} = await runSearchCommand('rg', rgArgs, cwd);
+		return {matches: parseRipgrepJson(stdout), outputCapped};
 	} catch (error: unknown) {
-		// grep returns exit code 1 when no matches found
-		if (error instanceof Error && 'code' in error && error.code === 1) {
-			return {matches: [], truncated: false};
+		if (error instanceof Error && 'code' in error && error.code === 'ENOENT') {
+			return null;
 		}
 		throw error;
 	}
 }
 
+export interface SearchOptions {
+	/** Directory to search; match paths are relative to it */
+	cwd: string;
+	caseSensitive: boolean;
+	/** True when the caller needs exact columns */
+	structured: boolean;
+}
+
+/**
+ * Where search_file_contents gets its matches from. Replace the default with
+ * setSearchBackend to route searches to a code-search index or a language
+ * server. Results still go through the gitignore filter and result caps.
+ */
+export interface SearchBackend {
+	name: string;
+	search(
+		query: string,
+		options: SearchOptions,
+	): Promise<{
+		matches: SearchMatch[];
+		/** True if the backend stopped early, so more matches may exist */
+		outputCapped?: boolean;
+	}>;
+}
+
+/**
+ * The default backend: grep, or ripgrep when structured results are requested
+ * and it is available
+ */
+export const commandLineSearchBackend: SearchBackend = {
+	name: 'grep/ripgrep',
+	async search(query, {cwd, caseSensitive, structured}) {
+		return (
+			(structured ? await runRipgrep(query, cwd, caseSensitive) : null) ??
+			(await runGrep(query, cwd, caseSensitive))
+		);
+	},
+};
+
+let searchBackend: SearchBackend = commandLineSearchBackend;
+
+/**
+ * Sets the backend used by search_file_contents
+ * @param backend - The backend to use, or null to restore the default
+ */
+export function setSearchBackend(backend: SearchBackend | null): void {
+	searchBackend = backend ?? commandLineSearchBackend;
+}
+
+/**
+ * Search file contents with the current search backend
+ */
+async function searchFileContents(
+	query: string,
+	cwd: string,
+	maxResults: number,
+	caseSensitive: boolean,
+	structured = false,
+): Promise<{
+	matches: SearchMatch[];
+	truncated: boolean;
+	totalMatches: number;
+	/** True if the output hit the buffer cap, so totalMatches is a minimum */
+	outputCapped: boolean;
+}> {
+	const ig = loadGitignore(cwd);
+
+	const result = await searchBackend.search(query, {
+		cwd,
+		caseSensitive,
+		structured,
+	});
+	const outputCapped = result.outputCapped ?? false;
+
+	const matches: SearchMatch[] = [];
+	// Count every match (up to a cap) so truncated output can report scope
+	let totalMatches = 0;
+
+	for (const match of result.matches) {
+		// Skip files ignored by gitignore
+		if (ig.ignores(match.file)) {
+			continue;
+		}
+
+		totalMatches++;
+		if (totalMatches >= MAX_SEARCH_TOTAL_COUNT) {
+			break;
+		}
+		if (matches.length >= maxResults) {
+			continue;
+		}
+
+		matches.push(match);
+	}
+
+	return {
+		matches,
+		truncated: outputCapped || totalMatches > matches.length,
+		totalMatches,
+		outputCapped,
+	};
+}
+
+function truncateContent(content: string): string {
+	return content.length > MAX_CONTENT_LENGTH
+		? content.slice(0, MAX_CONTENT_LENGTH) + '…'
+		: content;
+}
+
 interface SearchFileContentsArgs {
 	query: string;
 	maxResults?: number;
 	caseSensitive?: boolean;
+	structured?: boolean;
 }
 
 const executeSearchFileContents = async (
@@ -129,25 +358,48 @@ const executeSearchFileContents = async (
 	const caseSensitive = args.caseSensitive || false;
 
 	try {
-		const {matches, truncated} = await searchFileContents(
-			args.query,
-			cwd,
-			maxResults,
-			caseSensitive,
-		);
+		const {matches, truncated, totalMatches, outputCapped} =
+			await searchFileContents(
+				args.query,
+				cwd,
+				maxResults,
+				caseSensitive,
+				args.structured,
+			);
+
+		if (args.structured) {
+			const structuredMatches: StructuredSearchMatch[] = matches.map(
+				match => ({
+					file: match.file,
+					line: match.line,
+					column: match.column,
+					text: truncateContent(match.text),
+					...(isLossyPath(match.file) && {lossyPath: true as const}),
+				}),
+			);
+			return JSON.stringify(structuredMatches, null, 2);
+		}
 
 		if (matches.length === 0) {
 			return `No matches found for "${args.query}"`;
 		}
 
 		// Format results with clear file:line format
-		let output = `Found ${matches.length} match${matches.length === 1 ? '' : 'es'}${truncated ? ` (showing first ${maxResults})` : ''}:\n\n`;
+		const totalLabel =
+			totalMatches >= MAX_SEARCH_TOTAL_COUNT
+				? `${MAX_SEARCH_TOTAL_COUNT.toLocaleString()}+`
+				: outputCapped
+					? `${totalMatches.toLocaleString()}+`
+					: totalMatches.toLocaleString();
+		let output = `Found ${matches.length} match${matches.length === 1 ? '' : 'es'}${truncated ? ` (showing first ${matches.length} of ${totalLabel} matches)` : ''}:\n\n`;
 
 		for (const match of matches) {
 			output += `${match.file}:${match.line}\n`;
-			output += `  ${match.content}\n\n`;
+			output += `  ${truncateContent(match.text.trim())}\n\n`;
 		}
 
+		output += formatLossyPathNote(matches.map(match => match.file));
+
 		return output.trim();
 	} catch (error: unknown) {
 		const errorMessage =
@@ -158,7 +410,7 @@ const executeSearchFileContents = async (
 
 const searchFileContentsCoreTool = tool({
 	description:
-		'Search for text or code inside files. AUTO-ACCEPTED (no user approval needed). Use this INSTEAD OF bash grep/rg/ag/ack commands. Supports extended regex (e.g., "foo|bar", "func(tion)?"). Returns file:line with matching content. Use to find: function definitions, variable usage, import statements, TODO comments. Case-insensitive by default (use caseSensitive=true for exact matching).',
+		'Search for text or code inside files. AUTO-ACCEPTED (no user approval needed). Use this INSTEAD OF bash grep/rg/ag/ack commands. Supports extended regex (e.g., "foo|bar", "func(tion)?"). Returns file:line with matching content. Use to find: function definitions, variable usage, import statements, TODO comments. Case-insensitive by default (use caseSensitive=true for exact matching). Set structured=true to get a JSON array of {file, line, column, text} objects instead.',
 	inputSchema: jsonSchema<SearchFileContentsArgs>({
 		type: 'object',
 		properties: {
@@ -177,6 +429,11 @@ const searchFileContentsCoreTool = tool({
 				description:
 					'Whether to perform case-sensitive search (default: false)',
 			},
+			structured: {
+				type: 'boolean',
+				description:
+					'Return a JSON array of {file, line, column, text} objects instead of the human-readable listing. Columns are 1-based (default: false)',
+			},
 		},
 		required: ['query'],
 	}),
@@ -192,6 +449,7 @@ interface SearchFileContentsFormatterProps {
 		query: string;
 		maxResults?: number;
 		caseSensitive?: boolean;
+		structured?: boolean;
 	};
 	result?: string;
 }
@@ -206,7 +464,13 @@ const SearchFileContentsFormatter = React.memo(
 
 		// Parse result to get match count
 		let matchCount = 0;
-		if (result && !result.startsWith('Error:')) {
+		if (args.structured && result?.startsWith('[')) {
+			try {
+				matchCount = (JSON.parse(result) as unknown[]).length;
+			} catch {
+				matchCount = 0;
+			}
+		} else if (result && !result.startsWith('Error:')) {
 			const firstLine = result.split('\n')[0];
 			const matchFound = firstLine.match(/Found (\d+)/);
 			if (matchFound) {
@@ -264,5 +528,21 @@ const searchFileContentsFormatter = (
 export const searchFileContentsTool: NanocoderToolExport = {
 	name: 'search_file_contents' as const,
 	tool: searchFileContentsCoreTool,
+	category: 'search',
+	idempotent: true,
+	examples: [
+		{
+			description: 'Where a function is defined',
+			arguments: {query: 'function handleSubmit'},
+		},
+		{
+			description: 'Either of two markers; | separates alternatives',
+			arguments: {query: 'TODO|FIXME'},
+		},
+		{
+			description: 'Calls to a function; escape ( to match it literally',
+			arguments: {query: 'useState\\(', caseSensitive: true},
+		},
+	],
 	formatter: searchFileContentsFormatter,
 };
@@ -7,6 +7,7 @@ import React from 'react';
 import {themes} from '../config/themes.js';
 import {setCurrentMode} from '../context/mode-context.js';
 import {ThemeContext} from '../hooks/useTheme.js';
+import {computeContentHash} from '../utils/content-hash.js';
 import {stringReplaceTool} from './string-replace.js';
 
 // ============================================================================
@@ -49,6 +50,8 @@ async function executeStringReplace(args: {
 	path: string;
 	old_str: string;
 	new_str: string;
+	dry_run?: boolean;
+	expected_hash?: string;
 }): Promise<string> {
 	// biome-ignore lint/suspicious/noExplicitAny: Tool internals require any
 	return await (stringReplaceTool.tool as any).execute(args, {
@@ -934,3 +937,92 @@ test('string_replace formatter: normalizes tabs to 2 spaces', async t => {
 test.after(() => {
 	setCurrentMode('normal');
 });
+
+// ============================================================================
+// Dry Run Tests
+// ============================================================================
+
+test('string_replace: dry run returns a diff without writing', async t => {
+	const filePath = await createTestFile(
+		'test.txt',
+		'Hello World\nGoodbye World\n',
+	);
+
+	const result = await executeStringReplace({
+		path: filePath,
+		old_str: 'Hello World',
+		new_str: 'Hi Universe',
+		dry_run: true,
+	});
+
+	const content = await readFile(filePath, 'utf-8');
+	t.is(content, 'Hello World\nGoodbye World\n');
+	t.true(result.includes('No changes were written'));
+	t.true(result.includes('@@ -1,2 +1,2 @@\n-Hello World\n+Hi Universe\n Goodbye World'));
+});
+
+test('string_replace: dry run still reports missing content', async t => {
+	const filePath = await createTestFile('test.txt', 'content\n');
+
+	await t.throwsAsync(
+		executeStringReplace({
+			path: filePath,
+			old_str: 'missing',
+			new_str: 'new',
+			dry_run: true,
+		}),
+		{message: /Content not found/},
+	);
+});
+
+test('string_replace: dry run does not require approval', async t => {
+	setCurrentMode('normal');
+	const needsApproval = stringReplaceTool.tool.needsApproval;
+
+	t.is(typeof needsApproval, 'function');
+	if (typeof needsApproval === 'function') {
+		const result = await needsApproval(
+			{path: 'test.txt', old_str: 'a', new_str: 'b', dry_run: true},
+			{toolCallId: 'test', messages: []},
+		);
+		t.false(result);
+	}
+});
+
+// ============================================================================
+// Expected Hash Tests
+// ============================================================================
+
+test('string_replace: edits when expected_hash matches the file', async t => {
+	const original = 'Hello World\nGoodbye World\n';
+	const filePath = await createTestFile('test.txt', original);
+
+	await executeStringReplace({
+		path: filePath,
+		old_str: 'Hello World',
+		new_str: 'Hi Universe',
+		expected_hash: computeContentHash(original),
+	});
+
+	const content = await readFile(filePath, 'utf-8');
+	t.is(content, 'Hi Universe\nGoodbye World\n');
+});
+
+test('string_replace: refuses the edit when the file changed since read', async t => {
+	const filePath = await createTestFile('test.txt', 'Hello World\n');
+	const readHash = computeContentHash('Hello World\n');
+	await writeFile(filePath, 'Hello World\nEdited by user\n', 'utf-8');
+
+	await t.throwsAsync(
+		executeStringReplace({
+			path: filePath,
+			old_str: 'Hello World',
+			new_str: 'Hi Universe',
+			expected_hash: readHash,
+		}),
+		{message: /changed since it was read/},
+	);
+
+	const content = await readFile(filePath, 'utf-8');
+	t.is(content, 'Hello World\nEdited by user\n');
+});
@@ -11,11 +11,13 @@ import {getCurrentMode} from '@/context/mode-context';
 import type {NanocoderToolExport} from '@/types/core';
 import {jsonSchema, tool} from '@/types/core';
 import type {Colors} from '@/types/index';
+import {assertFileUnchanged} from '@/utils/content-hash';
 import {getCachedFileContent, invalidateCache} from '@/utils/file-cache';
 import {normalizeIndentation} from '@/utils/indentation-normalizer';
 import {areLinesSimlar, computeInlineDiff} from '@/utils/inline-diff';
 import {isValidFilePath, resolveFilePath} from '@/utils/path-validation';
 import {getLanguageFromExtension} from '@/utils/programming-language-helper';
+import {createUnifiedDiff} from '@/utils/unified-diff';
 import {
 	closeDiffInVSCode,
 	isVSCodeConnected,
@@ -26,6 +28,8 @@ interface StringReplaceArgs {
 	path: string;
 	old_str: string;
 	new_str: string;
+	dry_run?: boolean;
+	expected_hash?: string;
 }
 
 const executeStringReplace = async (
@@ -41,6 +45,10 @@ const executeStringReplace = async (
 	}
 
 	const absPath = resolve(path);
+	if (args.expected_hash) {
+		await assertFileUnchanged(absPath, args.expected_hash, path);
+	}
+
 	const cached = await getCachedFileContent(absPath);
 	const fileContent = cached.content;
 
@@ -62,6 +70,11 @@ const executeStringReplace = async (
 	// Perform the replacement
 	const newContent = fileContent.replace(old_str, new_str);
 
+	if (args.dry_run) {
+		const diff = createUnifiedDiff(path, fileContent, newContent);
+		return `Dry run: would edit ${path}. No changes were written.\n\n${diff || 'No changes.'}`;
+	}
+
 	// Write updated content
 	await writeFile(absPath, newContent, 'utf-8');
 
@@ -130,11 +143,25 @@ const stringReplaceCoreTool = tool({
 				description:
 					'The replacement string. Can be empty to delete content. Must preserve proper indentation and formatting.',
 			},
+			dry_run: {
+				type: 'boolean',
+				description:
+					'Preview the change as a unified diff without writing the file (default: false).',
+			},
+			expected_hash: {
+				type: 'string',
+				description:
+					'Optional: Hash from read_file metadata. The edit is refused if the file has changed since it was read.',
+			},
 		},
 		required: ['path', 'old_str', 'new_str'],
 	}),
 	// Medium risk: file write operation, requires approval except in auto-accept mode
-	needsApproval: () => {
+	// A dry run writes nothing, so it never needs approval
+	needsApproval: args => {
+		if (args.dry_run) {
+			return false;
+		}
 		const mode = getCurrentMode();
 		return mode !== 'auto-accept'; // true in normal/plan, false in auto-accept
 	},
@@ -157,7 +184,8 @@ async function formatStringReplacePreview(
 	const themeColors = colors || getColors();
 	const {path, old_str, new_str} = args;
 
-	const isResult = result !== undefined;
+	// A dry run leaves the file untouched, so its result renders like a preview
+	const isResult = result !== undefined && !args.dry_run;
 
 	try {
 		const absPath = resolve(path);
@@ -501,7 +529,11 @@ async function formatStringReplacePreview(
 
 				<Box flexDirection="column" marginTop={1}>
 					<Text color={themeColors.success}>
-						{isResult ? '✓ Replace completed' : '✓ Replacing'}{' '}
+						{isResult
+							? '✓ Replace completed'
+							: args.dry_run
+								? '✓ Dry run: would replace'
+								: '✓ Replacing'}{' '}
 						{oldStrLines.length} line{oldStrLines.length > 1 ? 's' : ''} with{' '}
 						{newStrLines.length} line
 						{newStrLines.length > 1 ? 's' : ''}
@@ -551,7 +583,7 @@ const stringReplaceFormatter = async (
 	const absPath = resolve(path);
 
 	// Send diff to VS Code during preview phase (before execution)
-	if (result === undefined && isVSCodeConnected()) {
+	if (result === undefined && isVSCodeConnected() && !args.dry_run) {
 		try {
 			const cached = await getCachedFileContent(absPath);
 			const fileContent = cached.content;
@@ -680,6 +712,26 @@ const stringReplaceValidator = async (
 export const stringReplaceTool: NanocoderToolExport = {
 	name: 'string_replace' as const,
 	tool: stringReplaceCoreTool,
+	category: 'file-write',
+	examples: [
+		{
+			description:
+				'Change one line, with a line of context on each side so the match is unique',
+			arguments: {
+				path: 'src/config.ts',
+				old_str: 'export const config = {\n\tport: 3000,\n\thost: "localhost",',
+				new_str: 'export const config = {\n\tport: 8080,\n\thost: "localhost",',
+			},
+		},
+		{
+			description: 'Delete a line by replacing it and its context without it',
+			arguments: {
+				path: 'src/index.ts',
+				old_str: 'import {a} from "./a";\nimport {unused} from "./unused";\n',
+				new_str: 'import {a} from "./a";\n',
+			},
+		},
+	],
 	formatter: stringReplaceFormatter,
 	validator: stringReplaceValidator,
 };
@@ -1,12 +1,30 @@
 import type {
 	MCPInitResult,
 	MCPServer,
+	ToolCategory,
 } from '@/types/index';
+import {appConfig} from '@/config/index';
+import {ToolResultCache} from '@/tools/tool-result-cache';
 import test from 'ava';
 import {ToolManager} from './tool-manager';
 
 console.log('\ntool-manager.spec.ts');
 
+// Register a fake tool whose tool.execute and handler both run `run`
+const registerFakeTool = (
+	manager: ToolManager,
+	name: string,
+	run: (args: any) => Promise<string>,
+	category: ToolCategory = 'other',
+) => {
+	(manager as any).registry.register({
+		name,
+		tool: {description: name, inputSchema: {}, execute: run},
+		handler: run,
+		category,
+	});
+};
+
 // ============================================================================
 // Constructor Tests
 // ============================================================================
@@ -33,6 +51,47 @@ test('constructor - static tools are accessible', t => {
 	t.true(Object.keys(allTools).length > 0, 'Should have accessible tools');
 });
 
+test('constructor - skips disabled built-in tools', t => {
+	const manager = new ToolManager(['execute_bash']);
+
+	t.false(manager.hasTool('execute_bash'));
+	t.false('execute_bash' in manager.getAllTools());
+	t.false(manager.getToolNames().includes('execute_bash'));
+	t.true(manager.hasTool('read_file'));
+});
+
+test('constructor - ignores unknown disabled tool names', t => {
+	const baseline = new ToolManager();
+	const manager = new ToolManager(['not_a_real_tool']);
+
+	t.is(manager.getToolCount(), baseline.getToolCount());
+});
+
+test('isIdempotent - only for registered read-only built-in tools', t => {
+	const manager = new ToolManager(['find_files']);
+
+	t.true(manager.isIdempotent('read_file'));
+	t.false(manager.isIdempotent('fetch_url'));
+	t.false(manager.isIdempotent('write_file'));
+	t.false(manager.isIdempotent('execute_bash'));
+	t.false(manager.isIdempotent('find_files'));
+	t.false(manager.isIdempotent('not_a_real_tool'));
+});
+
+test('disconnectMCP - keeps disabled tools out of the rebuilt registry', async t => {
+	const manager = new ToolManager(['execute_bash']);
+
+	// Force the disconnect path that rebuilds the static registry
+	(manager as any).mcpClient = {
+		getNativeToolsRegistry: () => ({}),
+		disconnect: async () => {},
+	};
+	await manager.disconnectMCP();
+
+	t.false(manager.hasTool('execute_bash'));
+	t.true(manager.hasTool('read_file'));
+});
+
 // ============================================================================
 // MCP Initialization Tests
 // ============================================================================
@@ -148,6 +207,137 @@ test('getToolHandler - returns undefined for non-existent tool', t => {
 	t.is(handler, undefined);
 });
 
+test.serial('getToolHandler - leaves results under the byte cap untouched', async t => {
+	const previous = appConfig.maxToolResultBytes;
+	appConfig.maxToolResultBytes = 64;
+	const manager = new ToolManager();
+	registerFakeTool(manager, 'small_tool', async () => 'x'.repeat(64));
+
+	try {
+		const result = await manager.getToolHandler('small_tool')?.({});
+
+		t.is(result, 'x'.repeat(64));
+	} finally {
+		appConfig.maxToolResultBytes = previous;
+	}
+});
+
+test.serial('getToolHandler - truncates results over the byte cap', async t => {
+	const previous = appConfig.maxToolResultBytes;
+	appConfig.maxToolResultBytes = 64;
+	const manager = new ToolManager();
+	registerFakeTool(manager, 'mcp_dump', async () => 'y'.repeat(1000));
+
+	try {
+		const handler = manager.getToolRegistry().mcp_dump;
+
+		t.is(
+			await handler({}),
+			`${'y'.repeat(64)}\n\n[truncated: 64 of 1000 bytes]`,
+		);
+	} finally {
+		appConfig.maxToolResultBytes = previous;
+	}
+});
+
+test.serial('getAllTools - truncates results of auto-executed tools', async t => {
+	const previous = appConfig.maxToolResultBytes;
+	appConfig.maxToolResultBytes = 64;
+	const manager = new ToolManager();
+	registerFakeTool(manager, 'mcp_dump', async () => 'y'.repeat(1000));
+
+	try {
+		const result = await manager.getAllTools().mcp_dump.execute?.(
+			{},
+			{toolCallId: 'test', messages: []},
+		);
+
+		t.is(result, `${'y'.repeat(64)}\n\n[truncated: 64 of 1000 bytes]`);
+	} finally {
+		appConfig.maxToolResultBytes = previous;
+	}
+});
+
+test('getToolHandler - reuses cached results until a write invalidates them', async t => {
+	let reads = 0;
+	const files: Record<string, string> = {'a.txt': 'one'};
+	const manager = new ToolManager();
+	// Replace the built-in read_file and write_file so their categories and
+	// idempotence still apply
+	registerFakeTool(
+		manager,
+		'read_file',
+		async (args: {path: string}) => {
+			reads++;
+			return files[args.path];
+		},
+		'file-read',
+	);
+	registerFakeTool(
+		manager,
+		'write_file',
+		async (args: {path: string; content: string}) => {
+			files[args.path] = args.content;
+			return 'written';
+		},
+		'file-write',
+	);
+	manager.setResultCache(new ToolResultCache());
+
+	const read = () => manager.getToolHandler('read_file')?.({path: 'a.txt'});
+
+	t.is(await read(), 'one');
+	t.is(await read(), 'one');
+	t.is(reads, 1);
+
+	// The AI SDK's auto-executed path shares the cache
+	await manager.getAllTools().write_file.execute?.(
+		{path: 'a.txt', content: 'two'},
+		{toolCallId: 'test', messages: []},
+	);
+	t.is(await read(), 'two');
+	t.is(reads, 2);
+});
+
+test('setCurrentModel - passes the model to tools as their context', async t => {
+	const manager = new ToolManager();
+	const seen: unknown[] = [];
+	(manager as any).registry.register({
+		name: 'model_probe',
+		tool: {
+			description: 'model_probe',
+			inputSchema: {},
+			execute: async (
+				_args: unknown,
+				options: {experimental_context?: unknown},
+			) => {
+				seen.push(options.experimental_context);
+				return 'ok';
+			},
+		},
+		handler: async (
+			_args: unknown,
+			_signal?: AbortSignal,
+			context?: unknown,
+		) => {
+			seen.push(context);
+			return 'ok';
+		},
+		category: 'other',
+	});
+
+	manager.setCurrentModel('gpt-4o', 'openai');
+	await manager.getToolHandler('model_probe')?.({});
+	await manager
+		.getAllTools()
+		.model_probe.execute?.({}, {toolCallId: 'test', messages: []});
+	manager.setCurrentModel('');
+	await manager.getToolHandler('model_probe')?.({});
+
+	const model = {model: 'gpt-4o', provider: 'openai'};
+	t.deepEqual(seen, [{model}, {model}, {model: undefined}]);
+});
+
 test('getToolFormatter - returns undefined for non-existent tool', t => {
 	const manager = new ToolManager();
 
@@ -202,6 +392,109 @@ test('hasTool - is case-sensitive', t => {
 	}
 });
 
+test('describeTool - returns the full input schema for a known tool', async t => {
+	const manager = new ToolManager();
+
+	const description = await manager.describeTool('read_file');
+
+	t.is(description?.name, 'read_file');
+	t.is(description?.category, 'file-read');
+	t.truthy(description?.description);
+	const properties = description?.inputSchema.properties as Record<
+		string,
+		{type?: string}
+	>;
+	t.is(properties.path.type, 'string');
+	t.deepEqual(description?.inputSchema.required, ['path']);
+});
+
+test('describeTool - returns undefined for an unknown tool', async t => {
+	const manager = new ToolManager();
+
+	t.is(await manager.describeTool('definitely-not-a-real-tool-xyz'), undefined);
+});
+
+test('describeTool - includes glob examples for find_files', async t => {
+	const manager = new ToolManager();
+
+	const description = await manager.describeTool('find_files');
+
+	const globs = (description?.examples ?? []).filter(example =>
+		/[*?{]/.test(String(example.arguments.pattern)),
+	);
+	t.true(globs.length >= 1);
+	t.truthy(globs[0].description);
+});
+
+test('describeTool - leaves examples out for tools without any', async t => {
+	const manager = new ToolManager();
+
+	const description = await manager.describeTool('read_file');
+
+	t.false(description !== undefined && 'examples' in description);
+	t.deepEqual(manager.getToolExamples('read_file'), []);
+	t.deepEqual(manager.getToolExamples('definitely-not-a-real-tool-xyz'), []);
+});
+
+test('listTools - examples give every required argument', async t => {
+	const manager = new ToolManager();
+
+	const tools = await manager.listTools();
+
+	for (const tool of tools) {
+		const required = (tool.inputSchema.required ?? []) as string[];
+		for (const example of tool.examples ?? []) {
+			for (const name of required) {
+				t.true(
+					name in example.arguments,
+					`${tool.name}: ${example.description}`,
+				);
+			}
+		}
+	}
+	t.true(tools.some(tool => tool.name === 'string_replace' && tool.examples));
+});
+
+test('listTools - returns every tool without a filter', async t => {
+	const manager = new ToolManager();
+
+	const tools = await manager.listTools();
+
+	t.deepEqual(
+		tools.map(tool => tool.name).sort(),
+		manager.getToolNames().sort(),
+	);
+	t.truthy(tools[0].inputSchema);
+});
+
+test('listTools - filters by a list of names', async t => {
+	const manager = new ToolManager();
+
+	const tools = await manager.listTools({
+		names: ['read_file', 'find_files', 'definitely-not-a-real-tool-xyz'],
+	});
+
+	t.deepEqual(tools.map(tool => tool.name).sort(), ['find_files', 'read_file']);
+});
+
+test('listTools - filters by name prefix and category', async t => {
+	const manager = new ToolManager();
+
+	const prefixed = await manager.listTools({namePrefix: 'read_'});
+	t.true(prefixed.length > 0);
+	t.true(prefixed.every(tool => tool.name.startsWith('read_')));
+
+	const webTools = await manager.listTools({categories: ['web']});
+	t.true(webTools.some(tool => tool.name === 'fetch_url'));
+	t.true(webTools.every(tool => tool.category === 'web'));
+
+	// Filters combine
+	t.deepEqual(
+		await manager.listTools({categories: ['web'], namePrefix: 'read_'}),
+		[],
+	);
+});
+
 // ============================================================================
 // MCP Tool Info Tests
 // ============================================================================
@@ -551,3 +844,49 @@ test('concurrent - multiple simultaneous tool accesses', t => {
 	t.true(new Set(names).size === 1, 'Tool names count should be consistent');
 	t.true(new Set(tools).size === 1, 'All tools count should be consistent');
 });
+
+// ============================================================================
+// Category Tests
+// ============================================================================
+
+test('getToolCategory - assigns a category to each built-in tool', t => {
+	const manager = new ToolManager();
+
+	const expected = {
+		read_file: 'file-read',
+		list_directory: 'file-read',
+		write_file: 'file-write',
+		string_replace: 'file-write',
+		find_files: 'search',
+		search_file_contents: 'search',
+		web_search: 'web',
+		fetch_url: 'web',
+		execute_bash: 'shell',
+		lsp_get_diagnostics: 'other',
+		git_smart_commit: 'other',
+		git_create_pr: 'other',
+		git_branch_suggest: 'other',
+		git_status_enhanced: 'other',
+	};
+
+	for (const [toolName, category] of Object.entries(expected)) {
+		t.is(manager.getToolCategory(toolName), category, toolName);
+	}
+	t.is(manager.getToolCount(), Object.keys(expected).length);
+});
+
+test('getToolNamesByCategory - lists every tool of a category', t => {
+	const manager = new ToolManager();
+
+	t.deepEqual(manager.getToolNamesByCategory('file-read').sort(), [
+		'list_directory',
+		'read_file',
+	]);
+	t.deepEqual(manager.getToolNamesByCategory('mcp'), []);
+});
+
+test('getToolCategory - returns undefined for non-existent tool', t => {
+	const manager = new ToolManager();
+
+	t.is(manager.getToolCategory('nonexistent_tool'), undefined);
+});
@@ -1,23 +1,60 @@
+import {asSchema} from 'ai';
+import {appConfig} from '@/config/index';
+import {MAX_TOOL_RESULT_BYTES} from '@/constants';
 import {MCPClient} from '@/mcp/mcp-client';
 import {
+	idempotentTools as staticIdempotentTools,
 	nativeToolsRegistry as staticNativeToolsRegistry,
+	toolCategories as staticToolCategories,
+	toolExamples as staticToolExamples,
 	toolFormatters as staticToolFormatters,
 	toolRegistry as staticToolRegistry,
 	toolStreamingFormatters as staticToolStreamingFormatters,
 	toolValidators as staticToolValidators,
 } from '@/tools/index';
 import {ToolRegistry} from '@/tools/tool-registry';
+import type {ToolResultCache} from '@/tools/tool-result-cache';
 import type {
 	AISDKCoreTool,
 	MCPInitResult,
+	MCPLogLevel,
 	MCPServer,
+	MCPServerLogMessage,
+	MCPServerStatus,
 	MCPTool,
 	StreamingFormatter,
+	ToolCategory,
+	ToolContext,
 	ToolEntry,
+	ToolExample,
 	ToolFormatter,
 	ToolHandler,
 	ToolValidator,
 } from '@/types/index';
+import {logWarning} from '@/utils/message-queue';
+import {truncateToolResult} from '@/utils/tool-result-truncation';
+
+/**
+ * A tool's definition with its full JSON Schema for arguments
+ */
+export interface ToolDescription {
+	name: string;
+	description?: string;
+	inputSchema: Record<string, unknown>;
+	category: ToolCategory;
+	// Sample calls, for tools that have them
+	examples?: ToolExample[];
+}
+
+/**
+ * Narrows listTools to tools in some categories, with a name prefix (such as
+ * an MCP server's tool prefix), or with exact names
+ */
+export interface ToolListFilter {
+	categories?: ToolCategory[];
+	namePrefix?: string;
+	names?: string[];
+}
 
 /**
  * Manages both static tools and dynamic MCP tools
@@ -34,15 +71,51 @@ export class ToolManager {
 	 */
 	private mcpClient: MCPClient | null = null;
 
-	constructor() {
-		// Initialize with static tools using ToolRegistry factory method
-		this.registry = ToolRegistry.fromRegistries(
+	/**
+	 * Built-in tools excluded from the registry by configuration
+	 */
+	private disabledTools: string[];
+
+	/**
+	 * Cache for results of idempotent tools, set when cacheToolResults is on
+	 */
+	private resultCache: ToolResultCache | null = null;
+
+	/**
+	 * Passed to every tool run as experimental_context
+	 */
+	private toolContext: ToolContext = {};
+
+	constructor(disabledTools: string[] = []) {
+		const unknownTools = disabledTools.filter(
+			name => !(name in staticToolRegistry),
+		);
+		if (unknownTools.length > 0) {
+			logWarning(
+				`Unknown tools in disabledTools will be ignored: ${unknownTools.join(', ')}`,
+			);
+		}
+
+		this.disabledTools = disabledTools.filter(
+			name => name in staticToolRegistry,
+		);
+		this.registry = this.createStaticRegistry();
+	}
+
+	/**
+	 * Build a registry of the static tools, minus any disabled ones
+	 */
+	private createStaticRegistry(): ToolRegistry {
+		const registry = ToolRegistry.fromRegistries(
 			staticToolRegistry,
 			staticNativeToolsRegistry,
 			staticToolFormatters,
 			staticToolValidators,
 			staticToolStreamingFormatters,
+			staticToolCategories,
 		);
+		registry.unregisterMany(this.disabledTools);
+		return registry;
 	}
 
 	/**
@@ -51,9 +124,10 @@ export class ToolManager {
 	async initializeMCP(
 		servers: MCPServer[],
 		onProgress?: (result: MCPInitResult) => void,
+		onServerLog?: (message: MCPServerLogMessage) => void,
 	): Promise<MCPInitResult[]> {
 		if (servers && servers.length > 0) {
-			this.mcpClient = new MCPClient();
+			this.mcpClient = new MCPClient(onServerLog);
 
 			const results = await this.mcpClient.connectToServers(
 				servers,
@@ -70,25 +144,141 @@ export class ToolManager {
 		return [];
 	}
 
+	/**
+	 * Set or clear the cache for results of idempotent tools
+	 */
+	setResultCache(cache: ToolResultCache | null): void {
+		this.resultCache = cache;
+	}
+
+	/**
+	 * Set the model tools count tokens for, e.g. to size read_file chunks
+	 * @param model - The current model, or an empty string for none
+	 * @param provider - The provider name, which helps with ambiguous model IDs
+	 */
+	setCurrentModel(model: string, provider = ''): void {
+		this.toolContext = {
+			...this.toolContext,
+			model: model ? {model, provider} : undefined,
+		};
+	}
+
+	/**
+	 * Run a tool and cap its result, so one runaway tool (often MCP) can't
+	 * flood the model's context. Every way of executing a tool goes through
+	 * here.
+	 */
+	private async runTool<T>(
+		toolName: string,
+		args: Record<string, unknown>,
+		run: () => Promise<T>,
+	): Promise<T> {
+		const result = await this.runWithCache(toolName, args, run);
+		if (typeof result !== 'string') {
+			return result;
+		}
+		const {content} = truncateToolResult(
+			result,
+			appConfig.maxToolResultBytes ?? MAX_TOOL_RESULT_BYTES,
+		);
+		return content as T;
+	}
+
+	/**
+	 * Reuses a cached result for an idempotent tool, or runs it. Results of
+	 * idempotent tools are cached; any other tool may have changed what they
+	 * read, so a file write invalidates entries for its path and anything
+	 * else clears the cache.
+	 */
+	private async runWithCache<T>(
+		toolName: string,
+		args: Record<string, unknown>,
+		run: () => Promise<T>,
+	): Promise<T> {
+		const cache = this.resultCache;
+		if (!cache) {
+			return run();
+		}
+
+		if (this.isIdempotent(toolName)) {
+			const cached = cache.get(toolName, args);
+			if (cached !== undefined) {
+				return cached as T;
+			}
+			const result = await run();
+			if (typeof result === 'string') {
+				cache.set(toolName, args, result);
+			}
+			return result;
+		}
+
+		try {
+			return await run();
+		} finally {
+			if (
+				this.getToolCategory(toolName) === 'file-write' &&
+				typeof args.path === 'string'
+			) {
+				cache.invalidatePath(args.path);
+			} else {
+				cache.clear();
+			}
+		}
+	}
+
 	/**
 	 * Get all available native AI SDK tools (static + MCP)
 	 */
 	getAllTools(): Record<string, AISDKCoreTool> {
-		return this.registry.getNativeTools();
+		const tools = this.registry.getNativeTools();
+		return Object.fromEntries(
+			Object.entries(tools).map(([name, tool]): [string, AISDKCoreTool] => {
+				const {execute} = tool;
+				if (!execute) {
+					return [name, tool];
+				}
+				return [
+					name,
+					{
+						...tool,
+						execute: (args, options) =>
+							this.runTool(name, args, async () =>
+								execute(args, {
+									...options,
+									experimental_context: this.toolContext,
+								}),
+							),
+					},
+				];
+			}),
+		);
 	}
 
 	/**
 	 * Get all tool handlers
 	 */
 	getToolRegistry(): Record<string, ToolHandler> {
-		return this.registry.getHandlers();
+		const handlers = this.registry.getHandlers();
+		return Object.fromEntries(
+			Object.keys(handlers).map(name => [
+				name,
+				this.getToolHandler(name) as ToolHandler,
+			]),
+		);
 	}
 
 	/**
 	 * Get a specific tool handler
 	 */
 	getToolHandler(toolName: string): ToolHandler | undefined {
-		return this.registry.getHandler(toolName);
+		const handler = this.registry.getHandler(toolName);
+		if (!handler) {
+			return undefined;
+		}
+		return (input, signal) =>
+			this.runTool(toolName, input, () =>
+				handler(input, signal, this.toolContext),
+			);
 	}
 
 	/**
@@ -112,6 +302,39 @@ export class ToolManager {
 		return this.registry.getStreamingFormatter(toolName);
 	}
 
+	/**
+	 * Get a specific tool's category
+	 */
+	getToolCategory(toolName: string): ToolCategory | undefined {
+		return this.registry.getCategory(toolName);
+	}
+
+	/**
+	 * Check if a tool's results can be cached. Only built-in tools can be; MCP
+	 * tools don't say whether they have side effects.
+	 */
+	isIdempotent(toolName: string): boolean {
+		return (
+			staticIdempotentTools.has(toolName) && this.registry.hasTool(toolName)
+		);
+	}
+
+	/**
+	 * Get a tool's sample calls. Only built-in tools have them; empty otherwise.
+	 */
+	getToolExamples(toolName: string): ToolExample[] {
+		return this.registry.hasTool(toolName)
+			? (staticToolExamples[toolName] ?? [])
+			: [];
+	}
+
+	/**
+	 * Get all tool names in a category (e.g. every 'file-read' tool)
+	 */
+	getToolNamesByCategory(category: ToolCategory): string[] {
+		return this.registry.getToolNamesByCategory(category);
+	}
+
 	/**
 	 * Check if a tool exists
 	 */
@@ -140,6 +363,37 @@ export class ToolManager {
 		return {isMCPTool: false};
 	}
 
+	/**
+	 * Send any JSON-RPC request to a connected MCP server, for debugging
+	 * @returns The server's raw result
+	 * @throws Error when MCP isn't initialized or the server isn't connected
+	 */
+	async rawMCPRequest(
+		serverName: string,
+		method: string,
+		params?: Record<string, unknown>,
+	): Promise<Record<string, unknown>> {
+		if (!this.mcpClient) {
+			throw new Error('No MCP servers are connected');
+		}
+		return this.mcpClient.rawRequest(serverName, method, params);
+	}
+
+	/**
+	 * Set a connected MCP server's log level with logging/setLevel
+	 * @returns false when the server doesn't support logging
+	 * @throws Error when MCP isn't initialized or the server isn't connected
+	 */
+	async setMCPLogLevel(
+		serverName: string,
+		level: MCPLogLevel,
+	): Promise<boolean> {
+		if (!this.mcpClient) {
+			throw new Error('No MCP servers are connected');
+		}
+		return this.mcpClient.setLogLevel(serverName, level);
+	}
+
 	/**
 	 * Disconnect from MCP servers and remove their tools
 	 */
@@ -156,13 +410,7 @@ export class ToolManager {
 			await this.mcpClient.disconnect();
 
 			// Reset registry to only static tools
-			this.registry = ToolRegistry.fromRegistries(
-				staticToolRegistry,
-				staticNativeToolsRegistry,
-				staticToolFormatters,
-				staticToolValidators,
-				staticToolStreamingFormatters,
-			);
+			this.registry = this.createStaticRegistry();
 
 			this.mcpClient = null;
 		}
@@ -177,6 +425,52 @@ export class ToolManager {
 		return this.registry.getEntry(toolName);
 	}
 
+	/**
+	 * Describe a tool with its full JSON Schema for arguments
+	 *
+	 * Returns undefined if no tool with that name is registered
+	 */
+	async describeTool(toolName: string): Promise<ToolDescription | undefined> {
+		const entry = this.registry.getEntry(toolName);
+		if (!entry) {
+			return undefined;
+		}
+
+		const inputSchema = await asSchema(entry.tool.inputSchema).jsonSchema;
+		const examples = this.getToolExamples(toolName);
+		return {
+			name: entry.name,
+			description: entry.tool.description,
+			inputSchema: inputSchema as Record<string, unknown>,
+			category: entry.category ?? 'other',
+			...(examples.length > 0 && {examples}),
+		};
+	}
+
+	/**
+	 * Describe every tool matching the filter, or all tools without one
+	 *
+	 * Filters combine: a tool must match each one that is given
+	 */
+	async listTools(filter: ToolListFilter = {}): Promise<ToolDescription[]> {
+		const {categories, namePrefix, names} = filter;
+		const matching = this.registry.getAllEntries().filter(
+			entry =>
+				(!categories ||
+					categories.includes(entry.category ?? 'other')) &&
+				(!namePrefix || entry.name.startsWith(namePrefix)) &&
+				(!names || names.includes(entry.name)),
+		);
+
+		const descriptions = await Promise.all(
+			matching.map(entry => this.describeTool(entry.name)),
+		);
+		return descriptions.filter(
+			(description): description is ToolDescription =>
+				description !== undefined,
+		);
+	}
+
 	/**
 	 * Get all registered tool names
 	 */
@@ -212,6 +506,13 @@ export class ToolManager {
 		return this.mcpClient?.getServerInfo(serverName);
 	}
 
+	/**
+	 * Get connection health and tool counts for all MCP servers
+	 */
+	getServerStatus(): MCPServerStatus[] {
+		return this.mcpClient?.getServerStatus() || [];
+	}
+
 	/**
 	 * Get the MCP client instance
 	 */
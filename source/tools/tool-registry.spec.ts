@@ -276,6 +276,25 @@ test('ToolRegistry - getNativeTools returns record of native tools', t => {
 	t.is(nativeTools.tool2, tool2);
 });
 
+test('ToolRegistry - getNativeToolsFor returns only the named tools', t => {
+	const registry = new ToolRegistry();
+	const tool1: ToolEntry['tool'] = { execute: async () => 'test 1' } as any;
+	const tool2: ToolEntry['tool'] = { execute: async () => 'test 2' } as any;
+	const tool3: ToolEntry['tool'] = { execute: async () => 'test 3' } as any;
+
+	registry.registerMany([
+		createMockToolEntry({ name: 'tool1', tool: tool1 }),
+		createMockToolEntry({ name: 'tool2', tool: tool2 }),
+		createMockToolEntry({ name: 'tool3', tool: tool3 })
+	]);
+
+	const nativeTools = registry.getNativeToolsFor(['tool1', 'tool3', 'missing']);
+
+	t.deepEqual(Object.keys(nativeTools), ['tool1', 'tool3']);
+	t.is(nativeTools.tool1, tool1);
+	t.is(nativeTools.tool3, tool3);
+});
+
 test('ToolRegistry - getAllEntries returns array of all entries', t => {
 	const registry = new ToolRegistry();
 	const entry1 = createMockToolEntry({ name: 'tool1' });
@@ -413,3 +432,93 @@ test('ToolRegistry - fromRegistries skips tools without matching handlers', t =>
 	t.true(registry.hasTool('tool1'));
 	t.false(registry.hasTool('tool2'));
 });
+
+test('ToolRegistry - getCategory returns the entry category', t => {
+	const registry = new ToolRegistry();
+	registry.register(createMockToolEntry({ name: 'reader', category: 'file-read' }));
+
+	t.is(registry.getCategory('reader'), 'file-read');
+});
+
+test('ToolRegistry - getCategory defaults to other', t => {
+	const registry = new ToolRegistry();
+	registry.register(createMockToolEntry());
+
+	t.is(registry.getCategory('test-tool'), 'other');
+	t.is(registry.getCategory('missing'), undefined);
+});
+
+test('ToolRegistry - getToolNamesByCategory groups tools', t => {
+	const registry = new ToolRegistry();
+	registry.registerMany([
+		createMockToolEntry({ name: 'reader', category: 'file-read' }),
+		createMockToolEntry({ name: 'lister', category: 'file-read' }),
+		createMockToolEntry({ name: 'writer', category: 'file-write' }),
+	]);
+
+	t.deepEqual(registry.getToolNamesByCategory('file-read'), ['reader', 'lister']);
+	t.deepEqual(registry.getCategories(), {
+		reader: 'file-read',
+		lister: 'file-read',
+		writer: 'file-write',
+	});
+});
+
+test('ToolRegistry - fromRegistries applies categories', t => {
+	const handler: ToolEntry['handler'] = async () => 'test';
+	const tool: ToolEntry['tool'] = { execute: async () => 'test' } as any;
+
+	const registry = ToolRegistry.fromRegistries(
+		{ tool1: handler, tool2: handler },
+		{ tool1: tool, tool2: tool },
+		undefined,
+		undefined,
+		undefined,
+		{ tool1: 'web' },
+	);
+
+	t.is(registry.getCategory('tool1'), 'web');
+	t.is(registry.getCategory('tool2'), 'other');
+});
+
+test('ToolRegistry - register replaces a tool with the same name', t => {
+	const registry = new ToolRegistry();
+	const replacementHandler: ToolEntry['handler'] = async () => 'replacement';
+	registry.register(createMockToolEntry({ category: 'file-read' }));
+
+	registry.register(
+		createMockToolEntry({ handler: replacementHandler, category: 'mcp' }),
+	);
+
+	t.is(registry.getToolCount(), 1);
+	t.is(registry.getHandler('test-tool'), replacementHandler);
+	t.is(registry.getCategory('test-tool'), 'mcp');
+});
+
+test('ToolRegistry - tryRegister rejects a name collision', t => {
+	const registry = new ToolRegistry();
+	const original = createMockToolEntry({ category: 'file-read' });
+	registry.tryRegister(original);
+
+	t.throws(
+		() => registry.tryRegister(createMockToolEntry({ category: 'mcp' })),
+		{ message: /Tool "test-tool" is already registered \(category: file-read\)/ },
+	);
+	t.is(registry.getEntry('test-tool'), original);
+});
+
+test('ToolRegistry - registerWithOverride replaces only when allowed', t => {
+	const registry = new ToolRegistry();
+	const original = createMockToolEntry();
+	const replacement = createMockToolEntry({ category: 'mcp' });
+	registry.registerWithOverride(original, false);
+
+	t.throws(() => registry.registerWithOverride(replacement, false), {
+		message: /already registered/,
+	});
+	t.is(registry.getEntry('test-tool'), original);
+
+	registry.registerWithOverride(replacement, true);
+	t.is(registry.getEntry('test-tool'), replacement);
+	t.is(registry.getToolCount(), 1);
+});
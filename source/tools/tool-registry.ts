@@ -1,11 +1,13 @@
 import type {
 	AISDKCoreTool,
 	StreamingFormatter,
+	ToolCategory,
 	ToolEntry,
 	ToolFormatter,
 	ToolHandler,
 	ToolValidator,
 } from '@/types/index';
+import {getLogger} from '@/utils/logging';
 
 /**
  * Helper class to encapsulate tool registry management
@@ -23,10 +25,49 @@ export class ToolRegistry {
 	private tools: Map<string, ToolEntry> = new Map();
 
 	/**
-	 * Register a complete tool entry
+	 * Register a complete tool entry, replacing any tool with the same name.
+	 * A warning is logged when an existing tool is replaced.
 	 * @param entry - The ToolEntry containing all tool metadata
 	 */
 	register(entry: ToolEntry): void {
+		const existing = this.tools.get(entry.name);
+		if (existing) {
+			getLogger().warn('Tool registration replaced an existing tool', {
+				toolName: entry.name,
+				previousCategory: existing.category ?? 'other',
+				category: entry.category ?? 'other',
+			});
+		}
+		this.tools.set(entry.name, entry);
+	}
+
+	/**
+	 * Register a tool entry only if no tool with the same name exists
+	 * @param entry - The ToolEntry containing all tool metadata
+	 * @throws Error if a tool with the same name is already registered
+	 */
+	tryRegister(entry: ToolEntry): void {
+		const existing = this.tools.get(entry.name);
+		if (existing) {
+			throw new Error(
+				`Tool "${entry.name}" is already registered (category: ${existing.category ?? 'other'})`,
+			);
+		}
+		this.tools.set(entry.name, entry);
+	}
+
+	/**
+	 * Register a tool entry with explicit intent about name collisions
+	 * @param entry - The ToolEntry containing all tool metadata
+	 * @param allowOverride - Replace an existing tool silently when true,
+	 * throw on a collision when false
+	 * @throws Error if the name is taken and allowOverride is false
+	 */
+	registerWithOverride(entry: ToolEntry, allowOverride: boolean): void {
+		if (!allowOverride) {
+			this.tryRegister(entry);
+			return;
+		}
 		this.tools.set(entry.name, entry);
 	}
 
@@ -103,6 +144,16 @@ export class ToolRegistry {
 		return this.tools.get(name)?.streamingFormatter;
 	}
 
+	/**
+	 * Get a tool's category by name
+	 * @param name - The tool name
+	 * @returns The ToolCategory ('other' if the tool has none) or undefined if not found
+	 */
+	getCategory(name: string): ToolCategory | undefined {
+		const entry = this.tools.get(name);
+		return entry ? (entry.category ?? 'other') : undefined;
+	}
+
 	/**
 	 * Get the native AI SDK tool by name
 	 * @param name - The tool name
@@ -152,6 +203,29 @@ export class ToolRegistry {
 		return validators;
 	}
 
+	/**
+	 * Get every tool's category as a record
+	 * @returns Record mapping tool names to categories
+	 */
+	getCategories(): Record<string, ToolCategory> {
+		const categories: Record<string, ToolCategory> = {};
+		for (const [name, entry] of this.tools) {
+			categories[name] = entry.category ?? 'other';
+		}
+		return categories;
+	}
+
+	/**
+	 * Get the names of all tools in a category
+	 * @param category - The category to match
+	 * @returns Array of tool names in that category
+	 */
+	getToolNamesByCategory(category: ToolCategory): string[] {
+		return Object.entries(this.getCategories())
+			.filter(([, toolCategory]) => toolCategory === category)
+			.map(([name]) => name);
+	}
+
 	/**
 	 * Get all native AI SDK tools as a record (compatible with old API)
 	 * @returns Record mapping tool names to AISDKCoreTool objects
@@ -164,6 +238,25 @@ export class ToolRegistry {
 		return nativeTools;
 	}
 
+	/**
+	 * Get the native AI SDK tools for just the named tools, for requests that
+	 * only need a subset. Unknown names are skipped with a debug log.
+	 * @param names - The tool names to include
+	 * @returns Record mapping the found tool names to AISDKCoreTool objects
+	 */
+	getNativeToolsFor(names: string[]): Record<string, AISDKCoreTool> {
+		const nativeTools: Record<string, AISDKCoreTool> = {};
+		for (const name of names) {
+			const entry = this.tools.get(name);
+			if (!entry) {
+				getLogger().debug('Skipping unknown tool', {toolName: name});
+				continue;
+			}
+			nativeTools[name] = entry.tool;
+		}
+		return nativeTools;
+	}
+
 	/**
 	 * Get all tool entries
 	 * @returns Array of all ToolEntry objects
@@ -211,6 +304,7 @@ export class ToolRegistry {
 	 * @param formatters - Optional record of tool formatters
 	 * @param validators - Optional record of tool validators
 	 * @param streamingFormatters - Optional record of streaming formatters
+	 * @param categories - Optional record of tool categories
 	 * @returns New ToolRegistry instance
 	 */
 	static fromRegistries(
@@ -219,6 +313,7 @@ export class ToolRegistry {
 		formatters?: Record<string, ToolFormatter>,
 		validators?: Record<string, ToolValidator>,
 		streamingFormatters?: Record<string, StreamingFormatter>,
+		categories?: Record<string, ToolCategory>,
 	): ToolRegistry {
 		const registry = new ToolRegistry();
 
@@ -232,6 +327,7 @@ export class ToolRegistry {
 					formatter: formatters?.[name],
 					validator: validators?.[name],
 					streamingFormatter: streamingFormatters?.[name],
+					category: categories?.[name],
 				});
 			}
 		}
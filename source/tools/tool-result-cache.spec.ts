@@ -0,0 +1,80 @@
+import {mkdtempSync, rmSync, utimesSync, writeFileSync} from 'node:fs';
+import {tmpdir} from 'node:os';
+import {join, resolve} from 'node:path';
+import test from 'ava';
+import {ToolResultCache} from './tool-result-cache';
+
+console.log(`\ntool-result-cache.spec.ts`);
+
+test('ToolResultCache: returns a cached result for the same tool and arguments', t => {
+	const cache = new ToolResultCache();
+	const args = {path: 'a.ts', start_line: 1, end_line: 5};
+	cache.set('read_file', args, 'lines');
+
+	// Argument order doesn't matter
+	t.is(
+		cache.get('read_file', {end_line: 5, start_line: 1, path: 'a.ts'}),
+		'lines',
+	);
+	t.is(cache.get('read_file', {...args, start_line: 2}), undefined);
+	t.is(cache.get('list_directory', args), undefined);
+});
+
+test('ToolResultCache: entries expire after the TTL', async t => {
+	const cache = new ToolResultCache({ttl: 20});
+	cache.set('find_files', {pattern: '*.ts'}, 'a.ts');
+
+	t.is(cache.get('find_files', {pattern: '*.ts'}), 'a.ts');
+	await new Promise(resolveDelay => setTimeout(resolveDelay, 50));
+	t.is(cache.get('find_files', {pattern: '*.ts'}), undefined);
+});
+
+test('ToolResultCache: evicts the oldest entry past maxSize', t => {
+	const cache = new ToolResultCache({maxSize: 2});
+	cache.set('read_file', {path: 'a.ts'}, 'a');
+	cache.set('read_file', {path: 'b.ts'}, 'b');
+	cache.set('read_file', {path: 'c.ts'}, 'c');
+
+	t.is(cache.get('read_file', {path: 'a.ts'}), undefined);
+	t.is(cache.get('read_file', {path: 'c.ts'}), 'c');
+});
+
+test('ToolResultCache: a write invalidates what it could have changed', t => {
+	const cache = new ToolResultCache();
+	const dir = resolve('src');
+	cache.set('read_file', {path: join(dir, 'a.ts')}, 'a');
+	cache.set('read_file', {path: join(dir, 'b.ts')}, 'b');
+	cache.set('list_directory', {path: dir}, 'a.ts b.ts');
+	cache.set('list_directory', {path: `${dir}-other`}, 'c.ts');
+	cache.set('search_file_contents', {query: 'foo'}, 'a.ts:1');
+
+	// A relative path is resolved like the tools resolve it
+	cache.invalidatePath(join('src', 'a.ts'));
+
+	t.is(cache.get('read_file', {path: join(dir, 'a.ts')}), undefined);
+	t.is(cache.get('list_directory', {path: dir}), undefined);
+	t.is(cache.get('search_file_contents', {query: 'foo'}), undefined);
+	t.is(cache.get('read_file', {path: join(dir, 'b.ts')}), 'b');
+	t.is(cache.get('list_directory', {path: `${dir}-other`}), 'c.ts');
+});
+
+test('ToolResultCache: drops a result once its file changes on disk', t => {
+	const dir = mkdtempSync(join(tmpdir(), 'tool-result-cache-'));
+	const file = join(dir, 'a.ts');
+	writeFileSync(file, 'one');
+	utimesSync(file, 1000, 1000);
+
+	try {
+		const cache = new ToolResultCache();
+		cache.set('read_file', {path: file}, 'one');
+		t.is(cache.get('read_file', {path: file}), 'one');
+
+		// Edited outside nanocoder, e.g. in another editor
+		writeFileSync(file, 'two');
+		utimesSync(file, 2000, 2000);
+
+		t.is(cache.get('read_file', {path: file}), undefined);
+	} finally {
+		rmSync(dir, {recursive: true, force: true});
+	}
+});
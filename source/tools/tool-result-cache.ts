@@ -0,0 +1,90 @@
+import {statSync} from 'node:fs';
+import {resolve, sep} from 'node:path';
+import {CACHE_TOOL_RESULT_TTL_MS, MAX_CACHED_TOOL_RESULTS} from '@/constants';
+import {BoundedMap} from '@/utils/bounded-map';
+import {computeContentHash} from '@/utils/content-hash';
+import {canonicalJSON} from '@/utils/tool-args-parser';
+
+interface CachedResult {
+	result: string;
+	// Absolute path the tool read, when it was given one
+	path?: string;
+	// The path's modification time when the result was cached
+	mtimeMs?: number;
+}
+
+export interface ToolResultCacheOptions {
+	ttl?: number;
+	maxSize?: number;
+}
+
+function modifiedAt(path: string): number | undefined {
+	try {
+		return statSync(path).mtimeMs;
+	} catch {
+		return undefined;
+	}
+}
+
+/**
+ * Results of idempotent tools, keyed by a hash of the tool name and its
+ * arguments. Entries expire after the TTL, the oldest are evicted past
+ * maxSize, and writes invalidate the entries they could have changed.
+ * An entry for a path is also dropped once the path's modification time
+ * changes, which catches edits made outside nanocoder.
+ */
+export class ToolResultCache {
+	private entries: BoundedMap<string, CachedResult>;
+
+	constructor({
+		ttl = CACHE_TOOL_RESULT_TTL_MS,
+		maxSize = MAX_CACHED_TOOL_RESULTS,
+	}: ToolResultCacheOptions = {}) {
+		this.entries = new BoundedMap({ttl, maxSize});
+	}
+
+	private key(toolName: string, args: Record<string, unknown>): string {
+		return computeContentHash(`${toolName}\0${canonicalJSON(args)}`);
+	}
+
+	get(toolName: string, args: Record<string, unknown>): string | undefined {
+		const key = this.key(toolName, args);
+		const entry = this.entries.get(key);
+		if (entry?.path && modifiedAt(entry.path) !== entry.mtimeMs) {
+			this.entries.delete(key);
+			return undefined;
+		}
+		return entry?.result;
+	}
+
+	set(toolName: string, args: Record<string, unknown>, result: string): void {
+		const path = typeof args.path === 'string' ? resolve(args.path) : undefined;
+		this.entries.set(this.key(toolName, args), {
+			result,
+			path,
+			mtimeMs: path ? modifiedAt(path) : undefined,
+		});
+	}
+
+	/**
+	 * Drops every entry a write to this path could have changed: reads of the
+	 * path itself, listings of a directory above it, and file searches that
+	 * weren't limited to a path
+	 */
+	invalidatePath(path: string): void {
+		const written = resolve(path);
+		for (const [key, entry] of [...this.entries.entries()]) {
+			if (
+				!entry.path ||
+				entry.path === written ||
+				written.startsWith(`${entry.path}${sep}`)
+			) {
+				this.entries.delete(key);
+			}
+		}
+	}
+
+	clear(): void {
+		this.entries.clear();
+	}
+}
@@ -1,6 +1,7 @@
 import test from 'ava';
 import {render} from 'ink-testing-library';
 import React from 'react';
+import {appConfig} from '../config/index';
 import {themes} from '../config/themes';
 import {ThemeContext} from '../hooks/useTheme';
 import {
@@ -440,6 +441,33 @@ test('executeWebSearch throws on HTTP error status', async t => {
 	}
 });
 
+test.serial('executeWebSearch applies the fetchUrl host policy', async t => {
+	if (!executeWebSearch) {
+		t.pass('Skipping test - web-search module not available');
+		return;
+	}
+
+	const previous = appConfig.fetchUrl;
+	appConfig.fetchUrl = {blockedHosts: ['search.brave.com']};
+	let fetched = false;
+	const originalFetch = globalThis.fetch;
+	globalThis.fetch = (async () => {
+		fetched = true;
+		return {ok: true, status: 200, text: async () => ''} as any;
+	}) as any;
+
+	try {
+		await t.throwsAsync(
+			async () => await executeWebSearch({query: 'test'}),
+			{message: /Host is blocked: search\.brave\.com/},
+		);
+		t.false(fetched);
+	} finally {
+		globalThis.fetch = originalFetch;
+		appConfig.fetchUrl = previous;
+	}
+});
+
 test('executeWebSearch handles non-Error objects in catch', async t => {
 	if (!executeWebSearch) {
 		t.pass('Skipping test - web-search module not available');
@@ -1,8 +1,8 @@
 import * as cheerio from 'cheerio';
 import {Box, Text} from 'ink';
 import React from 'react';
-import {fetch} from 'undici';
 
+import {appConfig} from '@/config/index';
 import {
 	DEFAULT_WEB_SEARCH_RESULTS,
 	MAX_WEB_SEARCH_QUERY_LENGTH,
@@ -12,6 +12,7 @@ import {useTheme} from '@/hooks/useTheme';
 import type {NanocoderToolExport} from '@/types/core';
 import {jsonSchema, tool} from '@/types/core';
 import {calculateTokens} from '@/utils/token-calculator';
+import {fetchWithPolicy} from '@/utils/url-policy';
 
 interface SearchArgs {
 	query: string;
@@ -32,14 +33,20 @@ const executeWebSearch = async (args: SearchArgs): Promise<string> => {
 		// Use Brave Search - scraper-friendly, no CAPTCHA
 		const searchUrl = `https://search.brave.com/search?q=${encodedQuery}`;
 
-		const response = await fetch(searchUrl, {
-			headers: {
-				'User-Agent':
-					'Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36',
-				Accept: 'text/html',
+		// The fetch_url policy applies, and redirects are checked so the search
+		// cannot be steered to internal or blocked hosts
+		const {response} = await fetchWithPolicy(
+			searchUrl,
+			appConfig.fetchUrl ?? {},
+			{
+				headers: {
+					'User-Agent':
+						'Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36',
+					Accept: 'text/html',
+				},
+				signal: AbortSignal.timeout(TIMEOUT_WEB_SEARCH_MS),
 			},
-			signal: AbortSignal.timeout(TIMEOUT_WEB_SEARCH_MS),
-		});
+		);
 
 		if (!response.ok) {
 			throw new Error(`HTTP ${response.status}: ${response.statusText}`);
@@ -218,6 +225,7 @@ const webSearchValidator = (
 export const webSearchTool: NanocoderToolExport = {
 	name: 'web_search' as const,
 	tool: webSearchCoreTool,
+	category: 'web',
 	formatter: webSearchFormatter,
 	validator: webSearchValidator,
 };
@@ -7,6 +7,7 @@ import React from 'react';
 import {themes} from '../config/themes.js';
 import {setCurrentMode} from '../context/mode-context.js';
 import {ThemeContext} from '../hooks/useTheme.js';
+import {computeContentHash} from '../utils/content-hash.js';
 import {writeFileTool} from './write-file.js';
 
 // ============================================================================
@@ -48,6 +49,8 @@ test.afterEach(async () => {
 async function executeWriteFile(args: {
 	path: string;
 	content: string;
+	dry_run?: boolean;
+	expected_hash?: string;
 }): Promise<string> {
 	// biome-ignore lint/suspicious/noExplicitAny: Tool internals require any
 	return await (writeFileTool.tool as any).execute(args, {
@@ -845,3 +848,81 @@ test('write_file formatter: renders syntax highlighting for code', async t => {
 test.after(() => {
 	setCurrentMode('normal');
 });
+
+// ============================================================================
+// Dry Run Tests
+// ============================================================================
+
+test('write_file: dry run returns a diff without overwriting', async t => {
+	const filePath = await createTestFile('existing.txt', 'one\ntwo\nthree\n');
+
+	const result = await executeWriteFile({
+		path: filePath,
+		content: 'one\n2\nthree\n',
+		dry_run: true,
+	});
+
+	const content = await readFile(filePath, 'utf-8');
+	t.is(content, 'one\ntwo\nthree\n');
+	t.true(result.startsWith(`Dry run: would overwrite ${filePath}`));
+	t.true(result.includes('@@ -1,3 +1,3 @@\n one\n-two\n+2\n three'));
+});
+
+test('write_file: dry run does not create a new file', async t => {
+	const filePath = join(testDir, 'new.txt');
+
+	const result = await executeWriteFile({
+		path: filePath,
+		content: 'Hello World\n',
+		dry_run: true,
+	});
+
+	await t.throwsAsync(readFile(filePath, 'utf-8'), {code: 'ENOENT'});
+	t.true(result.startsWith(`Dry run: would create ${filePath}`));
+	t.true(result.includes('+Hello World'));
+});
+
+test('write_file: dry run does not require approval', async t => {
+	setCurrentMode('normal');
+	const needsApproval = writeFileTool.tool.needsApproval;
+
+	t.is(typeof needsApproval, 'function');
+	if (typeof needsApproval === 'function') {
+		const result = await needsApproval(
+			{path: 'test.txt', content: 'test', dry_run: true},
+			{toolCallId: 'test', messages: []},
+		);
+		t.false(result);
+	}
+});
+
+// ============================================================================
+// Expected Hash Tests
+// ============================================================================
+
+test('write_file: overwrites when expected_hash matches the file', async t => {
+	const filePath = await createTestFile('existing.txt', 'Old content\n');
+
+	await executeWriteFile({
+		path: filePath,
+		content: 'New content\n',
+		expected_hash: computeContentHash('Old content\n'),
+	});
+
+	t.is(await readFile(filePath, 'utf-8'), 'New content\n');
+});
+
+test('write_file: refuses to overwrite with a stale expected_hash', async t => {
+	const filePath = await createTestFile('existing.txt', 'Changed content\n');
+
+	await t.throwsAsync(
+		executeWriteFile({
+			path: filePath,
+			content: 'New content\n',
+			expected_hash: computeContentHash('Old content\n'),
+		}),
+		{message: /changed since it was read/},
+	);
+
+	t.is(await readFile(filePath, 'utf-8'), 'Changed content\n');
+});
@@ -10,11 +10,13 @@ import {getCurrentMode} from '@/context/mode-context';
 import {ThemeContext} from '@/hooks/useTheme';
 import type {NanocoderToolExport} from '@/types/core';
 import {jsonSchema, tool} from '@/types/core';
+import {assertFileUnchanged} from '@/utils/content-hash';
 import {getCachedFileContent, invalidateCache} from '@/utils/file-cache';
 import {normalizeIndentation} from '@/utils/indentation-normalizer';
 import {isValidFilePath, resolveFilePath} from '@/utils/path-validation';
 import {getLanguageFromExtension} from '@/utils/programming-language-helper';
 import {calculateTokens} from '@/utils/token-calculator';
+import {createUnifiedDiff} from '@/utils/unified-diff';
 import {
 	closeDiffInVSCode,
 	isVSCodeConnected,
@@ -24,10 +26,26 @@ import {
 const executeWriteFile = async (args: {
 	path: string;
 	content: string;
+	dry_run?: boolean;
+	expected_hash?: string;
 }): Promise<string> => {
 	const absPath = resolve(args.path);
+
+	if (args.expected_hash) {
+		await assertFileUnchanged(absPath, args.expected_hash, args.path);
+	}
+
 	const fileExists = existsSync(absPath);
 
+	if (args.dry_run) {
+		const currentContent = fileExists
+			? await readFile(absPath, 'utf-8')
+			: '';
+		const diff = createUnifiedDiff(args.path, currentContent, args.content);
+		const action = fileExists ? 'overwrite' : 'create';
+		return `Dry run: would ${action} ${args.path}. No changes were written.\n\n${diff || 'No changes.'}`;
+	}
+
 	await writeFile(absPath, args.content, 'utf-8');
 
 	// Invalidate cache after write
@@ -55,7 +73,12 @@ const executeWriteFile = async (args: {
 const writeFileCoreTool = tool({
 	description:
 		'Write content to a file (creates new file or overwrites existing file). Use this for complete file rewrites, generated code, or when most of the file needs to change. For small targeted edits, use string_replace instead.',
-	inputSchema: jsonSchema<{path: string; content: string}>({
+	inputSchema: jsonSchema<{
+		path: string;
+		content: string;
+		dry_run?: boolean;
+		expected_hash?: string;
+	}>({
 		type: 'object',
 		properties: {
 			path: {
@@ -66,11 +89,25 @@ const writeFileCoreTool = tool({
 				type: 'string',
 				description: 'The complete content to write to the file.',
 			},
+			dry_run: {
+				type: 'boolean',
+				description:
+					'Preview the change as a unified diff without writing the file (default: false).',
+			},
+			expected_hash: {
+				type: 'string',
+				description:
+					'Optional: Hash from read_file metadata. The edit is refused if the file has changed since it was read.',
+			},
 		},
 		required: ['path', 'content'],
 	}),
 	// Medium risk: file write operation, requires approval except in auto-accept mode
-	needsApproval: () => {
+	// A dry run writes nothing, so it never needs approval
+	needsApproval: args => {
+		if (args.dry_run) {
+			return false;
+		}
 		const mode = getCurrentMode();
 		return mode !== 'auto-accept'; // true in normal/plan, false in auto-accept
 	},
@@ -83,6 +120,7 @@ interface WriteFileArgs {
 	path?: string;
 	file_path?: string;
 	content?: string;
+	dry_run?: boolean;
 }
 
 // Create a component that will re-render when theme changes
@@ -106,7 +144,9 @@ const WriteFileFormatter = React.memo(({args}: {args: WriteFileArgs}) => {
 
 	const messageContent = (
 		<Box flexDirection="column">
-			<Text color={colors.tool}>⚒ write_file</Text>
+			<Text color={colors.tool}>
+				⚒ write_file{args.dry_run ? ' (dry run)' : ''}
+			</Text>
 
 			<Box>
 				<Text color={colors.secondary}>Path: </Text>
@@ -167,7 +207,7 @@ const writeFileFormatter = async (
 	const absPath = resolve(path);
 
 	// Send diff to VS Code during preview phase (before execution)
-	if (result === undefined && isVSCodeConnected()) {
+	if (result === undefined && isVSCodeConnected() && !args.dry_run) {
 		const content = args.content || '';
 
 		// Get original content if file exists (use cache if available)
@@ -280,6 +320,7 @@ const writeFileValidator = async (args: {
 export const writeFileTool: NanocoderToolExport = {
 	name: 'write_file' as const,
 	tool: writeFileCoreTool,
+	category: 'file-write',
 	formatter: writeFileFormatter,
 	validator: writeFileValidator,
 };
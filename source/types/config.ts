@@ -4,11 +4,15 @@ import type {ThemePreset} from '@/types/ui';
 // AI provider configurations (OpenAI-compatible)
 export interface AIProviderConfig {
 	name: string;
-	type: string;
+	type: string; // 'openai' (OpenAI-compatible API) or 'ollama' (native Ollama API)
 	models: string[];
 	requestTimeout?: number;
 	socketTimeout?: number;
 	maxRetries?: number; // Maximum number of retries for failed requests (default: 2)
+	logBodies?: boolean; // Log full request/response payloads at debug level (default: false)
+	autoMaxTokens?: boolean; // Size max output tokens to the model's context window (default: false)
+	maxToolTokens?: number; // Trim tool definitions sent per request to this many tokens (default: no limit)
+	toolCalling?: 'native' | 'prompt'; // Send tools natively or describe them in the system prompt (default: 'native')
 	connectionPool?: {
 		idleTimeout?: number;
 		cumulativeMaxIdleTimeout?: number;
@@ -30,6 +34,7 @@ export interface ProviderConfig {
 	socketTimeout?: number;
 	maxRetries?: number; // Maximum number of retries for failed requests (default: 2)
 	organizationId?: string;
+	projectId?: string;
 	timeout?: number;
 	connectionPool?: {
 		idleTimeout?: number;
@@ -42,9 +47,20 @@ export interface AppConfig {
 	// Providers array structure - all OpenAI compatible
 	providers?: {
 		name: string;
+		type?: 'openai' | 'ollama'; // Client to use (default: 'openai' compatible)
 		baseUrl?: string;
 		apiKey?: string;
 		models: string[];
+		keepAlive?: string | number; // Ollama native only: how long to keep the model loaded
+		thinkingBudget?: number; // Token budget for extended thinking (Ollama: enables thinking)
+		organizationId?: string; // Sent as the OpenAI-Organization header
+		projectId?: string; // Sent as the OpenAI-Project header
+		anthropicVersion?: string; // Sent as the anthropic-version header (default: 2023-06-01 when listing Anthropic models)
+		anthropicBeta?: string[]; // Beta features, sent comma-joined as the anthropic-beta header
+		logBodies?: boolean; // Log full request/response payloads at debug level (default: false)
+		autoMaxTokens?: boolean; // Size max output tokens to the model's context window (default: false)
+		maxToolTokens?: number; // Trim tool definitions sent per request to this many tokens (default: no limit)
+		toolCalling?: 'native' | 'prompt'; // Send tools natively or describe them in the system prompt (default: 'native')
 		requestTimeout?: number;
 		socketTimeout?: number;
 		maxRetries?: number; // Maximum number of retries for failed requests (default: 2)
@@ -61,6 +77,9 @@ export interface AppConfig {
 		command?: string;
 		args?: string[];
 		env?: Record<string, string>;
+		cwd?: string; // Working directory for stdio servers
+		inheritEnv?: boolean; // Pass nanocoder's full environment to stdio servers
+		shutdownTimeout?: number; // SIGTERM grace period in ms before SIGKILL (default: 2000)
 		url?: string;
 		headers?: Record<string, string>;
 		auth?: {
@@ -72,6 +91,7 @@ export interface AppConfig {
 			customHeaders?: Record<string, string>;
 		};
 		timeout?: number;
+		startupTimeout?: number;
 		reconnect?: {
 			enabled: boolean;
 			maxAttempts: number;
@@ -90,6 +110,58 @@ export interface AppConfig {
 		languages: string[]; // File extensions this server handles
 		env?: Record<string, string>;
 	}[];
+
+	// Merge every agents.config.json found (project, user and legacy) instead
+	// of using only the closest one. Read from the closest file. (default: false)
+	mergeConfigs?: boolean;
+
+	// Names of built-in tools that should never be registered
+	disabledTools?: string[];
+
+	// Skip the rest of a batch of auto-run tool calls once one fails
+	stopToolsOnError?: boolean;
+
+	// Run identical tool calls (same tool and arguments) from one response
+	// more than once instead of keeping only the first (default: false)
+	allowDuplicateToolCalls?: boolean;
+
+	// Tool results larger than this many bytes are truncated (default: 102400)
+	maxToolResultBytes?: number;
+
+	// Reuse the results of read-only file tools (read_file, find_files, ...)
+	// called again with the same arguments within a minute. Writes invalidate
+	// the results they affect; web tools are never cached. (default: false)
+	cacheToolResults?: boolean;
+
+	// Limits for the read_file tool
+	readFile?: {
+		maxFileBytes?: number; // Larger files need start_line/end_line (default: 10485760, 10 MB)
+	};
+
+	// Network controls for the fetch_url tool
+	fetchUrl?: {
+		maxRedirects?: number; // Redirects to follow before giving up (default: 5)
+		allowedHosts?: string[]; // Only these hosts (and subdomains) may be fetched
+		blockedHosts?: string[]; // These hosts (and subdomains) are never fetched
+		allowHttpsDowngrade?: boolean; // Follow HTTPS to HTTP redirects (default: false)
+		allowPrivate?: boolean; // Allow loopback, link-local and private addresses (default: false)
+		userAgent?: string; // User-Agent header sent with every request
+		headers?: Record<string, Record<string, string>>; // Extra headers by host, sent only to that host and its subdomains
+	};
+
+	// Outbound HTTP settings for AI providers and web tools
+	network?: {
+		proxy?: string; // Proxy URL (default: HTTPS_PROXY / HTTP_PROXY)
+		noProxy?: string; // Comma-separated hosts that skip the proxy (default: NO_PROXY)
+		caCertFile?: string; // Extra PEM CA certificate(s) to trust
+	};
+}
+
+// Generation parameters remembered per model
+export interface ModelSettings {
+	temperature?: number;
+	maxTokens?: number;
+	topP?: number;
 }
 
 export interface UserPreferences {
@@ -102,4 +174,7 @@ export interface UserPreferences {
 	selectedTheme?: ThemePreset;
 	trustedDirectories?: string[];
 	titleShape?: TitleShape;
+	modelSettings?: {
+		[key in string]?: ModelSettings;
+	};
 }
@@ -12,7 +12,7 @@ export type AISDKCoreTool = AISDKTool<any, any>;
 // Current Nanocoder message format (OpenAI-compatible)
 // Note: We maintain this format internally and convert to ModelMessage at AI SDK boundary
 export interface Message {
-	role: 'user' | 'assistant' | 'system' | 'tool';
+	role: 'user' | 'assistant' | 'system' | 'developer' | 'tool';
 	content: string;
 	tool_calls?: ToolCall[];
 	tool_call_id?: string;
@@ -32,6 +32,8 @@ export interface ToolResult {
 	role: 'tool';
 	name: string;
 	content: string;
+	// Set when the tool failed; content is then the error for the model
+	isError?: boolean;
 }
 
 export interface ToolParameterSchema {
@@ -49,13 +51,27 @@ export interface Tool {
 			type: 'object';
 			properties: Record<string, ToolParameterSchema>;
 			required: string[];
+			// Any other JSON Schema keywords, e.g. $defs or additionalProperties
+			[keyword: string]: unknown;
 		};
 	};
 }
 
-// Tool handlers accept dynamic args from LLM, so any is appropriate here
-// biome-ignore lint/suspicious/noExplicitAny: Dynamic typing required -- Tool arguments are dynamically typed
-export type ToolHandler = (input: any) => Promise<string>;
+// Session state the ToolManager passes to tools as experimental_context in
+// their execution options
+export interface ToolContext {
+	// The current model, whose tokenizer tools can count tokens with
+	model?: {model: string; provider: string};
+}
+
+// Tool handlers accept dynamic args from LLM, so any is appropriate here.
+// Handlers that run for a while stop early when the signal aborts.
+export type ToolHandler = (
+	// biome-ignore lint/suspicious/noExplicitAny: Dynamic typing required -- Tool arguments are dynamically typed
+	input: any,
+	signal?: AbortSignal,
+	context?: ToolContext,
+) => Promise<string>;
 
 /**
  * Tool formatter type for Ink UI
@@ -95,6 +111,27 @@ export type StreamingFormatter = (
 	executionId: string,
 ) => React.ReactElement;
 
+/**
+ * Kind of work a tool does, used to group tools in the UI and to apply
+ * approval policies to a whole category at once
+ */
+export type ToolCategory =
+	| 'file-read'
+	| 'file-write'
+	| 'search'
+	| 'web'
+	| 'shell'
+	| 'mcp'
+	| 'other';
+
+/**
+ * A sample call showing how a tool is meant to be used
+ */
+export interface ToolExample {
+	description: string;
+	arguments: Record<string, unknown>;
+}
+
 /**
  * Nanocoder tool export structure
  *
@@ -107,10 +144,14 @@ export type StreamingFormatter = (
  * - formatter: Optional React component for rich CLI UI display
  * - streamingFormatter: Optional formatter for real-time progress (called before execution)
  * - validator: Optional pre-execution validation function
+ * - category: Optional kind of tool for grouping and policy (defaults to 'other')
  */
 export interface NanocoderToolExport {
 	name: string;
 	tool: AISDKCoreTool; // AI SDK v6 tool with execute()
+	category?: ToolCategory; // Defaults to 'other'
+	idempotent?: boolean; // No side effects and the same result for the same arguments; results may be cached
+	examples?: ToolExample[]; // Few-shot guidance for models that misuse the tool
 	formatter?: ToolFormatter; // For UI display (after execution)
 	streamingFormatter?: StreamingFormatter; // For real-time progress (before execution)
 	validator?: ToolValidator; // For pre-execution validation
@@ -137,6 +178,7 @@ export interface ToolEntry {
 	formatter?: ToolFormatter; // For UI (React component, after execution)
 	streamingFormatter?: StreamingFormatter; // For real-time progress (before execution)
 	validator?: ToolValidator; // For validation
+	category?: ToolCategory; // For grouping and approval policy
 }
 
 interface LLMMessage {
@@ -148,29 +190,66 @@ interface LLMMessage {
 export interface LLMChatResponse {
 	choices: Array<{
 		message: LLMMessage;
+		// Why generation stopped (e.g. 'stop', 'tool-calls', 'length')
+		// 'length' means the max output tokens were hit and the answer is cut off
+		finish_reason?: string;
 	}>;
+	// Reasoning/thinking content, kept apart from the final answer so callers
+	// can show or hide it
+	reasoning?: string;
+	// Token usage reported by the provider, when available
+	usage?: {
+		prompt_tokens: number;
+		completion_tokens: number;
+	};
 	// Auto-executed messages (assistant + tool results) from AI SDK multi-step execution
 	// These need to be added to message history for proper context tracking
 	autoExecutedMessages?: Message[];
 }
 
+// Whether the model may call tools: as it decides (auto), never (none), at
+// least one (required), or one named tool
+export type ToolChoice =
+	| 'auto'
+	| 'none'
+	| 'required'
+	| {type: 'tool'; toolName: string};
+
+// Per-request options for LLMClient.chat
+export interface ChatOptions {
+	toolChoice?: ToolChoice; // Provider's default (auto) when unset
+}
+
 export interface StreamCallbacks {
 	onToken?: (token: string) => void;
+	onReasoning?: (reasoning: string) => void;
 	onToolCall?: (toolCall: ToolCall) => void;
 	onToolExecuted?: (toolCall: ToolCall, result: string) => void;
 	onFinish?: () => void;
 }
 
+// A model reported by the provider's model listing endpoint
+export interface ProviderModelInfo {
+	id: string;
+	name?: string; // Display name, when the provider has one
+	contextLength?: number; // When the provider reports it
+	family?: string; // Ollama model family, e.g. 'llama'
+	parameterSize?: string; // Ollama, e.g. '8.0B'
+}
+
 export interface LLMClient {
 	getCurrentModel(): string;
 	setModel(model: string): void;
 	getContextSize(): number;
 	getAvailableModels(): Promise<string[]>;
+	// Asks the provider which models it offers; absent if it can't say
+	listModels?(): Promise<ProviderModelInfo[]>;
 	chat(
 		messages: Message[],
 		tools: Record<string, AISDKCoreTool>,
 		callbacks: StreamCallbacks,
 		signal?: AbortSignal,
+		options?: ChatOptions,
 	): Promise<LLMChatResponse>;
 	clearContext(): Promise<void>;
 }
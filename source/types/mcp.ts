@@ -17,12 +17,26 @@ export interface MCPServer {
 	command?: string;
 	args?: string[];
 	env?: Record<string, string>;
+	// Working directory for the server process (default: nanocoder's)
+	cwd?: string;
+	// Start from nanocoder's full environment and overlay env (true), or pass
+	// only a minimal safe environment plus env (false). When unset, the full
+	// environment is inherited only if env is set.
+	inheritEnv?: boolean;
+	// Grace period in ms for the server to exit after SIGTERM on close before
+	// it is killed with SIGKILL (default: 2000)
+	shutdownTimeout?: number;
 
 	// Remote transport-specific fields
 	url?: string;
 	headers?: Record<string, string>;
 	auth?: MCPAuthConfig;
+	// Time in ms to connect and list tools before the server is reported as
+	// failed, on top of startupTimeout (default: 30000)
 	timeout?: number;
+	// Grace period in ms for a slow-starting server to answer initialize,
+	// retrying failed attempts until it expires
+	startupTimeout?: number;
 	reconnect?: {
 		enabled: boolean;
 		maxAttempts: number;
@@ -49,4 +63,39 @@ export interface MCPInitResult {
 	success: boolean;
 	toolCount?: number;
 	error?: string;
+	// Time taken to connect or fail, in milliseconds
+	durationMs?: number;
+}
+
+// Severities for logging/setLevel and server log messages, from RFC 5424
+export const MCP_LOG_LEVELS = [
+	'debug',
+	'info',
+	'notice',
+	'warning',
+	'error',
+	'critical',
+	'alert',
+	'emergency',
+] as const;
+
+export type MCPLogLevel = (typeof MCP_LOG_LEVELS)[number];
+
+// A notifications/message log event sent by a server
+export interface MCPServerLogMessage {
+	serverName: string;
+	level: MCPLogLevel;
+	// Name of the server component that logged it, if given
+	logger?: string;
+	data: unknown;
+}
+
+export interface MCPServerStatus {
+	name: string;
+	connected: boolean;
+	toolCount: number;
+	// Most recent connection error, if the server failed to connect
+	lastError?: string;
+	// Responses that matched no pending request, when there were any
+	droppedResponses?: number;
 }
@@ -18,6 +18,16 @@ export interface Tokenizer {
 	 */
 	countTokens(message: Message): number;
 
+	/**
+	 * Fixed tokens added to every message for role and framing markers
+	 */
+	getMessageOverhead(): number;
+
+	/**
+	 * Fixed tokens added once per request (e.g. priming the assistant reply)
+	 */
+	getRequestOverhead(): number;
+
 	/**
 	 * Get the tokenizer name/type
 	 */
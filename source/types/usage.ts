@@ -56,3 +56,40 @@ export interface CurrentSessionStats {
 	contextLimit: number | null;
 	percentUsed: number;
 }
+
+/**
+ * How much of the model's context window a request would use
+ */
+export interface ContextHeadroom {
+	used: number;
+	max: number;
+	remaining: number;
+	percent: number; // Share of the window used, to one decimal place
+}
+
+/**
+ * Tokens used by a request, as providers report them
+ */
+export interface TokenUsage {
+	prompt_tokens: number;
+	completion_tokens: number;
+}
+
+/**
+ * Estimated spend in USD, split into prompt and completion tokens
+ */
+export interface CostEstimate {
+	input: number;
+	output: number;
+	total: number;
+}
+
+/**
+ * Tokens a request would use with one model's tokenizer. maxContext and fits
+ * are left out when the model's context size is unknown.
+ */
+export interface TokenCount {
+	tokens: number;
+	maxContext?: number;
+	fits?: boolean;
+}
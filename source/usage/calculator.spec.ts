@@ -28,6 +28,14 @@ class MockTokenizer implements Tokenizer {
 		// Return token count (1 token per 4 characters)
 		return Math.ceil(text.length / 4);
 	}
+
+	getMessageOverhead(): number {
+		return 0;
+	}
+
+	getRequestOverhead(): number {
+		return 0;
+	}
 }
 
 // ============================================================================
@@ -39,6 +39,7 @@ export function calculateTokenBreakdown(
 
 		switch (message.role) {
 			case 'system':
+			case 'developer':
 				breakdown.system += tokens;
 				break;
 
@@ -0,0 +1,32 @@
+import test from 'ava';
+import {calculateCost, estimateCost} from './cost.js';
+
+console.log('\ncost.spec.ts');
+
+test('calculateCost prices prompt and completion tokens per million', t => {
+	// gpt-4o: $2.50 per million input tokens, $10 per million output tokens
+	const cost = calculateCost(
+		{prompt_tokens: 1_000_000, completion_tokens: 500_000},
+		{input: 2.5, output: 10},
+	);
+
+	t.deepEqual(cost, {input: 2.5, output: 5, total: 7.5});
+});
+
+test('calculateCost is zero for free models', t => {
+	const cost = calculateCost(
+		{prompt_tokens: 1200, completion_tokens: 300},
+		{input: 0, output: 0},
+	);
+
+	t.deepEqual(cost, {input: 0, output: 0, total: 0});
+});
+
+test('estimateCost returns null for an unknown model', async t => {
+	const cost = await estimateCost('no-such-model-7f3a9c', {
+		prompt_tokens: 100,
+		completion_tokens: 100,
+	});
+
+	t.is(cost, null);
+});
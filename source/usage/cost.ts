@@ -0,0 +1,34 @@
+/**
+ * Cost estimates
+ * Prices token usage with the per-model rates models.dev publishes
+ */
+
+import {getModelCost} from '@/models/index';
+import type {CostEstimate, TokenUsage} from '../types/usage';
+
+const TOKENS_PER_PRICE_UNIT = 1_000_000;
+
+/**
+ * Cost of token usage at prices in USD per million tokens
+ */
+export function calculateCost(
+	usage: TokenUsage,
+	pricing: {input: number; output: number},
+): CostEstimate {
+	const input = (usage.prompt_tokens * pricing.input) / TOKENS_PER_PRICE_UNIT;
+	const output =
+		(usage.completion_tokens * pricing.output) / TOKENS_PER_PRICE_UNIT;
+	return {input, output, total: input + output};
+}
+
+/**
+ * Cost of token usage with a model, or null when models.dev has no prices
+ * for it
+ */
+export async function estimateCost(
+	model: string,
+	usage: TokenUsage,
+): Promise<CostEstimate | null> {
+	const pricing = await getModelCost(model);
+	return pricing ? calculateCost(usage, pricing) : null;
+}
@@ -0,0 +1,131 @@
+import test from 'ava';
+import {countToolTokens} from '@/ai-sdk-client/chat/max-tokens';
+import {countRequestTokens, createTokenizer} from '@/tokenization/index';
+import {jsonSchema, tool} from '@/types/core';
+import type {AISDKCoreTool, Message} from '@/types/core';
+import type {Tokenizer} from '@/types/tokenization';
+import {
+	calculateContextHeadroom,
+	countModelTokens,
+	getSessionHeadroom,
+} from './headroom.js';
+
+console.log('\nheadroom.spec.ts');
+
+// Roughly four characters per token, with fixed framing overheads
+const tokenizer: Tokenizer = {
+	encode: text => Math.ceil(text.length / 4),
+	countTokens: message => Math.ceil((message.content || '').length / 4) + 4,
+	getMessageOverhead: () => 4,
+	getRequestOverhead: () => 3,
+	getName: () => 'test',
+};
+
+const messages: Message[] = [
+	{role: 'system', content: 'You are a coding assistant.'},
+	{role: 'user', content: 'Explain the build script in package.json.'},
+];
+
+const tools: Record<string, AISDKCoreTool> = {
+	read_file: tool({
+		description: 'Read the contents of a file from disk',
+		inputSchema: jsonSchema<{path: string}>({
+			type: 'object',
+			properties: {path: {type: 'string'}},
+			required: ['path'],
+		}),
+	}),
+};
+
+test('calculateContextHeadroom counts messages and tool definitions', async t => {
+	const used =
+		countRequestTokens(messages, tokenizer) +
+		(await countToolTokens('read_file', tools.read_file, tokenizer));
+
+	const headroom = await calculateContextHeadroom(
+		messages,
+		tools,
+		tokenizer,
+		1000,
+	);
+
+	t.deepEqual(headroom, {
+		used,
+		max: 1000,
+		remaining: 1000 - used,
+		percent: used / 10,
+	});
+});
+
+test('calculateContextHeadroom never reports negative headroom', async t => {
+	const headroom = await calculateContextHeadroom(messages, {}, tokenizer, 10);
+
+	t.true(headroom.used > 10);
+	t.is(headroom.remaining, 0);
+	t.true(headroom.percent > 100);
+});
+
+test('getSessionHeadroom uses the client model tokenizer and window', async t => {
+	const client = {
+		getCurrentModel: () => 'gpt-4o',
+		getContextSize: () => 128000,
+	};
+	const expectedTokenizer = createTokenizer('openai', 'gpt-4o');
+	const used =
+		countRequestTokens(messages, expectedTokenizer) +
+		(await countToolTokens('read_file', tools.read_file, expectedTokenizer));
+	expectedTokenizer.free?.();
+
+	const headroom = await getSessionHeadroom({
+		provider: 'openai',
+		client,
+		messages,
+		tools,
+	});
+
+	t.is(headroom.used, used);
+	t.is(headroom.max, 128000);
+	t.is(headroom.remaining, 128000 - used);
+	t.is(headroom.percent, Math.round((used / 128000) * 1000) / 10);
+});
+
+test('getSessionHeadroom fails without a model', async t => {
+	await t.throwsAsync(
+		getSessionHeadroom({
+			provider: 'openai',
+			client: {getCurrentModel: () => '', getContextSize: () => 0},
+			messages,
+			tools,
+		}),
+		{message: 'No model is selected'},
+	);
+});
+
+test('countModelTokens counts with the named model tokenizer', async t => {
+	const expectedTokenizer = createTokenizer('openai', 'gpt-4');
+	const expected = countRequestTokens(messages, expectedTokenizer);
+	expectedTokenizer.free?.();
+
+	const count = await countModelTokens({
+		provider: 'openai',
+		model: 'gpt-4',
+		messages,
+		tools: {},
+		maxContext: 8192,
+	});
+
+	t.deepEqual(count, {tokens: expected, maxContext: 8192, fits: true});
+});
+
+test('countModelTokens reports a request over the window as not fitting', async t => {
+	const count = await countModelTokens({
+		provider: 'openai',
+		model: 'gpt-4',
+		messages,
+		tools,
+		maxContext: 10,
+	});
+
+	t.is(count.maxContext, 10);
+	t.false(count.fits);
+});
@@ -0,0 +1,107 @@
+/**
+ * Context headroom
+ * Measures how much of the active model's context window a session uses
+ */
+
+import {countToolTokens} from '@/ai-sdk-client/chat/max-tokens';
+import {getModelContextLimit} from '@/models/index';
+import {countRequestTokens, createTokenizer} from '@/tokenization/index';
+import type {AISDKCoreTool, LLMClient, Message} from '@/types/core';
+import type {Tokenizer} from '@/types/tokenization';
+import type {ContextHeadroom, TokenCount} from '../types/usage';
+
+// Tokens used by messages and tool definitions together
+async function countTokens(
+	messages: Message[],
+	tools: Record<string, AISDKCoreTool>,
+	tokenizer: Tokenizer,
+): Promise<number> {
+	let used = countRequestTokens(messages, tokenizer);
+	for (const [name, coreTool] of Object.entries(tools)) {
+		used += await countToolTokens(name, coreTool, tokenizer);
+	}
+	return used;
+}
+
+/**
+ * Tokens used by messages and tool definitions against a context window
+ */
+export async function calculateContextHeadroom(
+	messages: Message[],
+	tools: Record<string, AISDKCoreTool>,
+	tokenizer: Tokenizer,
+	max: number,
+): Promise<ContextHeadroom> {
+	const used = await countTokens(messages, tools, tokenizer);
+
+	return {
+		used,
+		max,
+		remaining: Math.max(0, max - used),
+		percent: max > 0 ? Math.round((used / max) * 1000) / 10 : 0,
+	};
+}
+
+/**
+ * Headroom for the live session, counted with the tokenizer for the client's
+ * current model. The window is the one the client reports, or else the
+ * model's known context limit.
+ * @throws Error when no model is selected or its context size is unknown
+ */
+export async function getSessionHeadroom({
+	provider,
+	client,
+	messages,
+	tools,
+}: {
+	provider: string;
+	client: Pick<LLMClient, 'getCurrentModel' | 'getContextSize'>;
+	messages: Message[];
+	tools: Record<string, AISDKCoreTool>;
+}): Promise<ContextHeadroom> {
+	const model = client.getCurrentModel();
+	if (!model) {
+		throw new Error('No model is selected');
+	}
+	const max = client.getContextSize() || (await getModelContextLimit(model));
+	if (!max) {
+		throw new Error(`The context size of ${model} is unknown`);
+	}
+
+	const tokenizer = createTokenizer(provider, model);
+	try {
+		return await calculateContextHeadroom(messages, tools, tokenizer, max);
+	} finally {
+		tokenizer.free?.();
+	}
+}
+
+/**
+ * Tokens a request would use with any model's tokenizer, not just the
+ * current one's, and whether it fits that model's context window. Without
+ * maxContext, the window is the model's known context limit.
+ */
+export async function countModelTokens({
+	provider,
+	model,
+	messages,
+	tools,
+	maxContext,
+}: {
+	provider: string;
+	model: string;
+	messages: Message[];
+	tools: Record<string, AISDKCoreTool>;
+	maxContext?: number;
+}): Promise<TokenCount> {
+	const tokenizer = createTokenizer(provider, model);
+	let tokens: number;
+	try {
+		tokens = await countTokens(messages, tools, tokenizer);
+	} finally {
+		tokenizer.free?.();
+	}
+
+	const max = maxContext || (await getModelContextLimit(model));
+	return max ? {tokens, maxContext: max, fits: tokens <= max} : {tokens};
+}
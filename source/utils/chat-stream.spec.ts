@@ -0,0 +1,160 @@
+import test from 'ava';
+import type {ToolCall} from '@/types/index';
+import {
+	type ChatStreamEvent,
+	collectChatStream,
+	PrematureStreamEndError,
+} from './chat-stream';
+
+console.log('\nchat-stream.spec.ts');
+
+async function* mockStream(
+	events: ChatStreamEvent[],
+): AsyncGenerator<ChatStreamEvent> {
+	for (const event of events) {
+		yield event;
+	}
+}
+
+const toolCall: ToolCall = {
+	id: 'call_1',
+	function: {name: 'read_file', arguments: {path: 'a.ts'}},
+};
+
+test('collectChatStream assembles text, reasoning, tool calls and usage', async t => {
+	const response = await collectChatStream(
+		mockStream([
+			{type: 'reasoning', text: 'Look at '},
+			{type: 'reasoning', text: 'the file.'},
+			{type: 'text', text: 'Reading '},
+			{type: 'text', text: 'it now.'},
+			{type: 'tool-call', toolCall},
+			{
+				type: 'finish',
+				finishReason: 'tool-calls',
+				usage: {prompt_tokens: 10, completion_tokens: 4},
+			},
+		]),
+	);
+
+	t.deepEqual(response, {
+		choices: [
+			{
+				message: {
+					role: 'assistant',
+					content: 'Reading it now.',
+					tool_calls: [toolCall],
+				},
+				finish_reason: 'tool-calls',
+			},
+		],
+		reasoning: 'Look at the file.',
+		usage: {prompt_tokens: 10, completion_tokens: 4},
+	});
+});
+
+test('collectChatStream feeds every event to the callbacks in order', async t => {
+	const calls: string[] = [];
+
+	await collectChatStream(
+		mockStream([
+			{type: 'reasoning', text: 'hmm'},
+			{type: 'text', text: 'Hi'},
+			{type: 'tool-call', toolCall},
+			{type: 'finish', finishReason: 'stop'},
+		]),
+		{
+			onReasoning: text => calls.push(`reasoning:${text}`),
+			onToken: text => calls.push(`token:${text}`),
+			onToolCall: call => calls.push(`tool:${call.function.name}`),
+			onFinish: () => calls.push('finish'),
+		},
+	);
+
+	t.deepEqual(calls, [
+		'reasoning:hmm',
+		'token:Hi',
+		'tool:read_file',
+		'finish',
+	]);
+});
+
+test('collectChatStream sums usage across finish events', async t => {
+	const response = await collectChatStream(
+		mockStream([
+			{
+				type: 'finish',
+				finishReason: 'tool-calls',
+				usage: {prompt_tokens: 10, completion_tokens: 4},
+			},
+			{type: 'text', text: 'Done'},
+			{
+				type: 'finish',
+				finishReason: 'stop',
+				usage: {prompt_tokens: 20, completion_tokens: 2},
+			},
+		]),
+	);
+
+	t.is(response.choices[0].finish_reason, 'stop');
+	t.deepEqual(response.usage, {prompt_tokens: 30, completion_tokens: 6});
+});
+
+test('collectChatStream leaves optional fields unset for an empty stream', async t => {
+	const response = await collectChatStream(mockStream([]));
+
+	t.is(response.choices[0].message.content, '');
+	t.is(response.choices[0].message.tool_calls, undefined);
+	t.is(response.choices[0].finish_reason, undefined);
+	t.is(response.reasoning, undefined);
+	t.is(response.usage, undefined);
+});
+
+test('collectChatStream propagates stream errors without finishing', async t => {
+	let finished = false;
+
+	async function* failingStream(): AsyncGenerator<ChatStreamEvent> {
+		yield {type: 'text', text: 'partial'};
+		throw new Error('Ollama error: model not found');
+	}
+
+	await t.throwsAsync(
+		collectChatStream(failingStream(), {onFinish: () => (finished = true)}),
+		{message: 'Ollama error: model not found'},
+	);
+	t.false(finished);
+});
+
+test('collectChatStream reports a stream that closes without a finish event', async t => {
+	let finished = false;
+
+	const error = await t.throwsAsync(
+		collectChatStream(
+			mockStream([
+				{type: 'reasoning', text: 'Thinking'},
+				{type: 'text', text: 'Half an ans'},
+			]),
+			{onFinish: () => (finished = true)},
+		),
+		{
+			instanceOf: PrematureStreamEndError,
+			message: 'Stream ended prematurely after 2 chunks',
+		},
+	);
+
+	t.is(error?.chunkCount, 2);
+	t.false(finished);
+});
+
+test('collectChatStream reports a stream that drops after a finished step', async t => {
+	await t.throwsAsync(
+		collectChatStream(
+			mockStream([
+				{type: 'tool-call', toolCall},
+				{type: 'finish', finishReason: 'tool-calls'},
+				{type: 'text', text: 'The file says'},
+			]),
+		),
+		{instanceOf: PrematureStreamEndError, message: /after 3 chunks/},
+	);
+});
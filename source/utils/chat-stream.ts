@@ -0,0 +1,101 @@
+import type {LLMChatResponse, StreamCallbacks, ToolCall} from '@/types/index';
+
+/**
+ * A provider-neutral event from a streamed chat response
+ */
+export type ChatStreamEvent =
+	| {type: 'text'; text: string}
+	| {type: 'reasoning'; text: string}
+	| {type: 'tool-call'; toolCall: ToolCall}
+	| {
+			type: 'finish';
+			finishReason?: string;
+			usage?: LLMChatResponse['usage'];
+	  };
+
+/**
+ * Thrown when a stream closes after sending events but before its finish
+ * event, e.g. because the connection dropped. The partial response is
+ * discarded so the caller can retry the whole request.
+ */
+export class PrematureStreamEndError extends Error {
+	constructor(public readonly chunkCount: number) {
+		super(`Stream ended prematurely after ${chunkCount} chunks`);
+		this.name = 'PrematureStreamEndError';
+	}
+}
+
+/**
+ * Drives a chat stream to completion, feeding each event to the callbacks as
+ * it arrives, and assembles the final response. Usage is summed across finish
+ * events and the last finish reason wins, so multi-step streams report their
+ * totals.
+ *
+ * @throws PrematureStreamEndError if events arrive after the last finish
+ * event, or no finish event arrives at all
+ */
+export async function collectChatStream(
+	events: AsyncIterable<ChatStreamEvent>,
+	callbacks: StreamCallbacks = {},
+): Promise<LLMChatResponse> {
+	let content = '';
+	let reasoning = '';
+	const toolCalls: ToolCall[] = [];
+	let finishReason: string | undefined;
+	let usage: LLMChatResponse['usage'];
+	let chunkCount = 0;
+	let finished = false;
+
+	for await (const event of events) {
+		chunkCount++;
+		finished = event.type === 'finish';
+		switch (event.type) {
+			case 'text':
+				content += event.text;
+				callbacks.onToken?.(event.text);
+				break;
+			case 'reasoning':
+				reasoning += event.text;
+				callbacks.onReasoning?.(event.text);
+				break;
+			case 'tool-call':
+				toolCalls.push(event.toolCall);
+				callbacks.onToolCall?.(event.toolCall);
+				break;
+			case 'finish':
+				finishReason = event.finishReason ?? finishReason;
+				if (event.usage) {
+					usage = {
+						prompt_tokens:
+							(usage?.prompt_tokens ?? 0) + event.usage.prompt_tokens,
+						completion_tokens:
+							(usage?.completion_tokens ?? 0) +
+							event.usage.completion_tokens,
+					};
+				}
+				break;
+		}
+	}
+
+	// An empty stream has nothing partial to lose; anything else must finish
+	if (chunkCount > 0 && !finished) {
+		throw new PrematureStreamEndError(chunkCount);
+	}
+
+	callbacks.onFinish?.();
+
+	return {
+		choices: [
+			{
+				message: {
+					role: 'assistant',
+					content,
+					tool_calls: toolCalls.length > 0 ? toolCalls : undefined,
+				},
+				finish_reason: finishReason,
+			},
+		],
+		reasoning: reasoning || undefined,
+		usage,
+	};
+}
@@ -0,0 +1,51 @@
+import {mkdtempSync, rmSync, writeFileSync} from 'node:fs';
+import {tmpdir} from 'node:os';
+import {join} from 'node:path';
+import test from 'ava';
+import {assertFileUnchanged, computeContentHash} from './content-hash';
+
+console.log('\ncontent-hash.spec.ts');
+
+let testDir: string;
+
+test.beforeEach(() => {
+	testDir = mkdtempSync(join(tmpdir(), 'content-hash-test-'));
+});
+
+test.afterEach.always(() => {
+	rmSync(testDir, {recursive: true, force: true});
+});
+
+test('computeContentHash is a stable 16-character hex fingerprint', t => {
+	const hash = computeContentHash('hello\n');
+
+	t.regex(hash, /^[0-9a-f]{16}$/);
+	t.is(computeContentHash(Buffer.from('hello\n')), hash);
+	t.not(computeContentHash('hello!\n'), hash);
+});
+
+test.serial('assertFileUnchanged accepts a matching hash', async t => {
+	const filePath = join(testDir, 'a.txt');
+	writeFileSync(filePath, 'one\ntwo\n');
+
+	await t.notThrowsAsync(
+		assertFileUnchanged(filePath, computeContentHash('one\ntwo\n'), 'a.txt'),
+	);
+});
+
+test.serial('assertFileUnchanged rejects a stale hash', async t => {
+	const filePath = join(testDir, 'a.txt');
+	writeFileSync(filePath, 'one\ntwo\n');
+	const staleHash = computeContentHash('one\n');
+
+	await t.throwsAsync(assertFileUnchanged(filePath, staleHash, 'a.txt'), {
+		message: /File "a.txt" changed since it was read/,
+	});
+});
+
+test.serial('assertFileUnchanged rejects a file that was deleted', async t => {
+	await t.throwsAsync(
+		assertFileUnchanged(join(testDir, 'gone.txt'), 'abc', 'gone.txt'),
+		{message: /no longer exists/},
+	);
+});
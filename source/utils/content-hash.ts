@@ -0,0 +1,75 @@
+import {createHash} from 'node:crypto';
+import {createReadStream} from 'node:fs';
+import {readFile} from 'node:fs/promises';
+
+/**
+ * Short SHA-256 fingerprint of a file's raw bytes, returned by read_file so the
+ * model can pass it back to edit tools as `expected_hash`
+ */
+export function computeContentHash(content: Uint8Array | string): string {
+	const hasher = createContentHasher();
+	hasher.update(content);
+	return hasher.digest();
+}
+
+/**
+ * Incremental form of computeContentHash, for files read in chunks
+ */
+export function createContentHasher(): {
+	update: (chunk: Uint8Array | string) => void;
+	digest: () => string;
+} {
+	const hash = createHash('sha256');
+	return {
+		update: chunk => {
+			hash.update(chunk);
+		},
+		digest: () => hash.digest('hex').slice(0, 16),
+	};
+}
+
+/**
+ * computeContentHash of a file, streamed so a large file is never held in
+ * memory whole
+ */
+export async function hashFile(absPath: string): Promise<string> {
+	const hasher = createContentHasher();
+	const stream = createReadStream(absPath) as AsyncIterable<Buffer>;
+	for await (const chunk of stream) {
+		hasher.update(chunk);
+	}
+	return hasher.digest();
+}
+
+/**
+ * Throws if the file on disk no longer matches the hash the model read, so an
+ * edit based on stale content never overwrites someone else's changes
+ */
+export async function assertFileUnchanged(
+	absPath: string,
+	expectedHash: string,
+	displayPath: string,
+): Promise<void> {
+	let currentHash: string;
+	try {
+		currentHash = computeContentHash(await readFile(absPath));
+	} catch (error) {
+		if (
+			error &&
+			typeof error === 'object' &&
+			'code' in error &&
+			error.code === 'ENOENT'
+		) {
+			throw new Error(
+				`File "${displayPath}" changed since it was read: it no longer exists.`,
+			);
+		}
+		throw error;
+	}
+
+	if (currentHash !== expectedHash.trim().toLowerCase()) {
+		throw new Error(
+			`File "${displayPath}" changed since it was read (expected hash ${expectedHash}, current hash ${currentHash}). Read the file again before editing.`,
+		);
+	}
+}
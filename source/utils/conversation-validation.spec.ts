@@ -0,0 +1,167 @@
+import test from 'ava';
+import type {Message} from '@/types/core';
+import {
+	repairDanglingToolCalls,
+	validateConversation,
+} from './conversation-validation';
+
+console.log('\nconversation-validation.spec.ts');
+
+const assistantWithCalls: Message = {
+	role: 'assistant',
+	content: '',
+	tool_calls: [
+		{id: 'call_1', function: {name: 'read_file', arguments: {path: 'a.ts'}}},
+		{id: 'call_2', function: {name: 'read_file', arguments: {path: 'b.ts'}}},
+	],
+};
+
+function toolResult(id: string): Message {
+	return {role: 'tool', content: 'ok', tool_call_id: id, name: 'read_file'};
+}
+
+test('validateConversation accepts a well-formed tool round trip', t => {
+	t.notThrows(() =>
+		validateConversation([
+			{role: 'system', content: 'You are helpful'},
+			{role: 'user', content: 'Read both files'},
+			assistantWithCalls,
+			toolResult('call_2'),
+			toolResult('call_1'),
+			{role: 'assistant', content: 'Done'},
+			{role: 'user', content: 'Thanks'},
+		]),
+	);
+});
+
+test('validateConversation rejects an orphan tool result', t => {
+	t.throws(
+		() =>
+			validateConversation([
+				{role: 'user', content: 'Hi'},
+				toolResult('call_9'),
+			]),
+		{
+			message:
+				'Invalid conversation: tool message at position 1 answers "call_9", which is not a pending tool call',
+		},
+	);
+});
+
+test('validateConversation rejects a tool result without tool_call_id', t => {
+	t.throws(
+		() =>
+			validateConversation([
+				{role: 'user', content: 'Hi'},
+				assistantWithCalls,
+				{role: 'tool', content: 'ok'},
+			]),
+		{message: /position 2 has no tool_call_id/},
+	);
+});
+
+test('validateConversation rejects a tool call answered twice', t => {
+	t.throws(
+		() =>
+			validateConversation([
+				{role: 'user', content: 'Hi'},
+				assistantWithCalls,
+				toolResult('call_1'),
+				toolResult('call_1'),
+			]),
+		{message: /position 3 answers "call_1"/},
+	);
+});
+
+test('validateConversation rejects an unmatched tool call before the next message', t => {
+	t.throws(
+		() =>
+			validateConversation([
+				{role: 'user', content: 'Read both files'},
+				assistantWithCalls,
+				toolResult('call_1'),
+				{role: 'user', content: 'Never mind'},
+			]),
+		{
+			message:
+				'Invalid conversation: user message at position 3 follows tool calls with no result: read_file (call_2)',
+		},
+	);
+});
+
+test('validateConversation rejects an unmatched tool call at the end', t => {
+	t.throws(
+		() =>
+			validateConversation([
+				{role: 'user', content: 'Read both files'},
+				assistantWithCalls,
+			]),
+		{
+			message:
+				'Invalid conversation: tool calls with no result: read_file (call_1), read_file (call_2)',
+		},
+	);
+});
+
+test('validateConversation requires system messages to come first', t => {
+	t.throws(
+		() =>
+			validateConversation([
+				{role: 'user', content: 'Hi'},
+				{role: 'system', content: 'Late instructions'},
+			]),
+		{message: /system message at position 1 must come before/},
+	);
+	t.notThrows(() =>
+		validateConversation([
+			{role: 'system', content: 'Base prompt'},
+			{role: 'developer', content: 'More instructions'},
+			{role: 'user', content: 'Hi'},
+		]),
+	);
+});
+
+test('repairDanglingToolCalls answers calls a restored history left open', t => {
+	const restored: Message[] = [
+		{role: 'system', content: 'You are helpful'},
+		{role: 'user', content: 'Read both files'},
+		assistantWithCalls,
+		toolResult('call_1'),
+		{role: 'user', content: 'Where were we?'},
+	];
+
+	const repaired = repairDanglingToolCalls(restored);
+
+	t.notThrows(() => validateConversation(repaired));
+	t.is(repaired.length, restored.length + 1);
+	t.is(repaired[4].role, 'tool');
+	t.is(repaired[4].tool_call_id, 'call_2');
+	t.is(repaired[4].name, 'read_file');
+	t.regex(repaired[4].content, /^Skipped: /);
+	t.deepEqual(repaired[5], restored[4]);
+});
+
+test('repairDanglingToolCalls answers calls at the end of the history', t => {
+	const repaired = repairDanglingToolCalls([
+		{role: 'user', content: 'Read both files'},
+		assistantWithCalls,
+	]);
+
+	t.notThrows(() => validateConversation(repaired));
+	t.deepEqual(
+		repaired.slice(2).map(message => message.tool_call_id),
+		['call_1', 'call_2'],
+	);
+});
+
+test('repairDanglingToolCalls leaves a well-formed history alone', t => {
+	const history: Message[] = [
+		{role: 'user', content: 'Read both files'},
+		assistantWithCalls,
+		toolResult('call_1'),
+		toolResult('call_2'),
+		{role: 'assistant', content: 'Done'},
+	];
+
+	t.deepEqual(repairDanglingToolCalls(history), history);
+});
@@ -0,0 +1,127 @@
+import type {Message, ToolCall} from '@/types/core';
+import {getLogger} from '@/utils/logging';
+
+/**
+ * Checks that a conversation has the shape chat APIs expect before it is sent,
+ * so a malformed history fails with a clear message instead of a provider 400.
+ *
+ * Checks:
+ * - System and developer messages come before everything else
+ * - Every tool message answers a tool call from the assistant message before it
+ * - Every assistant tool call is answered before the next user or assistant
+ *   message, and before the conversation ends
+ *
+ * @throws Error describing the first problem found
+ */
+export function validateConversation(messages: Message[]): void {
+	let seenConversation = false;
+	// Tool calls from the latest assistant message still waiting for a result
+	const pending = new Map<string, string>();
+
+	const describePending = () =>
+		Array.from(pending, ([id, name]) => `${name} (${id})`).join(', ');
+
+	for (const [index, message] of messages.entries()) {
+		if (message.role === 'system' || message.role === 'developer') {
+			if (seenConversation) {
+				throw new Error(
+					`Invalid conversation: ${message.role} message at position ${index} must come before all other messages`,
+				);
+			}
+			continue;
+		}
+		seenConversation = true;
+
+		if (message.role === 'tool') {
+			if (!message.tool_call_id) {
+				throw new Error(
+					`Invalid conversation: tool message at position ${index} has no tool_call_id`,
+				);
+			}
+			if (!pending.has(message.tool_call_id)) {
+				throw new Error(
+					`Invalid conversation: tool message at position ${index} answers "${message.tool_call_id}", which is not a pending tool call`,
+				);
+			}
+			pending.delete(message.tool_call_id);
+			continue;
+		}
+
+		if (pending.size > 0) {
+			throw new Error(
+				`Invalid conversation: ${message.role} message at position ${index} follows tool calls with no result: ${describePending()}`,
+			);
+		}
+
+		if (message.role === 'assistant') {
+			for (const toolCall of message.tool_calls ?? []) {
+				pending.set(toolCall.id, toolCall.function.name);
+			}
+		}
+	}
+
+	if (pending.size > 0) {
+		throw new Error(
+			`Invalid conversation: tool calls with no result: ${describePending()}`,
+		);
+	}
+}
+
+/**
+ * Result for a tool call that never got one, e.g. because the session was
+ * interrupted while it ran
+ */
+function createUnansweredResult(toolCall: ToolCall): Message {
+	return {
+		role: 'tool',
+		content:
+			'Skipped: no result was recorded for this tool call, so it may not have run',
+		tool_call_id: toolCall.id,
+		name: toolCall.function.name,
+	};
+}
+
+/**
+ * Answers tool calls left without a result, as a restored or interrupted
+ * history can have, so the conversation can still be sent. Each one gets a
+ * synthetic result right after the other results of its assistant message.
+ * Other problems are left for validateConversation to report.
+ */
+export function repairDanglingToolCalls(messages: Message[]): Message[] {
+	const repaired: Message[] = [];
+	// Tool calls from the latest assistant message still waiting for a result
+	const pending = new Map<string, ToolCall>();
+	let added = 0;
+
+	const answerPending = () => {
+		for (const toolCall of pending.values()) {
+			repaired.push(createUnansweredResult(toolCall));
+			added++;
+		}
+		pending.clear();
+	};
+
+	for (const message of messages) {
+		if (message.role === 'tool') {
+			if (message.tool_call_id) {
+				pending.delete(message.tool_call_id);
+			}
+		} else if (message.role !== 'system' && message.role !== 'developer') {
+			answerPending();
+			if (message.role === 'assistant') {
+				for (const toolCall of message.tool_calls ?? []) {
+					pending.set(toolCall.id, toolCall);
+				}
+			}
+		}
+		repaired.push(message);
+	}
+	answerPending();
+
+	if (added > 0) {
+		getLogger().warn('Answered tool calls that had no result', {
+			count: added,
+		});
+	}
+	return repaired;
+}
@@ -0,0 +1,35 @@
+import {execFile} from 'node:child_process';
+import {promisify} from 'node:util';
+import test from 'ava';
+import {getOverflowedStdout} from './exec-output';
+
+console.log('\nexec-output.spec.ts');
+
+const execFileAsync = promisify(execFile);
+
+test('getOverflowedStdout keeps the complete lines read before the cap', async t => {
+	const error = await t.throwsAsync(
+		execFileAsync(
+			process.execPath,
+			['-e', 'for (let i = 0; i < 1000; i++) console.log(`line ${i}`)'],
+			{maxBuffer: 100},
+		),
+	);
+
+	const stdout = getOverflowedStdout(error);
+
+	t.truthy(stdout);
+	t.true(stdout!.startsWith('line 0\nline 1\n'));
+	t.true(stdout!.endsWith('\n'));
+	t.true(stdout!.length <= 100);
+});
+
+test('getOverflowedStdout ignores other errors', t => {
+	const error = Object.assign(new Error('Command failed'), {
+		code: 1,
+		stdout: 'partial\n',
+	});
+
+	t.is(getOverflowedStdout(error), undefined);
+	t.is(getOverflowedStdout('not an error'), undefined);
+});
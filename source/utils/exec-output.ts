@@ -0,0 +1,18 @@
+/**
+ * When a command's output passes execFile's maxBuffer, Node kills it and
+ * rejects, but the error still carries everything read up to the cap. Returns
+ * that output minus the last, possibly cut-off, line, or undefined for any
+ * other error.
+ */
+export function getOverflowedStdout(error: unknown): string | undefined {
+	if (
+		error instanceof Error &&
+		'code' in error &&
+		error.code === 'ERR_CHILD_PROCESS_STDIO_MAXBUFFER' &&
+		'stdout' in error &&
+		typeof error.stdout === 'string'
+	) {
+		return error.stdout.slice(0, error.stdout.lastIndexOf('\n') + 1);
+	}
+	return undefined;
+}
@@ -1,5 +1,7 @@
 import {readFile, stat} from 'node:fs/promises';
 import {CACHE_FILE_TTL_MS, MAX_FILE_READ_RETRIES} from '@/constants';
+import {computeContentHash} from '@/utils/content-hash';
+import {decodeText, type TextEncodingName} from '@/utils/text-decoding';
 
 /**
  * File content cache to reduce duplicate file reads during tool confirmation flow.
@@ -14,6 +16,11 @@ export const MAX_CACHE_SIZE = 50;
 export interface CachedFile {
 	content: string;
 	lines: string[];
+	encoding: TextEncodingName;
+	/** True if the file had invalid byte sequences, replaced with U+FFFD */
+	lossy: boolean;
+	/** Fingerprint of the raw bytes, see computeContentHash */
+	hash: string;
 	mtime: number;
 	cachedAt: number;
 }
@@ -115,7 +122,10 @@ async function readAndCacheFile(
 	// Get mtime before reading (or use known mtime from caller)
 	const mtimeBefore = knownMtime ?? (await stat(absPath)).mtimeMs;
 
-	const content = await readFile(absPath, 'utf-8');
+	// Decode leniently so every caller shares one read; callers that need
+	// strict decoding check the lossy flag
+	const buffer = await readFile(absPath);
+	const {text: content, encoding, lossy} = decodeText(buffer, {lossy: true});
 
 	// Verify mtime didn't change during read
 	const mtimeAfter = (await stat(absPath)).mtimeMs;
@@ -132,6 +142,9 @@ async function readAndCacheFile(
 	const cachedFile: CachedFile = {
 		content,
 		lines: content.split('\n'),
+		encoding,
+		lossy,
+		hash: computeContentHash(buffer),
 		mtime: mtimeAfter,
 		cachedAt: now,
 	};
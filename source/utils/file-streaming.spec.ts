@@ -0,0 +1,77 @@
+import {mkdtempSync, rmSync, writeFileSync} from 'node:fs';
+import {tmpdir} from 'node:os';
+import {join} from 'node:path';
+import test from 'ava';
+import {computeContentHash} from './content-hash';
+import {readLineRange, scanFile, shouldStreamFile} from './file-streaming';
+
+console.log('\nfile-streaming.spec.ts');
+
+let testDir: string;
+
+test.beforeEach(() => {
+	testDir = mkdtempSync(join(tmpdir(), 'file-streaming-test-'));
+});
+
+test.afterEach.always(() => {
+	rmSync(testDir, {recursive: true, force: true});
+});
+
+function writeTestFile(name: string, content: string | Buffer): string {
+	const filePath = join(testDir, name);
+	writeFileSync(filePath, content);
+	return filePath;
+}
+
+// Lines long enough that the file spans several 64 KB read chunks
+const manyLines = Array.from(
+	{length: 5000},
+	(_, i) => `line ${i + 1} ${'x'.repeat(40)}`,
+);
+
+test('shouldStreamFile only streams files over the threshold', t => {
+	t.false(shouldStreamFile(1024));
+	t.true(shouldStreamFile(50 * 1024 * 1024));
+});
+
+test.serial('scanFile counts lines like split and hashes the bytes', async t => {
+	const content = `${manyLines.join('\n')}\n`;
+	const filePath = writeTestFile('many.txt', content);
+
+	const summary = await scanFile(filePath);
+
+	t.is(summary.lineCount, content.split('\n').length);
+	t.is(summary.charCount, content.length);
+	t.is(summary.hash, computeContentHash(content));
+	t.is(summary.encoding, 'utf-8');
+	t.false(summary.lossy);
+});
+
+test.serial('readLineRange returns a range spanning chunk boundaries', async t => {
+	const filePath = writeTestFile('many.txt', manyLines.join('\n'));
+
+	const range = await readLineRange(filePath, 2999, 3001);
+
+	t.deepEqual(range.lines, manyLines.slice(2998, 3001));
+	t.false(range.lossy);
+});
+
+test.serial('readLineRange reads to the end without an end line', async t => {
+	const filePath = writeTestFile('short.txt', 'a\nb\nc\n');
+
+	t.deepEqual((await readLineRange(filePath, 2)).lines, ['b', 'c', '']);
+	t.deepEqual((await readLineRange(filePath, 2, 99)).lines, ['b', 'c', '']);
+	t.deepEqual((await readLineRange(filePath, 10)).lines, []);
+});
+
+test.serial('readLineRange flags invalid bytes as lossy', async t => {
+	const filePath = writeTestFile(
+		'latin1.txt',
+		Buffer.from([0x63, 0x61, 0x66, 0xe9, 0x0a, 0x6f, 0x6b]),
+	);
+
+	const range = await readLineRange(filePath, 1, 2);
+
+	t.deepEqual(range.lines, ['caf�', 'ok']);
+	t.true(range.lossy);
+});
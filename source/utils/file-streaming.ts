@@ -0,0 +1,172 @@
+import {createReadStream} from 'node:fs';
+import {stat} from 'node:fs/promises';
+import {FILE_READ_STREAMING_THRESHOLD_BYTES} from '@/constants';
+import {createContentHasher} from '@/utils/content-hash';
+import {getCachedFileContent} from '@/utils/file-cache';
+import {detectEncoding, type TextEncodingName} from '@/utils/text-decoding';
+
+const STREAM_CHUNK_BYTES = 64 * 1024;
+
+export interface FileSummary {
+	lineCount: number;
+	charCount: number;
+	encoding: TextEncodingName;
+	/** True if invalid byte sequences were replaced with U+FFFD */
+	lossy: boolean;
+	hash: string;
+}
+
+export interface LineRange {
+	lines: string[];
+	encoding: TextEncodingName;
+	/** True if invalid byte sequences in the range were replaced with U+FFFD */
+	lossy: boolean;
+}
+
+/**
+ * Whether a file is big enough that it should be streamed rather than read
+ * whole into the file cache
+ */
+export function shouldStreamFile(sizeBytes: number): boolean {
+	return sizeBytes > FILE_READ_STREAMING_THRESHOLD_BYTES;
+}
+
+/**
+ * Decodes a file chunk by chunk, stopping early once onChunk returns true.
+ * The encoding is detected from the BOM in the first chunk, and the UTF-8 BOM
+ * is kept to match decodeText.
+ */
+async function streamDecoded(
+	absPath: string,
+	fatal: boolean,
+	onChunk: (text: string, bytes: Buffer) => boolean | undefined,
+): Promise<TextEncodingName> {
+	let decoder: TextDecoder | undefined;
+	let encoding: TextEncodingName = 'utf-8';
+	const stream = createReadStream(absPath, {highWaterMark: STREAM_CHUNK_BYTES});
+
+	try {
+		for await (const bytes of stream as AsyncIterable<Buffer>) {
+			if (!decoder) {
+				encoding = detectEncoding(bytes);
+				decoder = new TextDecoder(encoding, {
+					fatal,
+					ignoreBOM: encoding === 'utf-8',
+				});
+			}
+			if (onChunk(decoder.decode(bytes, {stream: true}), bytes)) {
+				return encoding;
+			}
+		}
+		if (decoder) {
+			onChunk(decoder.decode(), Buffer.alloc(0));
+		}
+		return encoding;
+	} finally {
+		stream.destroy();
+	}
+}
+
+// Runs a streaming pass strictly, and again leniently if the bytes turn out
+// not to be valid text
+async function withLossyFallback<T>(
+	pass: (fatal: boolean) => Promise<T>,
+): Promise<{result: T; lossy: boolean}> {
+	try {
+		return {result: await pass(true), lossy: false};
+	} catch (error) {
+		if (!(error instanceof TypeError)) {
+			throw error;
+		}
+		return {result: await pass(false), lossy: true};
+	}
+}
+
+/**
+ * Counts lines and hashes a file in one streaming pass, without holding more
+ * than a chunk of it in memory. Line counts match `content.split('\n')`.
+ */
+export async function scanFile(absPath: string): Promise<FileSummary> {
+	const {result, lossy} = await withLossyFallback(async fatal => {
+		const hasher = createContentHasher();
+		let lineCount = 1;
+		let charCount = 0;
+
+		const encoding = await streamDecoded(absPath, fatal, (text, bytes) => {
+			hasher.update(bytes);
+			charCount += text.length;
+			lineCount += text.split('\n').length - 1;
+			return undefined;
+		});
+
+		return {lineCount, charCount, encoding, hash: hasher.digest()};
+	});
+
+	return {...result, lossy};
+}
+
+/**
+ * Reads lines startLine..endLine (1-indexed, inclusive) by streaming the file
+ * and stopping once endLine is reached, so memory use is bounded by the range
+ * rather than the file size. Without endLine, reads to the end of the file.
+ */
+export async function readLineRange(
+	absPath: string,
+	startLine: number,
+	endLine?: number,
+): Promise<LineRange> {
+	const {result, lossy} = await withLossyFallback(async fatal => {
+		const lines: string[] = [];
+		let lineNumber = 1;
+		let current = '';
+		let reachedEnd = false;
+
+		const encoding = await streamDecoded(absPath, fatal, text => {
+			const parts = text.split('\n');
+			for (let i = 0; i < parts.length - 1; i++) {
+				if (lineNumber >= startLine) {
+					lines.push(current + parts[i]);
+				}
+				current = '';
+				lineNumber++;
+				if (endLine !== undefined && lineNumber > endLine) {
+					reachedEnd = true;
+					return true;
+				}
+			}
+			// Lines before the range are skipped without being buffered
+			if (lineNumber >= startLine) {
+				current += parts[parts.length - 1];
+			}
+			return undefined;
+		});
+
+		// The last line has no trailing newline to flush it
+		if (!reachedEnd && lineNumber >= startLine) {
+			lines.push(current);
+		}
+		return {lines, encoding};
+	});
+
+	return {...result, lossy};
+}
+
+/**
+ * Line count, size and hash of a file, from the file cache for ordinary files
+ * and from a streaming scan for ones too large to cache
+ */
+export async function getFileSummary(absPath: string): Promise<FileSummary> {
+	const {size} = await stat(absPath);
+	if (shouldStreamFile(size)) {
+		return scanFile(absPath);
+	}
+
+	const cached = await getCachedFileContent(absPath);
+	return {
+		lineCount: cached.lines.length,
+		charCount: cached.content.length,
+		encoding: cached.encoding,
+		lossy: cached.lossy,
+		hash: cached.hash,
+	};
+}
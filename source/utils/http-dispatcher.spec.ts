@@ -0,0 +1,197 @@
+import {mkdtempSync, rmSync, writeFileSync} from 'node:fs';
+import {createServer} from 'node:http';
+import type {AddressInfo} from 'node:net';
+import {tmpdir} from 'node:os';
+import {join} from 'node:path';
+import {rootCertificates} from 'node:tls';
+import test from 'ava';
+import {Agent, EnvHttpProxyAgent, MockAgent} from 'undici';
+import {
+	closeSharedHttpDispatcher,
+	createHttpDispatcher,
+	getLookupHttpDispatcher,
+	getSharedHttpDispatcher,
+	setSharedHttpDispatcher,
+} from './http-dispatcher';
+import {fetchWithPolicy, publicOnlyLookup} from './url-policy';
+
+console.log('\nhttp-dispatcher.spec.ts');
+
+const PROXY_ENV_VARS = [
+	'HTTPS_PROXY',
+	'https_proxy',
+	'HTTP_PROXY',
+	'http_proxy',
+	'NO_PROXY',
+	'no_proxy',
+];
+
+let savedEnv: Record<string, string | undefined> = {};
+
+test.beforeEach(() => {
+	savedEnv = {};
+	for (const name of PROXY_ENV_VARS) {
+		savedEnv[name] = process.env[name];
+		delete process.env[name];
+	}
+});
+
+test.afterEach.always(() => {
+	for (const name of PROXY_ENV_VARS) {
+		if (savedEnv[name] === undefined) {
+			delete process.env[name];
+		} else {
+			process.env[name] = savedEnv[name];
+		}
+	}
+});
+
+test.serial('createHttpDispatcher builds a plain agent without a proxy', async t => {
+	const dispatcher = createHttpDispatcher({}, {});
+
+	t.true(dispatcher instanceof Agent);
+	await dispatcher.close();
+});
+
+test.serial('createHttpDispatcher builds a proxy agent from config', async t => {
+	const dispatcher = createHttpDispatcher(
+		{bodyTimeout: 1000},
+		{proxy: 'http://proxy.internal:8080', noProxy: 'localhost'},
+	);
+
+	t.true(dispatcher instanceof EnvHttpProxyAgent);
+	await dispatcher.close();
+});
+
+test.serial('createHttpDispatcher picks up HTTPS_PROXY from the environment', async t => {
+	process.env.HTTPS_PROXY = 'http://proxy.internal:3128';
+
+	const dispatcher = createHttpDispatcher({}, {});
+
+	t.true(dispatcher instanceof EnvHttpProxyAgent);
+	await dispatcher.close();
+});
+
+test.serial('createHttpDispatcher accepts a custom CA certificate', async t => {
+	const dir = mkdtempSync(join(tmpdir(), 'nanocoder-ca-'));
+	const caCertFile = join(dir, 'corporate-ca.pem');
+	writeFileSync(caCertFile, rootCertificates[0]);
+
+	try {
+		const dispatcher = createHttpDispatcher(
+			{},
+			{proxy: 'http://proxy.internal:8080', caCertFile},
+		);
+
+		t.true(dispatcher instanceof EnvHttpProxyAgent);
+		await dispatcher.close();
+	} finally {
+		rmSync(dir, {recursive: true, force: true});
+	}
+});
+
+test.serial('createHttpDispatcher reports an unreadable CA file', t => {
+	t.throws(
+		() => createHttpDispatcher({}, {caCertFile: '/nonexistent/ca.pem'}),
+		{message: /Failed to read caCertFile \/nonexistent\/ca.pem/},
+	);
+});
+
+test.serial('web requests share one injected connection pool', async t => {
+	let connections = 0;
+	const server = createServer((_req, res) => res.end('ok'));
+	server.on('connection', () => connections++);
+	await new Promise<void>(resolve => server.listen(0, '127.0.0.1', resolve));
+	const {port} = server.address() as AddressInfo;
+	const url = `http://127.0.0.1:${port}/`;
+	const policy = {allowedHosts: ['127.0.0.1']};
+
+	const shared = new Agent({connections: 1});
+	setSharedHttpDispatcher(shared);
+
+	try {
+		t.is(getSharedHttpDispatcher(), shared);
+
+		// fetch_url and web_search both go through fetchWithPolicy
+		for (let i = 0; i < 2; i++) {
+			const {response} = await fetchWithPolicy(url, policy);
+			t.is(await response.text(), 'ok');
+		}
+
+		t.is(connections, 1);
+	} finally {
+		await closeSharedHttpDispatcher();
+		server.close();
+	}
+
+	// The injected pool belongs to the caller, so it stays open
+	t.false(shared.closed);
+	// A fresh dispatcher replaces the detached one
+	t.not(getSharedHttpDispatcher(), shared);
+	await closeSharedHttpDispatcher();
+	await shared.close();
+});
+
+test.serial('hostname requests go through an injected dispatcher', async t => {
+	// A name that needs its addresses checked, so fetchWithPolicy pins them
+	// when it owns the connection
+	const injected = new MockAgent();
+	injected.disableNetConnect();
+	injected
+		.get('http://nanocoder.invalid')
+		.intercept({path: '/page'})
+		.reply(200, 'from the host pool');
+	setSharedHttpDispatcher(injected);
+
+	try {
+		t.is(getLookupHttpDispatcher(publicOnlyLookup), injected);
+		const {response} = await fetchWithPolicy(
+			'http://nanocoder.invalid/page',
+			{},
+		);
+		t.is(await response.text(), 'from the host pool');
+		injected.assertNoPendingInterceptors();
+	} finally {
+		await closeSharedHttpDispatcher();
+		await injected.close();
+	}
+});
+
+test.serial('closeSharedHttpDispatcher closes a dispatcher it created', async t => {
+	const created = getSharedHttpDispatcher() as Agent;
+
+	await closeSharedHttpDispatcher();
+
+	t.true(created.closed);
+});
+
+test.serial('injecting a dispatcher closes the one created here', async t => {
+	const created = getSharedHttpDispatcher() as Agent;
+	const injected = new Agent();
+
+	setSharedHttpDispatcher(injected);
+
+	t.is(getSharedHttpDispatcher(), injected);
+	await closeSharedHttpDispatcher();
+	t.true(created.closed);
+	t.false(injected.closed);
+	await injected.close();
+});
+
+test.serial('the lookup dispatcher is closed and rebuilt with the shared one', async t => {
+	const created = getLookupHttpDispatcher(publicOnlyLookup) as Agent;
+	t.is(getLookupHttpDispatcher(publicOnlyLookup), created);
+
+	await closeSharedHttpDispatcher();
+
+	t.true(created.closed);
+	const rebuilt = getLookupHttpDispatcher(publicOnlyLookup) as Agent;
+	t.not(rebuilt, created);
+
+	const injected = new Agent();
+	setSharedHttpDispatcher(injected);
+
+	t.true(rebuilt.closed);
+	await closeSharedHttpDispatcher();
+	await injected.close();
+});
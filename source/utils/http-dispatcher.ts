@@ -0,0 +1,155 @@
+import {readFileSync} from 'node:fs';
+import type {LookupFunction} from 'node:net';
+import {rootCertificates} from 'node:tls';
+import {
+	Agent,
+	type Dispatcher,
+	EnvHttpProxyAgent,
+	type ProxyAgent,
+} from 'undici';
+import {appConfig} from '@/config/index';
+import type {AppConfig} from '@/types/index';
+
+type NetworkConfig = NonNullable<AppConfig['network']>;
+
+let sharedDispatcher: Dispatcher | null = null;
+// Only a dispatcher this module created is closed here; an injected one
+// belongs to the caller
+let ownsSharedDispatcher = false;
+// Created here for requests that resolve hosts with their own lookup, and
+// always owned by this module
+let lookupDispatcher: Dispatcher | null = null;
+
+function getEnvProxy(): string | undefined {
+	return (
+		process.env.HTTPS_PROXY ||
+		process.env.https_proxy ||
+		process.env.HTTP_PROXY ||
+		process.env.http_proxy ||
+		undefined
+	);
+}
+
+// Extra CAs are added to Node's bundled roots rather than replacing them
+function loadCertificateAuthorities(
+	caCertFile: string | undefined,
+): string[] | undefined {
+	if (!caCertFile) {
+		return undefined;
+	}
+
+	try {
+		return [...rootCertificates, readFileSync(caCertFile, 'utf-8')];
+	} catch (error) {
+		throw new Error(
+			`Failed to read caCertFile ${caCertFile}: ${error instanceof Error ? error.message : String(error)}`,
+		);
+	}
+}
+
+/**
+ * Whether outbound requests go through a proxy, from the network config or
+ * HTTPS_PROXY / HTTP_PROXY
+ */
+function usesHttpProxy(
+	network: NetworkConfig = appConfig.network ?? {},
+): boolean {
+	return Boolean(network.proxy || getEnvProxy());
+}
+
+/**
+ * Creates the undici dispatcher used for outbound HTTP requests.
+ *
+ * Requests go through a proxy when one is configured, or when HTTPS_PROXY /
+ * HTTP_PROXY is set, with NO_PROXY honoured. A configured CA certificate is
+ * trusted for both the proxy and the target, for TLS-intercepting proxies.
+ */
+export function createHttpDispatcher(
+	options: Agent.Options = {},
+	network: NetworkConfig = appConfig.network ?? {},
+): Dispatcher {
+	const ca = loadCertificateAuthorities(network.caCertFile);
+	const connect =
+		ca && typeof options.connect !== 'function'
+			? {...options.connect, ca}
+			: options.connect;
+
+	const proxy = network.proxy || getEnvProxy();
+	if (!proxy) {
+		return new Agent({...options, connect});
+	}
+
+	// EnvHttpProxyAgent passes these through to its inner ProxyAgents
+	const proxyOptions: EnvHttpProxyAgent.Options &
+		Pick<ProxyAgent.Options, 'requestTls' | 'proxyTls'> = {
+		...options,
+		connect,
+		httpProxy: proxy,
+		httpsProxy: proxy,
+		noProxy: network.noProxy ?? process.env.NO_PROXY ?? process.env.no_proxy,
+		...(ca && {requestTls: {ca}, proxyTls: {ca}}),
+	};
+
+	return new EnvHttpProxyAgent(proxyOptions);
+}
+
+/**
+ * Dispatcher shared by the web tools, created on first use
+ */
+export function getSharedHttpDispatcher(): Dispatcher {
+	if (!sharedDispatcher) {
+		sharedDispatcher = createHttpDispatcher();
+		ownsSharedDispatcher = true;
+	}
+	return sharedDispatcher;
+}
+
+/**
+ * Dispatcher for web requests whose connections resolve hosts with `lookup`,
+ * e.g. to refuse private addresses. It is created on first use with the
+ * lookup given then, and replaced along with the shared dispatcher.
+ *
+ * An injected shared dispatcher is returned instead, so every web request
+ * uses the host's pool, as is the shared one behind a proxy, where the proxy
+ * resolves the host. The lookup does not apply to either.
+ */
+export function getLookupHttpDispatcher(lookup: LookupFunction): Dispatcher {
+	if ((sharedDispatcher && !ownsSharedDispatcher) || usesHttpProxy()) {
+		return getSharedHttpDispatcher();
+	}
+	lookupDispatcher ??= createHttpDispatcher({connect: {lookup}});
+	return lookupDispatcher;
+}
+
+// Detaches the dispatchers this module created and closes them
+async function closeOwnedDispatchers(): Promise<void> {
+	const owned = [
+		ownsSharedDispatcher ? sharedDispatcher : null,
+		lookupDispatcher,
+	];
+	sharedDispatcher = null;
+	ownsSharedDispatcher = false;
+	lookupDispatcher = null;
+	await Promise.all(owned.map(dispatcher => dispatcher?.close()));
+}
+
+/**
+ * Makes the web tools use the given dispatcher, so a host embedding nanocoder
+ * can share one connection pool with its own requests. The caller keeps
+ * ownership and closes it; dispatchers this module created are closed when
+ * replaced.
+ */
+export function setSharedHttpDispatcher(dispatcher: Dispatcher): void {
+	void closeOwnedDispatchers();
+	sharedDispatcher = dispatcher;
+}
+
+/**
+ * Stops the web tools using the shared dispatcher. Dispatchers this module
+ * created have their pooled connections closed, once requests in flight
+ * finish; an injected one is left open for its owner. The next web request
+ * creates fresh dispatchers from the current network config.
+ */
+export async function closeSharedHttpDispatcher(): Promise<void> {
+	await closeOwnedDispatchers();
+}
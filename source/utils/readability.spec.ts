@@ -0,0 +1,58 @@
+import test from 'ava';
+import {extractMainContent} from './readability';
+
+console.log(`\nreadability.spec.ts`);
+
+const paragraph = (topic: string) =>
+	`<p>${topic} is explained here in enough detail to read like real prose, with clauses, examples, and the kind of length an article paragraph has.</p>`;
+
+const ARTICLE_PAGE = `<!doctype html>
+<html>
+<head><title>Configuring Widgets</title></head>
+<body>
+	<nav><a href="/">Home</a> <a href="/docs">Docs</a> <a href="/blog">Blog</a></nav>
+	<div class="sidebar"><a href="/a">Popular post one</a><a href="/b">Popular post two</a></div>
+	<article>
+		<header><h1>Configuring Widgets</h1><p>By A. Author</p></header>
+		${paragraph('Widget setup')}
+		${paragraph('Widget tuning')}
+	</article>
+	<div class="ad-banner">Buy our premium widgets today</div>
+	<footer>Copyright Widget Corp. All rights reserved.</footer>
+</body>
+</html>`;
+
+test('extractMainContent keeps the article and drops navigation', t => {
+	const result = extractMainContent(ARTICLE_PAGE);
+
+	t.true(result.includes('Widget setup is explained'));
+	t.true(result.includes('Widget tuning is explained'));
+	t.true(result.includes('By A. Author'));
+	t.false(result.includes('href="/docs"'));
+	t.false(result.includes('Popular post one'));
+	t.false(result.includes('Buy our premium widgets'));
+	t.false(result.includes('Copyright Widget Corp'));
+});
+
+test('extractMainContent finds the densest block without an article element', t => {
+	const html = `<html><head><title>Release Notes</title></head><body>
+		<div id="menu"><a href="/1">One</a><a href="/2">Two</a></div>
+		<div class="layout">
+			<div class="links"><a href="/x">A long list of related links</a></div>
+			<div class="content">${paragraph('The release')}${paragraph('Upgrading')}</div>
+		</div>
+	</body></html>`;
+
+	const result = extractMainContent(html);
+
+	t.true(result.includes('<h1>Release Notes</h1>'));
+	t.true(result.includes('Upgrading is explained'));
+	t.false(result.includes('href="/1"'));
+	t.false(result.includes('related links'));
+});
+
+test('extractMainContent returns pages without a content block unchanged', t => {
+	const html = '<html><body><p>Short page</p></body></html>';
+
+	t.is(extractMainContent(html), html);
+});
@@ -0,0 +1,147 @@
+import * as cheerio from 'cheerio';
+import type {AnyNode, Cheerio} from 'cheerio';
+
+// Elements that never hold article content
+const BOILERPLATE_SELECTOR = [
+	'script',
+	'style',
+	'noscript',
+	'template',
+	'iframe',
+	'form',
+	'nav',
+	'header',
+	'footer',
+	'aside',
+	'[role="navigation"]',
+	'[role="banner"]',
+	'[role="contentinfo"]',
+	'[role="complementary"]',
+	'[aria-hidden="true"]',
+].join(', ');
+
+// class/id hints for navigation, ads and other page chrome
+const BOILERPLATE_HINT =
+	/(^|[-_\s])(nav|navbar|menu|breadcrumbs?|sidebar|footer|masthead|comments?|ads?|advert\w*|sponsor\w*|promo\w*|banner|cookie\w*|share|social|related|newsletter|popup|modal)([-_\s]|$)/i;
+
+// Containers that usually wrap the main content when a page marks it up
+const MAIN_CONTENT_SELECTOR = 'article, main, [role="main"]';
+
+// Minimum text length for a block to count as content
+const MIN_CONTENT_LENGTH = 140;
+
+type Selection = Cheerio<AnyNode>;
+
+function escapeHtml(text: string): string {
+	return text
+		.replace(/&/g, '&amp;')
+		.replace(/</g, '&lt;')
+		.replace(/>/g, '&gt;');
+}
+
+function textLength($el: Selection): number {
+	return $el.text().replace(/\s+/g, ' ').trim().length;
+}
+
+// Share of an element's text that sits inside links; menus are mostly links
+function linkDensity($: cheerio.CheerioAPI, $el: Selection): number {
+	const total = textLength($el);
+	if (total === 0) {
+		return 1;
+	}
+	let linked = 0;
+	$el.find('a').each((_i, a) => {
+		linked += textLength($(a));
+	});
+	return linked / total;
+}
+
+/**
+ * Picks the element with the highest paragraph score, crediting each paragraph
+ * to its parent and, at half weight, its grandparent
+ */
+function findDensestBlock($: cheerio.CheerioAPI): Selection | undefined {
+	const scores = new Map<AnyNode, {$el: Selection; score: number}>();
+	const credit = ($el: Selection, score: number) => {
+		const node = $el.get(0);
+		if (!node || $el.is('body, html')) {
+			return;
+		}
+		const entry = scores.get(node) ?? {$el, score: 0};
+		entry.score += score;
+		scores.set(node, entry);
+	};
+
+	$('p, pre, td, blockquote').each((_i, elem) => {
+		const length = textLength($(elem));
+		if (length < 25) {
+			return;
+		}
+		// Longer paragraphs with more clauses look more like prose
+		const score =
+			1 + Math.min(length / 100, 3) + $(elem).text().split(',').length;
+		const $parent = $(elem).parent();
+		credit($parent, score);
+		credit($parent.parent(), score / 2);
+	});
+
+	let best: {$el: Selection; score: number} | undefined;
+	for (const entry of scores.values()) {
+		const adjusted = entry.score * (1 - linkDensity($, entry.$el));
+		if (!best || adjusted > best.score) {
+			best = {$el: entry.$el, score: adjusted};
+		}
+	}
+	return best?.$el;
+}
+
+/**
+ * Reduces an HTML page to its main content, in the spirit of Mozilla's
+ * Readability: navigation, headers, footers, sidebars and ads are removed,
+ * then the article/main element or the block densest in paragraph text is
+ * kept. Returns the original HTML when no content block stands out.
+ */
+export function extractMainContent(html: string): string {
+	const $ = cheerio.load(html);
+	const title = $('title').first().text().trim();
+
+	$(BOILERPLATE_SELECTOR)
+		.filter(
+			(_i, elem) =>
+				// An article's own header and footer hold its title and byline
+				!$(elem).is('header, footer') ||
+				$(elem).closest(MAIN_CONTENT_SELECTOR).length === 0,
+		)
+		.remove();
+	$('[class], [id]').each((_i, elem) => {
+		const $el = $(elem);
+		if ($el.is(`body, html, ${MAIN_CONTENT_SELECTOR}`)) {
+			return;
+		}
+		const hint = `${$el.attr('class') ?? ''} ${$el.attr('id') ?? ''}`;
+		if (BOILERPLATE_HINT.test(hint)) {
+			$el.remove();
+		}
+	});
+
+	let $content: Selection | undefined;
+	for (const elem of $(MAIN_CONTENT_SELECTOR).toArray()) {
+		const $el = $(elem);
+		if (!$content || textLength($el) > textLength($content)) {
+			$content = $el;
+		}
+	}
+	if (!$content || textLength($content) < MIN_CONTENT_LENGTH) {
+		$content = findDensestBlock($);
+	}
+	if (!$content || textLength($content) < MIN_CONTENT_LENGTH) {
+		return html;
+	}
+
+	const escapedTitle = escapeHtml(title);
+	const heading =
+		title && $content.find('h1').length === 0
+			? `<h1>${escapedTitle}</h1>`
+			: '';
+	return `<html><head><title>${escapedTitle}</title></head><body>${heading}${$.html($content)}</body></html>`;
+}
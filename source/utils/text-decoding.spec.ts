@@ -0,0 +1,69 @@
+import test from 'ava';
+import {
+	decodeText,
+	detectEncoding,
+	formatLossyPathNote,
+	isLossyPath,
+} from './text-decoding';
+
+console.log('\ntext-decoding.spec.ts');
+
+// "café" encoded as Latin-1 - the é (0xE9) is not valid UTF-8
+const LATIN1_CAFE = Buffer.from('café\n', 'latin1');
+
+test('detectEncoding defaults to UTF-8', t => {
+	t.is(detectEncoding(Buffer.from('hello')), 'utf-8');
+	t.is(detectEncoding(Buffer.alloc(0)), 'utf-8');
+});
+
+test('detectEncoding recognises UTF-16 byte order marks', t => {
+	t.is(detectEncoding(Buffer.from([0xff, 0xfe, 0x41, 0x00])), 'utf-16le');
+	t.is(detectEncoding(Buffer.from([0xfe, 0xff, 0x00, 0x41])), 'utf-16be');
+});
+
+test('decodeText decodes valid UTF-8 strictly', t => {
+	const result = decodeText(Buffer.from('café ☕', 'utf-8'));
+
+	t.deepEqual(result, {text: 'café ☕', encoding: 'utf-8', lossy: false});
+});
+
+test('decodeText rejects Latin-1 content by default', t => {
+	t.throws(() => decodeText(LATIN1_CAFE), {
+		message: 'Content is not valid UTF-8',
+	});
+});
+
+test('decodeText replaces invalid bytes when lossy', t => {
+	const result = decodeText(LATIN1_CAFE, {lossy: true});
+
+	t.is(result.text, 'caf\uFFFD\n');
+	t.true(result.lossy);
+});
+
+test('decodeText keeps a UTF-8 byte order mark', t => {
+	const result = decodeText(Buffer.from([0xef, 0xbb, 0xbf, 0x41]));
+
+	t.is(result.text, '\uFEFFA');
+});
+
+test('decodeText decodes UTF-16LE using its byte order mark', t => {
+	const result = decodeText(Buffer.from([0xff, 0xfe, 0x68, 0x00, 0x69, 0x00]));
+
+	t.deepEqual(result, {text: 'hi', encoding: 'utf-16le', lossy: false});
+});
+
+test('isLossyPath spots names decoded with replacement characters', t => {
+	const name = Buffer.from([0x61, 0xff, 0x2e, 0x74, 0x78, 0x74]).toString();
+	t.true(isLossyPath(name));
+	t.false(isLossyPath('caf\u00e9.txt'));
+});
+
+test('formatLossyPathNote counts lossy paths and is empty otherwise', t => {
+	t.is(formatLossyPathNote(['a.ts', 'b.ts']), '');
+	t.true(formatLossyPathNote(['a\uFFFD.ts']).startsWith('[1 path contains'));
+	t.true(
+		formatLossyPathNote(['a\uFFFD.ts', 'b\uFFFD.ts', 'c.ts']).startsWith(
+			'[2 paths contain',
+		),
+	);
+});
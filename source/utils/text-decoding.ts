@@ -0,0 +1,73 @@
+export type TextEncodingName = 'utf-8' | 'utf-16le' | 'utf-16be';
+
+export interface DecodedText {
+	text: string;
+	encoding: TextEncodingName;
+	/** True if invalid byte sequences were replaced with U+FFFD */
+	lossy: boolean;
+}
+
+/**
+ * Detects the encoding of a buffer from its byte order mark, defaulting to
+ * UTF-8 when there is none
+ */
+export function detectEncoding(buffer: Uint8Array): TextEncodingName {
+	if (buffer[0] === 0xff && buffer[1] === 0xfe) {
+		return 'utf-16le';
+	}
+	if (buffer[0] === 0xfe && buffer[1] === 0xff) {
+		return 'utf-16be';
+	}
+	return 'utf-8';
+}
+
+/**
+ * Decodes a buffer as text.
+ *
+ * By default invalid byte sequences throw. With `lossy` they are replaced with
+ * U+FFFD instead and the result is flagged as lossy. A UTF-8 byte order mark
+ * is kept so that rewriting the text preserves it.
+ */
+export function decodeText(
+	buffer: Uint8Array,
+	options: {lossy?: boolean} = {},
+): DecodedText {
+	const encoding = detectEncoding(buffer);
+	// Only UTF-8 keeps its BOM; UTF-16 text is re-encoded as UTF-8 on write
+	const ignoreBOM = encoding === 'utf-8';
+
+	try {
+		const text = new TextDecoder(encoding, {fatal: true, ignoreBOM}).decode(
+			buffer,
+		);
+		return {text, encoding, lossy: false};
+	} catch {
+		if (!options.lossy) {
+			throw new Error(`Content is not valid ${encoding.toUpperCase()}`);
+		}
+	}
+
+	const text = new TextDecoder(encoding, {ignoreBOM}).decode(buffer);
+	return {text, encoding, lossy: true};
+}
+
+/**
+ * Whether a path was decoded lossily. Node decodes file names and command
+ * output as UTF-8, so a name with bytes that aren't valid UTF-8 comes back
+ * with U+FFFD in their place and no longer names the file on disk.
+ */
+export function isLossyPath(path: string): boolean {
+	return path.includes('\uFFFD');
+}
+
+/**
+ * Note for tool output that lists paths, warning that the lossily decoded
+ * ones can't be passed back as written. Empty when every path is exact.
+ */
+export function formatLossyPathNote(paths: string[]): string {
+	const count = paths.filter(isLossyPath).length;
+	if (count === 0) {
+		return '';
+	}
+	return `[${count} path${count === 1 ? '' : 's'} contain${count === 1 ? 's' : ''} bytes that aren't valid UTF-8, shown as \uFFFD. Such a path can't be used with other tools as written; use a shell glob in execute_bash instead]`;
+}
@@ -52,3 +52,17 @@ export function parseToolArguments<T = Record<string, unknown>>(
 	}
 	return args as T;
 }
+
+/**
+ * JSON with object keys sorted, so the same arguments given in a different
+ * order serialize identically
+ */
+export function canonicalJSON(value: unknown): string {
+	return JSON.stringify(value, (_key, nested: unknown) =>
+		nested && typeof nested === 'object' && !Array.isArray(nested)
+			? Object.fromEntries(
+					Object.entries(nested).sort(([a], [b]) => a.localeCompare(b)),
+				)
+			: nested,
+	);
+}
@@ -0,0 +1,26 @@
+import test from 'ava';
+import {truncateToolResult} from './tool-result-truncation';
+
+console.log('\ntool-result-truncation.spec.ts');
+
+test('truncateToolResult - leaves results under the cap untouched', t => {
+	const result = truncateToolResult('x'.repeat(64), 64);
+
+	t.is(result.content, 'x'.repeat(64));
+	t.is(result.originalBytes, undefined);
+});
+
+test('truncateToolResult - cuts results over the cap and marks them', t => {
+	const result = truncateToolResult('y'.repeat(1000), 64);
+
+	t.is(result.content, `${'y'.repeat(64)}\n\n[truncated: 64 of 1000 bytes]`);
+	t.is(result.originalBytes, 1000);
+});
+
+test('truncateToolResult - never splits a multi-byte character', t => {
+	// Each '€' is three bytes in UTF-8
+	const {content, originalBytes} = truncateToolResult('€€€€', 7);
+
+	t.is(content, '€€\n\n[truncated: 6 of 12 bytes]');
+	t.is(originalBytes, 12);
+});
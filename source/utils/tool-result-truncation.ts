@@ -0,0 +1,24 @@
+/**
+ * Cuts a tool result down to maxBytes of UTF-8, never splitting a character,
+ * and appends a marker saying how much was kept. originalBytes is set only
+ * when the content was cut.
+ */
+export function truncateToolResult(
+	content: string,
+	maxBytes: number,
+): {content: string; originalBytes?: number} {
+	const bytes = Buffer.from(content, 'utf8');
+	if (bytes.length <= maxBytes) {
+		return {content};
+	}
+
+	// Back up to the start of a character so no U+FFFD is produced
+	let end = maxBytes;
+	while (end > 0 && (bytes[end] & 0xc0) === 0x80) {
+		end--;
+	}
+	return {
+		content: `${bytes.subarray(0, end).toString('utf8')}\n\n[truncated: ${end} of ${bytes.length} bytes]`,
+		originalBytes: bytes.length,
+	};
+}
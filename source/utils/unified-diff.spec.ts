@@ -0,0 +1,39 @@
+import test from 'ava';
+import {createUnifiedDiff} from './unified-diff';
+
+console.log('\nunified-diff.spec.ts');
+
+test('createUnifiedDiff returns an empty string when nothing changes', t => {
+	t.is(createUnifiedDiff('a.txt', 'same\n', 'same\n'), '');
+});
+
+test('createUnifiedDiff produces a git-style hunk for a changed line', t => {
+	const diff = createUnifiedDiff(
+		'src/a.ts',
+		'one\ntwo\nthree\n',
+		'one\nTWO\nthree\n',
+	);
+
+	t.is(
+		diff,
+		[
+			'--- a/src/a.ts',
+			'+++ b/src/a.ts',
+			'@@ -1,3 +1,3 @@',
+			' one',
+			'-two',
+			'+TWO',
+			' three',
+		].join('\n'),
+	);
+});
+
+test('createUnifiedDiff limits context lines around a change', t => {
+	const before = `${Array.from({length: 10}, (_, i) => `line ${i + 1}`).join('\n')}\n`;
+	const after = before.replace('line 8', 'line eight');
+
+	const diff = createUnifiedDiff('big.txt', before, after, 1);
+
+	t.true(diff.includes('@@ -7,3 +7,3 @@'));
+	t.false(diff.includes('line 1\n'));
+});
@@ -0,0 +1,35 @@
+import {structuredPatch} from 'diff';
+
+/**
+ * Builds a unified diff of a file's content before and after a change, in the
+ * same format as `git diff`. Returns an empty string when nothing changes.
+ */
+export function createUnifiedDiff(
+	path: string,
+	before: string,
+	after: string,
+	context = 3,
+): string {
+	if (before === after) {
+		return '';
+	}
+
+	const patch = structuredPatch(
+		`a/${path}`,
+		`b/${path}`,
+		before,
+		after,
+		undefined,
+		undefined,
+		{context},
+	);
+
+	const lines = [`--- a/${path}`, `+++ b/${path}`];
+	for (const hunk of patch.hunks) {
+		lines.push(
+			`@@ -${hunk.oldStart},${hunk.oldLines} +${hunk.newStart},${hunk.newLines} @@`,
+			...hunk.lines,
+		);
+	}
+	return lines.join('\n');
+}
@@ -0,0 +1,309 @@
+import {createServer, type Server} from 'node:http';
+import type {AddressInfo} from 'node:net';
+import test from 'ava';
+import {
+	checkUrlAllowed,
+	checkUrlAllowedResolved,
+	fetchWithPolicy,
+	isPrivateAddress,
+	publicOnlyLookup,
+} from './url-policy';
+
+console.log('\nurl-policy.spec.ts');
+
+let server: Server;
+let baseUrl: string;
+let port: number;
+
+test.before(async () => {
+	// Minimal server: /redirect?to=<url> issues a 302, /loop redirects to
+	// itself, /headers echoes the credential headers, everything else
+	// returns 200
+	server = createServer((req, res) => {
+		const url = new URL(req.url || '/', 'http://127.0.0.1');
+		if (url.pathname === '/headers') {
+			res.writeHead(200, {'content-type': 'application/json'});
+			res.end(
+				JSON.stringify({
+					authorization: req.headers.authorization ?? null,
+					cookie: req.headers.cookie ?? null,
+				}),
+			);
+			return;
+		}
+		if (url.pathname === '/redirect') {
+			res.writeHead(302, {location: url.searchParams.get('to') || '/'});
+			res.end();
+			return;
+		}
+		if (url.pathname === '/loop') {
+			res.writeHead(302, {location: '/loop'});
+			res.end();
+			return;
+		}
+		res.writeHead(200, {'content-type': 'text/plain'});
+		res.end('ok');
+	});
+
+	await new Promise<void>(resolve => server.listen(0, '127.0.0.1', resolve));
+	port = (server.address() as AddressInfo).port;
+	baseUrl = `http://127.0.0.1:${port}`;
+});
+
+test.after.always(() => {
+	server?.close();
+});
+
+// ============================================================================
+// isPrivateAddress
+// ============================================================================
+
+test('isPrivateAddress detects loopback and private IPv4 ranges', t => {
+	for (const host of [
+		'localhost',
+		'127.0.0.1',
+		'0.0.0.0',
+		'10.1.2.3',
+		'172.16.0.1',
+		'172.31.255.255',
+		'192.168.1.1',
+		'169.254.169.254',
+		'100.64.0.1',
+		'100.127.255.255',
+	]) {
+		t.true(isPrivateAddress(host), host);
+	}
+});
+
+test('isPrivateAddress detects loopback and private IPv6 addresses', t => {
+	for (const host of ['[::1]', '[fd00::1]', '[fe80::1]', '[::ffff:7f00:1]']) {
+		t.true(isPrivateAddress(host), host);
+	}
+});
+
+test('isPrivateAddress allows public addresses and hostnames', t => {
+	for (const host of ['8.8.8.8', '172.32.0.1', '100.128.0.1', 'example.com', '[2606:4700::1]']) {
+		t.false(isPrivateAddress(host), host);
+	}
+});
+
+// ============================================================================
+// checkUrlAllowed
+// ============================================================================
+
+test('checkUrlAllowed blocks private addresses by default', t => {
+	t.regex(
+		checkUrlAllowed(new URL('http://localhost:3000'), {}) || '',
+		/internal\/private network/,
+	);
+	t.is(checkUrlAllowed(new URL('https://example.com'), {}), undefined);
+});
+
+test('checkUrlAllowed blocks cloud metadata and localhost by default', t => {
+	for (const url of [
+		'http://169.254.169.254/latest/meta-data/',
+		'http://localhost:8080/',
+		'http://localhost./',
+		'http://[::1]/',
+		'http://2130706433/',
+	]) {
+		t.regex(
+			checkUrlAllowed(new URL(url), {}) || '',
+			/internal\/private network/,
+			url,
+		);
+	}
+});
+
+test('checkUrlAllowed permits private addresses with allowPrivate', t => {
+	t.is(
+		checkUrlAllowed(new URL('http://169.254.169.254/'), {allowPrivate: true}),
+		undefined,
+	);
+});
+
+test('checkUrlAllowed lets allowed hosts reach private addresses', t => {
+	t.is(
+		checkUrlAllowed(new URL('http://localhost:3000'), {
+			allowedHosts: ['localhost'],
+		}),
+		undefined,
+	);
+});
+
+test('checkUrlAllowed restricts fetches to the allowed hosts list', t => {
+	const policy = {allowedHosts: ['example.com']};
+
+	t.is(checkUrlAllowed(new URL('https://docs.example.com'), policy), undefined);
+	t.regex(
+		checkUrlAllowed(new URL('https://other.org'), policy) || '',
+		/not in the allowed hosts list/,
+	);
+});
+
+test('checkUrlAllowed refuses blocked hosts and their subdomains', t => {
+	const policy = {blockedHosts: ['tracker.net']};
+
+	t.regex(
+		checkUrlAllowed(new URL('https://cdn.tracker.net/x'), policy) || '',
+		/Host is blocked/,
+	);
+	t.is(checkUrlAllowed(new URL('https://nottracker.net'), policy), undefined);
+});
+
+test('checkUrlAllowed rejects non-HTTP protocols', t => {
+	t.regex(
+		checkUrlAllowed(new URL('ftp://example.com'), {}) || '',
+		/Invalid URL protocol/,
+	);
+});
+
+// ============================================================================
+// checkUrlAllowedResolved
+// ============================================================================
+
+test('checkUrlAllowedResolved refuses hosts that resolve to private addresses', async t => {
+	const refusal = await checkUrlAllowedResolved(
+		new URL('https://internal.example.com'),
+		{},
+		async () => ['93.184.216.34', '10.0.0.5'],
+	);
+
+	t.regex(refusal || '', /internal.example.com resolves to 10.0.0.5/);
+});
+
+test('checkUrlAllowedResolved permits hosts that resolve to public addresses', async t => {
+	const refusal = await checkUrlAllowedResolved(
+		new URL('https://example.com'),
+		{},
+		async () => ['93.184.216.34'],
+	);
+
+	t.is(refusal, undefined);
+});
+
+test('checkUrlAllowedResolved skips resolution with allowPrivate or allowed hosts', async t => {
+	const resolve = async () => ['127.0.0.1'];
+
+	t.is(
+		await checkUrlAllowedResolved(
+			new URL('https://dev.example.com'),
+			{allowPrivate: true},
+			resolve,
+		),
+		undefined,
+	);
+	t.is(
+		await checkUrlAllowedResolved(
+			new URL('https://dev.example.com'),
+			{allowedHosts: ['dev.example.com']},
+			resolve,
+		),
+		undefined,
+	);
+});
+
+test('checkUrlAllowedResolved leaves DNS failures to the request', async t => {
+	const refusal = await checkUrlAllowedResolved(
+		new URL('https://missing.invalid'),
+		{},
+		async () => {
+			throw new Error('ENOTFOUND');
+		},
+	);
+
+	t.is(refusal, undefined);
+});
+
+// ============================================================================
+// fetchWithPolicy
+// ============================================================================
+
+test('fetchWithPolicy follows redirects to allowed hosts', async t => {
+	const {response, url} = await fetchWithPolicy(
+		`${baseUrl}/redirect?to=/final`,
+		{allowedHosts: ['127.0.0.1']},
+	);
+
+	t.is(response.status, 200);
+	t.is(await response.text(), 'ok');
+	t.is(url, `${baseUrl}/final`);
+});
+
+test('fetchWithPolicy refuses a redirect to a private host', async t => {
+	// 127.0.0.1 is allowed, but the redirect target localhost is not
+	await t.throwsAsync(
+		fetchWithPolicy(
+			`${baseUrl}/redirect?to=${encodeURIComponent(`http://localhost:${port}/`)}`,
+			{allowedHosts: ['127.0.0.1']},
+		),
+		{message: /internal\/private network address: localhost/},
+	);
+});
+
+test('fetchWithPolicy refuses a redirect to a blocked host', async t => {
+	await t.throwsAsync(
+		fetchWithPolicy(
+			`${baseUrl}/redirect?to=${encodeURIComponent('http://evil.example/')}`,
+			{allowedHosts: ['127.0.0.1', 'evil.example'], blockedHosts: ['evil.example']},
+		),
+		{message: /Host is blocked: evil.example/},
+	);
+});
+
+test('fetchWithPolicy stops after maxRedirects', async t => {
+	await t.throwsAsync(
+		fetchWithPolicy(`${baseUrl}/loop`, {
+			allowedHosts: ['127.0.0.1'],
+			maxRedirects: 2,
+		}),
+		{message: /Too many redirects \(max 2\)/},
+	);
+});
+
+test('fetchWithPolicy blocks the metadata address by default', async t => {
+	await t.throwsAsync(
+		fetchWithPolicy('http://169.254.169.254/latest/meta-data/', {}),
+		{message: /internal\/private network address: 169.254.169.254/},
+	);
+});
+
+test('fetchWithPolicy refuses private hosts before connecting', async t => {
+	await t.throwsAsync(fetchWithPolicy(`${baseUrl}/`, {}), {
+		message: /internal\/private network address: 127.0.0.1/,
+	});
+});
+
+test('fetchWithPolicy drops credentials on a cross-origin redirect', async t => {
+	const init = {headers: {Authorization: 'Bearer secret', Cookie: 'session=1'}};
+	const policy = {allowedHosts: ['127.0.0.1', 'localhost']};
+
+	const sameOrigin = await fetchWithPolicy(
+		`${baseUrl}/redirect?to=/headers`,
+		policy,
+		init,
+	);
+	t.deepEqual(await sameOrigin.response.json(), {
+		authorization: 'Bearer secret',
+		cookie: 'session=1',
+	});
+
+	const crossOrigin = await fetchWithPolicy(
+		`${baseUrl}/redirect?to=${encodeURIComponent(`http://localhost:${port}/headers`)}`,
+		policy,
+		init,
+	);
+	t.deepEqual(await crossOrigin.response.json(), {
+		authorization: null,
+		cookie: null,
+	});
+});
+
+test('publicOnlyLookup refuses names that resolve to private addresses', async t => {
+	const error = await new Promise<Error | null>(resolve => {
+		publicOnlyLookup('localhost', {}, lookupError => resolve(lookupError));
+	});
+
+	t.regex(error?.message ?? '', /localhost resolves to/);
+});
+
@@ -0,0 +1,291 @@
+import {lookup as dnsLookup} from 'node:dns';
+import {lookup} from 'node:dns/promises';
+import {isIP, type LookupFunction} from 'node:net';
+import {
+	type Dispatcher,
+	fetch,
+	Headers,
+	type RequestInit,
+	type Response,
+} from 'undici';
+import {DEFAULT_FETCH_URL_MAX_REDIRECTS} from '@/constants';
+import type {AppConfig} from '@/types/index';
+import {
+	getLookupHttpDispatcher,
+	getSharedHttpDispatcher,
+} from '@/utils/http-dispatcher';
+
+export type FetchPolicy = NonNullable<AppConfig['fetchUrl']>;
+
+function isPrivateIPv4(address: string): boolean {
+	const [a, b] = address.split('.').map(Number);
+	return (
+		a === 0 ||
+		a === 10 ||
+		a === 127 ||
+		(a === 100 && b >= 64 && b <= 127) ||
+		(a === 169 && b === 254) ||
+		(a === 172 && b >= 16 && b <= 31) ||
+		(a === 192 && b === 168)
+	);
+}
+
+function isPrivateIPv6(address: string): boolean {
+	if (address === '::' || address === '::1') {
+		return true;
+	}
+
+	// IPv4-mapped addresses, in dotted form or as normalized by the URL parser
+	// (e.g. ::ffff:127.0.0.1 becomes ::ffff:7f00:1)
+	const dottedMapped = address.match(/^::ffff:(\d+\.\d+\.\d+\.\d+)$/);
+	if (dottedMapped) {
+		return isPrivateIPv4(dottedMapped[1]);
+	}
+	const hexMapped = address.match(/^::ffff:([0-9a-f]{1,4}):([0-9a-f]{1,4})$/);
+	if (hexMapped) {
+		const high = Number.parseInt(hexMapped[1], 16);
+		const low = Number.parseInt(hexMapped[2], 16);
+		return isPrivateIPv4(
+			`${high >> 8}.${high & 255}.${low >> 8}.${low & 255}`,
+		);
+	}
+
+	// Unique local (fc00::/7) and link-local (fe80::/10)
+	const firstHextet = Number.parseInt(address.split(':')[0] || '0', 16);
+	return (
+		(firstHextet & 0xfe00) === 0xfc00 || (firstHextet & 0xffc0) === 0xfe80
+	);
+}
+
+/**
+ * Returns true for localhost and loopback, link-local and private IP literals
+ */
+export function isPrivateAddress(hostname: string): boolean {
+	const host = hostname
+		.toLowerCase()
+		.replace(/^\[|\]$/g, '')
+		.replace(/\.$/, '');
+
+	if (host === 'localhost' || host.endsWith('.localhost')) {
+		return true;
+	}
+
+	switch (isIP(host)) {
+		case 4:
+			return isPrivateIPv4(host);
+		case 6:
+			return isPrivateIPv6(host);
+		default:
+			return false;
+	}
+}
+
+// Matches the host itself or any subdomain of a listed host
+export function matchesHost(hostname: string, hosts: string[] = []): boolean {
+	const host = hostname.toLowerCase();
+	return hosts.some(entry => {
+		const listed = entry.toLowerCase();
+		return host === listed || host.endsWith(`.${listed}`);
+	});
+}
+
+/**
+ * Checks a URL against the fetch policy. Returns the reason it is refused, or
+ * undefined if it may be fetched.
+ */
+export function checkUrlAllowed(
+	url: URL,
+	policy: FetchPolicy,
+): string | undefined {
+	if (!['http:', 'https:'].includes(url.protocol)) {
+		return `Invalid URL protocol "${url.protocol}". Only http: and https: are supported.`;
+	}
+
+	const hostname = url.hostname.toLowerCase();
+
+	if (matchesHost(hostname, policy.blockedHosts)) {
+		return `Host is blocked: ${hostname}`;
+	}
+
+	const isAllowedHost = matchesHost(hostname, policy.allowedHosts);
+	if (policy.allowedHosts?.length && !isAllowedHost) {
+		return `Host is not in the allowed hosts list: ${hostname}`;
+	}
+
+	// Explicitly allowed hosts may point at internal services
+	if (!policy.allowPrivate && !isAllowedHost && isPrivateAddress(hostname)) {
+		return `Cannot fetch from internal/private network address: ${hostname}`;
+	}
+
+	return undefined;
+}
+
+type HostLookup = (hostname: string) => Promise<string[]>;
+
+const lookupAddresses: HostLookup = async hostname => {
+	const results = await lookup(hostname, {all: true});
+	return results.map(result => result.address);
+};
+
+// Hosts whose resolved addresses must be checked: names that are neither
+// allowed explicitly nor IP literals, unless private addresses are allowed
+function needsAddressCheck(hostname: string, policy: FetchPolicy): boolean {
+	return !(
+		policy.allowPrivate ||
+		matchesHost(hostname, policy.allowedHosts) ||
+		isIP(hostname.replace(/^\[|\]$/g, '')) !== 0
+	);
+}
+
+/**
+ * Like checkUrlAllowed, but also resolves the host so that names pointing at
+ * internal addresses (e.g. a public DNS record for 127.0.0.1) are refused
+ */
+export async function checkUrlAllowedResolved(
+	url: URL,
+	policy: FetchPolicy,
+	resolve: HostLookup = lookupAddresses,
+): Promise<string | undefined> {
+	const refusal = checkUrlAllowed(url, policy);
+	if (refusal) {
+		return refusal;
+	}
+
+	const hostname = url.hostname.toLowerCase();
+	if (!needsAddressCheck(hostname, policy)) {
+		return undefined;
+	}
+
+	let addresses: string[];
+	try {
+		addresses = await resolve(hostname);
+	} catch {
+		// Let the request itself report DNS failures
+		return undefined;
+	}
+
+	const privateAddress = addresses.find(address => isPrivateAddress(address));
+	if (privateAddress) {
+		return `Cannot fetch from internal/private network address: ${hostname} resolves to ${privateAddress}`;
+	}
+
+	return undefined;
+}
+
+/**
+ * Resolves a host for a new connection like dns.lookup, but fails when any
+ * address is private. The address checked is then the one connected to, so a
+ * DNS answer that changes after checkUrlAllowedResolved (DNS rebinding)
+ * cannot reach an internal service.
+ */
+export const publicOnlyLookup: LookupFunction = (
+	hostname,
+	options,
+	callback,
+) => {
+	dnsLookup(hostname, {...options, all: true}, (error, addresses) => {
+		if (error) {
+			callback(error, '');
+			return;
+		}
+
+		const privateAddress = addresses.find(entry =>
+			isPrivateAddress(entry.address),
+		);
+		if (privateAddress) {
+			callback(
+				new Error(
+					`Cannot fetch from internal/private network address: ${hostname} resolves to ${privateAddress.address}`,
+				),
+				'',
+			);
+			return;
+		}
+
+		if (options.all) {
+			callback(null, addresses);
+		} else {
+			callback(null, addresses[0].address, addresses[0].family);
+		}
+	});
+};
+
+// Behind a proxy, or with an injected dispatcher, addresses can't be pinned
+// and only the check before the request applies
+function getDispatcher(checkAddresses: boolean): Dispatcher {
+	return checkAddresses
+		? getLookupHttpDispatcher(publicOnlyLookup)
+		: getSharedHttpDispatcher();
+}
+
+// Sent only to the origin they were given for, not on to a redirect target
+const CREDENTIAL_HEADERS = ['authorization', 'cookie', 'proxy-authorization'];
+
+function withoutCredentials(
+	init: RequestInit,
+	originOnlyHeaders: string[],
+): RequestInit {
+	const headers = new Headers(init.headers);
+	for (const name of [...CREDENTIAL_HEADERS, ...originOnlyHeaders]) {
+		headers.delete(name);
+	}
+	return {...init, headers};
+}
+
+/**
+ * Fetches a URL, following redirects by hand so that every hop is checked
+ * against the policy rather than only the initial URL. Credential headers,
+ * and any named in `originOnlyHeaders`, are dropped once a redirect leaves
+ * the original origin.
+ */
+export async function fetchWithPolicy(
+	url: string,
+	policy: FetchPolicy,
+	init: RequestInit = {},
+	originOnlyHeaders: string[] = [],
+): Promise<{response: Response; url: string}> {
+	const maxRedirects = policy.maxRedirects ?? DEFAULT_FETCH_URL_MAX_REDIRECTS;
+	let current = new URL(url);
+	let requestInit = init;
+
+	for (let redirects = 0; ; redirects++) {
+		const refusal = await checkUrlAllowedResolved(current, policy);
+		if (refusal) {
+			throw new Error(refusal);
+		}
+
+		const response = await fetch(current, {
+			dispatcher: getDispatcher(
+				needsAddressCheck(current.hostname.toLowerCase(), policy),
+			),
+			...requestInit,
+			redirect: 'manual',
+		});
+		const location = response.headers.get('location');
+
+		if (response.status < 300 || response.status >= 400 || !location) {
+			return {response, url: current.href};
+		}
+
+		// Release the connection held by the redirect body
+		await response.body?.cancel();
+
+		if (redirects >= maxRedirects) {
+			throw new Error(`Too many redirects (max ${maxRedirects})`);
+		}
+
+		const next = new URL(location, current);
+		if (
+			current.protocol === 'https:' &&
+			next.protocol === 'http:' &&
+			!policy.allowHttpsDowngrade
+		) {
+			throw new Error(`Refusing redirect from HTTPS to HTTP: ${next.href}`);
+		}
+
+		if (next.origin !== current.origin) {
+			requestInit = withoutCredentials(requestInit, originOnlyHeaders);
+		}
+		current = next;
+	}
+}
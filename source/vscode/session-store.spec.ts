@@ -0,0 +1,99 @@
+import test from 'ava';
+import * as fs from 'fs/promises';
+import * as path from 'path';
+import {CheckpointManager} from '@/services/checkpoint-manager';
+import type {Message} from '@/types/core';
+import {BridgeSessionStore} from './session-store';
+
+console.log('\nsession-store.spec.ts');
+
+async function createTempDir(): Promise<string> {
+	const tempDir = path.join(
+		process.cwd(),
+		'.test-temp',
+		`session-test-${Date.now()}-${Math.random().toString(36).slice(2)}`,
+	);
+	await fs.mkdir(tempDir, {recursive: true});
+	return tempDir;
+}
+
+const conversation: Message[] = [
+	{role: 'user', content: 'What is in a.ts?'},
+	{
+		role: 'assistant',
+		content: '',
+		tool_calls: [
+			{id: 'call_1', function: {name: 'read_file', arguments: {path: 'a.ts'}}},
+		],
+	},
+	{
+		role: 'tool',
+		content: 'export const a = 1;',
+		tool_call_id: 'call_1',
+		name: 'read_file',
+	},
+	{role: 'assistant', content: 'It exports a.'},
+];
+
+test.serial('a saved session loads back unchanged', async t => {
+	const tempDir = await createTempDir();
+	try {
+		const store = new BridgeSessionStore(tempDir);
+
+		await store.save('work', conversation, 'TestProvider', 'test-model');
+
+		t.deepEqual(await store.load('work'), conversation);
+	} finally {
+		await fs.rm(tempDir, {recursive: true, force: true});
+	}
+});
+
+test.serial('saving a session replaces one with the same id', async t => {
+	const tempDir = await createTempDir();
+	try {
+		const store = new BridgeSessionStore(tempDir);
+
+		await store.save('work', conversation, 'TestProvider', 'test-model');
+		await store.save('work', conversation.slice(0, 1), 'TestProvider', 'm');
+
+		t.deepEqual(await store.load('work'), conversation.slice(0, 1));
+		const sessions = await store.list();
+		t.deepEqual(
+			sessions.map(({id, messageCount}) => ({id, messageCount})),
+			[{id: 'work', messageCount: 1}],
+		);
+	} finally {
+		await fs.rm(tempDir, {recursive: true, force: true});
+	}
+});
+
+test.serial('sessions and checkpoints do not touch each other', async t => {
+	const tempDir = await createTempDir();
+	try {
+		const checkpoints = new CheckpointManager(tempDir);
+		const store = new BridgeSessionStore(tempDir);
+		for (const name of ['work', 'other']) {
+			await checkpoints.saveCheckpoint(
+				name,
+				conversation.slice(0, 1),
+				'TestProvider',
+				'test-model',
+				[],
+			);
+		}
+
+		await store.save('work', conversation, 'TestProvider', 'test-model');
+
+		const {conversation: saved} = await checkpoints.loadCheckpoint('work');
+		t.deepEqual(saved.messages, conversation.slice(0, 1));
+		t.deepEqual(await store.load('work'), conversation);
+		const sessions = await store.list();
+		t.deepEqual(sessions.map(session => session.id), ['work']);
+		const names = (await checkpoints.listCheckpoints()).map(
+			checkpoint => checkpoint.name,
+		);
+		t.deepEqual(names.sort(), ['other', 'work']);
+	} finally {
+		await fs.rm(tempDir, {recursive: true, force: true});
+	}
+});
@@ -0,0 +1,53 @@
+import {CheckpointManager} from '@/services/checkpoint-manager';
+import type {Message} from '@/types/core';
+import type {SessionInfo} from './protocol';
+
+/**
+ * Conversations saved and restored by name over the VS Code bridge. Sessions
+ * are conversation-only checkpoints kept in .nanocoder/sessions/, apart from
+ * the ones /checkpoint makes, so neither can overwrite or list the other.
+ */
+export class BridgeSessionStore {
+	private readonly checkpoints: CheckpointManager;
+
+	constructor(workspaceRoot: string = process.cwd()) {
+		this.checkpoints = new CheckpointManager(workspaceRoot, 'sessions');
+	}
+
+	/**
+	 * Saves a conversation, replacing any session already saved under the id
+	 */
+	async save(
+		id: string,
+		messages: Message[],
+		provider: string,
+		model: string,
+	): Promise<void> {
+		if (this.checkpoints.checkpointExists(id)) {
+			await this.checkpoints.deleteCheckpoint(id);
+		}
+		await this.checkpoints.saveCheckpoint(id, messages, provider, model, []);
+	}
+
+	/**
+	 * Loads a saved conversation
+	 */
+	async load(id: string): Promise<Message[]> {
+		const {conversation} = await this.checkpoints.loadCheckpoint(id, {
+			validateIntegrity: true,
+		});
+		return conversation.messages;
+	}
+
+	/**
+	 * Lists the saved sessions, most recently updated first
+	 */
+	async list(): Promise<SessionInfo[]> {
+		const checkpoints = await this.checkpoints.listCheckpoints();
+		return checkpoints.map(({name, metadata}) => ({
+			id: name,
+			updatedAt: metadata.timestamp,
+			messageCount: metadata.messageCount,
+		}));
+	}
+}
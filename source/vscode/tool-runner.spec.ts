@@ -0,0 +1,117 @@
+import test from 'ava';
+import {
+	setToolConfirmationHandler,
+	setToolManagerGetter,
+	setToolRegistryGetter,
+} from '@/message-handler';
+import {ToolManager} from '@/tools/tool-manager';
+import {BridgeToolRunner} from './tool-runner';
+
+console.log('\ntool-runner.spec.ts');
+
+const toolManager = new ToolManager();
+
+test.beforeEach(() => {
+	setToolManagerGetter(() => toolManager);
+	setToolRegistryGetter(() => toolManager.getToolRegistry());
+});
+
+test.afterEach.always(() => {
+	setToolConfirmationHandler(null);
+	setToolManagerGetter(() => null);
+	setToolRegistryGetter(() => ({}));
+});
+
+const echo = (id: string, text: string) => ({
+	id,
+	function: {name: 'execute_bash', arguments: {command: `echo ${text}`}},
+});
+
+test.serial('executeMany runs two bash echoes in one call', async t => {
+	setToolConfirmationHandler(async () => true);
+	const runner = new BridgeToolRunner();
+
+	const results = await runner.executeMany([
+		echo('first', 'one'),
+		echo('second', 'two'),
+	]);
+
+	t.deepEqual(results.map(result => result.id), ['first', 'second']);
+	t.is(results[0].error, undefined);
+	t.true(results[0].output?.includes('one'));
+	t.true(results[1].output?.includes('two'));
+});
+
+test.serial('tools that need approval are refused without a handler', async t => {
+	const runner = new BridgeToolRunner();
+
+	const [result] = await runner.executeMany([echo('call', 'hi')]);
+
+	t.deepEqual(result, {
+		id: 'call',
+		error: 'execute_bash needs approval and was not approved',
+	});
+});
+
+test.serial('tools the handler denies are not run', async t => {
+	setToolConfirmationHandler(async () => false);
+	const runner = new BridgeToolRunner();
+
+	const [result] = await runner.executeMany([echo('call', 'hi')]);
+
+	t.is(result.output, undefined);
+	t.is(result.error, 'execute_bash needs approval and was not approved');
+});
+
+test.serial('validation failures come back as errors', async t => {
+	const runner = new BridgeToolRunner();
+
+	const [result] = await runner.executeMany([
+		{id: 'call', function: {name: 'read_file', arguments: {}}},
+	]);
+
+	t.is(result.id, 'call');
+	t.truthy(result.error);
+	t.is(result.output, undefined);
+});
+
+test.serial('unknown tools come back as errors', async t => {
+	setToolConfirmationHandler(async () => true);
+	const runner = new BridgeToolRunner();
+
+	const [result] = await runner.executeMany([
+		{id: 'call', function: {name: 'no_such_tool', arguments: {}}},
+	]);
+
+	t.is(result.error, 'Unknown tool: no_such_tool');
+});
+
+test.serial('cancel stops a running bash call', async t => {
+	setToolConfirmationHandler(async () => true);
+	const runner = new BridgeToolRunner();
+	const started = Date.now();
+
+	const running = runner.execute({
+		id: 'sleep',
+		function: {name: 'execute_bash', arguments: {command: 'sleep 10'}},
+	});
+	await new Promise(resolve => setTimeout(resolve, 200));
+	t.true(runner.cancel('sleep'));
+	const result = await running;
+
+	t.true(Date.now() - started < 5000);
+	t.is(result.id, 'sleep');
+	t.true(result.cancelled);
+	t.true(result.output?.startsWith('Error: Cancelled by user'));
+	t.false(runner.cancel('sleep'));
+});
+
+test.serial('finished calls are not marked cancelled', async t => {
+	setToolConfirmationHandler(async () => true);
+	const runner = new BridgeToolRunner();
+
+	const result = await runner.execute(echo('call', 'hi'));
+
+	t.is(result.cancelled, undefined);
+	t.false(runner.cancel('call'));
+});
@@ -0,0 +1,112 @@
+import {
+	getToolConfirmationHandler,
+	getToolManager,
+	processToolUse,
+} from '@/message-handler';
+import type {ToolCall} from '@/types/core';
+import {formatError} from '@/utils/error-formatter';
+import {parseToolArguments} from '@/utils/tool-args-parser';
+import type {ToolCallInfo, ToolResultInfo} from './protocol';
+
+type NeedsApproval = boolean | ((args: unknown) => boolean | Promise<boolean>);
+
+/**
+ * Whether a tool call needs approval, from its tool's needsApproval. Unknown
+ * tools and failed checks need approval, as in the conversation loop.
+ */
+async function needsApproval(toolCall: ToolCall): Promise<boolean> {
+	const tool = getToolManager()?.getToolEntry(toolCall.function.name)?.tool as
+		| {needsApproval?: NeedsApproval}
+		| undefined;
+	const needsApprovalProp = tool?.needsApproval;
+	if (typeof needsApprovalProp === 'boolean') {
+		return needsApprovalProp;
+	}
+	if (typeof needsApprovalProp === 'function') {
+		try {
+			return await needsApprovalProp(
+				parseToolArguments(toolCall.function.arguments),
+			);
+		} catch {
+			return true;
+		}
+	}
+	return true;
+}
+
+/**
+ * Runs tool calls sent over the VS Code bridge. Calls are validated and
+ * approved the way the conversation loop does it: tools that need approval
+ * run only when the embedder's confirmation handler approves them, since
+ * nobody is at the prompt to answer for a bridge request.
+ */
+export class BridgeToolRunner {
+	// Aborts the tool calls still running, by call id
+	private readonly running = new Map<string, AbortController>();
+
+	/**
+	 * Runs one tool call. Failures come back as the result's error rather
+	 * than being thrown.
+	 */
+	async execute(toolCall: ToolCallInfo): Promise<ToolResultInfo> {
+		try {
+			const validator = getToolManager()?.getToolValidator(
+				toolCall.function.name,
+			);
+			if (validator) {
+				const validation = await validator(toolCall.function.arguments);
+				if (!validation.valid) {
+					return {id: toolCall.id, error: validation.error};
+				}
+			}
+
+			if (await needsApproval(toolCall)) {
+				const confirm = getToolConfirmationHandler();
+				if (!confirm || !(await confirm(toolCall))) {
+					return {
+						id: toolCall.id,
+						error: `${toolCall.function.name} needs approval and was not approved`,
+					};
+				}
+			}
+
+			const controller = new AbortController();
+			this.running.set(toolCall.id, controller);
+			try {
+				const result = await processToolUse(toolCall, controller.signal);
+				const info: ToolResultInfo = result.isError
+					? {id: toolCall.id, error: result.content}
+					: {id: toolCall.id, output: result.content};
+				return controller.signal.aborted ? {...info, cancelled: true} : info;
+			} finally {
+				if (this.running.get(toolCall.id) === controller) {
+					this.running.delete(toolCall.id);
+				}
+			}
+		} catch (error) {
+			return {id: toolCall.id, error: formatError(error)};
+		}
+	}
+
+	/**
+	 * Cancels a running tool call. Tools that honour the abort signal, such
+	 * as execute_bash, stop and return what they had so far.
+	 *
+	 * @returns false when no call with the id is running
+	 */
+	cancel(id: string): boolean {
+		const controller = this.running.get(id);
+		if (!controller) {
+			return false;
+		}
+		controller.abort();
+		return true;
+	}
+
+	/**
+	 * Runs tool calls in parallel, returning their results in call order
+	 */
+	executeMany(toolCalls: ToolCallInfo[]): Promise<ToolResultInfo[]> {
+		return Promise.all(toolCalls.map(toolCall => this.execute(toolCall)));
+	}
+}
@@ -2,11 +2,26 @@ import test from 'ava';
 import {WebSocket} from 'ws';
 import type {
 	AssistantMessage,
+	BashCancelledMessage,
+	ChatResponseMessage,
 	ConnectionAckMessage,
+	ContextRemainingMessage,
+	CostEstimateMessage,
 	DiagnosticsRequestMessage,
 	FileChangeMessage,
+	MCPRawResultMessage,
+	MCPStatusMessage,
+	ModelsMessage,
+	ProviderReadyMessage,
 	ServerMessage,
+	SessionMessage,
 	StatusMessage,
+	TokenCountMessage,
+	ToolCallInfo,
+	ToolDescriptionMessage,
+	ToolListMessage,
+	ToolResultMessage,
+	ToolResultsMessage,
 } from './protocol.js';
 import {
 	VSCodeServer,
@@ -576,6 +591,643 @@ test('VSCodeServer handles client messages - diagnostics_response', async t => {
 	await server.stop();
 });
 
+async function requestModels(server: VSCodeServer, port: number) {
+	await server.start();
+
+	const client = new WebSocket(`ws://127.0.0.1:${port}`);
+	await new Promise<void>(resolve => {
+		client.on('message', () => resolve());
+	});
+
+	const messagePromise = new Promise<ModelsMessage>(resolve => {
+		client.on('message', (data: {toString(): string}) => {
+			const msg = JSON.parse(data.toString()) as ServerMessage;
+			if (msg.type === 'models') {
+				resolve(msg);
+			}
+		});
+	});
+
+	client.send(JSON.stringify({type: 'get_models'}));
+	const message = await messagePromise;
+
+	client.close();
+	await server.stop();
+	return message;
+}
+
+test('VSCodeServer handles client messages - get_models', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	server.onCallbacks({
+		onGetModels: async () => [{id: 'gpt-4o', contextLength: 128000}],
+	});
+
+	const message = await requestModels(server, port);
+
+	t.deepEqual(message.models, [{id: 'gpt-4o', contextLength: 128000}]);
+	t.is(message.error, undefined);
+});
+
+test('VSCodeServer reports get_models failures to the client', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	server.onCallbacks({
+		onGetModels: async () => {
+			throw new Error('Failed to list models (401): unauthorized');
+		},
+	});
+
+	const message = await requestModels(server, port);
+
+	t.deepEqual(message.models, []);
+	t.is(message.error, 'Failed to list models (401): unauthorized');
+});
+
+test('VSCodeServer replies to get_models only on the requesting socket', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	server.onCallbacks({
+		onGetModels: async () => [{id: 'gpt-4o', contextLength: 128000}],
+	});
+	await server.start();
+
+	const requester = new WebSocket(`ws://127.0.0.1:${port}`);
+	const bystander = new WebSocket(`ws://127.0.0.1:${port}`);
+	await Promise.all([
+		new Promise<void>(resolve => requester.on('message', () => resolve())),
+		new Promise<void>(resolve => bystander.on('message', () => resolve())),
+	]);
+
+	let bystanderReceived = false;
+	bystander.on('message', (data: {toString(): string}) => {
+		const msg = JSON.parse(data.toString()) as ServerMessage;
+		if (msg.type === 'models') {
+			bystanderReceived = true;
+		}
+	});
+	const reply = new Promise<ModelsMessage>(resolve => {
+		requester.on('message', (data: {toString(): string}) => {
+			const msg = JSON.parse(data.toString()) as ServerMessage;
+			if (msg.type === 'models') {
+				resolve(msg);
+			}
+		});
+	});
+
+	requester.send(JSON.stringify({type: 'get_models'}));
+	const message = await reply;
+	await new Promise(resolve => setTimeout(resolve, 100));
+
+	requester.close();
+	bystander.close();
+	await server.stop();
+
+	t.deepEqual(message.models, [{id: 'gpt-4o', contextLength: 128000}]);
+	t.false(bystanderReceived);
+});
+
+async function requestToolDescription(
+	server: VSCodeServer,
+	port: number,
+	name: string,
+) {
+	await server.start();
+
+	const client = new WebSocket(`ws://127.0.0.1:${port}`);
+	await new Promise<void>(resolve => {
+		client.on('message', () => resolve());
+	});
+
+	const messagePromise = new Promise<ToolDescriptionMessage>(resolve => {
+		client.on('message', (data: {toString(): string}) => {
+			const msg = JSON.parse(data.toString()) as ServerMessage;
+			if (msg.type === 'tool_description') {
+				resolve(msg);
+			}
+		});
+	});
+
+	client.send(JSON.stringify({type: 'describe_tool', name}));
+	const message = await messagePromise;
+
+	client.close();
+	await server.stop();
+	return message;
+}
+
+const readFileDescription = {
+	name: 'read_file',
+	description: 'Read a file',
+	inputSchema: {
+		type: 'object',
+		properties: {path: {type: 'string', description: 'File to read'}},
+		required: ['path'],
+	},
+	category: 'file-read',
+};
+
+test('VSCodeServer handles client messages - describe_tool', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	server.onCallbacks({
+		onDescribeTool: async name =>
+			name === 'read_file' ? readFileDescription : undefined,
+	});
+
+	const message = await requestToolDescription(server, port, 'read_file');
+
+	t.is(message.name, 'read_file');
+	t.deepEqual(message.tool, readFileDescription);
+	t.is(message.error, undefined);
+});
+
+test('VSCodeServer reports describe_tool for an unknown tool', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	server.onCallbacks({
+		onDescribeTool: async name =>
+			name === 'read_file' ? readFileDescription : undefined,
+	});
+
+	const message = await requestToolDescription(server, port, 'no_such_tool');
+
+	t.is(message.name, 'no_such_tool');
+	t.is(message.tool, undefined);
+	t.is(message.error, 'Unknown tool: no_such_tool');
+});
+
+test('VSCodeServer handles client messages - list_tools', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	let receivedFilter: unknown;
+	server.onCallbacks({
+		onListTools: async filter => {
+			receivedFilter = filter;
+			return [readFileDescription];
+		},
+	});
+	await server.start();
+
+	const client = new WebSocket(`ws://127.0.0.1:${port}`);
+	await new Promise<void>(resolve => {
+		client.on('message', () => resolve());
+	});
+
+	const messagePromise = new Promise<ToolListMessage>(resolve => {
+		client.on('message', (data: {toString(): string}) => {
+			const msg = JSON.parse(data.toString()) as ServerMessage;
+			if (msg.type === 'tools') {
+				resolve(msg);
+			}
+		});
+	});
+
+	client.send(
+		JSON.stringify({
+			type: 'list_tools',
+			categories: ['file-read'],
+			namePrefix: 'read_',
+		}),
+	);
+	const message = await messagePromise;
+
+	client.close();
+	await server.stop();
+
+	t.deepEqual(receivedFilter, {categories: ['file-read'], namePrefix: 'read_'});
+	t.deepEqual(message.tools, [readFileDescription]);
+	t.is(message.error, undefined);
+});
+
+// Sends one client message and resolves with the first reply of a type
+async function requestReply<T extends ServerMessage>(
+	port: number,
+	request: object,
+	replyType: T['type'],
+): Promise<T> {
+	const client = new WebSocket(`ws://127.0.0.1:${port}`);
+	await new Promise<void>(resolve => {
+		client.on('message', () => resolve());
+	});
+
+	const reply = new Promise<T>(resolve => {
+		client.on('message', (data: {toString(): string}) => {
+			const msg = JSON.parse(data.toString()) as ServerMessage;
+			if (msg.type === replyType) {
+				resolve(msg as T);
+			}
+		});
+	});
+	client.send(JSON.stringify(request));
+	const message = await reply;
+	client.close();
+	return message;
+}
+
+test('VSCodeServer handles client messages - session_save and session_load', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	const conversation = [
+		{role: 'user' as const, content: 'hi'},
+		{
+			role: 'assistant' as const,
+			content: '',
+			tool_calls: [
+				{
+					id: 'call_1',
+					function: {name: 'read_file', arguments: {path: 'a'}},
+				},
+			],
+		},
+	];
+	const saved: string[] = [];
+	server.onCallbacks({
+		onSaveSession: async id => {
+			saved.push(id);
+		},
+		onLoadSession: async id => {
+			if (id !== 'known') {
+				throw new Error(`Session "${id}" not found`);
+			}
+			return conversation;
+		},
+	});
+	await server.start();
+
+	const save = await requestReply<SessionMessage>(
+		port,
+		{type: 'session_save', id: 'known'},
+		'session',
+	);
+	const load = await requestReply<SessionMessage>(
+		port,
+		{type: 'session_load', id: 'known'},
+		'session',
+	);
+	const missing = await requestReply<SessionMessage>(
+		port,
+		{type: 'session_load', id: 'other'},
+		'session',
+	);
+	await server.stop();
+
+	t.deepEqual(saved, ['known']);
+	t.deepEqual(save, {type: 'session', action: 'save', id: 'known'});
+	t.deepEqual(load.messages, conversation);
+	t.is(missing.error, 'Session "other" not found');
+	t.is(missing.messages, undefined);
+});
+
+test('VSCodeServer handles client messages - count_remaining', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	let received: unknown;
+	server.onCallbacks({
+		onCountRemaining: async request => {
+			received = request;
+			return {used: 300, max: 1000, remaining: 700, percent: 30};
+		},
+	});
+	await server.start();
+
+	const message = await requestReply<ContextRemainingMessage>(
+		port,
+		{type: 'count_remaining', draft: 'next question'},
+		'context_remaining',
+	);
+	await server.stop();
+
+	t.deepEqual(received, {draft: 'next question'});
+	t.deepEqual(message, {
+		type: 'context_remaining',
+		used: 300,
+		max: 1000,
+		remaining: 700,
+		percent: 30,
+	});
+});
+
+test('VSCodeServer handles client messages - count_tokens', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	let received: unknown;
+	server.onCallbacks({
+		onCountTokens: async request => {
+			received = request;
+			return {tokens: 12, maxContext: 8192, fits: true};
+		},
+	});
+	await server.start();
+
+	const request = {
+		type: 'count_tokens',
+		model: 'gpt-4',
+		messages: [{role: 'user', content: 'hi'}],
+	};
+	const message = await requestReply<TokenCountMessage>(
+		port,
+		request,
+		'token_count',
+	);
+	await server.stop();
+
+	t.deepEqual(received, {
+		model: 'gpt-4',
+		messages: [{role: 'user', content: 'hi'}],
+	});
+	t.deepEqual(message, {
+		type: 'token_count',
+		model: 'gpt-4',
+		tokens: 12,
+		maxContext: 8192,
+		fits: true,
+	});
+});
+
+test('VSCodeServer handles client messages - estimate_cost', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	server.onCallbacks({
+		onEstimateCost: async ({model}) =>
+			model === 'gpt-4o' ? {input: 2.5, output: 5, total: 7.5} : null,
+	});
+	await server.start();
+
+	const usage = {prompt_tokens: 1_000_000, completion_tokens: 500_000};
+	const known = await requestReply<CostEstimateMessage>(
+		port,
+		{type: 'estimate_cost', model: 'gpt-4o', usage},
+		'cost_estimate',
+	);
+	const unknown = await requestReply<CostEstimateMessage>(
+		port,
+		{type: 'estimate_cost', model: 'mystery', usage},
+		'cost_estimate',
+	);
+	await server.stop();
+
+	t.deepEqual(known, {
+		type: 'cost_estimate',
+		model: 'gpt-4o',
+		cost: {input: 2.5, output: 5, total: 7.5},
+	});
+	t.deepEqual(unknown, {type: 'cost_estimate', model: 'mystery', cost: null});
+});
+
+test('VSCodeServer handles client messages - get_mcp_status', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	const servers = [
+		{name: 'filesystem', connected: true, toolCount: 4},
+		{name: 'github', connected: false, toolCount: 0, lastError: 'ENOENT'},
+	];
+	server.onCallbacks({onGetMCPStatus: async () => servers});
+	await server.start();
+
+	const reply = await requestReply<MCPStatusMessage>(
+		port,
+		{type: 'get_mcp_status'},
+		'mcp_status',
+	);
+	await server.stop();
+
+	t.deepEqual(reply, {type: 'mcp_status', servers});
+});
+
+test('VSCodeServer handles client messages - init_provider and chat', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	const initialized: string[] = ['openrouter'];
+	server.onCallbacks({
+		onInitProvider: async provider => {
+			initialized.push(provider);
+			return {model: `${provider}-model`};
+		},
+		onChat: async ({provider, messages}) => {
+			if (provider && !initialized.includes(provider)) {
+				throw new Error(`Provider "${provider}" is not initialized`);
+			}
+			const name = provider ?? 'openrouter';
+			return {provider: name, content: `${name}: ${messages[0].content}`};
+		},
+	});
+	await server.start();
+
+	const messages = [{role: 'user' as const, content: 'hi'}];
+	const ready = await requestReply<ProviderReadyMessage>(
+		port,
+		{type: 'init_provider', provider: 'ollama'},
+		'provider_ready',
+	);
+	const routed = await requestReply<ChatResponseMessage>(
+		port,
+		{type: 'chat', provider: 'ollama', messages},
+		'chat_response',
+	);
+	const fallback = await requestReply<ChatResponseMessage>(
+		port,
+		{type: 'chat', messages},
+		'chat_response',
+	);
+	const missing = await requestReply<ChatResponseMessage>(
+		port,
+		{type: 'chat', provider: 'lmstudio', messages},
+		'chat_response',
+	);
+	await server.stop();
+
+	t.deepEqual(ready, {
+		type: 'provider_ready',
+		provider: 'ollama',
+		model: 'ollama-model',
+	});
+	t.deepEqual(routed, {
+		type: 'chat_response',
+		provider: 'ollama',
+		content: 'ollama: hi',
+	});
+	t.deepEqual(fallback, {
+		type: 'chat_response',
+		provider: 'openrouter',
+		content: 'openrouter: hi',
+	});
+	t.is(missing.error, 'Provider "lmstudio" is not initialized');
+});
+
+test('VSCodeServer handles client messages - execute_tools', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	server.onCallbacks({
+		onExecuteTools: async toolCalls =>
+			toolCalls.map(({id, function: {arguments: args}}) =>
+				args.fail ? {id, error: 'failed'} : {id, output: `ran ${id}`},
+			),
+	});
+	await server.start();
+
+	const reply = await requestReply<ToolResultsMessage>(
+		port,
+		{
+			type: 'execute_tools',
+			toolCalls: [
+				{id: 'a', function: {name: 'read_file', arguments: {}}},
+				{id: 'b', function: {name: 'read_file', arguments: {fail: true}}},
+			],
+		},
+		'tool_results',
+	);
+	await server.stop();
+
+	t.deepEqual(reply, {
+		type: 'tool_results',
+		results: [
+			{id: 'a', output: 'ran a'},
+			{id: 'b', error: 'failed'},
+		],
+	});
+});
+
+test('VSCodeServer handles client messages - execute_tool', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	const received: ToolCallInfo[] = [];
+	server.onCallbacks({
+		onExecuteTools: async toolCalls => {
+			received.push(...toolCalls);
+			return toolCalls.map(({id, function: {name}}) => ({
+				id,
+				output: `ran ${name}`,
+			}));
+		},
+	});
+	await server.start();
+
+	const full = await requestReply<ToolResultMessage>(
+		port,
+		{
+			type: 'execute_tool',
+			toolCall: {
+				id: 'a',
+				function: {name: 'read_file', arguments: {path: 'a.ts'}},
+			},
+		},
+		'tool_result',
+	);
+	const named = await requestReply<ToolResultMessage>(
+		port,
+		{
+			type: 'execute_tool',
+			toolCall: {name: 'find_files', arguments: {pattern: '*.ts'}},
+		},
+		'tool_result',
+	);
+	await server.stop();
+
+	t.deepEqual(full, {
+		type: 'tool_result',
+		result: {id: 'a', output: 'ran read_file'},
+	});
+	t.deepEqual(received[0], {
+		id: 'a',
+		function: {name: 'read_file', arguments: {path: 'a.ts'}},
+	});
+	// The short form runs under a generated id, which the result reports
+	t.truthy(received[1].id);
+	t.deepEqual(received[1].function, {
+		name: 'find_files',
+		arguments: {pattern: '*.ts'},
+	});
+	t.deepEqual(named, {
+		type: 'tool_result',
+		result: {id: received[1].id, output: 'ran find_files'},
+	});
+});
+
+test('VSCodeServer handles client messages - cancel_bash', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	server.onCallbacks({onCancelBash: id => id === 'running'});
+	await server.start();
+
+	const running = await requestReply<BashCancelledMessage>(
+		port,
+		{type: 'cancel_bash', id: 'running'},
+		'bash_cancelled',
+	);
+	const finished = await requestReply<BashCancelledMessage>(
+		port,
+		{type: 'cancel_bash', id: 'finished'},
+		'bash_cancelled',
+	);
+	await server.stop();
+
+	t.deepEqual(running, {
+		type: 'bash_cancelled',
+		id: 'running',
+		cancelled: true,
+	});
+	t.deepEqual(finished, {
+		type: 'bash_cancelled',
+		id: 'finished',
+		cancelled: false,
+	});
+});
+
+test.serial('VSCodeServer handles client messages - mcp_raw_request', async t => {
+	const port = getNextPort();
+	const server = new VSCodeServer(port);
+	const requests: unknown[] = [];
+	server.onCallbacks({
+		onMCPRawRequest: async request => {
+			requests.push(request);
+			return {level: 'debug'};
+		},
+	});
+	await server.start();
+	const request = {
+		type: 'mcp_raw_request',
+		server: 'docs',
+		method: 'logging/setLevel',
+		params: {level: 'debug'},
+	};
+	const previous = process.env.NANOCODER_LOG_LEVEL;
+
+	try {
+		delete process.env.NANOCODER_LOG_LEVEL;
+		const refused = await requestReply<MCPRawResultMessage>(
+			port,
+			request,
+			'mcp_raw_result',
+		);
+		process.env.NANOCODER_LOG_LEVEL = 'debug';
+		const answered = await requestReply<MCPRawResultMessage>(
+			port,
+			request,
+			'mcp_raw_result',
+		);
+
+		t.regex(refused.error ?? '', /NANOCODER_LOG_LEVEL=debug/);
+		t.is(refused.result, undefined);
+		t.deepEqual(answered, {
+			type: 'mcp_raw_result',
+			server: 'docs',
+			method: 'logging/setLevel',
+			result: {level: 'debug'},
+		});
+		t.deepEqual(requests, [
+			{server: 'docs', method: 'logging/setLevel', params: {level: 'debug'}},
+		]);
+	} finally {
+		if (previous === undefined) {
+			delete process.env.NANOCODER_LOG_LEVEL;
+		} else {
+			process.env.NANOCODER_LOG_LEVEL = previous;
+		}
+		await server.stop();
+	}
+});
+
 test('VSCodeServer handles multiple clients', async t => {
 	const port = getNextPort();
 	const server = new VSCodeServer(port);
@@ -5,22 +5,52 @@
 import {readFile} from 'node:fs/promises';
 import {randomUUID} from 'crypto';
 import {WebSocket, WebSocketServer} from 'ws';
+import {generateToolCallId} from '@/ai-sdk-client/converters/tool-converter';
 import {BoundedMap} from '@/utils/bounded-map';
 import {formatError} from '@/utils/error-formatter';
 import {getLogger} from '@/utils/logging';
 import {
 	AssistantMessage,
+	BashCancelledMessage,
+	ChatRequestMessage,
+	ChatResponseMessage,
 	ClientMessage,
 	CloseDiffMessage,
 	ConnectionAckMessage,
+	ContextRemainingMessage,
+	ConversationMessage,
+	CostEstimateMessage,
+	CountRemainingMessage,
+	CountTokensMessage,
 	DEFAULT_PORT,
 	DiagnosticInfo,
 	DiagnosticsRequestMessage,
+	EstimateCostMessage,
+	ExecuteToolMessage,
 	FileChangeMessage,
+	ListToolsMessage,
+	MCPRawRequestMessage,
+	MCPRawResultMessage,
+	MCPServerInfo,
+	MCPStatusMessage,
+	ModelInfo,
+	ModelsMessage,
 	PendingChange,
 	PROTOCOL_VERSION,
+	ProviderReadyMessage,
 	ServerMessage,
+	SessionInfo,
+	SessionListMessage,
+	SessionMessage,
 	StatusMessage,
+	TokenCountMessage,
+	ToolCallInfo,
+	ToolDescription,
+	ToolDescriptionMessage,
+	ToolListMessage,
+	ToolResultInfo,
+	ToolResultMessage,
+	ToolResultsMessage,
 } from './protocol';
 
 let cachedCliVersion: string | null = null;
@@ -66,6 +96,33 @@ export interface VSCodeServerCallbacks {
 		diagnostics?: DiagnosticInfo[];
 	}) => void;
 	onDiagnosticsResponse?: (diagnostics: DiagnosticInfo[]) => void;
+	onGetModels?: () => Promise<ModelInfo[]>;
+	onDescribeTool?: (name: string) => Promise<ToolDescription | undefined>;
+	onListTools?: (
+		filter: Omit<ListToolsMessage, 'type'>,
+	) => Promise<ToolDescription[]>;
+	onGetMCPStatus?: () => Promise<MCPServerInfo[]>;
+	onMCPRawRequest?: (
+		request: Omit<MCPRawRequestMessage, 'type'>,
+	) => Promise<Record<string, unknown>>;
+	onSaveSession?: (id: string) => Promise<void>;
+	onLoadSession?: (id: string) => Promise<ConversationMessage[]>;
+	onListSessions?: () => Promise<SessionInfo[]>;
+	onCountRemaining?: (
+		request: Omit<CountRemainingMessage, 'type'>,
+	) => Promise<Omit<ContextRemainingMessage, 'type' | 'error'>>;
+	onCountTokens?: (
+		request: Omit<CountTokensMessage, 'type'>,
+	) => Promise<Omit<TokenCountMessage, 'type' | 'model' | 'error'>>;
+	onEstimateCost?: (
+		request: Omit<EstimateCostMessage, 'type'>,
+	) => Promise<CostEstimateMessage['cost']>;
+	onInitProvider?: (provider: string) => Promise<{model?: string}>;
+	onChat?: (
+		request: Omit<ChatRequestMessage, 'type'>,
+	) => Promise<Omit<ChatResponseMessage, 'type' | 'error'>>;
+	onExecuteTools?: (toolCalls: ToolCallInfo[]) => Promise<ToolResultInfo[]>;
+	onCancelBash?: (id: string) => boolean;
 	onConnect?: () => void;
 	onDisconnect?: () => void;
 }
@@ -354,7 +411,7 @@ export class VSCodeServer {
 		ws.on('message', (data: {toString(): string}) => {
 			try {
 				const message = JSON.parse(data.toString()) as ClientMessage;
-				this.handleMessage(message);
+				this.handleMessage(ws, message);
 			} catch (error) {
 				const logger = getLogger();
 				logger.error(
@@ -374,7 +431,7 @@ export class VSCodeServer {
 		});
 	}
 
-	private handleMessage(message: ClientMessage): void {
+	private handleMessage(ws: WebSocket, message: ClientMessage): void {
 		switch (message.type) {
 			case 'send_prompt':
 				this.callbacks.onPrompt?.(message.prompt, message.context);
@@ -406,6 +463,474 @@ export class VSCodeServer {
 			case 'diagnostics_response':
 				this.callbacks.onDiagnosticsResponse?.(message.diagnostics);
 				break;
+
+			case 'get_models':
+				void this.sendModels(ws);
+				break;
+
+			case 'describe_tool':
+				void this.sendToolDescription(ws, message.name);
+				break;
+
+			case 'list_tools': {
+				const {type: _type, ...filter} = message;
+				void this.sendToolList(ws, filter);
+				break;
+			}
+
+			case 'get_mcp_status':
+				void this.sendMCPStatus(ws);
+				break;
+
+			case 'mcp_raw_request': {
+				const {type: _type, ...request} = message;
+				void this.sendMCPRawResult(ws, request);
+				break;
+			}
+
+			case 'session_save':
+				void this.sendSessionResult(ws, 'save', message.id);
+				break;
+
+			case 'session_load':
+				void this.sendSessionResult(ws, 'load', message.id);
+				break;
+
+			case 'session_list':
+				void this.sendSessionList(ws);
+				break;
+
+			case 'count_remaining': {
+				const {type: _type, ...request} = message;
+				void this.sendContextRemaining(ws, request);
+				break;
+			}
+
+			case 'count_tokens': {
+				const {type: _type, ...request} = message;
+				void this.sendTokenCount(ws, request);
+				break;
+			}
+
+			case 'estimate_cost': {
+				const {type: _type, ...request} = message;
+				void this.sendCostEstimate(ws, request);
+				break;
+			}
+
+			case 'init_provider':
+				void this.sendProviderReady(ws, message.provider);
+				break;
+
+			case 'chat': {
+				const {type: _type, ...request} = message;
+				void this.sendChatResponse(ws, request);
+				break;
+			}
+
+			case 'execute_tools':
+				void this.sendToolResults(ws, message.toolCalls);
+				break;
+
+			case 'execute_tool':
+				void this.sendToolResult(ws, message.toolCall);
+				break;
+
+			case 'cancel_bash':
+				this.sendBashCancelled(ws, message.id);
+				break;
+		}
+	}
+
+	/**
+	 * Send the current provider's models to VS Code
+	 */
+	private async sendModels(ws: WebSocket): Promise<void> {
+		let message: ModelsMessage;
+		if (!this.callbacks.onGetModels) {
+			message = {
+				type: 'models',
+				models: [],
+				error: 'The current provider cannot list its models',
+			};
+		} else {
+			try {
+				const models = await this.callbacks.onGetModels();
+				message = {type: 'models', models};
+			} catch (error) {
+				message = {
+					type: 'models',
+					models: [],
+					error: error instanceof Error ? error.message : String(error),
+				};
+			}
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Send one tool's full definition to VS Code, or an error if it is unknown
+	 */
+	private async sendToolDescription(
+		ws: WebSocket,
+		name: string,
+	): Promise<void> {
+		let message: ToolDescriptionMessage;
+		try {
+			const tool = await this.callbacks.onDescribeTool?.(name);
+			message = tool
+				? {type: 'tool_description', name, tool}
+				: {type: 'tool_description', name, error: `Unknown tool: ${name}`};
+		} catch (error) {
+			message = {
+				type: 'tool_description',
+				name,
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Send the definitions of the tools matching a filter to VS Code
+	 */
+	private async sendToolList(
+		ws: WebSocket,
+		filter: Omit<ListToolsMessage, 'type'>,
+	): Promise<void> {
+		let message: ToolListMessage;
+		try {
+			const tools = (await this.callbacks.onListTools?.(filter)) ?? [];
+			message = {type: 'tools', tools};
+		} catch (error) {
+			message = {
+				type: 'tools',
+				tools: [],
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Send the health of each MCP server to VS Code
+	 */
+	private async sendMCPStatus(ws: WebSocket): Promise<void> {
+		let message: MCPStatusMessage;
+		try {
+			const servers = (await this.callbacks.onGetMCPStatus?.()) ?? [];
+			message = {type: 'mcp_status', servers};
+		} catch (error) {
+			message = {
+				type: 'mcp_status',
+				servers: [],
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Send a raw JSON-RPC request to an MCP server and its result to VS Code.
+	 * Only allowed with debug logging, as it bypasses the usual tool approval.
+	 */
+	private async sendMCPRawResult(
+		ws: WebSocket,
+		request: Omit<MCPRawRequestMessage, 'type'>,
+	): Promise<void> {
+		const {server, method} = request;
+		let message: MCPRawResultMessage;
+		try {
+			if (process.env.NANOCODER_LOG_LEVEL !== 'debug') {
+				throw new Error(
+					'mcp_raw_request is a debugging aid. Set NANOCODER_LOG_LEVEL=debug to use it.',
+				);
+			}
+			if (!this.callbacks.onMCPRawRequest) {
+				throw new Error('No MCP servers are connected');
+			}
+			const result = await this.callbacks.onMCPRawRequest(request);
+			message = {type: 'mcp_raw_result', server, method, result};
+		} catch (error) {
+			message = {
+				type: 'mcp_raw_result',
+				server,
+				method,
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Save or load a session and tell VS Code how it went. A load also sends
+	 * the restored conversation.
+	 */
+	private async sendSessionResult(
+		ws: WebSocket,
+		action: SessionMessage['action'],
+		id: string,
+	): Promise<void> {
+		let message: SessionMessage;
+		try {
+			if (action === 'save') {
+				if (!this.callbacks.onSaveSession) {
+					throw new Error('Sessions cannot be saved right now');
+				}
+				await this.callbacks.onSaveSession(id);
+				message = {type: 'session', action, id};
+			} else {
+				if (!this.callbacks.onLoadSession) {
+					throw new Error('Sessions cannot be loaded right now');
+				}
+				const messages = await this.callbacks.onLoadSession(id);
+				message = {type: 'session', action, id, messages};
+			}
+		} catch (error) {
+			message = {
+				type: 'session',
+				action,
+				id,
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Send the saved sessions to VS Code
+	 */
+	private async sendSessionList(ws: WebSocket): Promise<void> {
+		let message: SessionListMessage;
+		try {
+			const sessions = (await this.callbacks.onListSessions?.()) ?? [];
+			message = {type: 'sessions', sessions};
+		} catch (error) {
+			message = {
+				type: 'sessions',
+				sessions: [],
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Send the context window headroom for a request to VS Code
+	 */
+	private async sendContextRemaining(
+		ws: WebSocket,
+		request: Omit<CountRemainingMessage, 'type'>,
+	): Promise<void> {
+		let message: ContextRemainingMessage;
+		try {
+			if (!this.callbacks.onCountRemaining) {
+				throw new Error('Token counts are not available yet');
+			}
+			const headroom = await this.callbacks.onCountRemaining(request);
+			message = {type: 'context_remaining', ...headroom};
+		} catch (error) {
+			message = {
+				type: 'context_remaining',
+				used: 0,
+				max: 0,
+				remaining: 0,
+				percent: 0,
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Send the token count for a request with one model's tokenizer to VS Code
+	 */
+	private async sendTokenCount(
+		ws: WebSocket,
+		request: Omit<CountTokensMessage, 'type'>,
+	): Promise<void> {
+		let message: TokenCountMessage;
+		try {
+			if (!this.callbacks.onCountTokens) {
+				throw new Error('Token counts are not available yet');
+			}
+			const count = await this.callbacks.onCountTokens(request);
+			message = {type: 'token_count', model: request.model, ...count};
+		} catch (error) {
+			message = {
+				type: 'token_count',
+				model: request.model,
+				tokens: 0,
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Send the estimated cost of token usage with a model to VS Code
+	 */
+	private async sendCostEstimate(
+		ws: WebSocket,
+		request: Omit<EstimateCostMessage, 'type'>,
+	): Promise<void> {
+		let message: CostEstimateMessage;
+		try {
+			if (!this.callbacks.onEstimateCost) {
+				throw new Error('Cost estimates are not available yet');
+			}
+			const cost = await this.callbacks.onEstimateCost(request);
+			message = {type: 'cost_estimate', model: request.model, cost};
+		} catch (error) {
+			message = {
+				type: 'cost_estimate',
+				model: request.model,
+				cost: null,
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Register a client for a provider and tell VS Code which model it is on
+	 */
+	private async sendProviderReady(
+		ws: WebSocket,
+		provider: string,
+	): Promise<void> {
+		let message: ProviderReadyMessage;
+		try {
+			if (!this.callbacks.onInitProvider) {
+				throw new Error('Providers cannot be initialized yet');
+			}
+			const {model} = await this.callbacks.onInitProvider(provider);
+			message = {type: 'provider_ready', provider, model};
+		} catch (error) {
+			message = {
+				type: 'provider_ready',
+				provider,
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Send a chat to the requested provider and its answer back to VS Code
+	 */
+	private async sendChatResponse(
+		ws: WebSocket,
+		request: Omit<ChatRequestMessage, 'type'>,
+	): Promise<void> {
+		let message: ChatResponseMessage;
+		try {
+			if (!this.callbacks.onChat) {
+				throw new Error('Chat is not available yet');
+			}
+			const response = await this.callbacks.onChat(request);
+			message = {type: 'chat_response', ...response};
+		} catch (error) {
+			message = {
+				type: 'chat_response',
+				provider: request.provider ?? '',
+				content: '',
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Run tool calls and send their results to VS Code in call order
+	 */
+	private async sendToolResults(
+		ws: WebSocket,
+		toolCalls: ToolCallInfo[],
+	): Promise<void> {
+		let message: ToolResultsMessage;
+		try {
+			if (!this.callbacks.onExecuteTools) {
+				throw new Error('Tools cannot be run yet');
+			}
+			const results = await this.callbacks.onExecuteTools(toolCalls);
+			message = {type: 'tool_results', results};
+		} catch (error) {
+			message = {
+				type: 'tool_results',
+				results: [],
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Run one tool call and send its result to VS Code. A call sent as just a
+	 * name and arguments gets a generated id.
+	 */
+	private async sendToolResult(
+		ws: WebSocket,
+		toolCall: ExecuteToolMessage['toolCall'],
+	): Promise<void> {
+		let message: ToolResultMessage;
+		try {
+			if (!this.callbacks.onExecuteTools) {
+				throw new Error('Tools cannot be run yet');
+			}
+			const call: ToolCallInfo =
+				'function' in toolCall
+					? toolCall
+					: {
+							id: generateToolCallId(),
+							function: {
+								name: toolCall.name,
+								arguments: toolCall.arguments,
+							},
+						};
+			const [result] = await this.callbacks.onExecuteTools([call]);
+			message = {type: 'tool_result', result};
+		} catch (error) {
+			message = {
+				type: 'tool_result',
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Cancel a running bash call and tell VS Code whether there was one
+	 */
+	private sendBashCancelled(ws: WebSocket, id: string): void {
+		let message: BashCancelledMessage;
+		try {
+			if (!this.callbacks.onCancelBash) {
+				throw new Error('Tools cannot be cancelled yet');
+			}
+			message = {
+				type: 'bash_cancelled',
+				id,
+				cancelled: this.callbacks.onCancelBash(id),
+			};
+		} catch (error) {
+			message = {
+				type: 'bash_cancelled',
+				id,
+				cancelled: false,
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		this.reply(ws, message);
+	}
+
+	/**
+	 * Answer a request on the socket it came from, so other connected windows
+	 * don't receive replies to requests they never made
+	 */
+	private reply(ws: WebSocket, message: ServerMessage): void {
+		if (ws.readyState === WebSocket.OPEN) {
+			ws.send(JSON.stringify(message));
 		}
 	}
 
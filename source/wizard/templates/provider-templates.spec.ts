@@ -124,6 +124,21 @@ test('openai template: preserves organizationId', t => {
 	t.deepEqual(config.models, ['gpt-5-codex']);
 });
 
+test('openai template: preserves projectId', t => {
+	const template = PROVIDER_TEMPLATES.find(t => t.id === 'openai');
+	t.truthy(template);
+
+	const config = template!.buildConfig({
+		providerName: 'openai',
+		apiKey: 'test-key',
+		model: 'gpt-5-codex',
+		projectId: 'proj_456',
+	});
+
+	t.is(config.projectId, 'proj_456');
+	t.is(config.organizationId, undefined);
+});
+
 test('openai template: handles multiple models', t => {
 	const template = PROVIDER_TEMPLATES.find(t => t.id === 'openai');
 	t.truthy(template);
@@ -190,6 +190,11 @@ export const PROVIDER_TEMPLATES: ProviderTemplate[] = [
 				prompt: 'Organization ID (optional)',
 				required: false,
 			},
+			{
+				name: 'projectId',
+				prompt: 'Project ID (optional)',
+				required: false,
+			},
 			{
 				name: 'providerName',
 				prompt: 'Provider name',
@@ -209,6 +214,9 @@ export const PROVIDER_TEMPLATES: ProviderTemplate[] = [
 			if (answers.organizationId) {
 				config.organizationId = answers.organizationId;
 			}
+			if (answers.projectId) {
+				config.projectId = answers.projectId;
+			}
 			return config;
 		},
 	},
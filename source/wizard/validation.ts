@@ -136,6 +136,7 @@ interface ConfigObject {
 			baseUrl?: string;
 			apiKey?: string;
 			organizationId?: string;
+			projectId?: string;
 			timeout?: number;
 		}>;
 		mcpServers?: Array<{
@@ -144,6 +145,9 @@ interface ConfigObject {
 			command?: string;
 			args?: string[];
 			env?: Record<string, string>;
+			cwd?: string;
+			inheritEnv?: boolean;
+			shutdownTimeout?: number;
 			url?: string;
 			headers?: Record<string, string>;
 			auth?: {
@@ -155,6 +159,7 @@ interface ConfigObject {
 				customHeaders?: Record<string, string>;
 			};
 			timeout?: number;
+			startupTimeout?: number;
 			reconnect?: {
 				enabled: boolean;
 				maxAttempts: number;
@@ -183,6 +188,7 @@ export function buildConfigObject(
 					baseUrl?: string;
 					apiKey?: string;
 					organizationId?: string;
+					projectId?: string;
 					timeout?: number;
 				} = {
 					name: p.name,
@@ -201,6 +207,10 @@ export function buildConfigObject(
 					providerConfig.organizationId = p.organizationId;
 				}
 
+				if (p.projectId) {
+					providerConfig.projectId = p.projectId;
+				}
+
 				if (p.timeout) {
 					providerConfig.timeout = p.timeout;
 				}